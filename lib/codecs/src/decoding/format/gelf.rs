@@ -0,0 +1,217 @@
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::Kind;
+use vector_core::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use super::Deserializer;
+
+/// The version of GELF which is supported.
+const GELF_VERSION: &str = "1.1";
+
+/// Config used to build a `GelfDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GelfDeserializerConfig;
+
+impl GelfDeserializerConfig {
+    /// Creates a new `GelfDeserializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `GelfDeserializer` from this configuration.
+    pub const fn build(&self) -> GelfDeserializer {
+        GelfDeserializer
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self) -> schema::Definition {
+        schema::Definition::empty()
+            .required_field(log_schema().message_key(), Kind::bytes(), Some("message"))
+            // The timestamp is the current time when the message doesn't have one.
+            .required_field(
+                log_schema().timestamp_key(),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .optional_field(log_schema().host_key(), Kind::bytes(), Some("host"))
+            .optional_field("full_message", Kind::bytes(), None)
+            .optional_field("level", Kind::integer(), Some("severity"))
+            .optional_field("facility", Kind::bytes(), None)
+            .optional_field("line", Kind::integer().or_float(), None)
+            .optional_field("file", Kind::bytes(), None)
+            // The additional fields, whose names start with an underscore, can only hold strings
+            // and numbers.
+            .unknown_fields(Kind::bytes().or_integer().or_float())
+    }
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a GELF
+/// 1.1 message.
+///
+/// The short message, the host and the timestamp are stored under the keys of
+/// the log schema, while the additional fields keep their leading underscore.
+#[derive(Debug, Clone, Default)]
+pub struct GelfDeserializer;
+
+impl GelfDeserializer {
+    /// Creates a new `GelfDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Deserializer for GelfDeserializer {
+    fn parse(&self, bytes: Bytes) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        let fields: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(&bytes)
+            .map_err(|error| format!("Error parsing GELF: {:?}", error))?;
+
+        let mut log = LogEvent::default();
+        let mut timestamp = None;
+
+        for (key, value) in fields {
+            match (key.as_str(), value) {
+                ("version", serde_json::Value::String(version)) if version == GELF_VERSION => {}
+                ("version", version) => {
+                    return Err(format!("Unsupported GELF version: {}", version).into())
+                }
+                ("short_message", serde_json::Value::String(message)) => {
+                    log.insert(log_schema().message_key(), message);
+                }
+                ("short_message", _) => return Err("GELF short_message is not a string".into()),
+                ("host", serde_json::Value::String(host)) => {
+                    log.insert(log_schema().host_key(), host);
+                }
+                ("timestamp", serde_json::Value::Number(seconds)) => {
+                    timestamp = Some(
+                        seconds
+                            .as_f64()
+                            .and_then(parse_timestamp)
+                            .ok_or_else(|| format!("Invalid GELF timestamp: {}", seconds))?,
+                    );
+                }
+                ("timestamp", _) => return Err("GELF timestamp is not a number".into()),
+                // The `_id` field is reserved by Graylog.
+                ("_id", _) => {}
+                (_, value) => {
+                    log.insert_flat(key, Value::from(value));
+                }
+            }
+        }
+
+        if !log.contains(log_schema().message_key()) {
+            return Err("GELF message has no short_message".into());
+        }
+        log.insert(
+            log_schema().timestamp_key(),
+            timestamp.unwrap_or_else(Utc::now),
+        );
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+/// Parses a GELF timestamp, which is a number of seconds since the UNIX epoch
+/// with an optional decimal part.
+///
+/// The decimal part is rounded to microseconds, since a `f64` can't represent
+/// the current time with a better precision.
+fn parse_timestamp(seconds: f64) -> Option<DateTime<Utc>> {
+    if !seconds.is_finite() {
+        return None;
+    }
+    let microseconds = (seconds * 1e6).round();
+    if microseconds.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    let microseconds = microseconds as i64;
+    Utc.timestamp_opt(
+        microseconds.div_euclid(1_000_000),
+        microseconds.rem_euclid(1_000_000) as u32 * 1000,
+    )
+    .single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_gelf() {
+        let input = Bytes::from(
+            r#"{
+                "version": "1.1",
+                "host": "example.org",
+                "short_message": "A short message that helps you identify what is going on",
+                "full_message": "Backtrace here\n\nmore stuff",
+                "timestamp": 1385053862.3072,
+                "level": 1,
+                "_user_id": 9001,
+                "_some_info": "foo",
+                "_id": "ignored"
+            }"#,
+        );
+        let deserializer = GelfDeserializer::new();
+
+        let events = deserializer.parse(input).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(
+            log[log_schema().message_key()],
+            "A short message that helps you identify what is going on".into()
+        );
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1385053862, 307_200_000).into()
+        );
+        assert_eq!(log["full_message"], "Backtrace here\n\nmore stuff".into());
+        assert_eq!(log["level"], 1.into());
+        assert_eq!(log["_user_id"], 9001.into());
+        assert_eq!(log["_some_info"], "foo".into());
+        assert!(log.get("_id").is_none());
+        assert!(log.get("version").is_none());
+    }
+
+    #[test]
+    fn deserialize_gelf_without_timestamp() {
+        let input = Bytes::from(r#"{"short_message":"foo","_foo.bar":"baz"}"#);
+        let deserializer = GelfDeserializer::new();
+
+        let events = deserializer.parse(input).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "foo".into());
+        assert!(log[log_schema().timestamp_key()].is_timestamp());
+        assert_eq!(log.get_flat("_foo.bar"), Some(&"baz".into()));
+    }
+
+    #[test]
+    fn deserialize_error_missing_short_message() {
+        let input = Bytes::from(r#"{"version":"1.1","host":"example.org"}"#);
+        let deserializer = GelfDeserializer::new();
+
+        assert!(deserializer.parse(input).is_err());
+    }
+
+    #[test]
+    fn deserialize_error_unsupported_version() {
+        let input = Bytes::from(r#"{"version":"1.0","short_message":"foo"}"#);
+        let deserializer = GelfDeserializer::new();
+
+        assert!(deserializer.parse(input).is_err());
+    }
+
+    #[test]
+    fn deserialize_error_invalid_json() {
+        let input = Bytes::from(r#"["short_message"]"#);
+        let deserializer = GelfDeserializer::new();
+
+        assert!(deserializer.parse(input).is_err());
+    }
+}
@@ -4,6 +4,7 @@
 #![deny(missing_docs)]
 
 mod bytes;
+mod gelf;
 mod json;
 mod native;
 mod native_json;
@@ -13,6 +14,7 @@ mod syslog;
 pub use self::bytes::{BytesDeserializer, BytesDeserializerConfig};
 #[cfg(feature = "syslog")]
 pub use self::syslog::{SyslogDeserializer, SyslogDeserializerConfig};
+pub use gelf::{GelfDeserializer, GelfDeserializerConfig};
 pub use json::{JsonDeserializer, JsonDeserializerConfig};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{NativeJsonDeserializer, NativeJsonDeserializerConfig};
@@ -40,6 +40,15 @@ pub struct CharacterDelimitedDecoderOptions {
     max_length: Option<usize>,
 }
 
+impl CharacterDelimitedDecoderOptions {
+    /// Limits the frames to `max_length` bytes, unless a maximum length is
+    /// already set.
+    pub fn or_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = self.max_length.or(Some(max_length));
+        self
+    }
+}
+
 /// A decoder for handling bytes that are delimited by (a) chosen character(s).
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct CharacterDelimitedDecoder {
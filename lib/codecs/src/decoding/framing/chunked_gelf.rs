@@ -0,0 +1,428 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, Read},
+    time::{Duration, Instant},
+};
+
+use bytes::{Bytes, BytesMut};
+use derivative::Derivative;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+use tracing::warn;
+
+use super::{BoxedFramingError, FramingError};
+use crate::decoding::StreamDecodingError;
+
+/// The magic bytes which start a chunk of a GELF message.
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+/// The length of the header of a chunk: the magic bytes, the message ID, the
+/// sequence number and the sequence count.
+const CHUNK_HEADER_LENGTH: usize = 12;
+/// The maximum number of chunks a GELF message can be split into.
+const MAX_CHUNKS: u8 = 128;
+/// The magic bytes which start a gzip-compressed message.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// The maximum length of a message, once reassembled and decompressed, unless
+/// configured otherwise.
+const DEFAULT_MAX_LENGTH: usize = 8 * 1024 * 1024;
+/// The maximum number of incomplete messages kept waiting for their missing
+/// chunks. The chunks of any further messages are discarded.
+const MAX_PENDING_MESSAGES: usize = 1000;
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+/// Config used to build a `ChunkedGelfDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ChunkedGelfDecoderConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the chunked GELF decoder.
+    pub chunked_gelf: ChunkedGelfDecoderOptions,
+}
+
+impl ChunkedGelfDecoderConfig {
+    /// Build the `ChunkedGelfDecoder` from this configuration.
+    pub fn build(&self) -> ChunkedGelfDecoder {
+        let timeout = Duration::from_secs(self.chunked_gelf.timeout_secs);
+        if let Some(max_length) = self.chunked_gelf.max_length {
+            ChunkedGelfDecoder::new_with_max_length(timeout, max_length)
+        } else {
+            ChunkedGelfDecoder::new(timeout)
+        }
+    }
+}
+
+/// Options for building a `ChunkedGelfDecoder`.
+#[derive(Debug, Clone, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+pub struct ChunkedGelfDecoderOptions {
+    /// The number of seconds the chunks of a message are kept waiting for its
+    /// missing chunks.
+    #[serde(default = "default_timeout_secs")]
+    #[derivative(Default(value = "default_timeout_secs()"))]
+    timeout_secs: u64,
+    /// The maximum length of a message, once reassembled and decompressed.
+    /// Defaults to 8 MiB.
+    #[serde(skip_serializing_if = "vector_core::serde::skip_serializing_if_default")]
+    max_length: Option<usize>,
+}
+
+/// A decoder for the GELF messages sent over UDP, which may be split into
+/// chunks and compressed with zlib or gzip.
+///
+/// Each call to `decode` takes the whole buffer as one datagram. The chunks
+/// are kept until all the chunks of their message are received, or until they
+/// are older than the timeout, which is checked whenever a chunk is received.
+#[derive(Clone, Debug)]
+pub struct ChunkedGelfDecoder {
+    timeout: Duration,
+    max_length: usize,
+    messages: HashMap<[u8; 8], ChunkedMessage>,
+}
+
+#[derive(Clone, Debug)]
+struct ChunkedMessage {
+    first_received: Instant,
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+    length: usize,
+}
+
+impl ChunkedGelfDecoder {
+    /// Creates a new `ChunkedGelfDecoder` which drops the incomplete messages
+    /// after `timeout`, and the messages longer than 8 MiB.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            max_length: DEFAULT_MAX_LENGTH,
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Creates a `ChunkedGelfDecoder` with a maximum message length limit.
+    ///
+    /// Any messages longer than `max_length` bytes will be discarded entirely.
+    pub fn new_with_max_length(timeout: Duration, max_length: usize) -> Self {
+        Self {
+            max_length,
+            ..Self::new(timeout)
+        }
+    }
+
+    /// Adds a chunk, returning its reassembled message if it was the last
+    /// chunk missing.
+    fn add_chunk(&mut self, chunk: Bytes) -> Result<Option<Bytes>, ChunkedGelfDecoderError> {
+        let now = Instant::now();
+        let timeout = self.timeout;
+        let count = self.messages.len();
+        self.messages
+            .retain(|_, message| now.duration_since(message.first_received) < timeout);
+        if self.messages.len() < count {
+            warn!(
+                message = "Discarding incomplete chunked messages.",
+                count = count - self.messages.len(),
+                internal_log_rate_secs = 30
+            );
+        }
+
+        if chunk.len() < CHUNK_HEADER_LENGTH {
+            return Err(ChunkedGelfDecoderError::TruncatedChunk);
+        }
+        let mut id = [0; 8];
+        id.copy_from_slice(&chunk[2..10]);
+        let (number, count) = (chunk[10], chunk[11]);
+        if count == 0 || count > MAX_CHUNKS || number >= count {
+            return Err(ChunkedGelfDecoderError::InvalidSequence { number, count });
+        }
+
+        if self.messages.len() >= MAX_PENDING_MESSAGES && !self.messages.contains_key(&id) {
+            return Err(ChunkedGelfDecoderError::TooManyPendingMessages);
+        }
+        let message = self.messages.entry(id).or_insert_with(|| ChunkedMessage {
+            first_received: now,
+            chunks: vec![None; count as usize],
+            received: 0,
+            length: 0,
+        });
+        if message.chunks.len() != count as usize {
+            self.messages.remove(&id);
+            return Err(ChunkedGelfDecoderError::InvalidSequence { number, count });
+        }
+        // The duplicated chunks are ignored.
+        if message.chunks[number as usize].is_none() {
+            let data = chunk.slice(CHUNK_HEADER_LENGTH..);
+            message.length += data.len();
+            message.received += 1;
+            message.chunks[number as usize] = Some(data);
+        }
+        if message.length > self.max_length {
+            self.messages.remove(&id);
+            return Err(ChunkedGelfDecoderError::MessageTooLong {
+                max_length: self.max_length,
+            });
+        }
+        if message.received < message.chunks.len() {
+            return Ok(None);
+        }
+
+        let message = self.messages.remove(&id).expect("message was just added");
+        let mut payload = BytesMut::with_capacity(message.length);
+        for data in message.chunks.into_iter().flatten() {
+            payload.extend_from_slice(&data);
+        }
+        Ok(Some(payload.freeze()))
+    }
+
+    /// Decompresses a message if it's compressed with gzip or zlib.
+    fn decompress(&self, payload: Bytes) -> Result<Bytes, ChunkedGelfDecoderError> {
+        let limit = (self.max_length as u64).saturating_add(1);
+        let mut decompressed = Vec::new();
+        let result = if payload.starts_with(&GZIP_MAGIC) {
+            GzDecoder::new(payload.as_ref())
+                .take(limit)
+                .read_to_end(&mut decompressed)
+        } else if is_zlib(&payload) {
+            ZlibDecoder::new(payload.as_ref())
+                .take(limit)
+                .read_to_end(&mut decompressed)
+        } else if payload.len() > self.max_length {
+            return Err(ChunkedGelfDecoderError::MessageTooLong {
+                max_length: self.max_length,
+            });
+        } else {
+            return Ok(payload);
+        };
+        result.map_err(|source| ChunkedGelfDecoderError::Decompression { source })?;
+        if decompressed.len() > self.max_length {
+            return Err(ChunkedGelfDecoderError::MessageTooLong {
+                max_length: self.max_length,
+            });
+        }
+        Ok(decompressed.into())
+    }
+}
+
+/// Whether a payload starts with a zlib header using the deflate method.
+fn is_zlib(payload: &[u8]) -> bool {
+    match payload {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+impl Decoder for ChunkedGelfDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+        let datagram = src.split().freeze();
+        let payload = if datagram.starts_with(&CHUNK_MAGIC) {
+            match self.add_chunk(datagram)? {
+                Some(payload) => payload,
+                None => return Ok(None),
+            }
+        } else {
+            datagram
+        };
+        Ok(Some(self.decompress(payload)?))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, Self::Error> {
+        self.decode(src)
+    }
+}
+
+#[derive(Debug)]
+enum ChunkedGelfDecoderError {
+    TruncatedChunk,
+    InvalidSequence { number: u8, count: u8 },
+    MessageTooLong { max_length: usize },
+    TooManyPendingMessages,
+    Decompression { source: io::Error },
+}
+
+impl fmt::Display for ChunkedGelfDecoderError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TruncatedChunk => write!(formatter, "Truncated GELF chunk"),
+            Self::InvalidSequence { number, count } => write!(
+                formatter,
+                "Invalid GELF chunk sequence number {} of {}",
+                number, count
+            ),
+            Self::MessageTooLong { max_length } => write!(
+                formatter,
+                "GELF message is longer than max_length {}",
+                max_length
+            ),
+            Self::TooManyPendingMessages => write!(
+                formatter,
+                "Too many incomplete GELF messages, more than {}",
+                MAX_PENDING_MESSAGES
+            ),
+            Self::Decompression { source } => {
+                write!(formatter, "Failed to decompress GELF message: {}", source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChunkedGelfDecoderError {}
+
+impl StreamDecodingError for ChunkedGelfDecoderError {
+    fn can_continue(&self) -> bool {
+        // Each datagram holds a whole chunk, so the next datagrams can always be decoded.
+        true
+    }
+}
+
+impl FramingError for ChunkedGelfDecoderError {}
+
+impl From<ChunkedGelfDecoderError> for BoxedFramingError {
+    fn from(error: ChunkedGelfDecoderError) -> Self {
+        Box::new(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{
+        write::{GzEncoder, ZlibEncoder},
+        Compression,
+    };
+    use std::io::Write;
+
+    fn chunk(id: u64, number: u8, count: u8, data: &[u8]) -> BytesMut {
+        let mut chunk = BytesMut::from(&CHUNK_MAGIC[..]);
+        chunk.extend_from_slice(&id.to_be_bytes());
+        chunk.extend_from_slice(&[number, count]);
+        chunk.extend_from_slice(data);
+        chunk
+    }
+
+    #[test]
+    fn decode_unchunked() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5));
+        let mut input = BytesMut::from(r#"{"short_message":"foo"}"#);
+
+        assert_eq!(
+            decoder.decode(&mut input).unwrap().unwrap(),
+            r#"{"short_message":"foo"}"#
+        );
+        assert!(input.is_empty());
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_chunks_out_of_order() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5));
+
+        assert_eq!(decoder.decode(&mut chunk(1, 2, 3, b"baz")).unwrap(), None);
+        assert_eq!(decoder.decode(&mut chunk(2, 0, 2, b"qux")).unwrap(), None);
+        assert_eq!(decoder.decode(&mut chunk(1, 0, 3, b"foo")).unwrap(), None);
+        assert_eq!(decoder.decode(&mut chunk(1, 0, 3, b"foo")).unwrap(), None);
+        assert_eq!(
+            decoder
+                .decode(&mut chunk(1, 1, 3, b"bar"))
+                .unwrap()
+                .unwrap(),
+            "foobarbaz"
+        );
+        assert_eq!(decoder.messages.len(), 1);
+    }
+
+    #[test]
+    fn decode_compressed_chunks() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5));
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"foobarbaz").unwrap();
+        let compressed = encoder.finish().unwrap();
+        let (first, second) = compressed.split_at(compressed.len() / 2);
+
+        assert_eq!(decoder.decode(&mut chunk(1, 0, 2, first)).unwrap(), None);
+        assert_eq!(
+            decoder
+                .decode(&mut chunk(1, 1, 2, second))
+                .unwrap()
+                .unwrap(),
+            "foobarbaz"
+        );
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"foobarbaz").unwrap();
+        let mut input = BytesMut::from(&encoder.finish().unwrap()[..]);
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foobarbaz");
+    }
+
+    #[test]
+    fn decode_drops_expired_chunks() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_millis(10));
+
+        assert_eq!(decoder.decode(&mut chunk(1, 0, 2, b"foo")).unwrap(), None);
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(decoder.decode(&mut chunk(1, 1, 2, b"bar")).unwrap(), None);
+        assert_eq!(decoder.messages.len(), 1);
+    }
+
+    #[test]
+    fn decode_error_invalid_chunks() {
+        let mut decoder = ChunkedGelfDecoder::new_with_max_length(Duration::from_secs(5), 4);
+
+        assert!(decoder.decode(&mut chunk(1, 2, 2, b"foo")).is_err());
+        assert!(decoder.decode(&mut chunk(1, 0, 129, b"foo")).is_err());
+        assert!(decoder
+            .decode(&mut BytesMut::from(&CHUNK_MAGIC[..]))
+            .is_err());
+
+        assert_eq!(decoder.decode(&mut chunk(1, 0, 2, b"foo")).unwrap(), None);
+        let error = decoder.decode(&mut chunk(1, 1, 2, b"bar")).unwrap_err();
+        assert!(error.can_continue());
+        assert!(decoder.messages.is_empty());
+    }
+
+    #[test]
+    fn decode_error_decompressed_too_long() {
+        let mut decoder = ChunkedGelfDecoder::new_with_max_length(Duration::from_secs(5), 1024);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[b'a'; 4096]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let (first, second) = compressed.split_at(compressed.len() / 2);
+
+        assert_eq!(decoder.decode(&mut chunk(1, 0, 2, first)).unwrap(), None);
+        let error = decoder.decode(&mut chunk(1, 1, 2, second)).unwrap_err();
+        assert!(error.to_string().contains("max_length 1024"));
+    }
+
+    #[test]
+    fn decode_error_too_many_pending_messages() {
+        let mut decoder = ChunkedGelfDecoder::new(Duration::from_secs(5));
+        for id in 0..MAX_PENDING_MESSAGES as u64 {
+            assert_eq!(decoder.decode(&mut chunk(id, 0, 2, b"foo")).unwrap(), None);
+        }
+
+        let error = decoder
+            .decode(&mut chunk(MAX_PENDING_MESSAGES as u64, 0, 2, b"foo"))
+            .unwrap_err();
+        assert!(error.can_continue());
+        assert_eq!(decoder.messages.len(), MAX_PENDING_MESSAGES);
+        // The messages already pending can still be completed.
+        assert_eq!(
+            decoder
+                .decode(&mut chunk(0, 1, 2, b"bar"))
+                .unwrap()
+                .unwrap(),
+            "foobar"
+        );
+    }
+}
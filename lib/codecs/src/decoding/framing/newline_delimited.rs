@@ -34,6 +34,13 @@ impl NewlineDelimitedDecoderOptions {
             max_length: Some(max_length),
         }
     }
+
+    /// Limits the frames to `max_length` bytes, unless a maximum length is
+    /// already set.
+    pub fn or_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = self.max_length.or(Some(max_length));
+        self
+    }
 }
 
 impl NewlineDelimitedDecoderConfig {
@@ -38,6 +38,15 @@ pub struct OctetCountingDecoderOptions {
     max_length: Option<usize>,
 }
 
+impl OctetCountingDecoderOptions {
+    /// Limits the frames to `max_length` bytes, unless a maximum length is
+    /// already set.
+    pub fn or_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = self.max_length.or(Some(max_length));
+        self
+    }
+}
+
 /// Codec using the `Octet Counting` format as specified in
 /// https://tools.ietf.org/html/rfc6587#section-3.4.1.
 #[derive(Clone, Debug)]
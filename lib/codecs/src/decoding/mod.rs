@@ -7,18 +7,19 @@ pub mod framing;
 
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, JsonDeserializer,
-    JsonDeserializerConfig, NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
-    NativeJsonDeserializerConfig,
+    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
+    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, NativeDeserializer,
+    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
 };
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
-    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
-    LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
-    OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, ChunkedGelfDecoderOptions, FramingError, LengthDelimitedDecoder,
+    LengthDelimitedDecoderConfig, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    NewlineDelimitedDecoderOptions, OctetCountingDecoder, OctetCountingDecoderConfig,
+    OctetCountingDecoderOptions,
 };
 
 use bytes::{Bytes, BytesMut};
@@ -68,7 +69,7 @@ impl StreamDecodingError for Error {
 // Unfortunately, copying options of the nested enum variants is necessary
 // since `serde` doesn't allow `flatten`ing these:
 // https://github.com/serde-rs/serde/issues/1402.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(tag = "method", rename_all = "snake_case")]
 pub enum FramingConfig {
     /// Configures the `BytesDecoder`.
@@ -78,6 +79,15 @@ pub enum FramingConfig {
         /// Options for the character delimited decoder.
         character_delimited: CharacterDelimitedDecoderOptions,
     },
+    /// Configures the `ChunkedGelfDecoder`.
+    ChunkedGelf {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Options for the chunked GELF decoder.
+        chunked_gelf: ChunkedGelfDecoderOptions,
+    },
     /// Configures the `LengthDelimitedDecoder`.
     LengthDelimited,
     /// Configures the `NewlineDelimitedDecoder`.
@@ -114,6 +124,14 @@ impl From<CharacterDelimitedDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<ChunkedGelfDecoderConfig> for FramingConfig {
+    fn from(config: ChunkedGelfDecoderConfig) -> Self {
+        Self::ChunkedGelf {
+            chunked_gelf: config.chunked_gelf,
+        }
+    }
+}
+
 impl From<LengthDelimitedDecoderConfig> for FramingConfig {
     fn from(_: LengthDelimitedDecoderConfig) -> Self {
         Self::LengthDelimited
@@ -137,6 +155,27 @@ impl From<OctetCountingDecoderConfig> for FramingConfig {
 }
 
 impl FramingConfig {
+    /// Limits the frames to `max_length` bytes, unless the framing sets a
+    /// maximum length of its own, or doesn't support one.
+    pub fn or_max_length(self, max_length: usize) -> Self {
+        match self {
+            FramingConfig::CharacterDelimited {
+                character_delimited,
+            } => FramingConfig::CharacterDelimited {
+                character_delimited: character_delimited.or_max_length(max_length),
+            },
+            FramingConfig::NewlineDelimited { newline_delimited } => {
+                FramingConfig::NewlineDelimited {
+                    newline_delimited: newline_delimited.or_max_length(max_length),
+                }
+            }
+            FramingConfig::OctetCounting { octet_counting } => FramingConfig::OctetCounting {
+                octet_counting: octet_counting.or_max_length(max_length),
+            },
+            config => config,
+        }
+    }
+
     /// Build the `Framer` from this configuration.
     pub fn build(self) -> Framer {
         match self {
@@ -149,6 +188,9 @@ impl FramingConfig {
                 }
                 .build(),
             ),
+            FramingConfig::ChunkedGelf { chunked_gelf } => {
+                Framer::ChunkedGelf(ChunkedGelfDecoderConfig { chunked_gelf }.build())
+            }
             FramingConfig::LengthDelimited => {
                 Framer::LengthDelimited(LengthDelimitedDecoderConfig.build())
             }
@@ -169,6 +211,8 @@ pub enum Framer {
     Bytes(BytesDecoder),
     /// Uses a `CharacterDelimitedDecoder` for framing.
     CharacterDelimited(CharacterDelimitedDecoder),
+    /// Uses a `ChunkedGelfDecoder` for framing.
+    ChunkedGelf(ChunkedGelfDecoder),
     /// Uses a `LengthDelimitedDecoder` for framing.
     LengthDelimited(LengthDelimitedDecoder),
     /// Uses a `NewlineDelimitedDecoder` for framing.
@@ -187,6 +231,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode(src),
             Framer::CharacterDelimited(framer) => framer.decode(src),
+            Framer::ChunkedGelf(framer) => framer.decode(src),
             Framer::LengthDelimited(framer) => framer.decode(src),
             Framer::NewlineDelimited(framer) => framer.decode(src),
             Framer::OctetCounting(framer) => framer.decode(src),
@@ -198,6 +243,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode_eof(src),
             Framer::CharacterDelimited(framer) => framer.decode_eof(src),
+            Framer::ChunkedGelf(framer) => framer.decode_eof(src),
             Framer::LengthDelimited(framer) => framer.decode_eof(src),
             Framer::NewlineDelimited(framer) => framer.decode_eof(src),
             Framer::OctetCounting(framer) => framer.decode_eof(src),
@@ -210,13 +256,15 @@ impl tokio_util::codec::Decoder for Framer {
 // Unfortunately, copying options of the nested enum variants is necessary
 // since `serde` doesn't allow `flatten`ing these:
 // https://github.com/serde-rs/serde/issues/1402.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(tag = "codec", rename_all = "snake_case")]
 pub enum DeserializerConfig {
     /// Configures the `BytesDeserializer`.
     Bytes,
     /// Configures the `JsonDeserializer`.
     Json,
+    /// Configures the `GelfDeserializer`.
+    Gelf,
     #[cfg(feature = "syslog")]
     /// Configures the `SyslogDeserializer`.
     Syslog,
@@ -238,6 +286,12 @@ impl From<JsonDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<GelfDeserializerConfig> for DeserializerConfig {
+    fn from(_: GelfDeserializerConfig) -> Self {
+        Self::Gelf
+    }
+}
+
 #[cfg(feature = "syslog")]
 impl From<SyslogDeserializerConfig> for DeserializerConfig {
     fn from(_: SyslogDeserializerConfig) -> Self {
@@ -251,6 +305,7 @@ impl DeserializerConfig {
         match self {
             DeserializerConfig::Bytes => Deserializer::Bytes(BytesDeserializerConfig.build()),
             DeserializerConfig::Json => Deserializer::Json(JsonDeserializerConfig.build()),
+            DeserializerConfig::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => Deserializer::Syslog(SyslogDeserializerConfig.build()),
             DeserializerConfig::Native => Deserializer::Native(NativeDeserializerConfig.build()),
@@ -265,6 +320,7 @@ impl DeserializerConfig {
         match self {
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(),
             DeserializerConfig::Json => JsonDeserializerConfig.schema_definition(),
+            DeserializerConfig::Gelf => GelfDeserializerConfig.schema_definition(),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig.schema_definition(),
             DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(),
@@ -280,6 +336,8 @@ pub enum Deserializer {
     Bytes(BytesDeserializer),
     /// Uses a `JsonDeserializer` for deserialization.
     Json(JsonDeserializer),
+    /// Uses a `GelfDeserializer` for deserialization.
+    Gelf(GelfDeserializer),
     #[cfg(feature = "syslog")]
     /// Uses a `SyslogDeserializer` for deserialization.
     Syslog(SyslogDeserializer),
@@ -296,6 +354,7 @@ impl format::Deserializer for Deserializer {
         match self {
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes),
             Deserializer::Json(deserializer) => deserializer.parse(bytes),
+            Deserializer::Gelf(deserializer) => deserializer.parse(bytes),
             #[cfg(feature = "syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes),
             Deserializer::Native(deserializer) => deserializer.parse(bytes),
@@ -0,0 +1,229 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value as JsonValue};
+use tokio_util::codec::Encoder;
+use value::Kind;
+use vector_core::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The version of GELF which is produced.
+const GELF_VERSION: &str = "1.1";
+
+/// The fields of the GELF specification which are passed through as is.
+const STANDARD_FIELDS: [&str; 5] = ["full_message", "level", "facility", "line", "file"];
+
+/// Config used to build a `GelfSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GelfSerializerConfig;
+
+impl GelfSerializerConfig {
+    /// Creates a new `GelfSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `GelfSerializer` from this configuration.
+    pub const fn build(&self) -> GelfSerializer {
+        GelfSerializer
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+            .require_meaning(log_schema().message_key(), Kind::any())
+            .require_meaning(log_schema().host_key(), Kind::any())
+    }
+}
+
+/// Serializer that converts a log `Event` to bytes using the GELF 1.1 format.
+///
+/// The short message, the host and the timestamp are read from the keys of
+/// the log schema. The fields which aren't part of the GELF specification are
+/// sent as additional fields, whose names are prefixed with an underscore and
+/// joined with underscores when they're nested.
+#[derive(Debug, Clone)]
+pub struct GelfSerializer;
+
+impl GelfSerializer {
+    /// Creates a new `GelfSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for GelfSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = match event {
+            Event::Log(log) => log,
+            Event::Metric(_) | Event::Trace(_) => {
+                return Err("GELF only supports log events".into())
+            }
+        };
+        let message = to_gelf(log)?;
+        serde_json::to_writer(buffer.writer(), &message).map_err(Into::into)
+    }
+}
+
+fn to_gelf(mut log: LogEvent) -> vector_core::Result<Map<String, JsonValue>> {
+    let message_key = log_schema().message_key();
+    let host_key = log_schema().host_key();
+    let timestamp_key = log_schema().timestamp_key();
+
+    let mut message = Map::new();
+    message.insert("version".into(), GELF_VERSION.into());
+
+    let short_message = log
+        .get_by_meaning(message_key)
+        .or_else(|| log.get(message_key))
+        .map(Value::to_string_lossy)
+        .filter(|short_message| !short_message.is_empty())
+        .ok_or("GELF requires a non-empty message")?;
+    message.insert("short_message".into(), short_message.into());
+    log.remove(message_key);
+
+    let host = log
+        .get_by_meaning(host_key)
+        .or_else(|| log.get(host_key))
+        .map(Value::to_string_lossy)
+        .ok_or("GELF requires a host")?;
+    message.insert("host".into(), host.into());
+    log.remove(host_key);
+
+    if let Some(Value::Timestamp(timestamp)) = log.remove(timestamp_key) {
+        let microseconds =
+            timestamp.timestamp() * 1_000_000 + i64::from(timestamp.timestamp_subsec_micros());
+        let seconds = microseconds as f64 / 1e6;
+        if let Some(seconds) = Number::from_f64(seconds) {
+            message.insert("timestamp".into(), seconds.into());
+        }
+    }
+
+    for field in STANDARD_FIELDS {
+        if let Some(value) = log.remove(field) {
+            if let Some(value) = to_gelf_value(value) {
+                message.insert(field.into(), value);
+            }
+        }
+    }
+
+    let (fields, _) = log.into_parts();
+    for (key, value) in fields {
+        insert_additional_field(&mut message, additional_field_name(&key), value);
+    }
+
+    Ok(message)
+}
+
+/// Inserts an additional field, flattening the objects into several fields.
+fn insert_additional_field(message: &mut Map<String, JsonValue>, name: String, value: Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let name = format!("{}_{}", name, sanitize_field_name(&key));
+                insert_additional_field(message, name, value);
+            }
+        }
+        // The `_id` field is reserved by Graylog.
+        _ if name == "_id" => {}
+        value => {
+            if let Some(value) = to_gelf_value(value) {
+                message.insert(name, value);
+            }
+        }
+    }
+}
+
+/// Returns the name of the additional field of a top-level field, which may
+/// already have the leading underscore.
+fn additional_field_name(key: &str) -> String {
+    let name = sanitize_field_name(key);
+    if name.starts_with('_') {
+        name
+    } else {
+        format!("_{}", name)
+    }
+}
+
+/// Replaces the characters GELF doesn't allow in the field names.
+fn sanitize_field_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Converts a value to a GELF value, which can only be a string or a number.
+fn to_gelf_value(value: Value) -> Option<JsonValue> {
+    match value {
+        Value::Integer(integer) => Some(integer.into()),
+        Value::Float(float) => Number::from_f64(float.into_inner()).map(JsonValue::Number),
+        Value::Null => None,
+        value => Some(value.to_string_lossy().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+
+    fn serialize(event: Event) -> JsonValue {
+        let mut serializer = GelfSerializer::new();
+        let mut bytes = BytesMut::new();
+        serializer.encode(event, &mut bytes).unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn serialize_gelf() {
+        let event = Event::from(btreemap! {
+            log_schema().message_key() => "foo",
+            log_schema().host_key() => "example.org",
+            log_schema().timestamp_key() => Utc.timestamp(1385053862, 307_200_000),
+            "level" => 6,
+            "full_message" => "foo\nbar",
+            "_user_id" => 9001,
+            "ok" => true,
+            "id" => "reserved",
+            "kubernetes" => Value::Object(btreemap! {
+                "pod name" => "vector",
+                "labels" => Value::Array(vec!["a".into(), "b".into()]),
+            }),
+        });
+
+        assert_eq!(
+            serialize(event),
+            serde_json::json!({
+                "version": "1.1",
+                "short_message": "foo",
+                "host": "example.org",
+                "timestamp": 1385053862.3072,
+                "level": 6,
+                "full_message": "foo\nbar",
+                "_user_id": 9001,
+                "_ok": "true",
+                "_kubernetes_pod_name": "vector",
+                "_kubernetes_labels": "[\"a\",\"b\"]",
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_error_missing_host() {
+        let event = Event::from("foo");
+        let mut serializer = GelfSerializer::new();
+
+        assert!(serializer.encode(event, &mut BytesMut::new()).is_err());
+    }
+}
@@ -3,11 +3,13 @@
 
 #![deny(missing_docs)]
 
+mod gelf;
 mod json;
 mod native;
 mod native_json;
 mod raw_message;
 
+pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
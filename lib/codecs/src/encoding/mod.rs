@@ -5,8 +5,9 @@ pub mod format;
 pub mod framing;
 
 pub use format::{
-    JsonSerializer, JsonSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig,
-    NativeSerializer, NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig,
+    GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig,
+    NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
+    RawMessageSerializer, RawMessageSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -154,6 +155,8 @@ impl tokio_util::codec::Encoder<()> for Framer {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "codec", rename_all = "snake_case")]
 pub enum SerializerConfig {
+    /// Configures the `GelfSerializer`.
+    Gelf,
     /// Configures the `JsonSerializer`.
     Json,
     /// Configures the `NativeSerializer`.
@@ -164,6 +167,12 @@ pub enum SerializerConfig {
     RawMessage,
 }
 
+impl From<GelfSerializerConfig> for SerializerConfig {
+    fn from(_: GelfSerializerConfig) -> Self {
+        Self::Gelf
+    }
+}
+
 impl From<JsonSerializerConfig> for SerializerConfig {
     fn from(_: JsonSerializerConfig) -> Self {
         Self::Json
@@ -180,6 +189,7 @@ impl SerializerConfig {
     /// Build the `Serializer` from this configuration.
     pub const fn build(&self) -> Serializer {
         match self {
+            SerializerConfig::Gelf => Serializer::Gelf(GelfSerializerConfig.build()),
             SerializerConfig::Json => Serializer::Json(JsonSerializerConfig.build()),
             SerializerConfig::Native => Serializer::Native(NativeSerializerConfig.build()),
             SerializerConfig::NativeJson => {
@@ -194,6 +204,7 @@ impl SerializerConfig {
     /// The schema required by the serializer.
     pub fn schema_requirement(&self) -> schema::Requirement {
         match self {
+            SerializerConfig::Gelf => GelfSerializerConfig.schema_requirement(),
             SerializerConfig::Json => JsonSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
@@ -205,6 +216,8 @@ impl SerializerConfig {
 /// Serialize structured events as bytes.
 #[derive(Debug, Clone)]
 pub enum Serializer {
+    /// Uses a `GelfSerializer` for serialization.
+    Gelf(GelfSerializer),
     /// Uses a `JsonSerializer` for serialization.
     Json(JsonSerializer),
     /// Uses a `NativeSerializer` for serialization.
@@ -215,6 +228,12 @@ pub enum Serializer {
     RawMessage(RawMessageSerializer),
 }
 
+impl From<GelfSerializer> for Serializer {
+    fn from(serializer: GelfSerializer) -> Self {
+        Self::Gelf(serializer)
+    }
+}
+
 impl From<JsonSerializer> for Serializer {
     fn from(serializer: JsonSerializer) -> Self {
         Self::Json(serializer)
@@ -232,6 +251,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         match self {
+            Serializer::Gelf(serializer) => serializer.encode(event, buffer),
             Serializer::Json(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
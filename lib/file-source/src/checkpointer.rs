@@ -179,6 +179,17 @@ impl CheckpointsView {
             self.update(fng, pos);
         }
 
+        // The files fingerprinted by their device and inode keep their
+        // checkpoints when their first bytes are added to the fingerprint.
+        if let FileFingerprint::DevInodeChecksum(dev, ino, _) = fng {
+            if let Some((_, pos)) = self
+                .checkpoints
+                .remove(&FileFingerprint::DevInode(dev, ino))
+            {
+                self.update(fng, pos);
+            }
+        }
+
         if self.checkpoints.get(&fng).is_none() {
             if let Ok(Some(fingerprint)) =
                 fingerprinter.get_legacy_checksum(path, fingerprint_buffer)
@@ -225,6 +236,7 @@ impl Checkpointer {
             BytesChecksum(c) => format!("g{:x}.{}", c, pos),
             FirstLinesChecksum(c) => format!("h{:x}.{}", c, pos),
             DevInode(dev, ino) => format!("i{:x}.{:x}.{}", dev, ino, pos),
+            DevInodeChecksum(dev, ino, c) => format!("j{:x}.{:x}.{:x}.{}", dev, ino, c, pos),
             Unknown(x) => format!("{:x}.{}", x, pos),
         };
         self.directory.join(path)
@@ -256,6 +268,18 @@ impl Checkpointer {
                         .unwrap();
                 (DevInode(dev, ino), pos)
             }
+            'j' => {
+                let (dev, ino, c, pos) = scan_fmt!(
+                    file_name,
+                    "j{x}.{x}.{x}.{}",
+                    [hex u64],
+                    [hex u64],
+                    [hex u64],
+                    FilePosition
+                )
+                .unwrap();
+                (DevInodeChecksum(dev, ino, c), pos)
+            }
             _ => {
                 let (c, pos) = scan_fmt!(file_name, "{x}.{}", [hex u64], FilePosition).unwrap();
                 (Unknown(c), pos)
@@ -390,6 +414,15 @@ impl Checkpointer {
         }
     }
 
+    /// Read the persisted checkpoints from disk, without recovering them from
+    /// an interrupted process nor writing them back, so that the checkpoints
+    /// of a running process can be inspected.
+    pub fn inspect_checkpoints(&self) -> Result<(), io::Error> {
+        let state = self.read_checkpoints_file(&self.stable_file_path)?;
+        self.checkpoints.set_state(state, None);
+        Ok(())
+    }
+
     fn read_checkpoints_file(&self, path: &Path) -> Result<State, io::Error> {
         let reader = io::BufReader::new(fs::File::open(path)?);
         serde_json::from_reader(reader).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
@@ -432,6 +465,7 @@ mod test {
     fn test_checkpointer_basics() {
         let fingerprints = vec![
             FileFingerprint::DevInode(1, 2),
+            FileFingerprint::DevInodeChecksum(1, 2, 3456),
             FileFingerprint::BytesChecksum(3456),
             FileFingerprint::FirstLinesChecksum(78910),
             FileFingerprint::Unknown(1337),
@@ -603,6 +637,68 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checkpointer_fingerprint_upgrades_dev_inode() {
+        let log_dir = tempdir().unwrap();
+        let path = log_dir.path().join("test.log");
+        std::fs::write(&path, "hello\n").unwrap();
+
+        let old_fingerprint = FileFingerprint::DevInode(1, 2);
+        let new_fingerprint = FileFingerprint::DevInodeChecksum(1, 2, 3456);
+        let position: FilePosition = 6;
+
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::DevInodeChecksum {
+                bytes: 6,
+                ignored_header_bytes: 0,
+            },
+            max_line_length: 102400,
+            ignore_not_found: false,
+        };
+
+        let mut buf = Vec::new();
+
+        let data_dir = tempdir().unwrap();
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.update_checkpoint(old_fingerprint, position);
+            chkptr.write_checkpoints().ok();
+        }
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.read_checkpoints(None);
+            assert_eq!(chkptr.get_checkpoint(new_fingerprint), None);
+
+            chkptr.maybe_upgrade(&path, new_fingerprint, &fingerprinter, &mut buf);
+
+            assert_eq!(chkptr.get_checkpoint(new_fingerprint), Some(position));
+            assert_eq!(chkptr.get_checkpoint(old_fingerprint), None);
+        }
+    }
+
+    #[test]
+    fn test_checkpointer_inspect() {
+        let fingerprint = FileFingerprint::DevInodeChecksum(1, 2, 3456);
+        let position: FilePosition = 1234;
+
+        let data_dir = tempdir().unwrap();
+        {
+            let chkptr = Checkpointer::new(data_dir.path());
+            assert!(chkptr.inspect_checkpoints().is_err());
+            assert!(!data_dir.path().join(STABLE_FILE_NAME).exists());
+        }
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.update_checkpoint(fingerprint, position);
+            chkptr.write_checkpoints().unwrap();
+        }
+        {
+            let chkptr = Checkpointer::new(data_dir.path());
+            chkptr.inspect_checkpoints().unwrap();
+            assert_eq!(chkptr.get_checkpoint(fingerprint), Some(position));
+        }
+    }
+
     #[test]
     fn test_checkpointer_file_upgrades() {
         let fingerprint = FileFingerprint::DevInode(1, 2);
@@ -21,6 +21,7 @@ use crate::{
     checkpointer::{Checkpointer, CheckpointsView},
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
+    framer::FrameDecoderBuilder,
     paths_provider::PathsProvider,
     FileSourceInternalEvents, ReadFrom,
 };
@@ -45,6 +46,7 @@ where
     pub ignore_before: Option<DateTime<Utc>>,
     pub max_line_bytes: usize,
     pub line_delimiter: Bytes,
+    pub frame_decoder: Option<FrameDecoderBuilder>,
     pub data_dir: PathBuf,
     pub glob_minimum_cooldown: Duration,
     pub fingerprinter: Fingerprinter,
@@ -239,6 +241,24 @@ where
                                     }
                                 }
                             }
+                        } else if let Some(old_file_id) = rewritten_file_id(&fp_map, file_id) {
+                            // The watched file has been truncated then written to
+                            // with other first bytes, so its watcher, which reads
+                            // it again from the beginning, is kept under the new
+                            // fingerprint instead of reading the file twice.
+                            info!(
+                                message = "Watched file has been rewritten from its beginning.",
+                                path = ?path,
+                            );
+                            let mut watcher = fp_map
+                                .shift_remove(&old_file_id)
+                                .expect("rewritten file is watched");
+                            checkpoints.set_dead(old_file_id);
+                            watcher.set_file_findable(true);
+                            if watcher.path != path {
+                                watcher.update_path(path).ok(); // ok if this fails: might fix next cycle
+                            }
+                            fp_map.insert(file_id, watcher);
                         } else {
                             // untracked file fingerprint
                             self.watch_new_file(path, file_id, &mut fp_map, &checkpoints, false);
@@ -419,6 +439,10 @@ where
             self.line_delimiter.clone(),
         ) {
             Ok(mut watcher) => {
+                if let Some(frame_decoder) = &self.frame_decoder {
+                    watcher.set_frame_decoder(frame_decoder());
+                }
+                watcher.set_detect_truncation(self.fingerprinter.strategy.detects_truncation());
                 if let ReadFrom::Checkpoint(file_position) = read_from {
                     self.emitter.emit_file_resumed(&path, file_position);
                 } else {
@@ -432,6 +456,27 @@ where
     }
 }
 
+/// Finds the watched file with the same device and inode as an untracked
+/// fingerprint, but other first bytes, which is the same file rewritten since.
+fn rewritten_file_id(
+    fp_map: &IndexMap<FileFingerprint, FileWatcher>,
+    file_id: FileFingerprint,
+) -> Option<FileFingerprint> {
+    let (dev, ino) = match file_id {
+        FileFingerprint::DevInodeChecksum(dev, ino, _) => (dev, ino),
+        _ => return None,
+    };
+    fp_map
+        .keys()
+        .copied()
+        .find(|old_file_id| match old_file_id {
+            FileFingerprint::DevInodeChecksum(old_dev, old_ino, _) => {
+                (*old_dev, *old_ino) == (dev, ino)
+            }
+            _ => false,
+        })
+}
+
 /// A sentinel type to signal that file server was gracefully shut down.
 ///
 /// The purpose of this type is to clarify the semantics of the result values
@@ -6,12 +6,15 @@ use std::{
 };
 
 use bytes::{Bytes, BytesMut};
+use bzip2::bufread::MultiBzDecoder;
 use chrono::{DateTime, Utc};
 use flate2::bufread::MultiGzDecoder;
-use tracing::debug;
+use tracing::{debug, info};
+use xz2::bufread::XzDecoder;
 
 use crate::{
-    buffer::read_until_with_max_size, metadata_ext::PortableFileExt, FilePosition, ReadFrom,
+    buffer::read_until_with_max_size, framer::FrameDecoder, metadata_ext::PortableFileExt,
+    FilePosition, ReadFrom,
 };
 #[cfg(test)]
 mod tests;
@@ -30,11 +33,14 @@ pub struct FileWatcher {
     file_position: FilePosition,
     devno: u64,
     inode: u64,
+    compressed: bool,
+    detect_truncation: bool,
     is_dead: bool,
     last_read_attempt: Instant,
     last_read_success: Instant,
     max_line_bytes: usize,
     line_delimiter: Bytes,
+    frame_decoder: Option<Box<dyn FrameDecoder>>,
     buf: BytesMut,
 }
 
@@ -65,53 +71,56 @@ impl FileWatcher {
             false
         };
 
-        let gzipped = is_gzipped(&mut reader)?;
+        let compression = Compression::detect(&mut reader)?;
 
         // Determine the actual position at which we should start reading
         let (reader, file_position): (Box<dyn BufRead>, FilePosition) =
-            match (gzipped, too_old, read_from) {
-                (true, true, _) => {
+            match (compression, too_old, read_from) {
+                (Some(compression), true, _) => {
                     debug!(
-                        message = "Not reading gzipped file older than `ignore_older`.",
+                        message = "Not reading compressed file older than `ignore_older`.",
                         ?path,
+                        ?compression,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, _, ReadFrom::Checkpoint(file_position)) => {
+                (Some(compression), _, ReadFrom::Checkpoint(file_position)) => {
                     debug!(
-                        message = "Not re-reading gzipped file with existing stored offset.",
+                        message = "Not re-reading compressed file with existing stored offset.",
                         ?path,
+                        ?compression,
                         %file_position
                     );
                     (Box::new(null_reader()), file_position)
                 }
-                // TODO: This may become the default, leading us to stop reading gzipped files that
-                // we were reading before. Should we merge this and the next branch to read
+                // TODO: This may become the default, leading us to stop reading compressed files
+                // that we were reading before. Should we merge this and the next branch to read
                 // compressed file from the beginning even when `read_from = "end"` (implicitly via
                 // default or explicitly via config)?
-                (true, _, ReadFrom::End) => {
+                (Some(compression), _, ReadFrom::End) => {
                     debug!(
                         message = "Can't read from the end of already-compressed file.",
                         ?path,
+                        ?compression,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, false, ReadFrom::Beginning) => {
-                    (Box::new(io::BufReader::new(MultiGzDecoder::new(reader))), 0)
+                (Some(compression), false, ReadFrom::Beginning) => {
+                    (compression.decoder(reader)?, 0)
                 }
-                (false, true, _) => {
+                (None, true, _) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Checkpoint(file_position)) => {
+                (None, false, ReadFrom::Checkpoint(file_position)) => {
                     let pos = reader.seek(io::SeekFrom::Start(file_position)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Beginning) => {
+                (None, false, ReadFrom::Beginning) => {
                     let pos = reader.seek(io::SeekFrom::Start(0)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::End) => {
+                (None, false, ReadFrom::End) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
                     (Box::new(reader), pos)
                 }
@@ -131,11 +140,14 @@ impl FileWatcher {
             file_position,
             devno,
             inode: ino,
+            compressed: compression.is_some(),
+            detect_truncation: false,
             is_dead: false,
             last_read_attempt: ts,
             last_read_success: ts,
             max_line_bytes,
             line_delimiter,
+            frame_decoder: None,
             buf: BytesMut::new(),
         })
     }
@@ -144,18 +156,17 @@ impl FileWatcher {
         let file_handle = File::open(&path)?;
         if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
             let mut reader = io::BufReader::new(fs::File::open(&path)?);
-            let gzipped = is_gzipped(&mut reader)?;
-            let new_reader: Box<dyn BufRead> = if gzipped {
-                if self.file_position != 0 {
-                    Box::new(null_reader())
-                } else {
-                    Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
+            let compression = Compression::detect(&mut reader)?;
+            let new_reader: Box<dyn BufRead> = match compression {
+                Some(_) if self.file_position != 0 => Box::new(null_reader()),
+                Some(compression) => compression.decoder(reader)?,
+                None => {
+                    reader.seek(io::SeekFrom::Start(self.reader_position()))?;
+                    Box::new(reader)
                 }
-            } else {
-                reader.seek(io::SeekFrom::Start(self.file_position))?;
-                Box::new(reader)
             };
             self.reader = new_reader;
+            self.compressed = compression.is_some();
             self.devno = file_handle.portable_dev()?;
             self.inode = file_handle.portable_ino()?;
         }
@@ -183,6 +194,58 @@ impl FileWatcher {
         self.file_position
     }
 
+    /// Splits the file into frames with the given decoder, instead of lines
+    /// ending with the line delimiter.
+    pub fn set_frame_decoder(&mut self, frame_decoder: Box<dyn FrameDecoder>) {
+        self.frame_decoder = Some(frame_decoder);
+    }
+
+    /// Reads the file again from the beginning when it shrinks below the
+    /// position read up to, as it does when truncated after being copied.
+    pub fn set_detect_truncation(&mut self, detect_truncation: bool) {
+        self.detect_truncation = detect_truncation;
+    }
+
+    /// Rewinds the reader to the beginning of the file if it's been truncated
+    /// since it was read.
+    ///
+    /// The compressed files aren't checked, since their position is in their
+    /// decompressed bytes.
+    fn rewind_if_truncated(&mut self) -> io::Result<()> {
+        if !self.detect_truncation || self.compressed {
+            return Ok(());
+        }
+        let file_handle = File::open(&self.path)?;
+        if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
+            // The path has been rotated away from the file being read.
+            return Ok(());
+        }
+        let len = file_handle.metadata()?.len();
+        if len >= self.reader_position() {
+            return Ok(());
+        }
+
+        info!(
+            message = "File has been truncated, reading it from the beginning.",
+            path = ?self.path,
+            file_position = %self.file_position,
+            len = %len,
+        );
+        self.reader = Box::new(io::BufReader::new(file_handle));
+        self.file_position = 0;
+        self.buf.clear();
+        Ok(())
+    }
+
+    /// The position the reader is at, which is past the bytes buffered for the
+    /// next frame when the file is split into frames.
+    fn reader_position(&self) -> FilePosition {
+        match self.frame_decoder {
+            Some(_) => self.file_position + self.buf.len() as u64,
+            None => self.file_position,
+        }
+    }
+
     /// Read a single line from the underlying file
     ///
     /// This function will attempt to read a new line from its file, blocking,
@@ -191,6 +254,10 @@ impl FileWatcher {
     pub fn read_line(&mut self) -> io::Result<Option<Bytes>> {
         self.track_read_attempt();
 
+        if self.frame_decoder.is_some() {
+            return self.read_frame();
+        }
+
         let reader = &mut self.reader;
         let file_position = &mut self.file_position;
         match read_until_with_max_size(
@@ -218,6 +285,9 @@ impl FileWatcher {
                         Ok(Some(buf))
                     }
                 } else {
+                    // The lines written since the truncation are read on the
+                    // next call.
+                    self.rewind_if_truncated().ok(); // ok if this fails: might fix next read
                     Ok(None)
                 }
             }
@@ -230,6 +300,61 @@ impl FileWatcher {
         }
     }
 
+    /// Read a single frame from the underlying file
+    ///
+    /// The file position is only advanced past the bytes of the frames split
+    /// off the buffer, so that it's at the end of the frame returned. The frames
+    /// longer than `max_line_bytes` are discarded.
+    fn read_frame(&mut self) -> io::Result<Option<Bytes>> {
+        let frame_decoder = self
+            .frame_decoder
+            .as_mut()
+            .expect("frames are only read with a frame decoder");
+        loop {
+            let buffered = self.buf.len();
+            let frame = frame_decoder.decode(&mut self.buf);
+            self.file_position += (buffered - self.buf.len()) as u64;
+            if let Some(frame) = frame? {
+                self.last_read_success = Instant::now();
+                return Ok(Some(frame));
+            }
+            if self.buf.len() > self.max_line_bytes {
+                // The frame is too long to be buffered whole, so it's discarded.
+                let buffered = self.buf.len();
+                frame_decoder.discard(&mut self.buf);
+                self.file_position += (buffered - self.buf.len()) as u64;
+            }
+
+            let available = match self.reader.fill_buf() {
+                Ok(available) => available,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    if let io::ErrorKind::NotFound = e.kind() {
+                        self.is_dead = true;
+                    }
+                    return Err(e);
+                }
+            };
+            if available.is_empty() {
+                if self.findable {
+                    self.rewind_if_truncated().ok(); // ok if this fails: might fix next read
+                    return Ok(None);
+                }
+                // File has been deleted, so return the incomplete frame we have
+                // in the buffer, if any.
+                self.is_dead = true;
+                let buffered = self.buf.len();
+                let frame = frame_decoder.decode_eof(&mut self.buf);
+                self.file_position += (buffered - self.buf.len()) as u64;
+                return frame;
+            }
+
+            let used = available.len();
+            self.buf.extend_from_slice(available);
+            self.reader.consume(used);
+        }
+    }
+
     #[inline]
     fn track_read_attempt(&mut self) {
         self.last_read_attempt = Instant::now();
@@ -252,11 +377,66 @@ impl FileWatcher {
     }
 }
 
-fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
-    let header_bytes = r.fill_buf()?;
-    // WARN: The paired `BufReader::consume` is not called intentionally. If we
-    // do we'll chop a decent part of the potential gzip stream off.
-    Ok(header_bytes.starts_with(&[0x1f, 0x8b]))
+/// The compression formats the files are read whole, and only once, with, as
+/// rotated files are often compressed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    /// Detects the compression of the file by its magic bytes.
+    fn detect(r: &mut io::BufReader<fs::File>) -> io::Result<Option<Self>> {
+        let header_bytes = r.fill_buf()?;
+        // WARN: The paired `BufReader::consume` is not called intentionally. If we
+        // do we'll chop a decent part of the potential compressed stream off.
+        let compression = if header_bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(Compression::Gzip)
+        } else if header_bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Compression::Zstd)
+        } else if is_bzip2(header_bytes) {
+            Some(Compression::Bzip2)
+        } else if header_bytes.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Compression::Xz)
+        } else {
+            None
+        };
+        Ok(compression)
+    }
+
+    /// Decompresses the whole file, including its concatenated streams.
+    fn decoder(self, reader: io::BufReader<fs::File>) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Compression::Gzip => Box::new(io::BufReader::new(MultiGzDecoder::new(reader))),
+            Compression::Zstd => Box::new(io::BufReader::new(zstd::Decoder::with_buffer(reader)?)),
+            Compression::Bzip2 => Box::new(io::BufReader::new(MultiBzDecoder::new(reader))),
+            Compression::Xz => Box::new(io::BufReader::new(XzDecoder::new_multi_decoder(reader))),
+        })
+    }
+}
+
+/// Bzip2 streams start with `BZh`, their block size and the magic of their
+/// first block, which is checked too as text may well start with `BZh`.
+fn is_bzip2(header_bytes: &[u8]) -> bool {
+    matches!(
+        header_bytes,
+        [
+            b'B',
+            b'Z',
+            b'h',
+            b'1'..=b'9',
+            0x31,
+            0x41,
+            0x59,
+            0x26,
+            0x53,
+            0x59,
+            ..
+        ]
+    )
 }
 
 fn null_reader() -> impl BufRead {
@@ -0,0 +1,114 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use bytes::Bytes;
+use chrono::{Duration, Utc};
+
+use crate::{file_watcher::FileWatcher, ReadFrom};
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn zstd(data: &[u8]) -> Vec<u8> {
+    zstd::encode_all(data, 0).unwrap()
+}
+
+fn bzip2(data: &[u8]) -> Vec<u8> {
+    let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn xz(data: &[u8]) -> Vec<u8> {
+    let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// Writes the data compressed as two concatenated streams, as appending to a
+/// compressed file does.
+fn write_compressed(path: &Path, compress: fn(&[u8]) -> Vec<u8>) {
+    let mut data = compress(b"first line\nsecond line\n");
+    data.extend(compress(b"third line\n"));
+    fs::write(path, data).unwrap();
+}
+
+fn read_lines(fw: &mut FileWatcher) -> io::Result<Vec<Bytes>> {
+    let mut lines = Vec::new();
+    while let Some(line) = fw.read_line()? {
+        lines.push(line);
+    }
+    Ok(lines)
+}
+
+fn file_watcher(path: &Path, read_from: ReadFrom, too_old: bool) -> FileWatcher {
+    let ignore_before = too_old.then(|| Utc::now() + Duration::hours(1));
+    FileWatcher::new(
+        path.to_owned(),
+        read_from,
+        ignore_before,
+        100_000,
+        Bytes::from("\n"),
+    )
+    .expect("must be able to create")
+}
+
+#[test]
+fn compressed_files_are_decompressed() {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    for (name, compress) in [
+        ("gzip", gzip as fn(&[u8]) -> Vec<u8>),
+        ("zstd", zstd),
+        ("bzip2", bzip2),
+        ("xz", xz),
+    ] {
+        let path = dir.path().join(format!("rotated.log.{}", name));
+        write_compressed(&path, compress);
+
+        let mut fw = file_watcher(&path, ReadFrom::Beginning, false);
+        assert_eq!(
+            read_lines(&mut fw).unwrap(),
+            vec![
+                Bytes::from("first line"),
+                Bytes::from("second line"),
+                Bytes::from("third line"),
+            ],
+            "{}",
+            name
+        );
+        assert_eq!(fw.get_file_position(), 34, "{}", name);
+    }
+}
+
+#[test]
+fn compressed_files_are_read_once() {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    for (name, compress) in [
+        ("gzip", gzip as fn(&[u8]) -> Vec<u8>),
+        ("zstd", zstd),
+        ("bzip2", bzip2),
+        ("xz", xz),
+    ] {
+        let path = dir.path().join(format!("rotated.log.{}", name));
+        write_compressed(&path, compress);
+
+        // The file has already been read up to its checkpoint.
+        let mut fw = file_watcher(&path, ReadFrom::Checkpoint(34), false);
+        assert!(read_lines(&mut fw).unwrap().is_empty(), "{}", name);
+        assert_eq!(fw.get_file_position(), 34, "{}", name);
+
+        // The file is older than `ignore_older`.
+        let mut fw = file_watcher(&path, ReadFrom::Beginning, true);
+        assert!(read_lines(&mut fw).unwrap().is_empty(), "{}", name);
+
+        // The end of the file can't be found without decompressing it.
+        let mut fw = file_watcher(&path, ReadFrom::End, false);
+        assert!(read_lines(&mut fw).unwrap().is_empty(), "{}", name);
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    fs,
+    io::{self, Write},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+
+use crate::{file_watcher::FileWatcher, FrameDecoder, ReadFrom};
+
+/// Frames prefixed with their length, as a single byte.
+struct LengthPrefixed;
+
+impl FrameDecoder for LengthPrefixed {
+    fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<Bytes>> {
+        match buf.first() {
+            Some(&len) if buf.len() > len as usize => {
+                buf.advance(1);
+                Ok(Some(buf.split_to(len as usize).freeze()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> io::Result<Option<Bytes>> {
+        match self.decode(buf)? {
+            Some(frame) => Ok(Some(frame)),
+            None if buf.is_empty() => Ok(None),
+            None => Ok(Some(buf.split().freeze())),
+        }
+    }
+}
+
+fn file_watcher(path: &std::path::Path, read_from: ReadFrom) -> FileWatcher {
+    let mut fw = FileWatcher::new(path.to_owned(), read_from, None, 100_000, Bytes::from("\n"))
+        .expect("must be able to create");
+    fw.set_frame_decoder(Box::new(LengthPrefixed));
+    fw
+}
+
+#[test]
+fn file_position_at_frame_boundaries() {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    let path = dir.path().join("a_file.bin");
+    let mut fp = fs::File::create(&path).expect("could not create");
+
+    let mut fw = file_watcher(&path, ReadFrom::Beginning);
+    fp.write_all(b"\x03foo\x07ba").unwrap();
+    fp.flush().unwrap();
+
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("foo")));
+    assert_eq!(fw.get_file_position(), 4);
+    // The second frame is incomplete, so the position stays at the end of the
+    // first one.
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert_eq!(fw.get_file_position(), 4);
+
+    fp.write_all(b"r\nbaz\x01").unwrap();
+    fp.flush().unwrap();
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("bar\nbaz")));
+    assert_eq!(fw.get_file_position(), 12);
+    assert_eq!(fw.read_line().unwrap(), None);
+
+    // Resuming from the position reads the next frame whole.
+    let mut fw = file_watcher(&path, ReadFrom::Checkpoint(12));
+    fp.write_all(b"!").unwrap();
+    fp.flush().unwrap();
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("!")));
+    assert_eq!(fw.get_file_position(), 14);
+}
+
+#[test]
+fn incomplete_frame_of_deleted_file() {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    let path = dir.path().join("a_file.bin");
+    let mut fp = fs::File::create(&path).expect("could not create");
+
+    let mut fw = file_watcher(&path, ReadFrom::Beginning);
+    fp.write_all(b"\x03foo\x07ba").unwrap();
+    fp.flush().unwrap();
+
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("foo")));
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert!(!fw.dead());
+
+    fs::remove_file(&path).unwrap();
+    fw.set_file_findable(false);
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("\x07ba")));
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert!(fw.dead());
+}
+
+#[test]
+fn discards_frames_longer_than_max_line_bytes() {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    let path = dir.path().join("a_file.bin");
+    let mut fp = fs::File::create(&path).expect("could not create");
+
+    let mut fw = FileWatcher::new(path, ReadFrom::Beginning, None, 4, Bytes::from("\n"))
+        .expect("must be able to create");
+    fw.set_frame_decoder(Box::new(LengthPrefixed));
+    fp.write_all(b"\x03foo\x09toolong").unwrap();
+    fp.flush().unwrap();
+
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("foo")));
+    // The buffered bytes of the long frame are skipped past.
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert_eq!(fw.get_file_position(), 12);
+}
@@ -1,5 +1,8 @@
+mod compression;
 mod experiment;
 mod experiment_no_truncations;
+mod frames;
+mod truncation;
 
 use std::str;
 
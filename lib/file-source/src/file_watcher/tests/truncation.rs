@@ -0,0 +1,66 @@
+use std::{fs, io::Write};
+
+use bytes::Bytes;
+
+use crate::{file_watcher::FileWatcher, ReadFrom};
+
+fn file_watcher(path: &std::path::Path, detect_truncation: bool) -> FileWatcher {
+    let mut fw = FileWatcher::new(
+        path.to_owned(),
+        ReadFrom::Beginning,
+        None,
+        100_000,
+        Bytes::from("\n"),
+    )
+    .expect("must be able to create");
+    fw.set_detect_truncation(detect_truncation);
+    fw
+}
+
+#[test]
+fn truncated_file_is_read_from_beginning() {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    let path = dir.path().join("a_file.log");
+    let mut fp = fs::File::create(&path).expect("could not create");
+
+    let mut fw = file_watcher(&path, true);
+    fp.write_all(b"first line\nsecond line\n").unwrap();
+    fp.flush().unwrap();
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("first line")));
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("second line")));
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert_eq!(fw.get_file_position(), 23);
+
+    // Truncate the file in place, like a copy-truncate rotation does.
+    fp.set_len(0).unwrap();
+    let mut fp = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    fp.write_all(b"third\n").unwrap();
+    fp.flush().unwrap();
+
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert_eq!(fw.get_file_position(), 0);
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("third")));
+    assert_eq!(fw.get_file_position(), 6);
+}
+
+#[test]
+fn truncation_not_detected_by_default() {
+    let dir = tempfile::TempDir::new().expect("could not create tempdir");
+    let path = dir.path().join("a_file.log");
+    let mut fp = fs::File::create(&path).expect("could not create");
+
+    let mut fw = file_watcher(&path, false);
+    fp.write_all(b"first line\nsecond line\n").unwrap();
+    fp.flush().unwrap();
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("first line")));
+    assert_eq!(fw.read_line().unwrap(), Some(Bytes::from("second line")));
+
+    fp.set_len(0).unwrap();
+    let mut fp = fs::OpenOptions::new().append(true).open(&path).unwrap();
+    fp.write_all(b"third\n").unwrap();
+    fp.flush().unwrap();
+
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert_eq!(fw.read_line().unwrap(), None);
+    assert_eq!(fw.get_file_position(), 23);
+}
@@ -32,6 +32,21 @@ pub enum FingerprintStrategy {
         lines: usize,
     },
     DevInode,
+    /// Combines the device and inode of the file with a checksum of its first
+    /// bytes, so that neither files sharing their first bytes nor reused
+    /// inodes are mistaken for one another.
+    DevInodeChecksum {
+        bytes: usize,
+        ignored_header_bytes: usize,
+    },
+}
+
+impl FingerprintStrategy {
+    /// Whether the files keep their fingerprint when truncated, so that the
+    /// truncation must be detected to read them again from the beginning.
+    pub const fn detects_truncation(&self) -> bool {
+        matches!(self, FingerprintStrategy::DevInodeChecksum { .. })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, Ord, PartialOrd)]
@@ -42,6 +57,8 @@ pub enum FileFingerprint {
     #[serde(alias = "first_line_checksum")]
     FirstLinesChecksum(u64),
     DevInode(u64, u64),
+    #[serde(rename = "device_and_inode_checksum")]
+    DevInodeChecksum(u64, u64, u64),
     Unknown(u64),
 }
 
@@ -58,6 +75,13 @@ impl FileFingerprint {
                 buf.write_all(&ino.to_be_bytes()).expect("writing to array");
                 FINGERPRINT_CRC.checksum(&buf[..])
             }
+            DevInodeChecksum(dev, ino, c) => {
+                let mut buf = Vec::with_capacity(std::mem::size_of_val(dev) * 3);
+                buf.write_all(&dev.to_be_bytes()).expect("writing to array");
+                buf.write_all(&ino.to_be_bytes()).expect("writing to array");
+                buf.write_all(&c.to_be_bytes()).expect("writing to array");
+                FINGERPRINT_CRC.checksum(&buf[..])
+            }
             Unknown(c) => *c,
         }
     }
@@ -84,6 +108,19 @@ impl Fingerprinter {
                 let ino = file_handle.portable_ino()?;
                 Ok(DevInode(dev, ino))
             }
+            FingerprintStrategy::DevInodeChecksum {
+                bytes,
+                ignored_header_bytes,
+            } => {
+                buffer.resize(bytes, 0u8);
+                let mut fp = fs::File::open(path)?;
+                let dev = fp.portable_dev()?;
+                let ino = fp.portable_ino()?;
+                fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
+                fp.read_exact(&mut buffer[..bytes])?;
+                let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..]);
+                Ok(DevInodeChecksum(dev, ino, fingerprint))
+            }
             FingerprintStrategy::Checksum {
                 ignored_header_bytes,
                 bytes: _,
@@ -439,6 +476,61 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_inode_checksum_fingerprint() {
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::DevInodeChecksum {
+                bytes: 16,
+                ignored_header_bytes: 0,
+            },
+            max_line_length: 42,
+            ignore_not_found: false,
+        };
+
+        let target_dir = tempdir().unwrap();
+        let small_data = vec![b'x'; 8];
+        let medium_data = vec![b'x'; 256];
+        let small_path = target_dir.path().join("small.log");
+        let medium_path = target_dir.path().join("medium.log");
+        let duplicate_path = target_dir.path().join("duplicate.log");
+        fs::write(&small_path, &small_data).unwrap();
+        fs::write(&medium_path, &medium_data).unwrap();
+        fs::write(&duplicate_path, &medium_data).unwrap();
+
+        let mut buf = Vec::new();
+        assert!(fingerprinter
+            .get_fingerprint_of_file(&small_path, &mut buf)
+            .is_err());
+        let medium = fingerprinter
+            .get_fingerprint_of_file(&medium_path, &mut buf)
+            .unwrap();
+        assert_ne!(
+            medium,
+            fingerprinter
+                .get_fingerprint_of_file(&duplicate_path, &mut buf)
+                .unwrap()
+        );
+
+        // Appending to the file doesn't change its fingerprint, but rewriting
+        // its first bytes does.
+        let mut appended_data = medium_data.clone();
+        appended_data.extend_from_slice(b"yyyy");
+        fs::write(&medium_path, &appended_data).unwrap();
+        assert_eq!(
+            medium,
+            fingerprinter
+                .get_fingerprint_of_file(&medium_path, &mut buf)
+                .unwrap()
+        );
+        fs::write(&medium_path, vec![b'y'; 256]).unwrap();
+        assert_ne!(
+            medium,
+            fingerprinter
+                .get_fingerprint_of_file(&medium_path, &mut buf)
+                .unwrap()
+        );
+    }
+
     #[test]
     fn no_error_on_dir() {
         let target_dir = tempdir().unwrap();
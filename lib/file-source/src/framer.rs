@@ -0,0 +1,29 @@
+use std::{io, sync::Arc};
+
+use bytes::{Bytes, BytesMut};
+
+/// Splits the content of a file into frames, instead of lines ending with the
+/// line delimiter.
+///
+/// The bytes read from the file are appended to the buffer the frames are split
+/// off, so that the file position reported with a frame is right after its last
+/// byte, and the frames are resumed from there when the file is reopened.
+pub trait FrameDecoder {
+    /// Splits the next frame off the buffer, if it holds a whole one.
+    fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<Bytes>>;
+
+    /// Splits the next frame off the buffer once the file is deleted, including
+    /// the trailing incomplete frame.
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> io::Result<Option<Bytes>>;
+
+    /// Discards the buffered bytes of a frame longer than the maximum line
+    /// length, so that its bytes read next are discarded as well, if the frame
+    /// end can be told apart.
+    fn discard(&mut self, buf: &mut BytesMut) {
+        buf.clear();
+    }
+}
+
+/// Builds the frame decoder of each file, since the files are framed
+/// independently.
+pub type FrameDecoderBuilder = Arc<dyn Fn() -> Box<dyn FrameDecoder> + Send + Sync>;
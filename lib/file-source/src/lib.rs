@@ -8,6 +8,7 @@ mod checkpointer;
 mod file_server;
 mod file_watcher;
 mod fingerprinter;
+mod framer;
 mod internal_events;
 mod metadata_ext;
 pub mod paths_provider;
@@ -16,6 +17,7 @@ pub use self::{
     checkpointer::{Checkpointer, CheckpointsView},
     file_server::{FileServer, Line, Shutdown as FileServerShutdown},
     fingerprinter::{FileFingerprint, FingerprintStrategy, Fingerprinter},
+    framer::{FrameDecoder, FrameDecoderBuilder},
     internal_events::FileSourceInternalEvents,
 };
 
@@ -5,7 +5,7 @@ use std::{
 };
 
 use futures::{SinkExt, StreamExt};
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
 use k8s_e2e_tests::*;
 use k8s_test_framework::{
     lock, namespace, test_pod, vector::Config as VectorConfig, wait_for_resource::WaitFor,
@@ -46,6 +46,53 @@ const CUSTOM_RESOURCE_VECTOR_CONFIG: &str = indoc! {r#"
             encoding = "json"
 "#};
 
+/// Deploys a `kubernetes_events` source, along with the permission to watch the
+/// events granted to the service account of Vector.
+fn custom_resource_kubernetes_events(namespace: &str, service_account: &str) -> String {
+    formatdoc! {r#"
+        apiVersion: v1
+        kind: ConfigMap
+        metadata:
+          name: vector-agent-config
+        data:
+          vector.toml: |
+            data_dir = "/vector-data-dir"
+            [api]
+                enabled = false
+            [sources.kubernetes_events]
+                type = "kubernetes_events"
+            [sinks.stdout]
+                type = "console"
+                inputs = ["kubernetes_events"]
+                encoding = "json"
+        ---
+        apiVersion: rbac.authorization.k8s.io/v1
+        kind: ClusterRole
+        metadata:
+          name: {service_account}-events
+        rules:
+        - apiGroups: ["events.k8s.io"]
+          resources: ["events"]
+          verbs: ["get", "list", "watch"]
+        ---
+        apiVersion: rbac.authorization.k8s.io/v1
+        kind: ClusterRoleBinding
+        metadata:
+          name: {service_account}-events
+        roleRef:
+          apiGroup: rbac.authorization.k8s.io
+          kind: ClusterRole
+          name: {service_account}-events
+        subjects:
+        - kind: ServiceAccount
+          name: {service_account}
+          namespace: {namespace}
+    "#,
+        namespace = namespace,
+        service_account = service_account,
+    }
+}
+
 /// This test validates that vector picks up logs at the simplest case
 /// possible - a new pod is deployed and prints to stdout, and we assert that
 /// vector picks that up
@@ -1947,3 +1994,107 @@ async fn simple_checkpoint() -> Result<(), Box<dyn std::error::Error>> {
     drop(vector);
     Ok(())
 }
+
+/// This test validates that vector collects the Kubernetes events reported
+/// about a new pod.
+#[tokio::test]
+async fn kubernetes_events() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = lock();
+    init();
+
+    let namespace = get_namespace();
+    let pod_namespace = get_namespace_appended(&namespace, "test-pod");
+    let framework = make_framework();
+    let override_name = get_override_name(&namespace, "vector-agent");
+
+    let vector = framework
+        .helm_chart(
+            &namespace,
+            "vector",
+            "vector",
+            "https://helm.vector.dev",
+            VectorConfig {
+                custom_helm_values: vec![
+                    &config_override_name(&override_name, true),
+                    HELM_VALUES_EXISTING_CONFIGMAP,
+                ],
+                custom_resource: &custom_resource_kubernetes_events(&namespace, &override_name),
+            },
+        )
+        .await?;
+    framework
+        .wait_for_rollout(
+            &namespace,
+            &format!("daemonset/{}", override_name),
+            vec!["--timeout=60s"],
+        )
+        .await?;
+
+    let test_namespace = framework
+        .namespace(namespace::Config::from_namespace(
+            &namespace::make_namespace(pod_namespace.clone(), None),
+        )?)
+        .await?;
+
+    let test_pod = framework
+        .test_pod(test_pod::Config::from_pod(&make_test_pod(
+            &pod_namespace,
+            "test-pod",
+            "echo MARKER",
+            vec![],
+            vec![],
+        ))?)
+        .await?;
+    framework
+        .wait(
+            &pod_namespace,
+            vec!["pods/test-pod"],
+            WaitFor::Condition("initialized"),
+            vec!["--timeout=60s"],
+        )
+        .await?;
+
+    let vector_pod = framework
+        .get_vector_pod_with_pod(&pod_namespace, "test-pod", &namespace, &override_name)
+        .await?;
+
+    let mut log_reader = framework.logs(&namespace, &format!("pod/{}", vector_pod))?;
+    smoke_check_first_line(&mut log_reader).await;
+
+    // Every pod of the cluster reports events, so only the scheduling of our
+    // test pod is looked for.
+    let mut seen_uids = HashSet::new();
+    let mut got_scheduled = false;
+    look_for_log_line(&mut log_reader, |val| {
+        if val["source_type"] != "kubernetes_events"
+            || val["regarding"]["namespace"] != pod_namespace.as_str()
+        {
+            return FlowControlCommand::GoOn;
+        }
+
+        // Ensure the updates of an event are deduplicated.
+        let uid = format!("{}/{}", val["uid"], val["resource_version"]);
+        assert!(seen_uids.insert(uid), "Event update seen more than once");
+
+        assert_eq!(val["regarding"]["kind"], "Pod");
+        assert_eq!(val["regarding"]["name"], "test-pod");
+        if val["reason"] != "Scheduled" {
+            return FlowControlCommand::GoOn;
+        }
+        assert_eq!(val["type"], "Normal");
+        assert!(val["message"].is_string());
+
+        got_scheduled = true;
+
+        // Request to stop the flow.
+        FlowControlCommand::Terminate
+    })
+    .await?;
+
+    assert!(got_scheduled);
+
+    drop(test_pod);
+    drop(test_namespace);
+    drop(vector);
+    Ok(())
+}
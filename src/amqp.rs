@@ -0,0 +1,83 @@
+//! The connection to AMQP 0.9.1 brokers, such as RabbitMQ, shared by the `amqp` source and sink.
+
+use std::time::Duration;
+
+use lapin::{
+    tcp::{HandshakeResult, NativeTlsConnector, TcpStream},
+    uri::{AMQPScheme, AMQPUri},
+    Channel, Connection, ConnectionProperties,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+use crate::tls::{TlsConfig, TlsError, TlsSettings};
+
+#[derive(Debug, Snafu)]
+pub(crate) enum AmqpError {
+    #[snafu(display("Invalid AMQP connection string: {}", message))]
+    ConnectionString { message: String },
+    #[snafu(display("AMQP TLS Config Error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("AMQP Connect Error: {}", source))]
+    Connect { source: lapin::Error },
+    #[snafu(display("AMQP Channel Error: {}", source))]
+    CreateChannel { source: lapin::Error },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct AmqpConfig {
+    /// The URI of the broker, as `amqp://<user>:<password>@<host>:<port>/<vhost>?<options>`.
+    ///
+    /// <https://www.rabbitmq.com/uri-spec.html>
+    pub(crate) connection_string: String,
+    pub(crate) tls: Option<TlsConfig>,
+}
+
+impl AmqpConfig {
+    /// Connects to the broker, opening a channel on the connection.
+    pub(crate) async fn connect(&self) -> Result<(Connection, Channel), AmqpError> {
+        let properties = ConnectionProperties::default()
+            .with_executor(tokio_executor_trait::Tokio::current())
+            .with_reactor(tokio_reactor_trait::Tokio);
+        let connection = match &self.tls {
+            Some(tls) if tls.enabled.unwrap_or(false) => {
+                let uri = self
+                    .connection_string
+                    .parse::<AMQPUri>()
+                    .map_err(|message| AmqpError::ConnectionString { message })?;
+                let connector = TlsSettings::from_options(&Some(tls.options.clone()))
+                    .and_then(|settings| settings.native_tls_connector())
+                    .context(TlsSnafu)?;
+                Connection::connector(
+                    uri,
+                    Box::new(move |uri| connect_tls(uri, &connector)),
+                    properties,
+                )
+                .await
+            }
+            _ => Connection::connect(&self.connection_string, properties).await,
+        }
+        .context(ConnectSnafu)?;
+        let channel = connection
+            .create_channel()
+            .await
+            .context(CreateChannelSnafu)?;
+        Ok((connection, channel))
+    }
+}
+
+/// Opens the stream to the broker as `lapin` does, but through the given connector so that the
+/// verification settings of the certificate and hostname are honored.
+fn connect_tls(uri: &AMQPUri, connector: &NativeTlsConnector) -> HandshakeResult {
+    let address = format!("{}:{}", uri.authority.host, uri.authority.port);
+    let stream = match uri.query.connection_timeout {
+        Some(timeout) => TcpStream::connect_timeout(address, Duration::from_millis(timeout)),
+        None => TcpStream::connect(address),
+    }?;
+    let stream = match uri.scheme {
+        AMQPScheme::AMQP => stream,
+        AMQPScheme::AMQPS => stream.into_native_tls(connector, &uri.authority.host)?,
+    };
+    stream.set_nonblocking(true)?;
+    Ok(stream)
+}
@@ -5,8 +5,11 @@ mod health;
 mod meta;
 mod metrics;
 mod relay;
+mod reload;
 pub mod sort;
 
+pub use reload::update_reload;
+
 use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
 
 #[derive(MergedObject, Default)]
@@ -15,6 +18,7 @@ pub struct Query(
     components::ComponentsQuery,
     metrics::MetricsQuery,
     meta::MetaQuery,
+    reload::ReloadQuery,
 );
 
 #[derive(MergedSubscription, Default)]
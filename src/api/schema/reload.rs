@@ -0,0 +1,78 @@
+use std::sync::RwLock;
+
+use async_graphql::{Enum, Object, SimpleObject};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use crate::topology::{ReloadOutcome, ReloadStatus};
+
+static LAST_RELOAD: Lazy<RwLock<Option<ReloadOutcome>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    /// The new configuration is running
+    Applied,
+    /// The new configuration was refused before any component was changed
+    Rejected,
+    /// The new configuration couldn't be started, and the previous one was restored
+    Restored,
+    /// The new configuration was unhealthy, and reverted to the previous one
+    RolledBack,
+    /// The previous configuration couldn't be restored
+    Failed,
+}
+
+impl From<ReloadStatus> for Status {
+    fn from(status: ReloadStatus) -> Self {
+        match status {
+            ReloadStatus::Applied => Self::Applied,
+            ReloadStatus::Rejected => Self::Rejected,
+            ReloadStatus::Restored => Self::Restored,
+            ReloadStatus::RolledBack => Self::RolledBack,
+            ReloadStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Reload {
+    /// How the reload ended
+    status: Status,
+    /// When the reload ended
+    timestamp: DateTime<Utc>,
+    /// Why the new configuration isn't running, if it isn't
+    reason: Option<String>,
+}
+
+impl From<&ReloadOutcome> for Reload {
+    fn from(outcome: &ReloadOutcome) -> Self {
+        Self {
+            status: outcome.status.into(),
+            timestamp: outcome.timestamp,
+            reason: outcome.reason.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ReloadQuery;
+
+#[Object]
+impl ReloadQuery {
+    /// Outcome of the last configuration reload, if Vector has been reloaded
+    async fn last_reload(&self) -> Option<Reload> {
+        LAST_RELOAD
+            .read()
+            .expect("Couldn't acquire lock on the last reload. Please report this.")
+            .as_ref()
+            .map(Into::into)
+    }
+}
+
+/// Update the outcome of the last configuration reload.
+pub fn update_reload(outcome: &ReloadOutcome) {
+    *LAST_RELOAD
+        .write()
+        .expect("Couldn't acquire lock on the last reload. Please report this.") =
+        Some(outcome.clone());
+}
@@ -58,6 +58,11 @@ impl Server {
     pub fn update_config(&self, config: &config::Config) {
         schema::components::update_config(config)
     }
+
+    /// Update the outcome of the last configuration reload exposed by the server.
+    pub fn update_reload(&self, outcome: &topology::ReloadOutcome) {
+        schema::update_reload(outcome)
+    }
 }
 
 fn make_routes(
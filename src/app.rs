@@ -10,6 +10,8 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 
 #[cfg(feature = "enterprise")]
 use crate::config::enterprise::PipelinesError;
+#[cfg(feature = "sources-file")]
+use crate::file_fingerprints;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api")]
@@ -135,6 +137,8 @@ impl Application {
                         SubCommand::Graph(g) => graph::cmd(&g),
                         SubCommand::Config(c) => config::cmd(&c),
                         SubCommand::List(l) => list::cmd(&l),
+                        #[cfg(feature = "sources-file")]
+                        SubCommand::FileFingerprints(f) => file_fingerprints::cmd(&f),
                         SubCommand::Test(t) => unit_test::cmd(&t).await,
                         #[cfg(windows)]
                         SubCommand::Service(s) => service::cmd(&s),
@@ -303,6 +307,12 @@ impl Application {
                                                 break SignalTo::Shutdown;
                                             }
                                         }
+                                        #[cfg(feature = "api")]
+                                        // Pass the outcome of the reload to the API server.
+                                        if let (Some(api_server), Some(outcome)) = (&api_server, topology.last_reload()) {
+                                            api_server.update_reload(outcome);
+                                        }
+
                                         sources_finished = topology.sources_finished();
                                     },
                                     Err(_) => {
@@ -351,6 +361,12 @@ impl Application {
                                             break SignalTo::Shutdown;
                                         }
                                     }
+                                    #[cfg(feature = "api")]
+                                    // Pass the outcome of the reload to the API server.
+                                    if let (Some(api_server), Some(outcome)) = (&api_server, topology.last_reload()) {
+                                        api_server.update_reload(outcome);
+                                    }
+
                                     sources_finished = topology.sources_finished();
                                 } else {
                                     emit!(VectorConfigLoadError);
@@ -359,6 +375,32 @@ impl Application {
                             _ => break signal,
                         }
                     }
+                    // Bring the previous configuration back if the components of the last reload
+                    // reported too many errors during its grace period.
+                    Some(reason) = topology.watch_reload() => {
+                        // Only the wait is raced against the other events, so that a signal never
+                        // interrupts the rollback halfway through.
+                        match topology.rollback_reload(reason).await {
+                            Ok(true) => {},
+                            Ok(false) => emit!(VectorReloadError),
+                            // Trigger graceful shutdown for what remains of the topology
+                            Err(()) => {
+                                emit!(VectorReloadError);
+                                emit!(VectorRecoveryError);
+                                break SignalTo::Shutdown;
+                            }
+                        }
+                        #[cfg(feature = "api")]
+                        // Pass the running config and the outcome of the reload to the API server.
+                        if let Some(ref api_server) = api_server {
+                            api_server.update_config(topology.config());
+                            if let Some(outcome) = topology.last_reload() {
+                                api_server.update_reload(outcome);
+                            }
+                        }
+
+                        sources_finished = topology.sources_finished();
+                    }
                     // Trigger graceful shutdown if a component crashed, or all sources have ended.
                     _ = graceful_crash.next() => break SignalTo::Shutdown,
                     _ = &mut sources_finished => break SignalTo::Shutdown,
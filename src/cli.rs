@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use clap::{AppSettings, FromArgMatches, IntoApp, Parser};
 
+#[cfg(feature = "sources-file")]
+use crate::file_fingerprints;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
@@ -192,6 +194,10 @@ pub enum SubCommand {
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Show the fingerprints the `file` source identifies the files matching a glob by, and the checkpoints they map to.
+    #[cfg(feature = "sources-file")]
+    FileFingerprints(file_fingerprints::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
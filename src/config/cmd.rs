@@ -1,11 +1,14 @@
-use super::{load_builder_from_paths, load_source_from_paths, process_paths};
+use super::{
+    format, load_builder_and_secrets_from_paths, load_source_from_paths, process_paths,
+    ConfigBuilder,
+};
 
 use crate::cli::handle_config_errors;
 use crate::config;
 
 use clap::Parser;
 use serde_json::Value;
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 #[derive(Parser, Debug, Clone)]
 #[clap(rename_all = "kebab-case")]
@@ -18,6 +21,17 @@ pub struct Opts {
     #[clap(short, long)]
     include_defaults: bool,
 
+    /// Render the configuration that Vector would actually run. Environment variables and secrets
+    /// are interpolated (secrets are redacted), all files are merged, every option is shown with
+    /// its default value, and components that expand into several components (such as `route`
+    /// or `pipelines`) are replaced by the components they generate, under their generated IDs.
+    #[clap(short, long)]
+    resolved: bool,
+
+    /// Format of the output. Pretty printing only applies to JSON.
+    #[clap(short, long, default_value = "json", possible_values = &["json", "toml", "yaml"])]
+    format: config::Format,
+
     /// Read configuration from one or more files. Wildcard paths are supported.
     /// File format is detected from the file name.
     /// If zero files are specified the default config path
@@ -89,22 +103,68 @@ fn merge_json(a: &mut Value, b: Value) {
     }
 }
 
+/// Replaces every occurrence of a secret value in the strings of a JSON value.
+pub(super) fn redact_secrets(value: &mut Value, secrets: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            for secret in secrets.values().filter(|secret| !secret.is_empty()) {
+                if s.contains(secret.as_str()) {
+                    *s = s.replace(secret.as_str(), "**REDACTED**");
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| redact_secrets(value, secrets)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|value| redact_secrets(value, secrets)),
+        _ => {}
+    }
+}
+
+/// Compiles a `ConfigBuilder` the same way Vector does before running it, and returns the result
+/// as JSON. Inputs are resolved and expanded components are replaced by the components they
+/// generate.
+fn resolve(builder: ConfigBuilder) -> Result<Value, Vec<String>> {
+    // Compiling relies on the log schema, which is set from the builder itself rather than by
+    // loading the config paths again, as that would retrieve the secrets a second time.
+    let log_schema = builder.global.log_schema.clone();
+    vector_core::config::init_log_schema(|| Ok(log_schema), true)?;
+
+    let config = builder.build()?;
+    Ok(serde_json::to_value(&ConfigBuilder::from(config))
+        .expect("should serialize ConfigBuilder to JSON. Please report."))
+}
+
 /// Function used by the `vector config` subcommand for outputting a normalized configuration.
 /// The purpose of this func is to combine user configuration after processing all paths,
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
+/// With `--resolved`, the output instead describes what Vector would actually run, which is
+/// useful for reviewing the effect of a configuration change.
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
-    let (paths, builder) = match process_paths(&paths) {
-        Some(paths) => match load_builder_from_paths(&paths) {
-            Ok((builder, _)) => (paths, builder),
+    let (paths, builder, secrets) = match process_paths(&paths) {
+        Some(paths) => match load_builder_and_secrets_from_paths(&paths) {
+            Ok((builder, secrets, _)) => (paths, builder, secrets),
             Err(errs) => return handle_config_errors(errs),
         },
         None => return exitcode::CONFIG,
     };
 
+    if opts.resolved {
+        return match resolve(builder) {
+            Ok(mut resolved) => {
+                redact_secrets(&mut resolved, &secrets);
+                output(resolved, opts)
+            }
+            Err(errs) => handle_config_errors(errs),
+        };
+    }
+
     // Serialize source against normalized paths, and get a TOML `Table` as JSON.
     let mut source = match load_source_from_paths(&paths) {
         Ok((map, _)) => serde_json::to_value(map)
@@ -127,24 +187,30 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
         source = builder
     }
 
-    // Get a JSON string. This will either be pretty printed or (default) minified.
-    let json = if opts.pretty {
-        serde_json::to_string_pretty(&source)
-    } else {
-        serde_json::to_string(&source)
-    };
+    // Secrets are only ever interpolated into the builder, but make sure that none of them can
+    // leak into the output, e.g. through a default derived from an interpolated value.
+    redact_secrets(&mut source, &secrets);
 
-    #[allow(clippy::print_stdout)]
-    {
-        println!("{}", json.expect("config should be serializable"));
-    }
+    output(source, opts)
+}
 
-    exitcode::OK
+/// Prints the config in the requested format. JSON is either pretty printed or (default) minified.
+fn output(config: Value, opts: &Opts) -> exitcode::ExitCode {
+    match format::serialize(config, opts.format, opts.pretty) {
+        Ok(output) => {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("{}", output);
+            }
+            exitcode::OK
+        }
+        Err(error) => handle_config_errors(vec![error]),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::merge_json;
+    use super::{merge_json, redact_secrets};
     use serde_json::json;
 
     #[test]
@@ -165,4 +231,33 @@ mod tests {
 
         assert_eq!(*json.get("arr").unwrap(), json!(["value3", "value4"]))
     }
+
+    #[test]
+    fn test_redact_secrets() {
+        let mut json = json!({
+            "sinks": {
+                "out": {
+                    "auth": { "password": "hunter2" },
+                    "headers": ["Bearer hunter2", "plain"]
+                }
+            }
+        });
+        let secrets = vec![("local.password".to_owned(), "hunter2".to_owned())]
+            .into_iter()
+            .collect();
+
+        redact_secrets(&mut json, &secrets);
+
+        assert_eq!(
+            json,
+            json!({
+                "sinks": {
+                    "out": {
+                        "auth": { "password": "**REDACTED**" },
+                        "headers": ["Bearer **REDACTED**", "plain"]
+                    }
+                }
+            })
+        );
+    }
 }
@@ -1,8 +1,13 @@
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+};
 
 use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
 
-use super::{ComponentKey, Config};
+use super::{cmd::redact_secrets, ComponentKey, Config};
 
 #[derive(Debug)]
 pub struct ConfigDiff {
@@ -134,3 +139,257 @@ impl Difference {
         self.to_change.iter().chain(self.to_remove.iter())
     }
 }
+
+/// A description of the changes between two configurations, down to the individual fields of
+/// each changed component. Used to preview a reload and to report it once applied.
+#[derive(Debug, Default, Serialize)]
+pub struct DiffSummary {
+    pub sources: ComponentChanges,
+    pub transforms: ComponentChanges,
+    pub sinks: ComponentChanges,
+    pub enrichment_tables: ComponentChanges,
+}
+
+impl DiffSummary {
+    pub fn new(old: &Config, new: &Config) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            sources: ComponentChanges::new(&old.sources, &new.sources)?,
+            transforms: ComponentChanges::new(&old.transforms, &new.transforms)?,
+            sinks: ComponentChanges::new(&old.sinks, &new.sinks)?,
+            enrichment_tables: ComponentChanges::new(
+                &old.enrichment_tables,
+                &new.enrichment_tables,
+            )?,
+        })
+    }
+
+    /// Replaces the secrets found in the old and new values of the changed fields, so that the
+    /// summary can be printed.
+    pub fn redact(&mut self, secrets: &HashMap<String, String>) {
+        for changes in [
+            &mut self.sources,
+            &mut self.transforms,
+            &mut self.sinks,
+            &mut self.enrichment_tables,
+        ] {
+            for field in changes.changed.values_mut().flatten() {
+                for value in field.old.iter_mut().chain(field.new.iter_mut()) {
+                    redact_secrets(value, secrets);
+                }
+            }
+        }
+    }
+
+    /// Iterates over each kind of component along with its changes.
+    pub fn kinds(&self) -> impl Iterator<Item = (&'static str, &ComponentChanges)> {
+        [
+            ("source", &self.sources),
+            ("transform", &self.transforms),
+            ("sink", &self.sinks),
+            ("enrichment_table", &self.enrichment_tables),
+        ]
+        .into_iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds().all(|(_, changes)| changes.is_empty())
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ComponentChanges {
+    pub added: Vec<ComponentKey>,
+    pub removed: Vec<ComponentKey>,
+    pub changed: IndexMap<ComponentKey, Vec<FieldChange>>,
+}
+
+impl ComponentChanges {
+    fn new<C>(
+        old: &IndexMap<ComponentKey, C>,
+        new: &IndexMap<ComponentKey, C>,
+    ) -> Result<Self, serde_json::Error>
+    where
+        C: serde::Serialize,
+    {
+        let to_values = |configs: &IndexMap<ComponentKey, C>| {
+            configs
+                .iter()
+                .map(|(key, config)| Ok((key.clone(), serde_json::to_value(config)?)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self::from_values(to_values(old)?, to_values(new)?))
+    }
+
+    /// Builds the changes between two sets of already serialized components.
+    pub fn from_values(
+        old: impl IntoIterator<Item = (ComponentKey, Value)>,
+        new: impl IntoIterator<Item = (ComponentKey, Value)>,
+    ) -> Self {
+        let old = old.into_iter().collect::<BTreeMap<_, _>>();
+        let new = new.into_iter().collect::<BTreeMap<_, _>>();
+
+        let added = new
+            .keys()
+            .filter(|key| !old.contains_key(key))
+            .cloned()
+            .collect();
+        let removed = old
+            .keys()
+            .filter(|key| !new.contains_key(key))
+            .cloned()
+            .collect();
+        let changed = old
+            .iter()
+            .filter_map(|(key, old)| {
+                let new = new.get(key)?;
+                let mut fields = Vec::new();
+                diff_fields(String::new(), Some(old), Some(new), &mut fields);
+                (!fields.is_empty()).then(|| (key.clone(), fields))
+            })
+            .collect();
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A single changed field of a component, addressed by its dotted path within the component.
+/// A missing value means the field was not set on that side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "<unset>".to_owned(),
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            display(&self.old),
+            display(&self.new)
+        )
+    }
+}
+
+/// Recursively compares two values, descending into objects so that changes are reported on the
+/// innermost field. Arrays and scalars are compared as a whole. Nulls are treated as unset.
+fn diff_fields(path: String, old: Option<&Value>, new: Option<&Value>, out: &mut Vec<FieldChange>) {
+    let old = old.filter(|value| !value.is_null());
+    let new = new.filter(|value| !value.is_null());
+
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_fields(path, old.get(key), new.get(key), out);
+            }
+        }
+        (old, new) if old != new => out.push(FieldChange {
+            path,
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn summarizes_component_changes() {
+        let old = vec![
+            ("in".into(), json!({"type": "stdin"})),
+            (
+                "out".into(),
+                json!({"type": "console", "encoding": {"codec": "json"}, "inputs": ["in"]}),
+            ),
+        ];
+        let new = vec![
+            (
+                "out".into(),
+                json!({"type": "console", "encoding": {"codec": "text"}, "inputs": ["in", "other"], "target": null}),
+            ),
+            ("other".into(), json!({"type": "file"})),
+        ];
+
+        let changes = ComponentChanges::from_values(old, new);
+
+        assert_eq!(changes.added, vec![ComponentKey::from("other")]);
+        assert_eq!(changes.removed, vec![ComponentKey::from("in")]);
+        assert_eq!(
+            changes.changed[&ComponentKey::from("out")],
+            vec![
+                FieldChange {
+                    path: "encoding.codec".into(),
+                    old: Some(json!("json")),
+                    new: Some(json!("text")),
+                },
+                FieldChange {
+                    path: "inputs".into(),
+                    old: Some(json!(["in"])),
+                    new: Some(json!(["in", "other"])),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn redacts_secrets_from_changed_fields() {
+        let mut summary = DiffSummary {
+            sinks: ComponentChanges::from_values(
+                vec![("out".into(), json!({"auth": {"password": "old-secret"}}))],
+                vec![("out".into(), json!({"auth": {"password": "new-secret"}}))],
+            ),
+            ..Default::default()
+        };
+        let secrets = vec![
+            ("backend.old".to_owned(), "old-secret".to_owned()),
+            ("backend.new".to_owned(), "new-secret".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+
+        summary.redact(&secrets);
+
+        assert_eq!(
+            summary.sinks.changed[&ComponentKey::from("out")],
+            vec![FieldChange {
+                path: "auth.password".into(),
+                old: Some(json!("**REDACTED**")),
+                new: Some(json!("**REDACTED**")),
+            }]
+        );
+    }
+
+    #[test]
+    fn unchanged_components_are_omitted() {
+        let config = vec![("in".into(), json!({"type": "stdin", "max_length": 100}))];
+        let changes = ComponentChanges::from_values(config.clone(), config);
+
+        assert!(changes.is_empty());
+    }
+}
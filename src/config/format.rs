@@ -2,7 +2,7 @@
 
 #![deny(missing_docs, missing_debug_implementations)]
 
-use std::path::Path;
+use std::{path::Path, str::FromStr};
 
 use serde::de;
 
@@ -38,6 +38,19 @@ impl Format {
     }
 }
 
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "toml" => Ok(Format::Toml),
+            "yaml" => Ok(Format::Yaml),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
 /// Parse the string represented in the specified format.
 /// If the format is unknown - fallback to the default format and attempt
 /// parsing using that.
@@ -52,6 +65,41 @@ where
     }
 }
 
+/// Serialize the given value in the specified format. JSON is pretty printed if `pretty` is set,
+/// while TOML and YAML always are. As TOML has no representation for `null`, null values are
+/// omitted from it.
+pub fn serialize(
+    mut value: serde_json::Value,
+    format: Format,
+    pretty: bool,
+) -> Result<String, String> {
+    match format {
+        Format::Toml => {
+            remove_nulls(&mut value);
+            toml::Value::try_from(value)
+                .and_then(|value| toml::to_string_pretty(&value))
+                .map_err(|e| e.to_string())
+        }
+        Format::Yaml => serde_yaml::to_string(&value).map_err(|e| e.to_string()),
+        Format::Json if pretty => serde_json::to_string_pretty(&value).map_err(|e| e.to_string()),
+        Format::Json => serde_json::to_string(&value).map_err(|e| e.to_string()),
+    }
+}
+
+fn remove_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+        serde_json::Value::Array(values) => {
+            values.retain(|value| !value.is_null());
+            values.iter_mut().for_each(remove_nulls);
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,4 +320,23 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn serialize_round_trips() {
+        let value = serde_json::json!({
+            "data_dir": "/var/lib/vector/",
+            "proxy": { "http": null, "no_proxy": [] },
+            "sources": { "in": { "type": "stdin", "max_length": 102400 } },
+        });
+
+        for format in [Format::Toml, Format::Yaml, Format::Json] {
+            let output = serialize(value.clone(), format, true).unwrap();
+            let parsed: serde_json::Value = deserialize(&output, format).unwrap();
+            let mut expected = value.clone();
+            if format == Format::Toml {
+                remove_nulls(&mut expected);
+            }
+            assert_eq!(parsed, expected, "{:?}", format);
+        }
+    }
 }
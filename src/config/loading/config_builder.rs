@@ -1,21 +1,24 @@
-use super::{deserialize_table, loader, prepare_input};
+use super::{deserialize_table, loader, prepare_input, secret};
 use super::{ComponentHint, Process};
 use crate::config::{
     ComponentKey, ConfigBuilder, EnrichmentTableOuter, SinkOuter, SourceOuter, TestDefinition,
     TransformOuter,
 };
 use indexmap::IndexMap;
-use std::io::Read;
+use std::{collections::HashMap, io::Read};
 use toml::value::Table;
 
 pub struct ConfigBuilderLoader {
     builder: ConfigBuilder,
+    secrets: HashMap<String, String>,
 }
 
 impl ConfigBuilderLoader {
-    pub fn new() -> Self {
+    /// Creates a loader interpolating the given secrets, keyed by `<backend>.<key>`.
+    pub fn with_secrets(secrets: HashMap<String, String>) -> Self {
         Self {
             builder: ConfigBuilder::default(),
+            secrets,
         }
     }
 }
@@ -26,8 +29,10 @@ impl Process for ConfigBuilderLoader {
         prepare_input(input)
     }
 
-    /// Merge a TOML `Table` with a `ConfigBuilder`. Component types extend specific keys.
-    fn merge(&mut self, table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+    /// Merge a TOML `Table` with a `ConfigBuilder`, once its secrets are interpolated. Component
+    /// types extend specific keys.
+    fn merge(&mut self, mut table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+        secret::interpolate(&mut table, &self.secrets)?;
         match hint {
             Some(ComponentHint::Source) => {
                 self.builder.sources.extend(deserialize_table::<
@@ -1,5 +1,6 @@
 mod config_builder;
 mod loader;
+mod secret;
 mod source;
 
 use std::{
@@ -12,6 +13,7 @@ use std::{
 
 use config_builder::ConfigBuilderLoader;
 use loader::process::Process;
+use secret::SecretBackendLoader;
 
 use super::{
     builder::ConfigBuilder, format, validation, vars, Config, ConfigPath, Format, FormatHint,
@@ -198,7 +200,30 @@ where
 pub fn load_builder_from_paths(
     config_paths: &[ConfigPath],
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
-    loader_from_paths(ConfigBuilderLoader::new(), config_paths)
+    load_builder_and_secrets_from_paths(config_paths)
+        .map(|(builder, _, warnings)| (builder, warnings))
+}
+
+/// Retrieves the secrets referenced by `ConfigPaths` from their secret backends, then uses
+/// `ConfigBuilderLoader` to deserialize a `ConfigBuilder` with those secrets interpolated.
+/// The secrets are returned as well, as a map of `<backend>.<key>` to value.
+pub fn load_builder_and_secrets_from_paths(
+    config_paths: &[ConfigPath],
+) -> Result<(ConfigBuilder, HashMap<String, String>, Vec<String>), Vec<String>> {
+    let secrets = load_secrets_from_paths(config_paths)?;
+    let (builder, warnings) = loader_from_paths(
+        ConfigBuilderLoader::with_secrets(secrets.clone()),
+        config_paths,
+    )?;
+    Ok((builder, secrets, warnings))
+}
+
+/// Uses `SecretBackendLoader` to process `ConfigPaths`, retrieving every secret they reference.
+fn load_secrets_from_paths(
+    config_paths: &[ConfigPath],
+) -> Result<HashMap<String, String>, Vec<String>> {
+    let (backends, _) = loader_from_paths(SecretBackendLoader::new(), config_paths)?;
+    backends.retrieve()
 }
 
 /// Uses `SourceLoader` to process `ConfigPaths`, deserializing to a toml `SourceMap`.
@@ -0,0 +1,325 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use toml::value::{Table, Value};
+
+use super::{deserialize_table, loader, prepare_input, ComponentHint, Process};
+use crate::config::{ComponentKey, SecretBackend};
+
+// The following regex aims to extract a pair of strings, the first being the secret backend name
+// and the second being the secret key. Here are some matching & non-matching examples:
+// - "SECRET[backend.secret_name]" will match and capture "backend" and "secret_name"
+// - "SECRET[backend.secret.name]" will match and capture "backend" and "secret.name"
+// - "SECRET[backend..secret.name]" will match and capture "backend" and ".secret.name"
+// - "SECRET[secret_name]" will not match
+// - "SECRET[.secret.name]" will not match
+static COLLECTOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"SECRET\[([[:word:]]+)\.([[:word:].-]+)\]").unwrap());
+
+/// Helper type for specifically deserializing secrets backends.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct SecretBackendOuter {
+    #[serde(default)]
+    secret: IndexMap<ComponentKey, Box<dyn SecretBackend>>,
+    /// The secret keys referenced in the config, grouped by backend name.
+    #[serde(skip)]
+    secret_keys: HashMap<String, HashSet<String>>,
+}
+
+impl SecretBackendOuter {
+    /// Retrieves every referenced secret, returning a map of `<backend>.<key>` to its value.
+    pub(crate) fn retrieve(self) -> Result<HashMap<String, String>, Vec<String>> {
+        let Self {
+            mut secret,
+            secret_keys,
+        } = self;
+        let mut secrets = HashMap::new();
+        let mut errors = Vec::new();
+
+        for (backend_name, keys) in secret_keys {
+            let backend = match secret.get_mut(&ComponentKey::from(backend_name.as_str())) {
+                Some(backend) => backend,
+                None => {
+                    errors.push(format!(
+                        "Secret backend {:?} is referenced but not defined.",
+                        backend_name
+                    ));
+                    continue;
+                }
+            };
+
+            debug!(
+                message = "Retrieving secrets.",
+                backend = %backend_name,
+                backend_type = backend.backend_type(),
+                count = keys.len(),
+            );
+            match backend.retrieve(keys) {
+                Ok(values) => {
+                    for (key, value) in values {
+                        secrets.insert(format!("{}.{}", backend_name, key), value);
+                    }
+                }
+                Err(error) => errors.push(format!(
+                    "Error retrieving secrets from backend {:?}: {}",
+                    backend_name, error
+                )),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(secrets)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Loads the secret backends of a config, along with the secret keys it references. Unlike the
+/// `ConfigBuilderLoader`, this does not interpolate secrets, as they haven't been retrieved yet.
+pub(crate) struct SecretBackendLoader {
+    outer: SecretBackendOuter,
+}
+
+impl SecretBackendLoader {
+    pub(crate) fn new() -> Self {
+        Self {
+            outer: SecretBackendOuter::default(),
+        }
+    }
+}
+
+impl Process for SecretBackendLoader {
+    /// Prepares input by interpolating environment variables, which backends may be configured with.
+    fn prepare<R: Read>(&self, input: R) -> Result<(String, Vec<String>), Vec<String>> {
+        prepare_input(input)
+    }
+
+    /// Collects the secret keys referenced in the table, and the backends defined at its root.
+    fn merge(&mut self, table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+        for value in table.values() {
+            collect_secret_keys(value, &mut self.outer.secret_keys);
+        }
+
+        if hint.is_none() {
+            let outer = deserialize_table::<SecretBackendOuter>(table)?;
+            let mut errors = Vec::new();
+            for (name, backend) in outer.secret {
+                if self.outer.secret.insert(name.clone(), backend).is_some() {
+                    errors.push(format!("duplicate secret backend name found: {}", name));
+                }
+            }
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl loader::Loader<SecretBackendOuter> for SecretBackendLoader {
+    /// Returns the secret backends and the keys to retrieve from each of them.
+    fn take(self) -> SecretBackendOuter {
+        self.outer
+    }
+}
+
+fn collect_secret_keys(value: &Value, keys: &mut HashMap<String, HashSet<String>>) {
+    match value {
+        Value::String(s) => {
+            for caps in COLLECTOR.captures_iter(s) {
+                if let (Some(backend), Some(key)) = (caps.get(1), caps.get(2)) {
+                    keys.entry(backend.as_str().to_owned())
+                        .or_default()
+                        .insert(key.as_str().to_owned());
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_secret_keys(value, keys)),
+        Value::Table(table) => table
+            .values()
+            .for_each(|value| collect_secret_keys(value, keys)),
+        _ => {}
+    }
+}
+
+/// Replaces every `SECRET[<backend>.<key>]` reference in the string values of the table with its
+/// retrieved value.
+///
+/// This works on parsed values rather than on the raw config text, so that references in comments
+/// are left alone, and so that secrets are never parsed as part of the config itself, whatever
+/// characters they hold.
+pub(crate) fn interpolate(
+    table: &mut Table,
+    secrets: &HashMap<String, String>,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    for value in table.values_mut() {
+        interpolate_value(value, secrets, &mut errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn interpolate_value(
+    value: &mut Value,
+    secrets: &HashMap<String, String>,
+    errors: &mut Vec<String>,
+) {
+    match value {
+        Value::String(s) => {
+            if COLLECTOR.is_match(s) {
+                *s = interpolate_str(s, secrets, errors);
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| interpolate_value(value, secrets, errors)),
+        Value::Table(table) => table
+            .values_mut()
+            .for_each(|value| interpolate_value(value, secrets, errors)),
+        _ => {}
+    }
+}
+
+fn interpolate_str(
+    input: &str,
+    secrets: &HashMap<String, String>,
+    errors: &mut Vec<String>,
+) -> String {
+    COLLECTOR
+        .replace_all(input, |caps: &Captures<'_>| {
+            let name = format!("{}.{}", &caps[1], &caps[2]);
+            match secrets.get(&name) {
+                Some(value) => value.clone(),
+                None => {
+                    errors.push(format!("Unable to find secret replacement for {}.", name));
+                    String::new()
+                }
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use indoc::indoc;
+
+    use super::*;
+
+    fn interpolate(input: &str, secrets: &HashMap<String, String>) -> Result<String, Vec<String>> {
+        let mut errors = Vec::new();
+        let output = interpolate_str(input, secrets, &mut errors);
+        if errors.is_empty() {
+            Ok(output)
+        } else {
+            Err(errors)
+        }
+    }
+
+    #[test]
+    fn replacement() {
+        let secrets: HashMap<String, String> = vec![
+            ("a.secret.key".into(), "value".into()),
+            ("a...key".into(), "a...value".into()),
+            ("b.secret_key".into(), "another value".into()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            Ok("value".into()),
+            interpolate("SECRET[a.secret.key]", &secrets)
+        );
+        assert_eq!(
+            Ok("value value".into()),
+            interpolate("SECRET[a.secret.key] SECRET[a.secret.key]", &secrets)
+        );
+        assert_eq!(
+            Ok("a...value".into()),
+            interpolate("SECRET[a...key]", &secrets)
+        );
+        assert_eq!(
+            Ok("xxxanother valueyyy".into()),
+            interpolate("xxxSECRET[b.secret_key]yyy", &secrets)
+        );
+        assert_eq!(
+            Ok("SECRET[secret_key]".into()),
+            interpolate("SECRET[secret_key]", &secrets)
+        );
+        assert!(interpolate("SECRET[c.secret_key]", &secrets).is_err());
+    }
+
+    #[test]
+    fn collection() {
+        let mut keys = HashMap::new();
+        let value: Value = toml::from_str(indoc! {r#"
+            key1 = "SECRET[first_backend.secret_key]"
+            key2 = "SECRET[first_backend.secret.key]"
+            [nested]
+            key3 = ["SECRET[second_backend.secret_key]", "${ENV}"]
+            key4 = "SECRET[first_backend.secret_key] SECRET[second_backend.other_key]"
+        "#})
+        .unwrap();
+        collect_secret_keys(&value, &mut keys);
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            keys["first_backend"],
+            vec!["secret_key".to_owned(), "secret.key".to_owned()]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(
+            keys["second_backend"],
+            vec!["secret_key".to_owned(), "other_key".to_owned()]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn replacement_in_table() {
+        let secrets: HashMap<String, String> = vec![
+            ("a.password".into(), "with \"quotes\"\nand a newline".into()),
+            ("a.user".into(), "vector".into()),
+        ]
+        .into_iter()
+        .collect();
+        let mut table: Table = toml::from_str(indoc! {r#"
+            # The user is SECRET[a.commented_out]
+            [sinks.out]
+            auth = { user = "SECRET[a.user]", password = "SECRET[a.password]" }
+            headers = ["X-User: SECRET[a.user]"]
+        "#})
+        .unwrap();
+
+        super::interpolate(&mut table, &secrets).unwrap();
+
+        let sink = &table["sinks"]["out"];
+        assert_eq!(sink["auth"]["user"].as_str(), Some("vector"));
+        assert_eq!(
+            sink["auth"]["password"].as_str(),
+            Some("with \"quotes\"\nand a newline")
+        );
+        assert_eq!(sink["headers"][0].as_str(), Some("X-User: vector"));
+
+        let mut table: Table = toml::from_str(r#"key = "SECRET[a.missing]""#).unwrap();
+        assert!(super::interpolate(&mut table, &secrets).is_err());
+    }
+}
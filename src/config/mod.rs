@@ -4,6 +4,7 @@ use std::{
     hash::Hash,
     net::SocketAddr,
     path::PathBuf,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -29,6 +30,7 @@ mod id;
 mod loading;
 pub mod provider;
 mod schema;
+mod secret;
 mod sink;
 mod source;
 mod transform;
@@ -39,13 +41,15 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
-pub use diff::ConfigDiff;
+pub use diff::{ComponentChanges, ConfigDiff, DiffSummary, FieldChange};
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, OutputId};
 pub use loading::{
-    load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider, load_from_str,
-    load_source_from_paths, merge_path_lists, process_paths, CONFIG_PATHS,
+    load, load_builder_and_secrets_from_paths, load_builder_from_paths, load_from_paths,
+    load_from_paths_with_provider, load_from_str, load_source_from_paths, merge_path_lists,
+    process_paths, CONFIG_PATHS,
 };
+pub use secret::SecretBackend;
 pub use sink::{SinkConfig, SinkContext, SinkDescription, SinkHealthcheckOptions, SinkOuter};
 pub use source::{SourceConfig, SourceContext, SourceDescription, SourceOuter};
 pub use transform::{TransformDescription, TransformOuter};
@@ -217,6 +221,15 @@ impl Config {
 pub struct HealthcheckOptions {
     pub enabled: bool,
     pub require_healthy: bool,
+    /// Reverts a reload to the previous configuration if the healthchecks of new or changed
+    /// components fail, or if those components report errors during the grace period.
+    pub reload_rollback: bool,
+    /// Left unset unless configured, so that merging config files only takes the values that
+    /// were set into account. See `reload_grace_period`.
+    pub reload_grace_period_secs: Option<u64>,
+    /// The number of errors new or changed components may report during the grace period
+    /// before the reload is reverted. See `reload_max_errors`.
+    pub reload_max_errors: Option<u64>,
 }
 
 impl HealthcheckOptions {
@@ -226,9 +239,33 @@ impl HealthcheckOptions {
         }
     }
 
+    pub fn reload_grace_period(&self) -> Duration {
+        Duration::from_secs(self.reload_grace_period_secs.unwrap_or(30))
+    }
+
+    pub fn reload_max_errors(&self) -> u64 {
+        self.reload_max_errors.unwrap_or(0)
+    }
+
     fn merge(&mut self, other: Self) {
         self.enabled &= other.enabled;
         self.require_healthy |= other.require_healthy;
+        self.reload_rollback |= other.reload_rollback;
+        // When several files set the same option, the most cautious value wins.
+        self.reload_grace_period_secs = merge_option(
+            self.reload_grace_period_secs,
+            other.reload_grace_period_secs,
+            u64::max,
+        );
+        self.reload_max_errors =
+            merge_option(self.reload_max_errors, other.reload_max_errors, u64::min);
+    }
+}
+
+fn merge_option<T>(a: Option<T>, b: Option<T>, f: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(f(a, b)),
+        (a, b) => a.or(b),
     }
 }
 
@@ -237,6 +274,9 @@ impl Default for HealthcheckOptions {
         Self {
             enabled: true,
             require_healthy: false,
+            reload_rollback: false,
+            reload_grace_period_secs: None,
+            reload_max_errors: None,
         }
     }
 }
@@ -550,7 +590,9 @@ mod tests {
 
     use indoc::indoc;
 
-    use super::{builder::ConfigBuilder, format, load_from_str, ComponentKey, Format};
+    use super::{
+        builder::ConfigBuilder, format, load_from_str, ComponentKey, Format, HealthcheckOptions,
+    };
 
     #[test]
     fn default_data_dir() {
@@ -630,6 +672,29 @@ mod tests {
         assert_eq!("then", config.global.log_schema.timestamp_key().to_string());
     }
 
+    #[test]
+    fn healthcheck_options_merge_set_values() {
+        let mut options: HealthcheckOptions = toml::from_str(indoc! {r#"
+            reload_grace_period_secs = 10
+            reload_max_errors = 5
+        "#})
+        .unwrap();
+        options.merge(toml::from_str("reload_rollback = true").unwrap());
+
+        assert!(options.reload_rollback);
+        assert_eq!(options.reload_grace_period().as_secs(), 10);
+        assert_eq!(options.reload_max_errors(), 5);
+
+        options.merge(toml::from_str("reload_max_errors = 2").unwrap());
+        assert_eq!(options.reload_max_errors(), 2);
+        assert_eq!(
+            HealthcheckOptions::default()
+                .reload_grace_period()
+                .as_secs(),
+            30
+        );
+    }
+
     #[test]
     fn config_append() {
         let mut config: ConfigBuilder = format::deserialize(
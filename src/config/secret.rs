@@ -0,0 +1,15 @@
+use std::collections::{HashMap, HashSet};
+
+use dyn_clone::DynClone;
+
+/// A backend able to resolve the secrets referenced as `SECRET[<backend>.<key>]` in a config.
+#[typetag::serde(tag = "type")]
+pub trait SecretBackend: core::fmt::Debug + Send + Sync + DynClone {
+    /// Retrieves the values of the given secret keys, returning a map of key to value. Every
+    /// requested key must be present in the result, otherwise loading the config fails.
+    fn retrieve(&mut self, secret_keys: HashSet<String>) -> crate::Result<HashMap<String, String>>;
+
+    fn backend_type(&self) -> &'static str;
+}
+
+dyn_clone::clone_trait_object!(SecretBackend);
@@ -0,0 +1,307 @@
+use std::{io, path::PathBuf};
+
+use clap::Parser;
+use file_source::{
+    paths_provider::{
+        glob::{Glob, MatchOptions},
+        PathsProvider,
+    },
+    Checkpointer, FileFingerprint, FilePosition, FingerprintStrategy, Fingerprinter,
+};
+use serde::Serialize;
+
+use crate::internal_events::FileSourceInternalEventsEmitter;
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Glob patterns of the files to fingerprint, as in the `include` option
+    /// of the `file` source.
+    #[clap(long, required = true, use_value_delimiter(true))]
+    include: Vec<PathBuf>,
+
+    /// Glob patterns of the files to leave out, as in the `exclude` option of
+    /// the `file` source.
+    #[clap(long, use_value_delimiter(true))]
+    exclude: Vec<PathBuf>,
+
+    /// The directory the `file` source checkpoints the files in, which is its
+    /// `data_dir`, or the global one, followed by the ID of the source.
+    #[clap(long)]
+    data_dir: Option<PathBuf>,
+
+    /// The strategy the files are fingerprinted with, as in the
+    /// `fingerprint.strategy` option of the `file` source.
+    #[clap(
+        long,
+        default_value = "checksum",
+        possible_values = &["checksum", "device_and_inode", "device_and_inode_checksum"]
+    )]
+    strategy: Strategy,
+
+    /// The number of bytes to checksum with the `device_and_inode_checksum`
+    /// strategy.
+    #[clap(long, default_value = "256")]
+    bytes: usize,
+
+    /// The number of bytes to skip before the bytes or lines to checksum.
+    #[clap(long, default_value = "0")]
+    ignored_header_bytes: usize,
+
+    /// The number of lines to checksum with the `checksum` strategy.
+    #[clap(long, default_value = "1")]
+    lines: usize,
+
+    /// The maximum number of bytes of the lines to checksum, as in the
+    /// `max_line_bytes` option of the `file` source.
+    #[clap(long, default_value = "102400")]
+    max_line_bytes: usize,
+
+    /// Output the files as JSON lines.
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Strategy {
+    Checksum,
+    DevInode,
+    DevInodeChecksum,
+}
+
+impl std::str::FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "checksum" => Ok(Strategy::Checksum),
+            "device_and_inode" => Ok(Strategy::DevInode),
+            "device_and_inode_checksum" => Ok(Strategy::DevInodeChecksum),
+            s => Err(format!(
+                "{} is not a valid option, expected `checksum`, `device_and_inode` or `device_and_inode_checksum`",
+                s
+            )),
+        }
+    }
+}
+
+impl Opts {
+    fn fingerprinter(&self) -> Fingerprinter {
+        let strategy = match self.strategy {
+            Strategy::Checksum => FingerprintStrategy::Checksum {
+                bytes: self.bytes,
+                ignored_header_bytes: self.ignored_header_bytes,
+                lines: self.lines,
+            },
+            Strategy::DevInode => FingerprintStrategy::DevInode,
+            Strategy::DevInodeChecksum => FingerprintStrategy::DevInodeChecksum {
+                bytes: self.bytes,
+                ignored_header_bytes: self.ignored_header_bytes,
+            },
+        };
+        Fingerprinter {
+            strategy,
+            max_line_length: self.max_line_bytes,
+            ignore_not_found: false,
+        }
+    }
+}
+
+/// The fingerprint of a file, and the checkpoint it maps to.
+#[derive(Debug, Serialize)]
+struct FileReport {
+    path: PathBuf,
+    fingerprint: Option<FileFingerprint>,
+    checkpoint: Option<FilePosition>,
+    error: Option<String>,
+}
+
+pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    for pattern in &opts.include {
+        if let Err(error) = glob::Pattern::new(&pattern.to_string_lossy()) {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Invalid include pattern {:?}: {}", pattern, error);
+            }
+            return exitcode::USAGE;
+        }
+    }
+    let paths_provider = match Glob::new(
+        &opts.include,
+        &opts.exclude,
+        MatchOptions::default(),
+        FileSourceInternalEventsEmitter,
+    ) {
+        Some(paths_provider) => paths_provider,
+        None => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("Invalid exclude patterns.");
+            }
+            return exitcode::USAGE;
+        }
+    };
+
+    let mut checkpointer = opts.data_dir.as_ref().and_then(|data_dir| {
+        let checkpointer = Checkpointer::new(data_dir);
+        match checkpointer.inspect_checkpoints() {
+            Ok(()) => Some(checkpointer),
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!(
+                        "Unable to read the checkpoints in {:?}: {}",
+                        data_dir, error
+                    );
+                }
+                None
+            }
+        }
+    });
+
+    let reports = file_reports(
+        &paths_provider,
+        &opts.fingerprinter(),
+        checkpointer.as_mut(),
+    );
+    for report in reports {
+        #[allow(clippy::print_stdout)]
+        if opts.json {
+            println!("{}", serde_json::to_string(&report).unwrap());
+        } else {
+            println!("{}", report.path.display());
+            match (&report.fingerprint, &report.error) {
+                (Some(fingerprint), _) => println!("  Fingerprint: {}", describe(fingerprint)),
+                (None, Some(error)) => println!("  Fingerprint: none, {}", error),
+                (None, None) => println!("  Fingerprint: none"),
+            }
+            match (&checkpointer, report.checkpoint) {
+                (None, _) => {}
+                (Some(_), Some(position)) => println!("  Checkpoint: {}", position),
+                (Some(_), None) => println!("  Checkpoint: none"),
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
+/// Fingerprints the files of the paths, and maps them to their checkpoints the
+/// way the `file` source does on start-up, upgrading the legacy fingerprints.
+fn file_reports(
+    paths_provider: &impl PathsProvider,
+    fingerprinter: &Fingerprinter,
+    mut checkpointer: Option<&mut Checkpointer>,
+) -> Vec<FileReport> {
+    let mut buffer = Vec::new();
+    paths_provider
+        .paths()
+        .into_iter()
+        .filter(|path| !path.is_dir())
+        .map(
+            |path| match fingerprinter.get_fingerprint_of_file(&path, &mut buffer) {
+                Ok(fingerprint) => {
+                    let checkpoint = checkpointer.as_mut().and_then(|checkpointer| {
+                        checkpointer.maybe_upgrade(&path, fingerprint, fingerprinter, &mut buffer);
+                        checkpointer.view().get(fingerprint)
+                    });
+                    FileReport {
+                        path,
+                        fingerprint: Some(fingerprint),
+                        checkpoint,
+                        error: None,
+                    }
+                }
+                Err(error) => FileReport {
+                    path,
+                    fingerprint: None,
+                    checkpoint: None,
+                    error: Some(describe_error(&error)),
+                },
+            },
+        )
+        .collect()
+}
+
+fn describe(fingerprint: &FileFingerprint) -> String {
+    match fingerprint {
+        FileFingerprint::BytesChecksum(c) => format!("checksum {:x}", c),
+        FileFingerprint::FirstLinesChecksum(c) => format!("first lines checksum {:x}", c),
+        FileFingerprint::DevInode(dev, ino) => format!("device {}, inode {}", dev, ino),
+        FileFingerprint::DevInodeChecksum(dev, ino, c) => {
+            format!("device {}, inode {}, checksum {:x}", dev, ino, c)
+        }
+        FileFingerprint::Unknown(c) => format!("legacy {:x}", c),
+    }
+}
+
+fn describe_error(error: &io::Error) -> String {
+    match error.kind() {
+        io::ErrorKind::UnexpectedEof => {
+            "the file is too small to be fingerprinted, so it isn't read yet".to_owned()
+        }
+        _ => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn file_reports_with_checkpoints() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("export.csv");
+        let other_path = dir.path().join("other.csv");
+        let small_path = dir.path().join("small.csv");
+        fs::write(&path, "id,name\n1,first\n").unwrap();
+        fs::write(&other_path, "id,name\n1,other\n").unwrap();
+        fs::write(&small_path, "id").unwrap();
+
+        let opts = Opts::parse_from(&[
+            "file-fingerprints",
+            "--include",
+            dir.path().join("*.csv").to_str().unwrap(),
+            "--strategy",
+            "device_and_inode_checksum",
+            "--bytes",
+            "8",
+        ]);
+        let fingerprinter = opts.fingerprinter();
+        let fingerprint = fingerprinter
+            .get_fingerprint_of_file(&path, &mut Vec::new())
+            .unwrap();
+
+        let mut checkpointer = Checkpointer::new(dir.path());
+        checkpointer.view().update(fingerprint, 8);
+
+        let paths_provider = Glob::new(
+            &opts.include,
+            &opts.exclude,
+            MatchOptions::default(),
+            FileSourceInternalEventsEmitter,
+        )
+        .unwrap();
+        let reports = file_reports(&paths_provider, &fingerprinter, Some(&mut checkpointer));
+
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].path, path);
+        assert_eq!(reports[0].fingerprint, Some(fingerprint));
+        assert_eq!(reports[0].checkpoint, Some(8));
+        // The files sharing their first bytes don't share their fingerprint.
+        assert_eq!(reports[1].path, other_path);
+        assert!(matches!(
+            reports[1].fingerprint,
+            Some(FileFingerprint::DevInodeChecksum(..))
+        ));
+        assert_ne!(reports[1].fingerprint, Some(fingerprint));
+        assert_eq!(reports[1].checkpoint, None);
+        assert_eq!(reports[2].path, small_path);
+        assert_eq!(reports[2].fingerprint, None);
+        assert!(reports[2].error.is_some());
+    }
+}
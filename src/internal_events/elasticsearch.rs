@@ -1,7 +1,7 @@
 use super::prelude::{error_stage, error_type};
 use http::Response;
 use metrics::counter;
-use vector_core::internal_event::InternalEvent;
+use vector_core::{event::EventStatus, internal_event::InternalEvent};
 
 #[derive(Debug)]
 pub struct ElasticsearchResponseError<'a> {
@@ -39,3 +39,37 @@ impl<'a> InternalEvent for ElasticsearchResponseError<'a> {
         );
     }
 }
+
+/// A single document of a bulk request was not indexed.
+#[derive(Debug)]
+pub struct ElasticsearchBulkItemFailed<'a> {
+    /// The status of the bulk item, or `0` if the request carrying it could not be sent.
+    pub status: u16,
+    pub reason: &'a str,
+    pub event_status: EventStatus,
+}
+
+impl<'a> InternalEvent for ElasticsearchBulkItemFailed<'a> {
+    fn emit(self) {
+        let error_code = if self.status == 0 {
+            "request_failed".to_owned()
+        } else {
+            super::prelude::http_error_code(self.status)
+        };
+        error!(
+            message = "Bulk item failed.",
+            reason = %self.reason,
+            error_code = %error_code,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            event_status = ?self.event_status,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => error_code,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
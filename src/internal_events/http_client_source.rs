@@ -0,0 +1,111 @@
+use super::prelude::{error_stage, error_type, http_error_code};
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct HttpClientEventsReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientEventsReceived<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Events received.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+            url = %self.url,
+        );
+        counter!(
+            "component_received_events_total", self.count as u64,
+            "uri" => self.url.to_owned(),
+        );
+        counter!(
+            "component_received_event_bytes_total", self.byte_size as u64,
+            "uri" => self.url.to_owned(),
+        );
+        counter!(
+            "events_in_total", self.count as u64,
+            "uri" => self.url.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientHttpResponseError<'a> {
+    pub code: hyper::StatusCode,
+    pub url: &'a str,
+}
+
+impl InternalEvent for HttpClientHttpResponseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "HTTP error response.",
+            url = %self.url,
+            stage = error_stage::RECEIVING,
+            error_type = error_type::REQUEST_FAILED,
+            error_code = %http_error_code(self.code.as_u16()),
+            internal_log_rate_secs = 10,
+        );
+        counter!("http_error_response_total", 1);
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.url.to_owned(),
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::REQUEST_FAILED,
+            "error_code" => http_error_code(self.code.as_u16()),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientHttpError<'a> {
+    pub error: crate::Error,
+    pub url: &'a str,
+}
+
+impl InternalEvent for HttpClientHttpError<'_> {
+    fn emit(self) {
+        error!(
+            message = "HTTP request processing error.",
+            url = %self.url,
+            error = ?self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!("http_request_errors_total", 1);
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.url.to_owned(),
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientCheckpointError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a std::path::Path,
+}
+
+impl InternalEvent for HttpClientCheckpointError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing the pagination checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "writing_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "writing_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
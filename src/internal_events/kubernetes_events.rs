@@ -0,0 +1,49 @@
+use super::prelude::{error_stage, error_type, io_error_code};
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct KubernetesEventsWatchError {
+    pub error: kube::Error,
+}
+
+impl InternalEvent for KubernetesEventsWatchError {
+    fn emit(self) {
+        error!(
+            message = "Failed to watch Kubernetes events.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsCheckpointError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for KubernetesEventsCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to checkpoint the resource version of Kubernetes events.",
+            error = %self.error,
+            error_code = io_error_code(&self.error),
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => io_error_code(&self.error),
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use super::prelude::{error_stage, error_type};
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct MqttEventsReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub topic: &'a str,
+}
+
+impl InternalEvent for MqttEventsReceived<'_> {
+    fn emit(self) {
+        trace!(
+            message = "Events received.",
+            count = self.count,
+            byte_size = self.byte_size,
+            topic = self.topic,
+        );
+        counter!(
+            "component_received_events_total", self.count as u64,
+            "topic" => self.topic.to_owned(),
+        );
+        counter!(
+            "component_received_event_bytes_total", self.byte_size as u64,
+            "topic" => self.topic.to_owned(),
+        );
+        // deprecated
+        counter!(
+            "events_in_total", self.count as u64,
+            "topic" => self.topic.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttConnectionError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MqttConnectionError {
+    fn emit(self) {
+        error!(
+            message = "MQTT connection error.",
+            error = %self.error,
+            error_code = "connection_failed",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "connection_failed",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttSubscribeError<'a> {
+    pub error: crate::Error,
+    pub topic: &'a str,
+}
+
+impl InternalEvent for MqttSubscribeError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to subscribe to topic.",
+            error = %self.error,
+            topic = self.topic,
+            error_code = "subscribe_failed",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "subscribe_failed",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttAckError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MqttAckError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge message.",
+            error = %self.error,
+            error_code = "ack_failed",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ack_failed",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttPublishError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MqttPublishError {
+    fn emit(self) {
+        error!(
+            message = "Failed to publish message.",
+            error = %self.error,
+            error_code = "publish_failed",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "publish_failed",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        // deprecated
+        counter!("send_errors_total", 1);
+    }
+}
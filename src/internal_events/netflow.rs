@@ -0,0 +1,87 @@
+use std::net::IpAddr;
+
+use super::prelude::{error_stage, error_type};
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::netflow::parser::ParseError;
+
+#[derive(Debug)]
+pub struct NetflowParseError<'a> {
+    pub error: &'a ParseError,
+    pub exporter: IpAddr,
+}
+
+impl InternalEvent for NetflowParseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse packet, discarding.",
+            error = %self.error,
+            exporter = %self.exporter,
+            error_code = "invalid_packet",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_packet",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowTemplateNotFoundError {
+    pub exporter: IpAddr,
+    pub observation_domain_id: u32,
+    pub template_id: u16,
+}
+
+impl InternalEvent for NetflowTemplateNotFoundError {
+    fn emit(self) {
+        error!(
+            message = "Template of data records not received yet, discarding them.",
+            exporter = %self.exporter,
+            observation_domain_id = self.observation_domain_id,
+            template_id = self.template_id,
+            error_code = "template_not_found",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "template_not_found",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowTemplatesPersistError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a std::path::Path,
+}
+
+impl InternalEvent for NetflowTemplatesPersistError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing the templates.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "writing_templates",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "writing_templates",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use super::prelude::{error_stage, error_type};
+use metrics::counter;
+use prost::DecodeError;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct OpentelemetryProtoDecodeError<'a> {
+    pub error: &'a DecodeError,
+    pub http_path: &'a str,
+}
+
+impl<'a> InternalEvent for OpentelemetryProtoDecodeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to decode OTLP request.",
+            error = ?self.error,
+            http_path = %self.http_path,
+            error_code = "protobuf",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "protobuf",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OpentelemetryPartialSuccess<'a> {
+    pub rejected: i64,
+    pub message: &'a str,
+}
+
+impl<'a> InternalEvent for OpentelemetryPartialSuccess<'a> {
+    fn emit(self) {
+        error!(
+            message = "Receiver rejected part of the export request.",
+            rejected = %self.rejected,
+            reason = %self.message,
+            error_code = "partial_success",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "partial_success",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OpentelemetryMetricConversionError<'a> {
+    pub name: &'a str,
+}
+
+impl<'a> InternalEvent for OpentelemetryMetricConversionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to convert distribution to histogram; metric rejected.",
+            name = %self.name,
+            error_code = "distribution_to_histogram",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "distribution_to_histogram",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OpentelemetryExponentialHistogramRangeError<'a> {
+    pub name: &'a str,
+    pub scale: i32,
+}
+
+impl<'a> InternalEvent for OpentelemetryExponentialHistogramRangeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Exponential histogram scale or bucket index out of range; data point dropped.",
+            name = %self.name,
+            scale = %self.scale,
+            error_code = "exponential_histogram_out_of_range",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "exponential_histogram_out_of_range",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
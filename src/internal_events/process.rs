@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use super::prelude::{error_stage, error_type};
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
@@ -37,6 +39,47 @@ impl InternalEvent for VectorReloaded<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct VectorConfigChanged<'a> {
+    pub summary: &'a config::DiffSummary,
+}
+
+impl InternalEvent for VectorConfigChanged<'_> {
+    fn emit(self) {
+        let keys = |select: fn(&config::ComponentChanges) -> Vec<&config::ComponentKey>| {
+            self.summary
+                .kinds()
+                .flat_map(|(kind, changes)| {
+                    select(changes)
+                        .into_iter()
+                        .map(move |key| format!("{}.{}", kind, key))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Only the paths of changed fields are logged, as their values may hold secrets.
+        let fields = self
+            .summary
+            .kinds()
+            .flat_map(|(kind, changes)| {
+                changes.changed.iter().map(move |(key, fields)| {
+                    let paths = fields.iter().map(|field| field.path.as_str());
+                    (format!("{}.{}", kind, key), paths.collect::<Vec<_>>())
+                })
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        info!(
+            target: "vector",
+            message = "Configuration changes applied.",
+            added = ?keys(|changes| changes.added.iter().collect()),
+            removed = ?keys(|changes| changes.removed.iter().collect()),
+            changed = ?keys(|changes| changes.changed.keys().collect()),
+            fields = ?fields,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct VectorStopped;
 
@@ -85,6 +128,21 @@ impl InternalEvent for VectorReloadError {
     }
 }
 
+#[derive(Debug)]
+pub struct VectorReloadRolledBack<'a> {
+    pub reason: &'a str,
+}
+
+impl InternalEvent for VectorReloadRolledBack<'_> {
+    fn emit(self) {
+        warn!(
+            message = "New configuration is unhealthy, rolling back to the previous configuration.",
+            reason = %self.reason,
+        );
+        counter!("reload_rollbacks_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct VectorConfigLoadError;
 
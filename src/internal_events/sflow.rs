@@ -0,0 +1,42 @@
+use super::prelude::{error_stage, error_type};
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SflowEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for SflowEventsReceived {
+    fn emit(self) {
+        trace!(message = "Events received.", count = %self.count, byte_size = %self.byte_size);
+        counter!("component_received_events_total", self.count as u64);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SflowParseError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for SflowParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Error occurred while parsing sFlow data.",
+            error = ?self.error,
+            stage = error_stage::PROCESSING,
+            error_type = error_type::PARSER_FAILED,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
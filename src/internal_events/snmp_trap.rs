@@ -0,0 +1,59 @@
+use std::net::SocketAddr;
+
+use super::prelude::{error_stage, error_type, io_error_code};
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::snmp_trap::ReceiveError;
+
+#[derive(Debug)]
+pub struct SnmpTrapReceiveError<'a> {
+    pub error: &'a ReceiveError,
+    pub peer: SocketAddr,
+}
+
+impl InternalEvent for SnmpTrapReceiveError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process SNMP message, discarding.",
+            error = %self.error,
+            peer = %self.peer,
+            error_code = self.error.error_code(),
+            error_type = self.error.error_type(),
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => self.error.error_code(),
+            "error_type" => self.error.error_type(),
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SnmpTrapResponseError {
+    pub error: std::io::Error,
+    pub peer: SocketAddr,
+}
+
+impl InternalEvent for SnmpTrapResponseError {
+    fn emit(self) {
+        error!(
+            message = "Failed to send SNMP response.",
+            error = %self.error,
+            peer = %self.peer,
+            error_code = io_error_code(&self.error),
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => io_error_code(&self.error),
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
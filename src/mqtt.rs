@@ -0,0 +1,329 @@
+//! The connection to MQTT brokers shared by the `mqtt` source and sink, speaking either MQTT 3.1.1
+//! or MQTT 5.
+
+use std::{convert::TryFrom, time::Duration};
+
+use bytes::Bytes;
+use rumqttc::{v5, Outgoing, TlsConfiguration, Transport};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+use crate::tls::{TlsConfig, TlsError, TlsSettings};
+
+/// The capacity of the channel of requests from the clients to their event loop.
+const REQUESTS_CAPACITY: usize = 100;
+
+#[derive(Debug, Snafu)]
+pub(crate) enum MqttConfigError {
+    #[snafu(display("MQTT TLS Config Error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("MQTT keep alive must be at least 5 seconds, got {}", keep_alive_secs))]
+    KeepAlive { keep_alive_secs: u64 },
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+pub(crate) enum MqttProtocol {
+    #[derivative(Default)]
+    #[serde(rename = "3.1.1")]
+    V3_1_1,
+    #[serde(rename = "5")]
+    V5,
+}
+
+/// The supported quality of service levels, configured as `0` or `1`.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(try_from = "u8", into = "u8")]
+pub(crate) enum MqttQos {
+    AtMostOnce,
+    #[derivative(Default)]
+    AtLeastOnce,
+}
+
+impl TryFrom<u8> for MqttQos {
+    type Error = String;
+
+    fn try_from(qos: u8) -> Result<Self, Self::Error> {
+        match qos {
+            0 => Ok(Self::AtMostOnce),
+            1 => Ok(Self::AtLeastOnce),
+            qos => Err(format!(
+                "unsupported QoS {}, only 0 and 1 are supported",
+                qos
+            )),
+        }
+    }
+}
+
+impl From<MqttQos> for u8 {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => 0,
+            MqttQos::AtLeastOnce => 1,
+        }
+    }
+}
+
+impl From<MqttQos> for rumqttc::QoS {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => Self::AtMostOnce,
+            MqttQos::AtLeastOnce => Self::AtLeastOnce,
+        }
+    }
+}
+
+impl From<MqttQos> for v5::mqttbytes::QoS {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => Self::AtMostOnce,
+            MqttQos::AtLeastOnce => Self::AtLeastOnce,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct MqttConnectionConfig {
+    pub(crate) host: String,
+    #[serde(default = "default_port")]
+    pub(crate) port: u16,
+    /// Identifies the session on the broker, so must be unique among its clients.
+    pub(crate) client_id: Option<String>,
+    #[serde(default)]
+    pub(crate) protocol: MqttProtocol,
+    #[serde(default = "default_keep_alive_secs")]
+    pub(crate) keep_alive_secs: u64,
+    pub(crate) user: Option<String>,
+    pub(crate) password: Option<String>,
+    pub(crate) tls: Option<TlsConfig>,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive_secs() -> u64 {
+    60
+}
+
+impl MqttConnectionConfig {
+    /// Builds a client along with the event loop driving its connection. Nothing is sent to the
+    /// broker until the event loop is polled, which (re)connects as needed.
+    ///
+    /// With a `session_expiry`, the broker keeps the subscriptions and the unacknowledged messages
+    /// of the session across reconnections. MQTT 5 brokers discard the session once it has been
+    /// disconnected for that many seconds, while MQTT 3.1.1 brokers apply their own policy.
+    /// Without it, each connection starts a clean session. With `manual_acks`, the received
+    /// messages must be acknowledged with [`MqttClient::ack`].
+    pub(crate) fn build(
+        &self,
+        default_client_id: &str,
+        session_expiry: Option<u32>,
+        manual_acks: bool,
+    ) -> Result<(MqttClient, MqttEventLoop), MqttConfigError> {
+        if self.keep_alive_secs < 5 {
+            return Err(MqttConfigError::KeepAlive {
+                keep_alive_secs: self.keep_alive_secs,
+            });
+        }
+        let client_id = self
+            .client_id
+            .clone()
+            .unwrap_or_else(|| default_client_id.to_owned());
+        let keep_alive = Duration::from_secs(self.keep_alive_secs);
+        let transport = self.transport()?;
+        let clean_session = session_expiry.is_none();
+
+        Ok(match self.protocol {
+            MqttProtocol::V3_1_1 => {
+                let mut options = rumqttc::MqttOptions::new(client_id, &self.host, self.port);
+                options
+                    .set_keep_alive(keep_alive)
+                    .set_clean_session(clean_session)
+                    .set_manual_acks(manual_acks)
+                    .set_transport(transport);
+                if let Some(user) = &self.user {
+                    options.set_credentials(user, self.password.as_deref().unwrap_or_default());
+                }
+                let (client, eventloop) = rumqttc::AsyncClient::new(options, REQUESTS_CAPACITY);
+                (MqttClient::V3_1_1(client), MqttEventLoop::V3_1_1(eventloop))
+            }
+            MqttProtocol::V5 => {
+                let mut options = v5::MqttOptions::new(client_id, &self.host, self.port);
+                options
+                    .set_keep_alive(keep_alive)
+                    .set_clean_start(clean_session)
+                    // A session expiring right away, the default of MQTT 5, wouldn't outlive
+                    // the connection.
+                    .set_session_expiry_interval(session_expiry)
+                    .set_manual_acks(manual_acks)
+                    .set_transport(transport);
+                if let Some(user) = &self.user {
+                    options.set_credentials(user, self.password.as_deref().unwrap_or_default());
+                }
+                let (client, eventloop) = v5::AsyncClient::new(options, REQUESTS_CAPACITY);
+                (MqttClient::V5(client), MqttEventLoop::V5(eventloop))
+            }
+        })
+    }
+
+    fn transport(&self) -> Result<Transport, MqttConfigError> {
+        match &self.tls {
+            Some(tls) if tls.enabled.unwrap_or(false) => {
+                let connector = TlsSettings::from_options(&Some(tls.options.clone()))
+                    .and_then(|settings| settings.native_tls_connector())
+                    .context(TlsSnafu)?;
+                Ok(Transport::tls_with_config(
+                    TlsConfiguration::NativeConnector(connector),
+                ))
+            }
+            _ => Ok(Transport::tcp()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum MqttClient {
+    V3_1_1(rumqttc::AsyncClient),
+    V5(v5::AsyncClient),
+}
+
+impl MqttClient {
+    /// Requests a subscription without waiting, as it's called while handling the events of the
+    /// event loop that would process the request.
+    pub(crate) fn try_subscribe(&self, topic: &str, qos: MqttQos) -> crate::Result<()> {
+        match self {
+            Self::V3_1_1(client) => client.try_subscribe(topic, qos.into())?,
+            Self::V5(client) => client.try_subscribe(topic, qos.into())?,
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn publish(
+        &self,
+        topic: String,
+        qos: MqttQos,
+        retain: bool,
+        payload: Bytes,
+    ) -> crate::Result<()> {
+        match self {
+            Self::V3_1_1(client) => {
+                client
+                    .publish(topic, qos.into(), retain, payload.to_vec())
+                    .await?
+            }
+            Self::V5(client) => client.publish(topic, qos.into(), retain, payload).await?,
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn ack(&self, message: &MqttMessage) -> crate::Result<()> {
+        match (self, &message.packet) {
+            (Self::V3_1_1(client), MqttPublish::V3_1_1(publish)) => client.ack(publish).await?,
+            (Self::V5(client), MqttPublish::V5(publish)) => client.ack(publish).await?,
+            _ => unreachable!("The messages are acknowledged by the client that received them."),
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn disconnect(&self) -> crate::Result<()> {
+        match self {
+            Self::V3_1_1(client) => client.disconnect().await?,
+            Self::V5(client) => client.disconnect().await?,
+        }
+        Ok(())
+    }
+}
+
+pub(crate) enum MqttEventLoop {
+    V3_1_1(rumqttc::EventLoop),
+    V5(v5::EventLoop),
+}
+
+/// The events of the event loop relevant to the source and sink.
+pub(crate) enum MqttEvent {
+    /// The connection to the broker is (re)established.
+    Connected,
+    /// A message is received on a subscribed topic.
+    Message(MqttMessage),
+    /// A message is sent to the broker, with the packet identifier its acknowledgement will carry
+    /// for QoS 1. Sent in the order the messages were published.
+    Published(u16),
+    /// The broker acknowledged the message with the given packet identifier.
+    Acknowledged(u16),
+    Other,
+}
+
+pub(crate) struct MqttMessage {
+    pub(crate) topic: String,
+    pub(crate) payload: Bytes,
+    packet: MqttPublish,
+}
+
+enum MqttPublish {
+    V3_1_1(rumqttc::Publish),
+    V5(v5::mqttbytes::v5::Publish),
+}
+
+impl MqttEventLoop {
+    /// Polls the next event, reconnecting after a connection error.
+    pub(crate) async fn poll(&mut self) -> crate::Result<MqttEvent> {
+        Ok(match self {
+            Self::V3_1_1(eventloop) => match eventloop.poll().await? {
+                rumqttc::Event::Incoming(rumqttc::Packet::ConnAck(_)) => MqttEvent::Connected,
+                rumqttc::Event::Incoming(rumqttc::Packet::Publish(publish)) => {
+                    MqttEvent::Message(MqttMessage {
+                        topic: publish.topic.clone(),
+                        payload: publish.payload.clone(),
+                        packet: MqttPublish::V3_1_1(publish),
+                    })
+                }
+                rumqttc::Event::Incoming(rumqttc::Packet::PubAck(ack)) => {
+                    MqttEvent::Acknowledged(ack.pkid)
+                }
+                rumqttc::Event::Outgoing(Outgoing::Publish(pkid)) => MqttEvent::Published(pkid),
+                _ => MqttEvent::Other,
+            },
+            Self::V5(eventloop) => match eventloop.poll().await? {
+                v5::Event::Incoming(v5::Incoming::ConnAck(_)) => MqttEvent::Connected,
+                v5::Event::Incoming(v5::Incoming::Publish(publish)) => {
+                    MqttEvent::Message(MqttMessage {
+                        topic: String::from_utf8_lossy(&publish.topic).into_owned(),
+                        payload: publish.payload.clone(),
+                        packet: MqttPublish::V5(publish),
+                    })
+                }
+                v5::Event::Incoming(v5::Incoming::PubAck(ack)) => MqttEvent::Acknowledged(ack.pkid),
+                v5::Event::Outgoing(Outgoing::Publish(pkid)) => MqttEvent::Published(pkid),
+                _ => MqttEvent::Other,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        #[serde(default)]
+        qos: MqttQos,
+        #[serde(default)]
+        protocol: MqttProtocol,
+    }
+
+    #[test]
+    fn parses_qos_and_protocol() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.qos, MqttQos::AtLeastOnce);
+        assert_eq!(config.protocol, MqttProtocol::V3_1_1);
+
+        let config: Config = toml::from_str("qos = 0\nprotocol = \"5\"").unwrap();
+        assert_eq!(config.qos, MqttQos::AtMostOnce);
+        assert_eq!(config.protocol, MqttProtocol::V5);
+
+        assert!(toml::from_str::<Config>("qos = 2").is_err());
+    }
+}
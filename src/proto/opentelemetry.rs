@@ -0,0 +1,55 @@
+//! The OpenTelemetry protocol (OTLP), as generated from the `opentelemetry.proto` packages. The
+//! modules mirror the package hierarchy, which the generated code relies on to refer to the
+//! messages of other packages.
+
+#![allow(clippy::clone_on_ref_ptr)]
+
+pub mod common {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.common.v1");
+    }
+}
+
+pub mod resource {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.resource.v1");
+    }
+}
+
+pub mod logs {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.logs.v1");
+    }
+}
+
+pub mod metrics {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.metrics.v1");
+    }
+}
+
+pub mod trace {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.trace.v1");
+    }
+}
+
+pub mod collector {
+    pub mod logs {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.logs.v1");
+        }
+    }
+
+    pub mod metrics {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.metrics.v1");
+        }
+    }
+
+    pub mod trace {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.trace.v1");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet};
+
+use aws_sdk_secretsmanager::{Client, Endpoint, Region};
+use aws_smithy_client::erase::DynConnector;
+use aws_types::credentials::SharedCredentialsProvider;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aws::{create_client, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
+    config::{ProxyConfig, SecretBackend},
+    tls::TlsOptions,
+};
+
+pub(crate) struct SecretsManagerClientBuilder;
+
+impl ClientBuilder for SecretsManagerClientBuilder {
+    type ConfigBuilder = aws_sdk_secretsmanager::config::Builder;
+    type Client = Client;
+
+    fn create_config_builder(
+        credentials_provider: SharedCredentialsProvider,
+    ) -> Self::ConfigBuilder {
+        aws_sdk_secretsmanager::config::Builder::new().credentials_provider(credentials_provider)
+    }
+
+    fn with_endpoint_resolver(
+        builder: Self::ConfigBuilder,
+        endpoint: Endpoint,
+    ) -> Self::ConfigBuilder {
+        builder.endpoint_resolver(endpoint)
+    }
+
+    fn with_region(builder: Self::ConfigBuilder, region: Region) -> Self::ConfigBuilder {
+        builder.region(region)
+    }
+
+    fn client_from_conf_conn(
+        builder: Self::ConfigBuilder,
+        connector: DynConnector,
+    ) -> Self::Client {
+        Self::Client::from_conf_conn(builder.build(), connector)
+    }
+}
+
+/// Reads secrets from a single AWS Secrets Manager secret, whose value is a JSON object of
+/// secret keys to values.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsSecretsManagerBackend {
+    pub secret_id: String,
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+    pub tls: Option<TlsOptions>,
+}
+
+impl AwsSecretsManagerBackend {
+    async fn fetch(&self) -> crate::Result<HashMap<String, String>> {
+        let client = create_client::<SecretsManagerClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &ProxyConfig::from_env(),
+            &self.tls,
+        )
+        .await?;
+        let output = client
+            .get_secret_value()
+            .secret_id(&self.secret_id)
+            .send()
+            .await?;
+        let secret_string = output
+            .secret_string()
+            .ok_or_else(|| format!("Secret {:?} has no string value.", self.secret_id))?;
+        Ok(serde_json::from_str(secret_string)?)
+    }
+}
+
+#[typetag::serde(name = "aws_secrets_manager")]
+impl SecretBackend for AwsSecretsManagerBackend {
+    fn retrieve(&mut self, secret_keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+        let backend = self.clone();
+        let mut secrets = super::block_on(async move { backend.fetch().await })?;
+        secrets.retain(|key, _| secret_keys.contains(key));
+        super::ensure_all_present(&secret_keys, &secrets)?;
+        Ok(secrets)
+    }
+
+    fn backend_type(&self) -> &'static str {
+        "aws_secrets_manager"
+    }
+}
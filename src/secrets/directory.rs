@@ -0,0 +1,43 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::SecretError;
+use crate::config::SecretBackend;
+
+/// Reads each secret from the file of the same name in a directory, which is how Kubernetes
+/// exposes secrets mounted as volumes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DirectoryBackend {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub remove_trailing_whitespace: bool,
+}
+
+#[typetag::serde(name = "directory")]
+impl SecretBackend for DirectoryBackend {
+    fn retrieve(&mut self, secret_keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+        let mut secrets = HashMap::new();
+        for key in secret_keys {
+            let mut value = std::fs::read_to_string(self.path.join(&key)).map_err(|error| {
+                SecretError::SecretRetrieval {
+                    key: key.clone(),
+                    error: error.to_string(),
+                }
+            })?;
+            if self.remove_trailing_whitespace {
+                value.truncate(value.trim_end().len());
+            }
+            secrets.insert(key, value);
+        }
+        Ok(secrets)
+    }
+
+    fn backend_type(&self) -> &'static str {
+        "directory"
+    }
+}
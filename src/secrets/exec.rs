@@ -0,0 +1,173 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::SecretError;
+use crate::config::SecretBackend;
+
+/// Retrieves secrets by running an external command, using the same protocol as the Datadog
+/// Agent's secrets backend.
+///
+/// The command receives `{"version": "1.0", "secrets": ["<key>", ...]}` on stdin and must print
+/// `{"<key>": {"value": "<value>", "error": null}, ...}` on stdout.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecBackend {
+    pub command: Vec<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+#[derive(Serialize)]
+struct ExecRequest<'a> {
+    version: &'static str,
+    secrets: Vec<&'a String>,
+}
+
+#[derive(Deserialize)]
+struct ExecSecret {
+    value: Option<String>,
+    error: Option<String>,
+}
+
+#[typetag::serde(name = "exec")]
+impl SecretBackend for ExecBackend {
+    fn retrieve(&mut self, secret_keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or("The `command` of an `exec` secret backend must not be empty.")?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let mut secrets = secret_keys.iter().collect::<Vec<_>>();
+        secrets.sort();
+        let request = serde_json::to_vec(&ExecRequest {
+            version: "1.0",
+            secrets,
+        })?;
+        // Dropping stdin once written closes it, signaling the end of the request.
+        child
+            .stdin
+            .take()
+            .expect("stdin should be piped")
+            .write_all(&request)?;
+
+        // Read stdout concurrently so that a large output can't block the command.
+        let mut stdout = child.stdout.take().expect("stdout should be piped");
+        let reader = thread::spawn(move || {
+            let mut output = Vec::new();
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(self.timeout_secs);
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "Secret command timed out after {} seconds.",
+                    self.timeout_secs
+                )
+                .into());
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        if !status.success() {
+            return Err(format!("Secret command failed with {}.", status).into());
+        }
+
+        let output = reader
+            .join()
+            .map_err(|_| "Failed to read the output of the secret command.")??;
+        let response = serde_json::from_slice::<HashMap<String, ExecSecret>>(&output)?;
+
+        let mut secrets = HashMap::new();
+        for (key, secret) in response {
+            if !secret_keys.contains(&key) {
+                continue;
+            }
+            match (secret.value, secret.error) {
+                (_, Some(error)) => return Err(SecretError::SecretRetrieval { key, error }.into()),
+                (Some(value), None) => {
+                    secrets.insert(key, value);
+                }
+                (None, None) => return Err(SecretError::MissingSecret { key }.into()),
+            }
+        }
+        super::ensure_all_present(&secret_keys, &secrets)?;
+        Ok(secrets)
+    }
+
+    fn backend_type(&self) -> &'static str {
+        "exec"
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn backend(script: &str) -> ExecBackend {
+        ExecBackend {
+            command: vec!["sh".into(), "-c".into(), script.into()],
+            timeout_secs: 5,
+        }
+    }
+
+    fn keys(keys: &[&str]) -> HashSet<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn retrieves_secrets() {
+        let mut backend = backend(
+            r#"cat > /dev/null; echo '{"a": {"value": "one", "error": null}, "b": {"value": "two", "error": null}}'"#,
+        );
+        let secrets = backend.retrieve(keys(&["a", "b"])).unwrap();
+        assert_eq!(secrets["a"], "one");
+        assert_eq!(secrets["b"], "two");
+    }
+
+    #[test]
+    fn reports_secret_errors() {
+        let mut backend =
+            backend(r#"cat > /dev/null; echo '{"a": {"value": null, "error": "access denied"}}'"#);
+        let error = backend.retrieve(keys(&["a"])).unwrap_err();
+        assert!(error.to_string().contains("access denied"));
+    }
+
+    #[test]
+    fn fails_on_missing_secret() {
+        let mut backend = backend(r#"cat > /dev/null; echo '{}'"#);
+        assert!(backend.retrieve(keys(&["a"])).is_err());
+    }
+
+    #[test]
+    fn times_out() {
+        let mut backend = ExecBackend {
+            timeout_secs: 0,
+            ..backend("sleep 5")
+        };
+        let error = backend.retrieve(keys(&["a"])).unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+    }
+}
@@ -0,0 +1,30 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::SecretBackend;
+
+/// Reads secrets from a JSON file containing an object of secret keys to values.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileBackend {
+    pub path: PathBuf,
+}
+
+#[typetag::serde(name = "file")]
+impl SecretBackend for FileBackend {
+    fn retrieve(&mut self, secret_keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+        let contents = std::fs::read_to_string(&self.path)?;
+        let mut secrets = serde_json::from_str::<HashMap<String, String>>(&contents)?;
+        secrets.retain(|key, _| secret_keys.contains(key));
+        super::ensure_all_present(&secret_keys, &secrets)?;
+        Ok(secrets)
+    }
+
+    fn backend_type(&self) -> &'static str {
+        "file"
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(feature = "secrets-aws-secrets-manager")]
+pub mod aws_secrets_manager;
+pub mod directory;
+pub mod exec;
+pub mod file;
+
+use std::collections::{HashMap, HashSet};
+
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+pub enum SecretError {
+    #[snafu(display("Secret {:?} was not returned by the backend.", key))]
+    MissingSecret { key: String },
+    #[snafu(display("Secret {:?} could not be retrieved: {}.", key, error))]
+    SecretRetrieval { key: String, error: String },
+}
+
+/// Checks that every requested key was resolved, as a backend silently omitting a secret would
+/// otherwise result in an empty value being interpolated into the config.
+fn ensure_all_present(
+    secret_keys: &HashSet<String>,
+    secrets: &HashMap<String, String>,
+) -> crate::Result<()> {
+    match secret_keys.iter().find(|key| !secrets.contains_key(*key)) {
+        Some(key) => Err(SecretError::MissingSecret { key: key.clone() }.into()),
+        None => Ok(()),
+    }
+}
+
+/// Runs a future to completion from the synchronous config loading path, on a dedicated thread
+/// with its own runtime. Vector loads its config from within its runtime, which may not be able to
+/// block in place, while subcommands such as `vector config` do not run one at all.
+#[cfg(feature = "secrets-aws-secrets-manager")]
+fn block_on<F>(future: F) -> F::Output
+where
+    F: std::future::Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Unable to create a runtime for retrieving secrets.")
+            .block_on(future)
+    })
+    .join()
+    .expect("Retrieving secrets panicked.")
+}
+
+#[cfg(all(test, feature = "secrets-aws-secrets-manager"))]
+mod tests {
+    #[tokio::test(flavor = "current_thread")]
+    async fn block_on_within_current_thread_runtime() {
+        assert_eq!(super::block_on(async { 42 }), 42);
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
             encoder::ElasticsearchEncoder,
             request_builder::ElasticsearchRequestBuilder,
             retry::ElasticsearchRetryLogic,
-            service::{ElasticsearchService, HttpRequestBuilder},
+            service::{ElasticsearchService, HttpRequestBuilder, PartialRetrySettings},
             sink::ElasticsearchSink,
             BatchActionTemplateSnafu, ElasticsearchAuth, ElasticsearchCommon,
             ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu,
@@ -38,6 +38,9 @@ use crate::{
 /// The field name for the timestamp required by data stream mode
 pub const DATA_STREAM_TIMESTAMP_KEY: &str = "@timestamp";
 
+/// The maximum number of times the failed documents of a bulk request are re-sent on their own
+const PARTIAL_RETRY_ATTEMPTS_MAX: usize = 5;
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ElasticsearchConfig {
@@ -63,6 +66,8 @@ pub struct ElasticsearchConfig {
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     #[serde(default)]
     pub request: RequestConfig,
+    #[serde(default)]
+    pub request_retry_partial: bool,
     pub auth: Option<ElasticsearchAuth>,
     pub query: Option<HashMap<String, String>>,
     pub aws: Option<RegionOrEndpoint>,
@@ -308,7 +313,8 @@ impl SinkConfig for ElasticsearchConfig {
 
         let request_builder = ElasticsearchRequestBuilder {
             compression: self.compression,
-            encoder: encoding,
+            encoder: encoding.clone(),
+            request_retry_partial: self.request_retry_partial,
         };
 
         let request_limits = self
@@ -316,6 +322,17 @@ impl SinkConfig for ElasticsearchConfig {
             .tower
             .unwrap_with(&TowerRequestConfig::default());
 
+        let partial_retry = self.request_retry_partial.then(|| PartialRetrySettings {
+            request_builder: ElasticsearchRequestBuilder {
+                compression: self.compression,
+                encoder: encoding,
+                request_retry_partial: true,
+            },
+            max_attempts: request_limits
+                .retry_attempts
+                .min(PARTIAL_RETRY_ATTEMPTS_MAX),
+        });
+
         let http_request_builder = HttpRequestBuilder {
             bulk_uri: common.bulk_uri,
             http_request_config: self.request.clone(),
@@ -328,7 +345,11 @@ impl SinkConfig for ElasticsearchConfig {
 
         let service = ServiceBuilder::new()
             .settings(request_limits, ElasticsearchRetryLogic)
-            .service(ElasticsearchService::new(http_client, http_request_builder));
+            .service(ElasticsearchService::new(
+                http_client,
+                http_request_builder,
+                partial_retry,
+            ));
 
         let sink = ElasticsearchSink {
             batch_settings,
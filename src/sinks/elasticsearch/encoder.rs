@@ -10,6 +10,7 @@ use crate::{
     },
 };
 
+#[derive(Clone)]
 pub struct ProcessedEvent {
     pub index: String,
     pub bulk_action: BulkAction,
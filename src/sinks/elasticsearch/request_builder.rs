@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bytes::Bytes;
 use vector_core::ByteSizeOf;
 
@@ -6,7 +8,7 @@ use crate::{
     sinks::{
         elasticsearch::{
             encoder::{ElasticsearchEncoder, ProcessedEvent},
-            service::ElasticsearchRequest,
+            service::{BulkDocument, ElasticsearchRequest},
         },
         util::{encoding::EncodingConfigFixed, Compression, RequestBuilder},
     },
@@ -15,12 +17,15 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: EncodingConfigFixed<ElasticsearchEncoder>,
+    /// Whether to keep a copy of each event so that failed bulk items can be re-sent on their own.
+    pub request_retry_partial: bool,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
     events_byte_size: usize,
+    documents: Vec<BulkDocument>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -40,16 +45,25 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
     }
 
     fn split_input(&self, mut events: Vec<ProcessedEvent>) -> (Self::Metadata, Self::Events) {
-        let events_byte_size = events
-            .iter()
-            .map(|x| x.log.size_of())
-            .reduce(|a, b| a + b)
-            .unwrap_or(0);
+        let mut finalizers = EventFinalizers::default();
+        let documents = events
+            .iter_mut()
+            .map(|event| {
+                let document_finalizers = event.take_finalizers();
+                finalizers.merge(document_finalizers.clone());
+                BulkDocument {
+                    finalizers: document_finalizers,
+                    byte_size: event.log.size_of(),
+                    event: self.request_retry_partial.then(|| event.clone()),
+                }
+            })
+            .collect::<Vec<_>>();
 
         let metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            finalizers,
             batch_size: events.len(),
-            events_byte_size,
+            events_byte_size: documents.iter().map(|document| document.byte_size).sum(),
+            documents,
         };
         (metadata, events)
     }
@@ -60,6 +74,8 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             finalizers: metadata.finalizers,
             batch_size: metadata.batch_size,
             events_byte_size: metadata.events_byte_size,
+            documents: Arc::new(metadata.documents),
+            pending: Arc::default(),
         }
     }
 }
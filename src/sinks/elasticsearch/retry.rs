@@ -4,7 +4,7 @@ use serde::Deserialize;
 use crate::{
     http::HttpError,
     sinks::{
-        elasticsearch::service::ElasticsearchResponse,
+        elasticsearch::service::{BulkItems, ElasticsearchResponse},
         util::retries::{RetryAction, RetryLogic},
     },
 };
@@ -15,7 +15,7 @@ struct EsResultResponse {
 }
 
 #[derive(Deserialize, Debug)]
-enum EsResultItem {
+pub(super) enum EsResultItem {
     #[serde(rename = "index")]
     Index(EsIndexResult),
     #[serde(rename = "create")]
@@ -24,7 +24,7 @@ enum EsResultItem {
 
 impl EsResultItem {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    fn result(self) -> EsIndexResult {
+    pub(super) fn result(self) -> EsIndexResult {
         match self {
             EsResultItem::Index(r) => r,
             EsResultItem::Create(r) => r,
@@ -33,15 +33,54 @@ impl EsResultItem {
 }
 
 #[derive(Deserialize, Debug)]
-struct EsIndexResult {
-    error: Option<EsErrorDetails>,
+pub(super) struct EsIndexResult {
+    #[serde(default)]
+    pub(super) status: u16,
+    pub(super) error: Option<EsErrorDetails>,
+}
+
+impl EsIndexResult {
+    /// Classifies the outcome of a single bulk item.
+    pub(super) fn outcome(&self) -> BulkItemOutcome {
+        match (&self.error, StatusCode::from_u16(self.status)) {
+            (None, Ok(status)) if status.is_success() => BulkItemOutcome::Delivered,
+            // Older Elasticsearch versions may omit the status of successful items.
+            (None, Err(_)) => BulkItemOutcome::Delivered,
+            (_, Ok(StatusCode::TOO_MANY_REQUESTS)) => BulkItemOutcome::Retriable,
+            (_, Ok(status))
+                if status.is_server_error() && status != StatusCode::NOT_IMPLEMENTED =>
+            {
+                BulkItemOutcome::Retriable
+            }
+            _ => BulkItemOutcome::Rejected,
+        }
+    }
+
+    /// Describes why this bulk item failed.
+    pub(super) fn reason(&self) -> String {
+        match &self.error {
+            Some(error) => format!("error type: {}, reason: {}", error.err_type, error.reason),
+            None => format!("item status: {}", self.status),
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
-struct EsErrorDetails {
-    reason: String,
+pub(super) struct EsErrorDetails {
+    pub(super) reason: String,
     #[serde(rename = "type")]
-    err_type: String,
+    pub(super) err_type: String,
+}
+
+/// The outcome of a single document in a bulk request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BulkItemOutcome {
+    /// The document was indexed.
+    Delivered,
+    /// The document was refused for a transient reason (e.g. `429`/`503`) and may be retried.
+    Retriable,
+    /// The document was permanently refused (e.g. a mapping error) and must not be retried.
+    Rejected,
 }
 
 #[derive(Clone)]
@@ -56,6 +95,14 @@ impl RetryLogic for ElasticsearchRetryLogic {
     }
 
     fn should_retry_response(&self, response: &ElasticsearchResponse) -> RetryAction {
+        match response.bulk_items {
+            BulkItems::Pending => {
+                return RetryAction::Retry("some bulk items failed for a transient reason".into())
+            }
+            BulkItems::Finalized => return RetryAction::Successful,
+            BulkItems::Unhandled => {}
+        }
+
         let status = response.http_response.status();
 
         match status {
@@ -89,6 +136,17 @@ impl RetryLogic for ElasticsearchRetryLogic {
     }
 }
 
+/// Parses the per-item results of a bulk response, in the same order as the documents of the request.
+pub(super) fn parse_bulk_items(body: &[u8]) -> serde_json::Result<Vec<EsIndexResult>> {
+    serde_json::from_slice::<EsResultResponse>(body).map(|response| {
+        response
+            .items
+            .into_iter()
+            .map(EsResultItem::result)
+            .collect()
+    })
+}
+
 fn get_error_reason(body: &str) -> String {
     match serde_json::from_str::<EsResultResponse>(body) {
         Err(json_error) => format!(
@@ -125,6 +183,7 @@ mod tests {
                 event_status: EventStatus::Rejected,
                 batch_size: 1,
                 events_byte_size: 1,
+                bulk_items: BulkItems::Unhandled,
             }),
             RetryAction::DontRetry(_)
         ));
@@ -143,4 +202,31 @@ mod tests {
         let reason = get_error_reason(json);
         assert_eq!(reason, "error type: mapper_parsing_exception, reason: object mapping for [host] tried to parse field [host] as object, but found a concrete value");
     }
+
+    #[test]
+    fn classifies_bulk_items() {
+        let json = r#"{"took":3,"errors":true,"items":[
+            {"index":{"_index":"test","_id":"1","status":201}},
+            {"index":{"_index":"test","_id":"2","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}},
+            {"create":{"_index":"test","_id":"3","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}},
+            {"index":{"_index":"test","_id":"4","status":503,"error":{"type":"unavailable_shards_exception","reason":"primary shard is not active"}}},
+            {"create":{"_index":"test","_id":"5","status":409,"error":{"type":"version_conflict_engine_exception","reason":"document already exists"}}}
+        ]}"#;
+        let items = parse_bulk_items(json.as_bytes()).unwrap();
+        let outcomes = items.iter().map(EsIndexResult::outcome).collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            vec![
+                BulkItemOutcome::Delivered,
+                BulkItemOutcome::Retriable,
+                BulkItemOutcome::Rejected,
+                BulkItemOutcome::Retriable,
+                BulkItemOutcome::Rejected,
+            ]
+        );
+        assert_eq!(
+            items[2].reason(),
+            "error type: mapper_parsing_exception, reason: failed to parse"
+        );
+    }
 }
@@ -2,13 +2,13 @@ use aws_types::credentials::SharedCredentialsProvider;
 use aws_types::region::Region;
 use std::{
     collections::HashMap,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use http::{Response, Uri};
+use http::{Response, StatusCode, Uri};
 use hyper::{service::Service, Body, Request};
 use tower::ServiceExt;
 use vector_core::{
@@ -19,10 +19,17 @@ use crate::sinks::elasticsearch::sign_request;
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::{Auth, HttpClient},
-    internal_events::ElasticsearchResponseError,
-    sinks::util::{
-        http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+    internal_events::{ElasticsearchBulkItemFailed, ElasticsearchResponseError},
+    sinks::{
+        elasticsearch::{
+            encoder::ProcessedEvent,
+            request_builder::ElasticsearchRequestBuilder,
+            retry::{parse_bulk_items, BulkItemOutcome},
+        },
+        util::{
+            http::{HttpBatchService, RequestConfig},
+            Compression, ElementCount, RequestBuilder,
+        },
     },
 };
 
@@ -32,14 +39,49 @@ pub struct ElasticsearchRequest {
     pub finalizers: EventFinalizers,
     pub batch_size: usize,
     pub events_byte_size: usize,
+    /// The documents of the bulk request, in the order they appear in the payload.
+    pub documents: Arc<Vec<BulkDocument>>,
+    /// The documents still to be re-sent after a partial failure, shared with the copies of the
+    /// request made by the retry layer.
+    pub pending: Arc<Mutex<PendingBulkItems>>,
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
     fn allocated_bytes(&self) -> usize {
-        self.payload.allocated_bytes() + self.finalizers.allocated_bytes()
+        self.payload.allocated_bytes()
+            + self.finalizers.allocated_bytes()
+            + self.documents.allocated_bytes()
     }
 }
 
+/// A single document of a bulk request.
+///
+/// Each document keeps its own finalizers so that the outcome of its bulk item can be reported
+/// individually, rather than applying the outcome of the worst item to the whole request.
+#[derive(Clone)]
+pub struct BulkDocument {
+    pub finalizers: EventFinalizers,
+    pub byte_size: usize,
+    /// The original event, only retained when partial retries are enabled.
+    pub event: Option<ProcessedEvent>,
+}
+
+impl ByteSizeOf for BulkDocument {
+    fn allocated_bytes(&self) -> usize {
+        self.finalizers.allocated_bytes() + self.event.allocated_bytes()
+    }
+}
+
+/// The retriable documents of a bulk request that was partially rejected.
+#[derive(Default)]
+pub struct PendingBulkItems {
+    /// The request holding only the retriable documents, along with their index in the original
+    /// request. It replaces the original request when the retry layer re-sends it.
+    retry: Option<(ElasticsearchRequest, Vec<usize>)>,
+    attempts: usize,
+    delivered: DeliveredItems,
+}
+
 impl ElementCount for ElasticsearchRequest {
     fn element_count(&self) -> usize {
         self.batch_size
@@ -58,18 +100,22 @@ impl Finalizable for ElasticsearchRequest {
     }
 }
 
+type BulkService = HttpBatchService<
+    BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
+    ElasticsearchRequest,
+>;
+
 #[derive(Clone)]
 pub struct ElasticsearchService {
-    batch_service: HttpBatchService<
-        BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
-        ElasticsearchRequest,
-    >,
+    batch_service: BulkService,
+    partial_retry: Option<Arc<PartialRetrySettings>>,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        partial_retry: Option<PartialRetrySettings>,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -78,7 +124,47 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            partial_retry: partial_retry.map(Arc::new),
+        }
+    }
+}
+
+/// Settings for re-sending only the retriable documents of a partially failed bulk request.
+pub struct PartialRetrySettings {
+    pub request_builder: ElasticsearchRequestBuilder,
+    /// The maximum number of times the retriable documents of a request are re-sent.
+    pub max_attempts: usize,
+}
+
+impl PartialRetrySettings {
+    fn build_request(
+        &self,
+        documents: &[BulkDocument],
+        indices: &[usize],
+    ) -> Option<ElasticsearchRequest> {
+        let events = indices
+            .iter()
+            .map(|&index| documents[index].event.clone())
+            .collect::<Option<Vec<_>>>()?;
+        let payload = self
+            .request_builder
+            .encode_events(events)
+            .map_err(|error| error!(message = "Failed to encode bulk items for retry.", %error))
+            .ok()?;
+
+        Some(ElasticsearchRequest {
+            payload,
+            finalizers: EventFinalizers::default(),
+            batch_size: indices.len(),
+            events_byte_size: indices
+                .iter()
+                .map(|&index| documents[index].byte_size)
+                .sum(),
+            documents: Arc::default(),
+            pending: Arc::default(),
+        })
     }
 }
 
@@ -130,6 +216,18 @@ pub struct ElasticsearchResponse {
     pub event_status: EventStatus,
     pub batch_size: usize,
     pub events_byte_size: usize,
+    pub bulk_items: BulkItems,
+}
+
+/// How the documents of a request were handled after a response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkItems {
+    /// The response applies to the request as a whole.
+    Unhandled,
+    /// Every document was finalized according to its own bulk item.
+    Finalized,
+    /// Some documents failed for a transient reason and are to be re-sent on their own.
+    Pending,
 }
 
 impl DriverResponse for ElasticsearchResponse {
@@ -157,27 +255,246 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
 
     fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let partial_retry = self.partial_retry.clone();
         Box::pin(async move {
             http_service.ready().await?;
             let batch_size = req.batch_size;
             let events_byte_size = req.events_byte_size;
+            let documents = Arc::clone(&req.documents);
+            let pending = Arc::clone(&req.pending);
+
+            // When the retry layer re-sends a partially rejected request, only its retriable
+            // documents are sent.
+            let retry = pending.lock().expect("mutex poisoned").retry.clone();
+            let (req, retried) = match retry {
+                Some((retry_req, indices)) => (retry_req, Some(indices)),
+                None => (req, None),
+            };
             let http_response = http_service.call(req).await?;
-            let event_status = get_event_status(&http_response);
-            Ok(ElasticsearchResponse {
-                event_status,
+
+            let indices = match retried {
+                Some(indices) => indices,
+                None if has_bulk_errors(&http_response) && documents.len() == batch_size => {
+                    (0..documents.len()).collect()
+                }
+                None => {
+                    let event_status = get_event_status(&http_response);
+                    return Ok(ElasticsearchResponse {
+                        event_status,
+                        http_response,
+                        batch_size,
+                        events_byte_size,
+                        bulk_items: BulkItems::Unhandled,
+                    });
+                }
+            };
+
+            let mut pending = pending.lock().expect("mutex poisoned");
+            Ok(handle_bulk_errors(
                 http_response,
-                batch_size,
-                events_byte_size,
+                &documents,
+                indices,
+                &mut pending,
+                partial_retry.as_deref(),
+            ))
+        })
+    }
+}
+
+/// Why a single bulk item was not indexed.
+struct ItemFailure {
+    status: u16,
+    reason: String,
+    retriable: bool,
+}
+
+fn has_bulk_errors(response: &Response<Bytes>) -> bool {
+    response.status().is_success()
+        && String::from_utf8_lossy(response.body()).contains("\"errors\":true")
+}
+
+fn is_retriable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status.is_server_error() && status != StatusCode::NOT_IMPLEMENTED)
+}
+
+/// Determines the outcome of each of the `count` documents sent in a bulk request.
+fn classify_response(response: &Response<Bytes>, count: usize) -> Vec<Option<ItemFailure>> {
+    let status = response.status();
+    if !status.is_success() {
+        let reason = format!("{}: {}", status, String::from_utf8_lossy(response.body()));
+        return (0..count)
+            .map(|_| {
+                Some(ItemFailure {
+                    status: status.as_u16(),
+                    reason: reason.clone(),
+                    retriable: is_retriable_status(status),
+                })
+            })
+            .collect();
+    }
+
+    if !has_bulk_errors(response) {
+        return (0..count).map(|_| None).collect();
+    }
+
+    match parse_bulk_items(response.body()) {
+        Ok(items) if items.len() == count => items
+            .into_iter()
+            .map(|item| match item.outcome() {
+                BulkItemOutcome::Delivered => None,
+                outcome => Some(ItemFailure {
+                    status: item.status,
+                    reason: item.reason(),
+                    retriable: outcome == BulkItemOutcome::Retriable,
+                }),
+            })
+            .collect(),
+        Ok(items) => {
+            let reason = format!(
+                "bulk response has {} items, expected {}",
+                items.len(),
+                count
+            );
+            unattributable_failures(status, &reason, count)
+        }
+        Err(error) => {
+            let reason = format!("could not parse bulk response, error: {}", error);
+            unattributable_failures(status, &reason, count)
+        }
+    }
+}
+
+fn unattributable_failures(
+    status: StatusCode,
+    reason: &str,
+    count: usize,
+) -> Vec<Option<ItemFailure>> {
+    (0..count)
+        .map(|_| {
+            Some(ItemFailure {
+                status: status.as_u16(),
+                reason: reason.to_owned(),
+                retriable: false,
             })
         })
+        .collect()
+}
+
+/// Finalizes each document of a bulk request that was partially rejected according to its own
+/// bulk item.
+///
+/// When partial retries are enabled, the retriable documents are kept in `pending` and the
+/// response asks the retry layer to re-send the request, which then only holds these documents.
+/// This goes on until every document is finalized or the attempts run out.
+fn handle_bulk_errors(
+    http_response: Response<Bytes>,
+    documents: &[BulkDocument],
+    indices: Vec<usize>,
+    pending: &mut PendingBulkItems,
+    partial_retry: Option<&PartialRetrySettings>,
+) -> ElasticsearchResponse {
+    let failures = classify_response(&http_response, indices.len());
+    let (delivered, retriable) = finalize_bulk_items(documents, indices, failures);
+    pending.delivered.count += delivered.count;
+    pending.delivered.byte_size += delivered.byte_size;
+    pending.retry = None;
+
+    if !retriable.is_empty() {
+        let indices = retriable
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>();
+        let retry = partial_retry
+            .filter(|settings| pending.attempts < settings.max_attempts)
+            .and_then(|settings| settings.build_request(documents, &indices));
+
+        match retry {
+            Some(request) => {
+                warn!(
+                    message = "Retrying failed bulk items.",
+                    count = indices.len()
+                );
+                pending.attempts += 1;
+                pending.retry = Some((request, indices));
+                return ElasticsearchResponse {
+                    // Only used if the retry layer gives up, in which case none of the documents
+                    // still waiting to be re-sent were delivered.
+                    event_status: EventStatus::Errored,
+                    http_response,
+                    batch_size: pending.delivered.count,
+                    events_byte_size: pending.delivered.byte_size,
+                    bulk_items: BulkItems::Pending,
+                };
+            }
+            None => give_up_bulk_items(documents, retriable),
+        }
+    }
+
+    ElasticsearchResponse {
+        // Failed documents have already been finalized with their own status, which takes
+        // precedence over the status applied to the whole request.
+        event_status: EventStatus::Delivered,
+        http_response,
+        batch_size: pending.delivered.count,
+        events_byte_size: pending.delivered.byte_size,
+        bulk_items: BulkItems::Finalized,
+    }
+}
+
+#[derive(Default)]
+struct DeliveredItems {
+    count: usize,
+    byte_size: usize,
+}
+
+/// Finalizes the documents that were indexed or permanently refused, returning the count of the
+/// former and the documents that may be retried.
+fn finalize_bulk_items(
+    documents: &[BulkDocument],
+    pending: Vec<usize>,
+    failures: Vec<Option<ItemFailure>>,
+) -> (DeliveredItems, Vec<(usize, ItemFailure)>) {
+    let mut delivered = DeliveredItems::default();
+    let mut retriable = Vec::new();
+    for (index, failure) in pending.into_iter().zip(failures) {
+        let document = &documents[index];
+        match failure {
+            None => {
+                delivered.count += 1;
+                delivered.byte_size += document.byte_size;
+            }
+            Some(failure) if failure.retriable => retriable.push((index, failure)),
+            Some(failure) => {
+                emit!(ElasticsearchBulkItemFailed {
+                    status: failure.status,
+                    reason: &failure.reason,
+                    event_status: EventStatus::Rejected,
+                });
+                document.finalizers.update_status(EventStatus::Rejected);
+            }
+        }
+    }
+    (delivered, retriable)
+}
+
+fn give_up_bulk_items(documents: &[BulkDocument], retriable: Vec<(usize, ItemFailure)>) {
+    for (index, failure) in retriable {
+        emit!(ElasticsearchBulkItemFailed {
+            status: failure.status,
+            reason: &failure.reason,
+            event_status: EventStatus::Errored,
+        });
+        documents[index]
+            .finalizers
+            .update_status(EventStatus::Errored);
     }
 }
 
 fn get_event_status(response: &Response<Bytes>) -> EventStatus {
     let status = response.status();
     if status.is_success() {
-        let body = String::from_utf8_lossy(response.body());
-        if body.contains("\"errors\":true") {
+        if has_bulk_errors(response) {
             emit!(ElasticsearchResponseError::new(
                 "Response containerd errors.",
                 response
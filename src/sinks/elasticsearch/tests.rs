@@ -1,16 +1,19 @@
 use super::BulkAction;
 use crate::sinks::elasticsearch::BulkConfig;
 use crate::{
-    event::{LogEvent, Metric, MetricKind, MetricValue, Value},
+    event::{BatchStatus, LogEvent, Metric, MetricKind, MetricValue, Value},
     sinks::{
         elasticsearch::{
-            sink::process_log, DataStreamConfig, ElasticsearchCommon, ElasticsearchConfig,
-            ElasticsearchMode,
+            service::{BulkItems, ElasticsearchResponse},
+            sink::process_log,
+            DataStreamConfig, ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode,
         },
         util::encoding::{Encoder, EncodingConfigFixed},
     },
     template::Template,
 };
+use http::{Response, StatusCode};
+use hyper::Body;
 use std::{collections::BTreeMap, convert::TryFrom};
 
 #[tokio::test]
@@ -300,3 +303,188 @@ async fn allows_using_excepted_fields() {
     assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
     assert_eq!(encoded.len(), encoded_size);
 }
+
+async fn send_partially_rejected_request(
+    responses: Vec<Response<Body>>,
+) -> (
+    ElasticsearchResponse,
+    usize,
+    Vec<String>,
+    BatchStatus,
+    BatchStatus,
+) {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+
+    use futures::StreamExt;
+    use tower::{Service, ServiceBuilder, ServiceExt};
+
+    use crate::{
+        config::ProxyConfig,
+        event::BatchNotifier,
+        http::HttpClient,
+        sinks::{
+            elasticsearch::{
+                encoder::ProcessedEvent,
+                request_builder::ElasticsearchRequestBuilder,
+                retry::ElasticsearchRetryLogic,
+                service::{ElasticsearchService, HttpRequestBuilder, PartialRetrySettings},
+            },
+            util::{
+                http::RequestConfig, test::build_test_server_generic, Compression, RequestBuilder,
+                ServiceBuilderExt, TowerRequestConfig,
+            },
+        },
+        test_util::next_addr,
+    };
+
+    // The first bulk request indexes the first document and refuses the second one with a 429,
+    // the later requests get the given responses in turn.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let responses = Arc::new(Mutex::new(responses.into_iter()));
+    let addr = next_addr();
+    let (mut rx, _trigger, server) = build_test_server_generic(addr, {
+        let requests = Arc::clone(&requests);
+        move || match requests.fetch_add(1, Ordering::SeqCst) {
+            0 => Response::new(Body::from(
+                r#"{"took":1,"errors":true,"items":[
+                    {"index":{"_index":"vector","_id":"1","status":201}},
+                    {"index":{"_index":"vector","_id":"2","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}}
+                ]}"#,
+            )),
+            _ => responses
+                .lock()
+                .unwrap()
+                .next()
+                .expect("unexpected request"),
+        }
+    });
+    tokio::spawn(server);
+
+    let request_builder = || ElasticsearchRequestBuilder {
+        compression: Compression::None,
+        encoder: EncodingConfigFixed::default(),
+        request_retry_partial: true,
+    };
+    let mut service = ServiceBuilder::new()
+        .settings(
+            TowerRequestConfig::default()
+                .retry_initial_backoff_secs(0)
+                .unwrap_with(&TowerRequestConfig::default()),
+            ElasticsearchRetryLogic,
+        )
+        .service(ElasticsearchService::new(
+            HttpClient::new(None, &ProxyConfig::default()).unwrap(),
+            HttpRequestBuilder {
+                bulk_uri: format!("http://{}/_bulk", addr).parse().unwrap(),
+                query_params: Default::default(),
+                region: None,
+                compression: Compression::None,
+                http_request_config: RequestConfig::default(),
+                http_auth: None,
+                credentials_provider: None,
+            },
+            Some(PartialRetrySettings {
+                request_builder: request_builder(),
+                max_attempts: 2,
+            }),
+        ));
+
+    let (delivered_batch, delivered_receiver) = BatchNotifier::new_with_receiver();
+    let (retried_batch, retried_receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![
+        ProcessedEvent {
+            index: "vector".into(),
+            bulk_action: BulkAction::Index,
+            log: LogEvent::from("indexed").with_batch_notifier(&delivered_batch),
+            id: None,
+        },
+        ProcessedEvent {
+            index: "vector".into(),
+            bulk_action: BulkAction::Index,
+            log: LogEvent::from("refused").with_batch_notifier(&retried_batch),
+            id: None,
+        },
+    ];
+    drop((delivered_batch, retried_batch));
+
+    let request_builder = request_builder();
+    let (metadata, events) = request_builder.split_input(events);
+    let payload = request_builder.encode_events(events).unwrap();
+    let mut request = request_builder.build_request(metadata, payload);
+    let mut finalizers = crate::event::Finalizable::take_finalizers(&mut request);
+
+    let response = service.ready().await.unwrap().call(request).await.unwrap();
+    finalizers.update_status(vector_core::stream::DriverResponse::event_status(&response));
+    drop(finalizers);
+    drop(service);
+
+    let delivered_status = delivered_receiver.await;
+    let retried_status = retried_receiver.await;
+    let mut bodies = Vec::new();
+    while let Ok(Some((_, body))) =
+        tokio::time::timeout(std::time::Duration::from_millis(100), rx.next()).await
+    {
+        bodies.push(String::from_utf8_lossy(&body).into_owned());
+    }
+
+    (
+        response,
+        requests.load(std::sync::atomic::Ordering::SeqCst),
+        bodies,
+        delivered_status,
+        retried_status,
+    )
+}
+
+#[tokio::test]
+async fn partial_retry_resends_only_failed_items() {
+    let (response, requests, bodies, delivered_status, retried_status) =
+        send_partially_rejected_request(vec![
+            Response::builder()
+                .status(StatusCode::SERVICE_UNAVAILABLE)
+                .body(Body::empty())
+                .unwrap(),
+            Response::new(Body::from(
+                r#"{"took":1,"errors":false,"items":[
+                    {"index":{"_index":"vector","_id":"2","status":201}}
+                ]}"#,
+            )),
+        ])
+        .await;
+
+    assert_eq!(response.bulk_items, BulkItems::Finalized);
+    assert_eq!(response.batch_size, 2);
+    assert_eq!(delivered_status, BatchStatus::Delivered);
+    assert_eq!(retried_status, BatchStatus::Delivered);
+    assert_eq!(requests, 3);
+    // The original request held both documents, the successful retry only the refused one.
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0].lines().count(), 4);
+    assert_eq!(bodies[1].lines().count(), 2);
+    assert!(bodies[1].contains("refused"));
+}
+
+#[tokio::test]
+async fn partial_retry_gives_up_on_items_after_max_attempts() {
+    let unavailable = || {
+        Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let (response, requests, bodies, delivered_status, retried_status) =
+        send_partially_rejected_request(vec![unavailable(), unavailable()]).await;
+
+    // The indexed document is never re-sent, even though the retries of the refused one failed.
+    assert_eq!(response.bulk_items, BulkItems::Finalized);
+    assert_eq!(response.batch_size, 1);
+    assert_eq!(delivered_status, BatchStatus::Delivered);
+    assert_eq!(retried_status, BatchStatus::Errored);
+    // The original request, then one for each of the two retry attempts of the refused item.
+    assert_eq!(requests, 3);
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(bodies[0].lines().count(), 4);
+}
@@ -0,0 +1,431 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::{mpsc, Notify};
+use tokio_util::codec::Encoder as _;
+use vector_core::{
+    buffers::Acker,
+    internal_event::{BytesSent, EventsSent},
+    ByteSizeOf,
+};
+
+use crate::{
+    codecs::Encoder,
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
+    },
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{MqttConnectionError, MqttPublishError, TemplateRenderingError},
+    mqtt::{MqttClient, MqttConfigError, MqttConnectionConfig, MqttEvent, MqttEventLoop, MqttQos},
+    sinks::util::{
+        encoding::{
+            EncodingConfig, EncodingConfigAdapter, StandardEncodings, StandardEncodingsMigrator,
+            Transformer,
+        },
+        StreamSink,
+    },
+    template::{Template, TemplateParseError},
+};
+
+/// The delay before polling the event loop again after a connection error, which reconnects.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("MQTT Config Error: {}", source))]
+    Config { source: MqttConfigError },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MqttSinkConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    topic: String,
+    #[serde(default)]
+    qos: MqttQos,
+    /// Whether the broker keeps the last message of the topic for the future subscribers.
+    #[serde(default)]
+    retain: bool,
+    #[serde(flatten)]
+    encoding: EncodingConfigAdapter<EncodingConfig<StandardEncodings>, StandardEncodingsMigrator>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SinkDescription::new::<MqttSinkConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            port = 1883
+            topic = "vector"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self.clone(), cx.acker())?;
+        let healthcheck = healthcheck(self.connection.clone()).boxed();
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+/// Every connection gets its own client id, as the sink doesn't rely on a persistent session.
+fn default_client_id() -> String {
+    format!("vector-{}", uuid::Uuid::new_v4())
+}
+
+async fn healthcheck(connection: MqttConnectionConfig) -> crate::Result<()> {
+    let (client, mut eventloop) = connection.build(&default_client_id(), None, false)?;
+    loop {
+        if let MqttEvent::Connected = eventloop.poll().await? {
+            break;
+        }
+    }
+    // The disconnection is only sent while the event loop is polled.
+    client.disconnect().await?;
+    let _ = eventloop.poll().await;
+    Ok(())
+}
+
+/// A message published to the broker, waiting to be finalized.
+struct PendingMessage {
+    finalizers: EventFinalizers,
+    event_byte_size: usize,
+    byte_size: usize,
+}
+
+pub struct MqttSink {
+    client: MqttClient,
+    eventloop: MqttEventLoop,
+    topic: Template,
+    qos: MqttQos,
+    retain: bool,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    acker: Acker,
+}
+
+impl MqttSink {
+    fn new(config: MqttSinkConfig, acker: Acker) -> crate::Result<Self> {
+        let (client, eventloop) = config
+            .connection
+            .build(&default_client_id(), None, false)
+            .context(ConfigSnafu)?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.encoding();
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(MqttSink {
+            client,
+            eventloop,
+            topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
+            qos: config.qos,
+            retain: config.retain,
+            transformer,
+            encoder,
+            acker,
+        })
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let MqttSink {
+            client,
+            eventloop,
+            topic,
+            qos,
+            retain,
+            transformer,
+            mut encoder,
+            acker,
+        } = *self;
+
+        // The messages are handed to the task driving the connection before being published, so
+        // that they are known once the event loop sends them.
+        let (pending_tx, pending_rx) = mpsc::unbounded_channel();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let finalized = Arc::new(Notify::new());
+        let driver = tokio::spawn(drive_connection(
+            eventloop,
+            pending_rx,
+            qos,
+            Arc::clone(&pending),
+            Arc::clone(&finalized),
+            acker.clone(),
+        ));
+
+        while let Some(mut event) = input.next().await {
+            let topic = match topic.render_string(&event) {
+                Ok(topic) => topic,
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("topic"),
+                        drop_event: true,
+                    });
+                    acker.ack(1);
+                    continue;
+                }
+            };
+
+            let event_byte_size = event.size_of();
+            let finalizers = event.take_finalizers();
+            transformer.transform(&mut event);
+            let mut body = BytesMut::new();
+            if encoder.encode(event, &mut body).is_err() {
+                // Error is logged by `crate::codecs`.
+                finalizers.update_status(EventStatus::Errored);
+                acker.ack(1);
+                continue;
+            }
+
+            // Topics containing wildcards are refused by the client, and would never be sent.
+            if topic.is_empty() || topic.contains(&['+', '#'][..]) {
+                emit!(MqttPublishError {
+                    error: format!("invalid topic {:?}", topic).into(),
+                });
+                finalizers.update_status(EventStatus::Rejected);
+                acker.ack(1);
+                continue;
+            }
+
+            pending.fetch_add(1, Ordering::AcqRel);
+            let body = body.freeze();
+            let _ = pending_tx.send(PendingMessage {
+                finalizers,
+                event_byte_size,
+                byte_size: body.len(),
+            });
+            if let Err(error) = client.publish(topic, qos, retain, body).await {
+                // The client only fails once the event loop is gone.
+                emit!(MqttPublishError { error });
+                driver.abort();
+                return Err(());
+            }
+        }
+
+        while pending.load(Ordering::Acquire) > 0 {
+            finalized.notified().await;
+        }
+        driver.abort();
+        Ok(())
+    }
+}
+
+/// Polls the event loop of the connection, finalizing the messages once they are sent for QoS 0
+/// or once the broker acknowledged them for QoS 1.
+async fn drive_connection(
+    mut eventloop: MqttEventLoop,
+    mut pending_rx: mpsc::UnboundedReceiver<PendingMessage>,
+    qos: MqttQos,
+    pending: Arc<AtomicUsize>,
+    finalized: Arc<Notify>,
+    acker: Acker,
+) {
+    let mut published = VecDeque::new();
+    let mut in_flight = HashMap::new();
+    let finalize = |message: PendingMessage| {
+        message.finalizers.update_status(EventStatus::Delivered);
+        emit!(EventsSent {
+            count: 1,
+            byte_size: message.event_byte_size,
+            output: None,
+        });
+        emit!(BytesSent {
+            byte_size: message.byte_size,
+            protocol: "tcp",
+        });
+        acker.ack(1);
+        pending.fetch_sub(1, Ordering::AcqRel);
+        finalized.notify_one();
+    };
+
+    loop {
+        match eventloop.poll().await {
+            Ok(MqttEvent::Published(pkid)) => {
+                // The messages in flight are sent again with the same packet identifier after a
+                // reconnection.
+                if in_flight.contains_key(&pkid) {
+                    continue;
+                }
+                while let Ok(message) = pending_rx.try_recv() {
+                    published.push_back(message);
+                }
+                let message = match published.pop_front() {
+                    Some(message) => message,
+                    None => continue,
+                };
+                match qos {
+                    MqttQos::AtMostOnce => finalize(message),
+                    MqttQos::AtLeastOnce => {
+                        in_flight.insert(pkid, message);
+                    }
+                }
+            }
+            Ok(MqttEvent::Acknowledged(pkid)) => {
+                if let Some(message) = in_flight.remove(&pkid) {
+                    finalize(message);
+                }
+            }
+            Ok(_) => {}
+            Err(error) => {
+                emit!(MqttConnectionError { error });
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        mqtt::MqttMessage,
+        sinks::VectorSink,
+        test_util::{random_lines_with_stream, random_string, trace_init},
+    };
+
+    fn mqtt_host() -> String {
+        std::env::var("MQTT_HOST").unwrap_or_else(|_| "127.0.0.1".to_owned())
+    }
+
+    /// Subscribes to the topic, forwarding the received messages.
+    async fn subscribe(
+        connection: &MqttConnectionConfig,
+        topic: String,
+    ) -> mpsc::UnboundedReceiver<MqttMessage> {
+        let (client, mut eventloop) = connection.build(&default_client_id(), None, false).unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                match eventloop.poll().await.unwrap() {
+                    MqttEvent::Connected => {
+                        client.try_subscribe(&topic, MqttQos::AtLeastOnce).unwrap()
+                    }
+                    MqttEvent::Message(message) => {
+                        if tx.send(message).is_err() {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+        // The messages aren't retained, so the subscription must be active before publishing.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        rx
+    }
+
+    async fn publishes_messages(protocol: &str, qos: u8) {
+        trace_init();
+        let topic = format!("vector/{}", random_string(10));
+        let config: MqttSinkConfig = toml::from_str(&format!(
+            r#"
+            host = "{}"
+            protocol = "{}"
+            topic = "{}"
+            qos = {}
+            encoding.codec = "text""#,
+            mqtt_host(),
+            protocol,
+            topic,
+            qos
+        ))
+        .unwrap();
+
+        healthcheck(config.connection.clone()).await.unwrap();
+        let mut messages = subscribe(&config.connection, topic.clone()).await;
+
+        let (acker, ack_counter) = Acker::basic();
+        let sink = VectorSink::from_event_streamsink(MqttSink::new(config, acker).unwrap());
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (lines, events) = random_lines_with_stream(100, 10, Some(batch));
+        sink.run(events).await.unwrap();
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(ack_counter.load(Ordering::Relaxed), lines.len());
+
+        let mut received = Vec::new();
+        while received.len() < lines.len() {
+            let message = tokio::time::timeout(Duration::from_secs(10), messages.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(message.topic, topic);
+            received.push(String::from_utf8_lossy(&message.payload).into_owned());
+        }
+        assert_eq!(received, lines);
+    }
+
+    #[tokio::test]
+    async fn mqtt_publishes_messages_v3_1_1() {
+        publishes_messages("3.1.1", 1).await;
+    }
+
+    #[tokio::test]
+    async fn mqtt_publishes_messages_v5() {
+        publishes_messages("5", 1).await;
+    }
+
+    #[tokio::test]
+    async fn mqtt_publishes_messages_at_most_once() {
+        publishes_messages("3.1.1", 0).await;
+    }
+}
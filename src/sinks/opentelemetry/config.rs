@@ -0,0 +1,152 @@
+use futures::{future, FutureExt};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use tower::ServiceBuilder;
+
+use super::{
+    encoder::OpentelemetryEncoder,
+    service::{build_headers, OpentelemetryRetryLogic, OpentelemetryService},
+    sink::{OpentelemetryRequestBuilder, OpentelemetrySink},
+};
+use crate::{
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{
+            grpc::{new_client, with_default_scheme, HyperSvc},
+            BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    endpoint: String,
+    #[serde(default)]
+    protocol: Protocol,
+    #[serde(default)]
+    compression: Compression,
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+    #[serde(default)]
+    resource_tags: Vec<String>,
+    #[serde(default = "default_histogram_buckets")]
+    buckets: Vec<f64>,
+    #[serde(default = "default_summary_quantiles")]
+    quantiles: Vec<f64>,
+    #[serde(default)]
+    batch: BatchConfig<OpentelemetryDefaultBatchSettings>,
+    #[serde(default)]
+    request: TowerRequestConfig,
+    tls: Option<TlsConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// The transport of the OTLP requests.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    #[derivative(Default)]
+    Grpc,
+    Http,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OpentelemetryDefaultBatchSettings;
+
+/// Receivers commonly limit gRPC messages to 4MiB, so the batches are kept well below that.
+impl SinkBatchSettings for OpentelemetryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+fn default_histogram_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+fn default_summary_quantiles() -> Vec<f64> {
+    vec![0.5, 0.75, 0.9, 0.95, 0.99]
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let headers = build_headers(&self.headers)?;
+
+        // gRPC compresses the messages itself.
+        let (service, payload_compression) = match self.protocol {
+            Protocol::Grpc => {
+                let client = HyperSvc::new(uri.clone(), new_client(&tls, cx.proxy())?);
+                (
+                    OpentelemetryService::grpc(client, uri, headers, self.compression),
+                    Compression::None,
+                )
+            }
+            Protocol::Http => {
+                let client = HttpClient::new(tls, cx.proxy())?;
+                (
+                    OpentelemetryService::http(client, uri, headers, self.compression),
+                    self.compression,
+                )
+            }
+        };
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpentelemetrySink {
+            service,
+            acker: cx.acker(),
+            request_builder: OpentelemetryRequestBuilder {
+                compression: payload_compression,
+                encoder: OpentelemetryEncoder {
+                    resource_tags: self.resource_tags.clone(),
+                    buckets: self.buckets.clone(),
+                    quantiles: self.quantiles.clone(),
+                },
+            },
+            batch_settings: self.batch.into_batcher_settings()?,
+        };
+
+        // OTLP has no health check, and export requests would be rejected by receivers that
+        // don't accept all signals.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::all())
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
@@ -0,0 +1,570 @@
+//! Conversion of Vector events to OTLP payloads.
+//!
+//! This mirrors the conversion done by the `opentelemetry` source: log and trace fields named
+//! after the OTLP ones are mapped back to them, and events sharing the same `resources` and
+//! `scope` are grouped together.
+
+use std::{collections::BTreeMap, io};
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use prost::Message;
+use vector_core::event::metric::{MetricSketch, Quantile};
+
+use super::sink::Signal;
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Bucket, MetricTags},
+        Event, LogEvent, Metric, MetricKind, MetricValue, TraceEvent, Value,
+    },
+    proto::opentelemetry::{
+        collector::{
+            logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+            trace::v1::ExportTraceServiceRequest,
+        },
+        common::v1::{
+            any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+        },
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric, number_data_point, summary_data_point::ValueAtQuantile, AggregationTemporality,
+            Gauge, Histogram, HistogramDataPoint, Metric as OtlpMetric, NumberDataPoint,
+            ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{span, status, ResourceSpans, ScopeSpans, Span, Status},
+    },
+    sinks::util::{encode_namespace, encoding::Encoder},
+};
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryEncoder {
+    /// Metric tags that are sent as resource attributes instead of data point attributes.
+    pub resource_tags: Vec<String>,
+    /// Bucket bounds of the histograms that distributions are converted to.
+    pub buckets: Vec<f64>,
+    /// Quantiles of the summaries that sketches are converted to.
+    pub quantiles: Vec<f64>,
+}
+
+impl Encoder<(Signal, Vec<Event>)> for OpentelemetryEncoder {
+    fn encode_input(
+        &self,
+        (signal, events): (Signal, Vec<Event>),
+        writer: &mut dyn io::Write,
+    ) -> io::Result<usize> {
+        let payload = match signal {
+            Signal::Logs => ExportLogsServiceRequest {
+                resource_logs: logs(events.into_iter().filter_map(Event::try_into_log)),
+            }
+            .encode_to_vec(),
+            Signal::Metrics => ExportMetricsServiceRequest {
+                resource_metrics: self
+                    .metrics(events.into_iter().filter_map(Event::try_into_metric)),
+            }
+            .encode_to_vec(),
+            Signal::Traces => ExportTraceServiceRequest {
+                resource_spans: traces(events.into_iter().filter_map(Event::try_into_trace)),
+            }
+            .encode_to_vec(),
+        };
+
+        writer.write_all(&payload)?;
+        Ok(payload.len())
+    }
+}
+
+fn logs(events: impl Iterator<Item = LogEvent>) -> Vec<ResourceLogs> {
+    group(events.map(log_record))
+        .into_iter()
+        .map(|((resource, scope), log_records)| ResourceLogs {
+            resource: Some(resource),
+            scope_logs: vec![ScopeLogs {
+                scope,
+                log_records,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        })
+        .collect()
+}
+
+type GroupKey = (Resource, Option<InstrumentationScope>);
+
+fn log_record(mut log: LogEvent) -> (GroupKey, LogRecord) {
+    let body = log.remove(log_schema().message_key()).map(any_value);
+    let time_unix_nano = log
+        .remove(log_schema().timestamp_key())
+        .as_ref()
+        .and_then(timestamp)
+        .unwrap_or_default();
+    log.remove(log_schema().source_type_key());
+
+    let (mut fields, _) = log.into_parts();
+    let mut take = |name: &str| fields.remove(name);
+
+    let resource = resource(take("resources"));
+    let scope = take("scope").map(scope);
+    let observed_time_unix_nano = take("observed_timestamp")
+        .as_ref()
+        .and_then(timestamp)
+        .unwrap_or_default();
+    let severity_text = take("severity_text")
+        .map(|value| value.to_string_lossy())
+        .unwrap_or_default();
+    let severity_number = take("severity_number").map(integer).unwrap_or_default() as i32;
+    let trace_id = take("trace_id").map(id).unwrap_or_default();
+    let span_id = take("span_id").map(id).unwrap_or_default();
+    let flags = take("flags").map(integer).unwrap_or_default() as u32;
+    let dropped_attributes_count = take("dropped_attributes_count")
+        .map(integer)
+        .unwrap_or_default() as u32;
+    let explicit_attributes = take("attributes");
+
+    // Remaining top-level fields, such as the ones added by other sources, are kept as
+    // attributes, with the explicit attributes taking precedence.
+    let mut attributes = fields;
+    if let Some(Value::Object(explicit)) = explicit_attributes {
+        attributes.extend(explicit);
+    }
+
+    let record = LogRecord {
+        time_unix_nano,
+        observed_time_unix_nano,
+        severity_number,
+        severity_text,
+        body,
+        attributes: key_values(attributes),
+        dropped_attributes_count,
+        flags,
+        trace_id,
+        span_id,
+    };
+    ((resource, scope), record)
+}
+
+fn traces(events: impl Iterator<Item = TraceEvent>) -> Vec<ResourceSpans> {
+    group(events.map(span))
+        .into_iter()
+        .map(|((resource, scope), spans)| ResourceSpans {
+            resource: Some(resource),
+            scope_spans: vec![ScopeSpans {
+                scope,
+                spans,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        })
+        .collect()
+}
+
+fn span(trace: TraceEvent) -> (GroupKey, Span) {
+    let (mut fields, _) = trace.into_parts();
+    let mut take = |name: &str| fields.remove(name);
+
+    let kind = match take("kind").map(|kind| kind.to_string_lossy()).as_deref() {
+        Some("internal") => span::SpanKind::Internal,
+        Some("server") => span::SpanKind::Server,
+        Some("client") => span::SpanKind::Client,
+        Some("producer") => span::SpanKind::Producer,
+        Some("consumer") => span::SpanKind::Consumer,
+        _ => span::SpanKind::Unspecified,
+    };
+    let status = take("status").map(|status| {
+        let code = match status_field(&status, "code").as_deref() {
+            Some("ok") => status::StatusCode::Ok,
+            Some("error") => status::StatusCode::Error,
+            _ => status::StatusCode::Unset,
+        };
+        Status {
+            message: status_field(&status, "message").unwrap_or_default(),
+            code: code as i32,
+        }
+    });
+
+    let span = Span {
+        trace_id: take("trace_id").map(id).unwrap_or_default(),
+        span_id: take("span_id").map(id).unwrap_or_default(),
+        trace_state: string(take("trace_state")),
+        parent_span_id: take("parent_span_id").map(id).unwrap_or_default(),
+        name: string(take("name")),
+        kind: kind as i32,
+        start_time_unix_nano: take("start_timestamp")
+            .as_ref()
+            .and_then(timestamp)
+            .unwrap_or_default(),
+        end_time_unix_nano: take("end_timestamp")
+            .as_ref()
+            .and_then(timestamp)
+            .unwrap_or_default(),
+        attributes: attributes(take("attributes")),
+        dropped_attributes_count: take("dropped_attributes_count")
+            .map(integer)
+            .unwrap_or_default() as u32,
+        events: array(take("events"))
+            .map(|mut event| span::Event {
+                time_unix_nano: remove(&mut event, "timestamp")
+                    .as_ref()
+                    .and_then(timestamp)
+                    .unwrap_or_default(),
+                name: string(remove(&mut event, "name")),
+                attributes: attributes(remove(&mut event, "attributes")),
+                dropped_attributes_count: remove(&mut event, "dropped_attributes_count")
+                    .map(integer)
+                    .unwrap_or_default() as u32,
+            })
+            .collect(),
+        dropped_events_count: take("dropped_events_count")
+            .map(integer)
+            .unwrap_or_default() as u32,
+        links: array(take("links"))
+            .map(|mut link| span::Link {
+                trace_id: remove(&mut link, "trace_id").map(id).unwrap_or_default(),
+                span_id: remove(&mut link, "span_id").map(id).unwrap_or_default(),
+                trace_state: string(remove(&mut link, "trace_state")),
+                attributes: attributes(remove(&mut link, "attributes")),
+                dropped_attributes_count: remove(&mut link, "dropped_attributes_count")
+                    .map(integer)
+                    .unwrap_or_default() as u32,
+            })
+            .collect(),
+        dropped_links_count: take("dropped_links_count").map(integer).unwrap_or_default() as u32,
+        status,
+    };
+    let resource = resource(take("resources"));
+    let scope = take("scope").map(scope);
+
+    ((resource, scope), span)
+}
+
+impl OpentelemetryEncoder {
+    /// Converts distributions to the histograms they are exported as. This is done before the
+    /// events are encoded, so that the metrics that can't be converted are handed back to be
+    /// rejected.
+    pub fn convert_distribution(&self, metric: Metric) -> Result<Metric, Metric> {
+        if !matches!(metric.value(), MetricValue::Distribution { .. }) {
+            return Ok(metric);
+        }
+        match metric.value().distribution_to_agg_histogram(&self.buckets) {
+            Some(value) => Ok(metric.with_value(value)),
+            None => Err(metric),
+        }
+    }
+
+    fn metrics(&self, events: impl Iterator<Item = Metric>) -> Vec<ResourceMetrics> {
+        group(events.filter_map(|metric| self.metric(metric)))
+            .into_iter()
+            .map(|(resource, metrics)| ResourceMetrics {
+                resource: Some(resource),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            })
+            .collect()
+    }
+
+    fn metric(&self, metric: Metric) -> Option<(Resource, OtlpMetric)> {
+        let name = encode_namespace(metric.namespace(), '.', metric.name());
+        let kind = metric.kind();
+        let time_unix_nano = nanos(metric.timestamp().unwrap_or_else(Utc::now));
+
+        let (resource_tags, tags): (MetricTags, MetricTags) = metric
+            .tags()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .partition(|(key, _)| self.resource_tags.contains(key));
+        let resource = Resource {
+            attributes: tags_key_values(resource_tags),
+            dropped_attributes_count: 0,
+        };
+        let attributes = tags_key_values(tags);
+
+        let number = |value: f64| NumberDataPoint {
+            attributes: attributes.clone(),
+            start_time_unix_nano: 0,
+            time_unix_nano,
+            exemplars: Vec::new(),
+            flags: 0,
+            value: Some(number_data_point::Value::AsDouble(value)),
+        };
+        let sum_data = |value: f64, is_monotonic: bool| {
+            metric::Data::Sum(Sum {
+                data_points: vec![number(value)],
+                aggregation_temporality: temporality(kind) as i32,
+                is_monotonic,
+            })
+        };
+        let gauge_data = |value: f64| {
+            metric::Data::Gauge(Gauge {
+                data_points: vec![number(value)],
+            })
+        };
+        let summary_data = |quantiles: &[Quantile], count: u32, sum: f64| {
+            metric::Data::Summary(Summary {
+                data_points: vec![SummaryDataPoint {
+                    attributes: attributes.clone(),
+                    start_time_unix_nano: 0,
+                    time_unix_nano,
+                    count: count.into(),
+                    sum,
+                    quantile_values: quantiles
+                        .iter()
+                        .map(|quantile| ValueAtQuantile {
+                            quantile: quantile.quantile,
+                            value: quantile.value,
+                        })
+                        .collect(),
+                    flags: 0,
+                }],
+            })
+        };
+
+        let data = match metric.value() {
+            MetricValue::Counter { value } => sum_data(*value, true),
+            // An incremental gauge holds the change of the gauge, which OTLP can only represent
+            // as a non-monotonic delta sum.
+            MetricValue::Gauge { value } => match kind {
+                MetricKind::Absolute => gauge_data(*value),
+                MetricKind::Incremental => sum_data(*value, false),
+            },
+            MetricValue::Set { values } => gauge_data(values.len() as f64),
+            // Distributions are already converted by `convert_distribution`.
+            MetricValue::Distribution { .. } => return None,
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => histogram(buckets, *count, *sum, kind, &attributes, time_unix_nano),
+            MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => summary_data(quantiles, *count, *sum),
+            MetricValue::Sketch { sketch } => match sketch {
+                MetricSketch::AgentDDSketch(ddsketch) => {
+                    let quantiles = self
+                        .quantiles
+                        .iter()
+                        .map(|&quantile| Quantile {
+                            quantile,
+                            value: ddsketch.quantile(quantile).unwrap_or(0.0),
+                        })
+                        .collect::<Vec<_>>();
+                    summary_data(&quantiles, ddsketch.count(), ddsketch.sum().unwrap_or(0.0))
+                }
+            },
+        };
+
+        Some((
+            resource,
+            OtlpMetric {
+                name,
+                description: String::new(),
+                unit: String::new(),
+                data: Some(data),
+            },
+        ))
+    }
+}
+
+/// Vector buckets only count the values above the previous bound, and leave out the values
+/// above the last bound, which OTLP counts in an extra bucket.
+fn histogram(
+    buckets: &[Bucket],
+    count: u32,
+    sum: f64,
+    kind: MetricKind,
+    attributes: &[KeyValue],
+    time_unix_nano: u64,
+) -> metric::Data {
+    let mut bucket_counts = buckets
+        .iter()
+        .map(|bucket| u64::from(bucket.count))
+        .collect::<Vec<_>>();
+    let bucketed = bucket_counts.iter().sum::<u64>();
+    bucket_counts.push(u64::from(count).saturating_sub(bucketed));
+
+    metric::Data::Histogram(Histogram {
+        data_points: vec![HistogramDataPoint {
+            attributes: attributes.to_vec(),
+            start_time_unix_nano: 0,
+            time_unix_nano,
+            count: count.into(),
+            sum,
+            bucket_counts,
+            explicit_bounds: buckets.iter().map(|bucket| bucket.upper_limit).collect(),
+            exemplars: Vec::new(),
+            flags: 0,
+        }],
+        aggregation_temporality: temporality(kind) as i32,
+    })
+}
+
+const fn temporality(kind: MetricKind) -> AggregationTemporality {
+    match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    }
+}
+
+/// The protobuf messages items are grouped by can't be hashed, so groups are indexed by the
+/// encoding of their keys instead.
+trait EncodeKey {
+    fn encode_key(&self) -> Vec<u8>;
+}
+
+impl EncodeKey for Resource {
+    fn encode_key(&self) -> Vec<u8> {
+        self.encode_length_delimited_to_vec()
+    }
+}
+
+impl EncodeKey for GroupKey {
+    fn encode_key(&self) -> Vec<u8> {
+        let (resource, scope) = self;
+        let mut key = resource.encode_key();
+        if let Some(scope) = scope {
+            key.extend(scope.encode_length_delimited_to_vec());
+        }
+        key
+    }
+}
+
+/// Groups the items by key, keeping the order in which the keys are first seen.
+fn group<K: EncodeKey, T>(items: impl Iterator<Item = (K, T)>) -> Vec<(K, Vec<T>)> {
+    let mut groups: IndexMap<Vec<u8>, (K, Vec<T>)> = IndexMap::new();
+    for (key, item) in items {
+        groups
+            .entry(key.encode_key())
+            .or_insert_with(|| (key, Vec::new()))
+            .1
+            .push(item);
+    }
+    groups.into_values().collect()
+}
+
+fn resource(value: Option<Value>) -> Resource {
+    Resource {
+        attributes: attributes(value),
+        dropped_attributes_count: 0,
+    }
+}
+
+fn scope(value: Value) -> InstrumentationScope {
+    let mut fields = match value {
+        Value::Object(fields) => fields,
+        _ => BTreeMap::new(),
+    };
+    InstrumentationScope {
+        name: string(fields.remove("name")),
+        version: string(fields.remove("version")),
+        attributes: attributes(fields.remove("attributes")),
+        dropped_attributes_count: 0,
+    }
+}
+
+fn attributes(value: Option<Value>) -> Vec<KeyValue> {
+    match value {
+        Some(Value::Object(fields)) => key_values(fields),
+        _ => Vec::new(),
+    }
+}
+
+fn key_values(fields: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    fields
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(any_value(value)),
+        })
+        .collect()
+}
+
+fn tags_key_values(tags: MetricTags) -> Vec<KeyValue> {
+    tags.into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value)),
+            }),
+        })
+        .collect()
+}
+
+fn any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => Some(any_value::Value::StringValue(string)),
+            Err(_) => Some(any_value::Value::BytesValue(bytes.to_vec())),
+        },
+        Value::Integer(value) => Some(any_value::Value::IntValue(value)),
+        Value::Float(value) => Some(any_value::Value::DoubleValue(value.into_inner())),
+        Value::Boolean(value) => Some(any_value::Value::BoolValue(value)),
+        Value::Array(values) => Some(any_value::Value::ArrayValue(ArrayValue {
+            values: values.into_iter().map(any_value).collect(),
+        })),
+        Value::Object(fields) => Some(any_value::Value::KvlistValue(KeyValueList {
+            values: key_values(fields),
+        })),
+        Value::Null => None,
+        value => Some(any_value::Value::StringValue(value.to_string_lossy())),
+    };
+    AnyValue { value }
+}
+
+fn remove(value: &mut Value, name: &str) -> Option<Value> {
+    match value {
+        Value::Object(fields) => fields.remove(name),
+        _ => None,
+    }
+}
+
+fn array(value: Option<Value>) -> impl Iterator<Item = Value> {
+    match value {
+        Some(Value::Array(values)) => values,
+        _ => Vec::new(),
+    }
+    .into_iter()
+}
+
+fn status_field(status: &Value, name: &str) -> Option<String> {
+    match status {
+        Value::Object(fields) => fields.get(name).map(Value::to_string_lossy),
+        _ => None,
+    }
+}
+
+fn string(value: Option<Value>) -> String {
+    value
+        .map(|value| value.to_string_lossy())
+        .unwrap_or_default()
+}
+
+fn integer(value: Value) -> i64 {
+    match value {
+        Value::Integer(value) => value,
+        Value::Float(value) => value.into_inner() as i64,
+        value => value.to_string_lossy().parse().unwrap_or_default(),
+    }
+}
+
+/// IDs are rendered in hex by the source, invalid ones are left out.
+fn id(value: Value) -> Vec<u8> {
+    hex::decode(value.to_string_lossy()).unwrap_or_default()
+}
+
+fn timestamp(value: &Value) -> Option<u64> {
+    match value {
+        Value::Timestamp(timestamp) => Some(nanos(*timestamp)),
+        _ => None,
+    }
+}
+
+fn nanos(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos().max(0) as u64
+}
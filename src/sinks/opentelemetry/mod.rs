@@ -0,0 +1,23 @@
+//! OpenTelemetry sink
+//!
+//! This sink exports logs, metrics and traces to receivers of the OpenTelemetry protocol (OTLP),
+//! over gRPC or HTTP.
+//!
+//! <https://opentelemetry.io/docs/reference/specification/protocol/otlp/>
+//!
+//! Events are batched per signal, and the events of a batch sharing the same resource are
+//! grouped under it.
+mod config;
+mod encoder;
+mod service;
+mod sink;
+#[cfg(test)]
+mod tests;
+
+use config::OpentelemetryConfig;
+
+use crate::config::SinkDescription;
+
+inventory::submit! {
+    SinkDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
@@ -0,0 +1,366 @@
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BufMut, Bytes};
+use futures::future::BoxFuture;
+use http::{header::HeaderName, uri::PathAndQuery, HeaderMap, HeaderValue, StatusCode, Uri};
+use prost::Message;
+use snafu::Snafu;
+use tonic::{
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    metadata::MetadataMap,
+    Code,
+};
+use tower::Service;
+use tracing_futures::Instrument;
+use vector_core::{
+    buffers::Ackable,
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::EventsSent,
+    stream::DriverResponse,
+};
+
+use super::sink::Signal;
+use crate::{
+    http::HttpClient,
+    internal_events::{EndpointBytesSent, OpentelemetryPartialSuccess},
+    proto::opentelemetry::collector::{
+        logs::v1::ExportLogsServiceResponse, metrics::v1::ExportMetricsServiceResponse,
+        trace::v1::ExportTraceServiceResponse,
+    },
+    sinks::util::{grpc::HyperSvc, retries::RetryLogic, uri, Compression},
+};
+
+#[derive(Clone)]
+pub struct OpentelemetryRequest {
+    pub signal: Signal,
+    pub payload: Bytes,
+    pub finalizers: EventFinalizers,
+    pub batch_size: usize,
+    pub events_byte_size: usize,
+}
+
+impl Ackable for OpentelemetryRequest {
+    fn ack_size(&self) -> usize {
+        self.batch_size
+    }
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+pub struct OpentelemetryResponse {
+    event_status: EventStatus,
+    batch_size: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        self.event_status
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.batch_size,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum OpentelemetryError {
+    #[snafu(display("gRPC request failed: {}", source))]
+    Grpc { source: tonic::Status },
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: crate::http::HttpError },
+    #[snafu(display("Failed to read the response: {}", error))]
+    ResponseError { error: hyper::Error },
+}
+
+/// Retries the failures the OTLP specification considers transient.
+#[derive(Clone, Debug)]
+pub struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetryError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            OpentelemetryError::Grpc { source } => matches!(
+                source.code(),
+                Code::Cancelled
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+                    | Code::OutOfRange
+                    | Code::Unavailable
+                    | Code::DataLoss
+            ),
+            OpentelemetryError::ServerError { code } => matches!(
+                *code,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            OpentelemetryError::HttpError { .. } | OpentelemetryError::ResponseError { .. } => true,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum OpentelemetryService {
+    Grpc {
+        client: tonic::client::Grpc<HyperSvc>,
+        metadata: MetadataMap,
+        uri: Uri,
+    },
+    Http {
+        client: HttpClient,
+        headers: HeaderMap,
+        compression: Compression,
+        uri: Uri,
+    },
+}
+
+impl OpentelemetryService {
+    pub fn grpc(client: HyperSvc, uri: Uri, headers: HeaderMap, compression: Compression) -> Self {
+        let mut client = tonic::client::Grpc::new(client);
+        if compression.is_compressed() {
+            client = client.send_gzip();
+        }
+        Self::Grpc {
+            client,
+            metadata: MetadataMap::from_headers(headers),
+            uri,
+        }
+    }
+
+    pub const fn http(
+        client: HttpClient,
+        uri: Uri,
+        headers: HeaderMap,
+        compression: Compression,
+    ) -> Self {
+        Self::Http {
+            client,
+            headers,
+            compression,
+            uri,
+        }
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = OpentelemetryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let service = self.clone();
+        let signal = request.signal;
+        let byte_size = request.payload.len();
+        let mut response = OpentelemetryResponse {
+            event_status: EventStatus::Delivered,
+            batch_size: request.batch_size,
+            events_byte_size: request.events_byte_size,
+        };
+
+        Box::pin(
+            async move {
+                let (uri, body) = match service {
+                    Self::Grpc {
+                        client,
+                        metadata,
+                        uri,
+                    } => (uri, grpc_export(client, metadata, request).await?),
+                    Self::Http {
+                        client,
+                        headers,
+                        compression,
+                        uri,
+                    } => {
+                        let uri = signal_uri(&uri, signal);
+                        let body = http_export(client, &uri, headers, compression, request).await?;
+                        (uri, body)
+                    }
+                };
+
+                let (protocol, endpoint) = uri::protocol_endpoint(uri);
+                emit!(EndpointBytesSent {
+                    byte_size,
+                    protocol: &protocol,
+                    endpoint: &endpoint,
+                });
+                // The response doesn't tell which items were rejected, so the whole request is.
+                if let Some((rejected, message)) = partial_success(signal, body) {
+                    emit!(OpentelemetryPartialSuccess {
+                        rejected,
+                        message: &message,
+                    });
+                    if rejected > 0 {
+                        response.event_status = EventStatus::Rejected;
+                    }
+                }
+
+                Ok(response)
+            }
+            .in_current_span(),
+        )
+    }
+}
+
+async fn grpc_export(
+    mut client: tonic::client::Grpc<HyperSvc>,
+    metadata: MetadataMap,
+    request: OpentelemetryRequest,
+) -> Result<Bytes, OpentelemetryError> {
+    client
+        .ready()
+        .await
+        .map_err(|error| OpentelemetryError::Grpc {
+            source: tonic::Status::new(Code::Unknown, format!("Service was not ready: {}", error)),
+        })?;
+
+    let path = PathAndQuery::from_static(match request.signal {
+        Signal::Logs => "/opentelemetry.proto.collector.logs.v1.LogsService/Export",
+        Signal::Metrics => "/opentelemetry.proto.collector.metrics.v1.MetricsService/Export",
+        Signal::Traces => "/opentelemetry.proto.collector.trace.v1.TraceService/Export",
+    });
+    let mut grpc_request = tonic::Request::new(request.payload);
+    *grpc_request.metadata_mut() = metadata;
+
+    client
+        .unary(grpc_request, path, PassthroughCodec)
+        .await
+        .map(tonic::Response::into_inner)
+        .map_err(|source| OpentelemetryError::Grpc { source })
+}
+
+async fn http_export(
+    mut client: HttpClient,
+    uri: &Uri,
+    headers: HeaderMap,
+    compression: Compression,
+    request: OpentelemetryRequest,
+) -> Result<Bytes, OpentelemetryError> {
+    let mut builder = http::Request::post(uri).header("Content-Type", "application/x-protobuf");
+    if let Some(content_encoding) = compression.content_encoding() {
+        builder = builder.header("Content-Encoding", content_encoding);
+    }
+    let mut http_request = builder
+        .body(hyper::Body::from(request.payload))
+        .expect("valid request");
+    http_request.headers_mut().extend(headers);
+
+    let response = client
+        .call(http_request)
+        .await
+        .map_err(|error| OpentelemetryError::HttpError { error })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(OpentelemetryError::ServerError { code: status });
+    }
+
+    hyper::body::to_bytes(response.into_body())
+        .await
+        .map_err(|error| OpentelemetryError::ResponseError { error })
+}
+
+/// The HTTP transport exports each signal to its own path below the endpoint.
+fn signal_uri(endpoint: &Uri, signal: Signal) -> Uri {
+    let base = endpoint.to_string();
+    format!("{}/v1/{}", base.trim_end_matches('/'), signal.as_str())
+        .parse()
+        .expect("valid URI")
+}
+
+/// Extracts the number of rejected items and the reason from an export response, if any were
+/// rejected.
+fn partial_success(signal: Signal, body: Bytes) -> Option<(i64, String)> {
+    let (rejected, message) = match signal {
+        Signal::Logs => {
+            let partial = ExportLogsServiceResponse::decode(body)
+                .ok()?
+                .partial_success?;
+            (partial.rejected_log_records, partial.error_message)
+        }
+        Signal::Metrics => {
+            let partial = ExportMetricsServiceResponse::decode(body)
+                .ok()?
+                .partial_success?;
+            (partial.rejected_data_points, partial.error_message)
+        }
+        Signal::Traces => {
+            let partial = ExportTraceServiceResponse::decode(body)
+                .ok()?
+                .partial_success?;
+            (partial.rejected_spans, partial.error_message)
+        }
+    };
+    (rejected > 0 || !message.is_empty()).then(|| (rejected, message))
+}
+
+/// Sends the payloads as they were encoded by the request builder, and hands back the raw
+/// responses, as the message types differ per signal.
+#[derive(Clone, Copy, Debug, Default)]
+struct PassthroughCodec;
+
+impl Codec for PassthroughCodec {
+    type Encode = Bytes;
+    type Decode = Bytes;
+    type Encoder = Self;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        Self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        Self
+    }
+}
+
+impl Encoder for PassthroughCodec {
+    type Item = Bytes;
+    type Error = tonic::Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put(item);
+        Ok(())
+    }
+}
+
+impl Decoder for PassthroughCodec {
+    type Item = Bytes;
+    type Error = tonic::Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining())))
+    }
+}
+
+/// Validates the configured headers, which are sent as gRPC metadata or HTTP headers.
+pub fn build_headers(headers: &indexmap::IndexMap<String, String>) -> crate::Result<HeaderMap> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            Ok::<_, crate::Error>((
+                HeaderName::from_bytes(name.to_lowercase().as_bytes())?,
+                HeaderValue::from_str(value)?,
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,168 @@
+use std::{fmt, io, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    buffers::Acker,
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    partition::Partitioner,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{encoder::OpentelemetryEncoder, service::OpentelemetryRequest};
+use crate::{
+    internal_events::OpentelemetryMetricConversionError,
+    sinks::util::{Compression, RequestBuilder, SinkBuilderExt, StreamSink},
+};
+
+/// The kind of telemetry data of an event, each being exported by a dedicated OTLP service.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Signal {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+impl Signal {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Logs => "logs",
+            Self::Metrics => "metrics",
+            Self::Traces => "traces",
+        }
+    }
+}
+
+#[derive(Default)]
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => Signal::Logs,
+            Event::Metric(_) => Signal::Metrics,
+            Event::Trace(_) => Signal::Traces,
+        }
+    }
+}
+
+pub struct RequestMetadata {
+    signal: Signal,
+    finalizers: EventFinalizers,
+    batch_size: usize,
+    events_byte_size: usize,
+}
+
+pub struct OpentelemetryRequestBuilder {
+    pub compression: Compression,
+    pub encoder: OpentelemetryEncoder,
+}
+
+impl RequestBuilder<(Signal, Vec<Event>)> for OpentelemetryRequestBuilder {
+    type Metadata = RequestMetadata;
+    type Events = (Signal, Vec<Event>);
+    type Encoder = OpentelemetryEncoder;
+    type Payload = bytes::Bytes;
+    type Request = OpentelemetryRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, input: (Signal, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (signal, events) = input;
+        let mut events = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Metric(metric) => match self.encoder.convert_distribution(metric) {
+                    Ok(metric) => Some(Event::Metric(metric)),
+                    Err(mut metric) => {
+                        emit!(OpentelemetryMetricConversionError {
+                            name: metric.name()
+                        });
+                        metric
+                            .take_finalizers()
+                            .update_status(EventStatus::Rejected);
+                        None
+                    }
+                },
+                event => Some(event),
+            })
+            .collect::<Vec<_>>();
+        let metadata = RequestMetadata {
+            signal,
+            finalizers: events.take_finalizers(),
+            batch_size: events.len(),
+            events_byte_size: events.size_of(),
+        };
+        (metadata, (signal, events))
+    }
+
+    fn build_request(&self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
+        OpentelemetryRequest {
+            signal: metadata.signal,
+            payload,
+            finalizers: metadata.finalizers,
+            batch_size: metadata.batch_size,
+            events_byte_size: metadata.events_byte_size,
+        }
+    }
+}
+
+pub struct OpentelemetrySink<S> {
+    pub service: S,
+    pub acker: Acker,
+    pub request_builder: OpentelemetryRequestBuilder,
+    pub batch_settings: BatcherSettings,
+}
+
+impl<S> OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request_builder_concurrency = NonZeroUsize::new(50);
+
+        input
+            .batched_partitioned(SignalPartitioner, self.batch_settings)
+            .request_builder(request_builder_concurrency, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        error!("Failed to build OTLP request: {:?}.", error);
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service, self.acker)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
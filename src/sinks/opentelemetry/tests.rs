@@ -0,0 +1,158 @@
+use futures::{stream, StreamExt};
+use prost::Message;
+use vector_core::event::{BatchNotifier, BatchStatus, Event, LogEvent};
+
+use super::config::OpentelemetryConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Metric, MetricKind, MetricValue},
+    proto::opentelemetry::collector::{
+        logs::v1::{ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse},
+        metrics::v1::ExportMetricsServiceRequest,
+    },
+    sinks::util::test::{build_test_server, build_test_server_generic, build_test_server_status},
+    test_util::{components, next_addr},
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpentelemetryConfig>();
+}
+
+#[tokio::test]
+async fn exports_logs_and_metrics_over_http() {
+    let address = next_addr();
+    let config: OpentelemetryConfig = toml::from_str(&format!(
+        r#"
+            endpoint = "http://{}"
+            protocol = "http"
+            resource_tags = ["service.name"]
+        "#,
+        address
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+    let (rx, trigger, server) = build_test_server(address);
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let mut log = LogEvent::from("hello");
+    log.insert("resources.\"service.name\"", "checkout");
+    log.insert("host", "a");
+    let metric = Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    )
+    .with_tags(Some(
+        vec![
+            ("service.name".to_owned(), "checkout".to_owned()),
+            ("path".to_owned(), "/".to_owned()),
+        ]
+        .into_iter()
+        .collect(),
+    ));
+    let events = vec![
+        Event::from(log).with_batch_notifier(&batch),
+        Event::from(metric).with_batch_notifier(&batch),
+    ];
+    drop(batch);
+
+    components::init_test();
+    sink.run(stream::iter(events)).await.unwrap();
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    components::SINK_TESTS.assert(&components::HTTP_SINK_TAGS);
+
+    let mut requests = rx.take(2).collect::<Vec<_>>().await;
+    requests.sort_by_key(|(parts, _)| parts.uri.path().to_owned());
+    assert_eq!(requests.len(), 2);
+
+    let (parts, body) = &requests[0];
+    assert_eq!(parts.uri.path(), "/v1/logs");
+    assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+    let logs = ExportLogsServiceRequest::decode(body.clone()).unwrap();
+    let resource_logs = &logs.resource_logs[0];
+    assert_eq!(
+        resource_logs.resource.as_ref().unwrap().attributes[0].key,
+        "service.name"
+    );
+    let record = &resource_logs.scope_logs[0].log_records[0];
+    assert_eq!(record.attributes[0].key, "host");
+
+    let (parts, body) = &requests[1];
+    assert_eq!(parts.uri.path(), "/v1/metrics");
+    let metrics = ExportMetricsServiceRequest::decode(body.clone()).unwrap();
+    let resource_metrics = &metrics.resource_metrics[0];
+    assert_eq!(
+        resource_metrics.resource.as_ref().unwrap().attributes[0].key,
+        "service.name"
+    );
+    assert_eq!(
+        resource_metrics.scope_metrics[0].metrics[0].name,
+        "requests"
+    );
+}
+
+#[tokio::test]
+async fn rejects_on_client_errors() {
+    let address = next_addr();
+    let config: OpentelemetryConfig = toml::from_str(&format!(
+        r#"
+            endpoint = "http://{}"
+            protocol = "http"
+        "#,
+        address
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+    let (_rx, trigger, server) = build_test_server_status(address, http::StatusCode::BAD_REQUEST);
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![Event::from(LogEvent::from("hello")).with_batch_notifier(&batch)];
+    drop(batch);
+
+    sink.run(stream::iter(events)).await.unwrap();
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+#[tokio::test]
+async fn rejects_on_partial_success() {
+    let address = next_addr();
+    let config: OpentelemetryConfig = toml::from_str(&format!(
+        r#"
+            endpoint = "http://{}"
+            protocol = "http"
+        "#,
+        address
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+    let (_rx, trigger, server) = build_test_server_generic(address, || {
+        let body = ExportLogsServiceResponse {
+            partial_success: Some(ExportLogsPartialSuccess {
+                rejected_log_records: 1,
+                error_message: "missing body".to_owned(),
+            }),
+        }
+        .encode_to_vec();
+        http::Response::new(hyper::Body::from(body))
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![
+        Event::from(LogEvent::from("hello")).with_batch_notifier(&batch),
+        Event::from(LogEvent::default()).with_batch_notifier(&batch),
+    ];
+    drop(batch);
+
+    sink.run(stream::iter(events)).await.unwrap();
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
@@ -4,7 +4,7 @@ use std::{
 };
 
 use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
-use redis::{aio::ConnectionManager, RedisError, RedisResult};
+use redis::{aio::ConnectionManager, streams::StreamMaxlen, RedisError, RedisResult};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use tower::{Service, ServiceBuilder};
@@ -48,6 +48,7 @@ pub enum DataTypeConfig {
     #[derivative(Default)]
     List,
     Channel,
+    Stream,
 }
 
 #[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
@@ -56,13 +57,30 @@ pub struct ListOption {
     method: Method,
 }
 
-#[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The field of the entries holding the encoded event.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    field: String,
+    /// The approximate maximum length the stream is trimmed to on every addition.
+    maxlen: Option<usize>,
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
 #[derivative(Default)]
 #[serde(rename_all = "lowercase")]
 pub enum DataType {
     #[derivative(Default)]
     List(Method),
     Channel,
+    Stream(StreamOption),
 }
 
 #[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
@@ -98,6 +116,8 @@ pub struct RedisSinkConfig {
     data_type: DataTypeConfig,
     #[serde(alias = "list")]
     list_option: Option<ListOption>,
+    #[serde(alias = "stream")]
+    stream_option: Option<StreamOption>,
     url: String,
     key: String,
     #[serde(default)]
@@ -176,6 +196,9 @@ impl RedisSinkConfig {
         let data_type = match self.data_type {
             DataTypeConfig::Channel => DataType::Channel,
             DataTypeConfig::List => DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => {
+                DataType::Stream(self.stream_option.clone().unwrap_or_default())
+            }
         };
 
         let batch = self.batch.into_batch_settings()?;
@@ -315,7 +338,7 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
 
         for kv in kvs {
             byte_size += kv.encoded_length();
-            match self.data_type {
+            match &self.data_type {
                 DataType::List(method) => match method {
                     Method::LPush => {
                         if count > 1 {
@@ -339,6 +362,19 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
                         pipe.publish(kv.key, kv.value);
                     }
                 }
+                DataType::Stream(option) => {
+                    if count > 1 {
+                        pipe.atomic();
+                    }
+                    let items = [(option.field.as_str(), kv.value)];
+                    // The IDs of the added entries are not part of the response.
+                    match option.maxlen {
+                        Some(maxlen) => pipe
+                            .xadd_maxlen(kv.key, StreamMaxlen::Approx(maxlen), "*", &items)
+                            .ignore(),
+                        None => pipe.xadd(kv.key, "*", &items).ignore(),
+                    };
+                }
             }
         }
 
@@ -456,6 +492,7 @@ mod integration_tests {
             key: key.clone(),
             encoding: Encoding::Json.into(),
             data_type: DataTypeConfig::List,
+            stream_option: None,
             list_option: Some(ListOption {
                 method: Method::LPush,
             }),
@@ -515,6 +552,7 @@ mod integration_tests {
             key: key.clone(),
             encoding: Encoding::Json.into(),
             data_type: DataTypeConfig::List,
+            stream_option: None,
             list_option: Some(ListOption {
                 method: Method::RPush,
             }),
@@ -590,6 +628,7 @@ mod integration_tests {
             encoding: Encoding::Json.into(),
             data_type: DataTypeConfig::Channel,
             list_option: None,
+            stream_option: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Option::from(u64::MAX),
@@ -618,4 +657,47 @@ mod integration_tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn redis_sink_stream_xadd() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+        let num_events = 1000;
+
+        let cnf = RedisSinkConfig {
+            url: redis_server(),
+            key: key.clone(),
+            encoding: Encoding::Text.into(),
+            data_type: DataTypeConfig::Stream,
+            list_option: None,
+            stream_option: Some(StreamOption {
+                field: "payload".to_owned(),
+                maxlen: None,
+            }),
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig {
+                rate_limit_num: Option::from(u64::MAX),
+                ..Default::default()
+            },
+            acknowledgements: Default::default(),
+        };
+
+        let conn = cnf.build_client().await.unwrap();
+        let cx = SinkContext::new_test();
+
+        let sink = cnf.new(conn, cx).unwrap();
+        let (input, events) = random_lines_with_stream(100, num_events, None);
+        sink.run(events).await.unwrap();
+
+        let mut conn = cnf.build_client().await.unwrap();
+        let reply: redis::streams::StreamRangeReply = conn.xrange_all(&key).await.unwrap();
+        let output = reply
+            .ids
+            .iter()
+            .map(|entry| entry.get::<String>("payload").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(output, input);
+    }
 }
@@ -4,6 +4,8 @@ pub mod buffer;
 pub mod builder;
 pub mod compressor;
 pub mod encoding;
+#[cfg(any(feature = "sinks-opentelemetry", feature = "sinks-vector"))]
+pub mod grpc;
 pub mod http;
 pub mod normalizer;
 pub mod partitioner;
@@ -33,7 +33,7 @@ mod tests {
         config::SinkContext,
         event::Event,
         proto::vector as proto,
-        sinks::{util::test::build_test_server_generic, vector::v2::config::with_default_scheme},
+        sinks::util::{grpc::with_default_scheme, test::build_test_server_generic},
         test_util::{components, next_addr, random_lines_with_stream},
     };
 
@@ -166,6 +166,10 @@ impl SourceConfig for DatadogAgentConfig {
             #[cfg(feature = "sources-syslog")]
             DeserializerConfig::Syslog => self.decoding.schema_definition(),
 
+            // GELF deserializer keeps the additional fields of the messages, similar to the JSON
+            // deserializer.
+            DeserializerConfig::Gelf => self.decoding.schema_definition(),
+
             DeserializerConfig::Native => self.decoding.schema_definition(),
             DeserializerConfig::NativeJson => self.decoding.schema_definition(),
         };
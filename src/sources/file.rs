@@ -1,10 +1,18 @@
-use std::{convert::TryInto, path::PathBuf, sync::Arc, time::Duration};
+use std::{convert::TryInto, io, path::PathBuf, sync::Arc, time::Duration};
 
-use bytes::Bytes;
+use bytes::{Buf, Bytes, BytesMut};
 use chrono::Utc;
+use codecs::{
+    decoding::{
+        format::Deserializer as _, BoxedFramingError, Deserializer, DeserializerConfig, Framer,
+        FramingConfig,
+    },
+    StreamDecodingError,
+};
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
-    Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
+    Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, FrameDecoder,
+    FrameDecoderBuilder, Line, ReadFrom,
 };
 use futures::{
     future::TryFutureExt,
@@ -13,8 +21,10 @@ use futures::{
 };
 use regex::bytes::Regex;
 use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 use snafu::{ResultExt, Snafu};
 use tokio::task::spawn_blocking;
+use tokio_util::codec::Decoder as _;
 
 use super::util::{finalizer::OrderedFinalizer, EncodingConfig, MultilineConfig};
 use crate::{
@@ -23,12 +33,13 @@ use crate::{
         SourceDescription,
     },
     encoding_transcode::{Decoder, Encoder},
-    event::{BatchNotifier, LogEvent},
+    event::{BatchNotifier, Event},
     internal_events::{
-        FileBytesReceived, FileEventsReceived, FileOpen, FileSourceInternalEventsEmitter,
+        DecoderDeserializeFailed, DecoderFramingFailed, FileBytesReceived, FileEventsReceived,
+        FileOpen, FileSourceInternalEventsEmitter,
     },
     line_agg::{self, LineAgg},
-    serde::bool_or_struct,
+    serde::{bool_or_struct, default_decoding},
     shutdown::ShutdownSignal,
     trace::{current_span, Instrument},
     SourceSender,
@@ -94,6 +105,9 @@ pub struct FileConfig {
     pub remove_after_secs: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    pub framing: Option<FramingConfig>,
+    #[serde(default = "default_decoding")]
+    pub decoding: DeserializerConfig,
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
 }
@@ -111,6 +125,13 @@ pub enum FingerprintConfig {
     },
     #[serde(rename = "device_and_inode")]
     DevInode,
+    #[serde(rename = "device_and_inode_checksum")]
+    DevInodeChecksum {
+        #[serde(default = "default_checksum_bytes")]
+        bytes: usize,
+        #[serde(default)]
+        ignored_header_bytes: usize,
+    },
 }
 
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
@@ -151,6 +172,13 @@ impl From<FingerprintConfig> for FingerprintStrategy {
                 }
             }
             FingerprintConfig::DevInode => FingerprintStrategy::DevInode,
+            FingerprintConfig::DevInodeChecksum {
+                bytes,
+                ignored_header_bytes,
+            } => FingerprintStrategy::DevInodeChecksum {
+                bytes,
+                ignored_header_bytes,
+            },
         }
     }
 }
@@ -163,6 +191,10 @@ const fn default_lines() -> usize {
     1
 }
 
+const fn default_checksum_bytes() -> usize {
+    256
+}
+
 #[derive(Debug)]
 pub(crate) struct FinalizerEntry {
     pub(crate) file_id: FileFingerprint,
@@ -197,6 +229,8 @@ impl Default for FileConfig {
             remove_after_secs: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            framing: None,
+            decoding: default_decoding(),
             acknowledgements: Default::default(),
         }
     }
@@ -292,6 +326,18 @@ pub fn file_source(
         None => Bytes::from(config.line_delimiter.clone()),
     };
 
+    // the files are split into frames by the framer, if any, instead of
+    // lines ending with the line delimiter, which is still used to resume
+    // after the frames that can't be decoded
+    let frame_decoder = config.framing.clone().map(|framing| {
+        let framing = framing.or_max_length(config.max_line_bytes);
+        let delimiter = line_delimiter_as_bytes.clone();
+        Arc::new(move || {
+            Box::new(FileFramer::new(framing.clone().build(), delimiter.clone()))
+                as Box<dyn FrameDecoder>
+        }) as FrameDecoderBuilder
+    });
+
     let checkpointer = Checkpointer::new(&data_dir);
     let file_server = FileServer {
         paths_provider,
@@ -301,6 +347,7 @@ pub fn file_source(
         ignore_before,
         max_line_bytes: config.max_line_bytes,
         line_delimiter: line_delimiter_as_bytes,
+        frame_decoder,
         data_dir,
         glob_minimum_cooldown,
         fingerprinter: Fingerprinter {
@@ -320,6 +367,7 @@ pub fn file_source(
         .clone()
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
+    let deserializer = config.decoding.build();
 
     let include = config.include.clone();
     let exclude = config.exclude.clone();
@@ -381,22 +429,35 @@ pub fn file_source(
         // logs in the queue.
         let span = current_span();
         let span2 = span.clone();
-        let mut messages = messages.map(move |line| {
-            let _enter = span2.enter();
-            let mut event = create_event(line.text, line.filename, &host_key, &hostname, &file_key);
-            if let Some(finalizer) = &finalizer {
-                let (batch, receiver) = BatchNotifier::new_with_receiver();
-                event = event.with_batch_notifier(&batch);
-                let entry = FinalizerEntry {
-                    file_id: line.file_id,
-                    offset: line.offset,
-                };
-                finalizer.add(entry, receiver);
-            } else {
-                checkpoints.update(line.file_id, line.offset);
-            }
-            event
-        });
+        let mut messages = messages
+            .map(move |line| {
+                let _enter = span2.enter();
+                let mut events = create_events(
+                    line.text,
+                    line.filename,
+                    &deserializer,
+                    &host_key,
+                    &hostname,
+                    &file_key,
+                );
+                if let Some(finalizer) = &finalizer {
+                    // The line is only checkpointed once all of its events
+                    // are acknowledged.
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    for event in &mut events {
+                        event.add_batch_notifier(Arc::clone(&batch));
+                    }
+                    let entry = FinalizerEntry {
+                        file_id: line.file_id,
+                        offset: line.offset,
+                    };
+                    finalizer.add(entry, receiver);
+                } else {
+                    checkpoints.update(line.file_id, line.offset);
+                }
+                futures::stream::iter(events)
+            })
+            .flatten();
         tokio::spawn(async move {
             out.send_event_stream(&mut messages)
                 .instrument(span.or_current())
@@ -460,33 +521,130 @@ fn wrap_with_line_agg(
     )
 }
 
-fn create_event(
+/// Splits the files into the frames of the `framing` option.
+///
+/// The bytes which can't be framed, or are part of a frame too long, are
+/// skipped up to the next line delimiter, where the frames are resumed from.
+struct FileFramer {
+    framer: Framer,
+    delimiter: Bytes,
+    skipping: bool,
+}
+
+impl FileFramer {
+    const fn new(framer: Framer, delimiter: Bytes) -> Self {
+        Self {
+            framer,
+            delimiter,
+            skipping: false,
+        }
+    }
+
+    /// Skips the buffered bytes up to the next delimiter, if they are being
+    /// skipped, returning whether the frames can be resumed.
+    fn skip_to_delimiter(&mut self, buf: &mut BytesMut) -> bool {
+        if !self.skipping || self.delimiter.is_empty() {
+            return true;
+        }
+        let delimiter = self.delimiter.as_ref();
+        match buf
+            .windows(delimiter.len())
+            .position(|window| window == delimiter)
+        {
+            Some(position) => {
+                buf.advance(position + delimiter.len());
+                self.skipping = false;
+            }
+            None => {
+                // The last bytes are kept, since they may start the delimiter.
+                let kept = buf.len().min(delimiter.len() - 1);
+                buf.advance(buf.len() - kept);
+            }
+        }
+        !self.skipping
+    }
+
+    fn handle_framing_result(
+        &mut self,
+        frame: Result<Option<Bytes>, BoxedFramingError>,
+    ) -> io::Result<Option<Bytes>> {
+        frame.map_err(|error| {
+            emit!(DecoderFramingFailed { error: &error });
+            if !error.can_continue() {
+                // The buffered bytes can't be framed, so they are skipped to
+                // resume from the next delimiter.
+                self.skipping = true;
+            }
+            io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+        })
+    }
+}
+
+impl FrameDecoder for FileFramer {
+    fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<Bytes>> {
+        if !self.skip_to_delimiter(buf) {
+            return Ok(None);
+        }
+        let frame = self.framer.decode(buf);
+        self.handle_framing_result(frame)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> io::Result<Option<Bytes>> {
+        if !self.skip_to_delimiter(buf) {
+            buf.clear();
+            return Ok(None);
+        }
+        let frame = self.framer.decode_eof(buf);
+        self.handle_framing_result(frame)
+    }
+
+    fn discard(&mut self, buf: &mut BytesMut) {
+        buf.clear();
+        self.skipping = true;
+    }
+}
+
+fn create_events(
     line: Bytes,
     file: String,
+    deserializer: &Deserializer,
     host_key: &str,
     hostname: &Option<String>,
     file_key: &Option<String>,
-) -> LogEvent {
+) -> SmallVec<[Event; 1]> {
+    let byte_size = line.len();
+    let mut events = match deserializer.parse(line) {
+        Ok(events) => events,
+        Err(error) => {
+            emit!(DecoderDeserializeFailed { error: &error });
+            return SmallVec::new();
+        }
+    };
+
     emit!(FileEventsReceived {
-        count: 1,
+        count: events.len(),
         file: &file,
-        byte_size: line.len(),
+        byte_size,
     });
 
-    let mut event = LogEvent::from(line);
-
-    // Add source type
-    event.insert(log_schema().source_type_key(), Bytes::from("file"));
+    let now = Utc::now();
+    for event in &mut events {
+        if let Event::Log(log) = event {
+            // Add source type
+            log.try_insert(log_schema().source_type_key(), Bytes::from("file"));
+            log.try_insert(log_schema().timestamp_key(), now);
 
-    if let Some(file_key) = &file_key {
-        event.insert(file_key.as_str(), file);
-    }
+            if let Some(file_key) = &file_key {
+                log.try_insert(file_key.as_str(), file.clone());
+            }
 
-    if let Some(hostname) = &hostname {
-        event.insert(host_key, hostname.clone());
+            if let Some(hostname) = &hostname {
+                log.try_insert(host_key, hostname.clone());
+            }
+        }
     }
 
-    event
+    events
 }
 
 #[cfg(test)]
@@ -572,6 +730,21 @@ mod tests {
         .unwrap();
         assert_eq!(config.fingerprint, FingerprintConfig::DevInode);
 
+        let config: FileConfig = toml::from_str(
+            r#"
+        [fingerprint]
+        strategy = "device_and_inode_checksum"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.fingerprint,
+            FingerprintConfig::DevInodeChecksum {
+                bytes: 256,
+                ignored_header_bytes: 0
+            }
+        );
+
         let config: FileConfig = toml::from_str(
             r#"
         [fingerprint]
@@ -614,6 +787,16 @@ mod tests {
         )
         .unwrap();
         assert_eq!(config.read_from, Some(ReadFromConfig::End));
+
+        let config: FileConfig = toml::from_str(
+            r#"
+        framing.method = "length_delimited"
+        decoding.codec = "json"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.framing, Some(FramingConfig::LengthDelimited));
+        assert_eq!(config.decoding, DeserializerConfig::Json);
     }
 
     #[test]
@@ -644,12 +827,48 @@ mod tests {
         let hostname = Some("Some.Machine".to_string());
         let file_key = Some("file".to_string());
 
-        let log = create_event(line, file, &host_key, &hostname, &file_key);
+        let deserializer = default_decoding().build();
+
+        let mut events = create_events(line, file, &deserializer, &host_key, &hostname, &file_key);
+        assert_eq!(events.len(), 1);
+        let log = events.remove(0).into_log();
 
         assert_eq!(log["file"], "some_file.rs".into());
         assert_eq!(log["host"], "Some.Machine".into());
         assert_eq!(log[log_schema().message_key()], "hello world".into());
         assert_eq!(log[log_schema().source_type_key()], "file".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn file_create_events_json() {
+        let line =
+            Bytes::from(r#"{"message":"hello world","host":"other.machine","level":"info"}"#);
+        let file = "some_file.rs".to_string();
+        let host_key = "host".to_string();
+        let hostname = Some("Some.Machine".to_string());
+        let file_key = Some("file".to_string());
+        let deserializer = DeserializerConfig::Json.build();
+
+        let mut events = create_events(line, file, &deserializer, &host_key, &hostname, &file_key);
+        assert_eq!(events.len(), 1);
+        let log = events.remove(0).into_log();
+
+        assert_eq!(log["file"], "some_file.rs".into());
+        assert_eq!(log["host"], "other.machine".into());
+        assert_eq!(log["level"], "info".into());
+        assert_eq!(log[log_schema().message_key()], "hello world".into());
+        assert_eq!(log[log_schema().source_type_key()], "file".into());
+
+        let events = create_events(
+            Bytes::from("not json"),
+            "some_file.rs".to_string(),
+            &deserializer,
+            &host_key,
+            &hostname,
+            &file_key,
+        );
+        assert!(events.is_empty());
     }
 
     #[tokio::test]
@@ -1153,6 +1372,107 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_device_and_inode_checksum_copy_truncate() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*.csv")],
+            fingerprint: FingerprintConfig::DevInodeChecksum {
+                bytes: 8,
+                ignored_header_bytes: 0,
+            },
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("export.csv");
+        let other_path = dir.path().join("other.csv");
+        let received = run_file_source(&config, false, NoAcks, async {
+            // The files share their header, but are still told apart.
+            let mut file = File::create(&path).unwrap();
+            let mut other_file = File::create(&other_path).unwrap();
+            writeln!(&mut file, "id,name").unwrap();
+            writeln!(&mut other_file, "id,name").unwrap();
+            sleep_500_millis().await;
+            writeln!(&mut file, "1,first").unwrap();
+            writeln!(&mut other_file, "1,other").unwrap();
+            sleep_500_millis().await;
+
+            // Copy-truncate rotation keeps the fingerprint of the file, which
+            // is read again from the beginning once its size shrinks.
+            fs::copy(&path, dir.path().join("export.csv.1")).unwrap();
+            file.set_len(0).unwrap();
+            let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+            writeln!(&mut file, "id,name").unwrap();
+            sleep_500_millis().await;
+            writeln!(&mut file, "2,second").unwrap();
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let mut lines = received
+            .into_iter()
+            .map(Event::into_log)
+            .map(|log| {
+                format!(
+                    "{} {}",
+                    log["file"].to_string_lossy(),
+                    log[log_schema().message_key()].to_string_lossy()
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        let path = path.to_str().unwrap();
+        let other_path = other_path.to_str().unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                format!("{} 1,first", path),
+                format!("{} 2,second", path),
+                format!("{} id,name", path),
+                format!("{} id,name", path),
+                format!("{} 1,other", other_path),
+                format!("{} id,name", other_path),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn file_device_and_inode_checksum_copy_truncate_changed_header() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*.csv")],
+            fingerprint: FingerprintConfig::DevInodeChecksum {
+                bytes: 8,
+                ignored_header_bytes: 0,
+            },
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("export.csv");
+        let received = run_file_source(&config, false, NoAcks, async {
+            let mut file = File::create(&path).unwrap();
+            writeln!(&mut file, "id,name").unwrap();
+            sleep_500_millis().await;
+            writeln!(&mut file, "1,first").unwrap();
+            sleep_500_millis().await;
+
+            // Writing another header after the truncation changes the
+            // fingerprint of the file, whose lines are still only read once.
+            fs::copy(&path, dir.path().join("export.csv.1")).unwrap();
+            file.set_len(0).unwrap();
+            let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+            writeln!(&mut file, "id,title").unwrap();
+            sleep_500_millis().await;
+            writeln!(&mut file, "2,second").unwrap();
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let mut lines = extract_messages_string(received);
+        lines.sort();
+        assert_eq!(lines, vec!["1,first", "2,second", "id,name", "id,title"]);
+    }
+
     #[cfg(unix)] // this test uses unix-specific function `futimes` during test time
     #[tokio::test]
     async fn file_start_position_ignore_old_files() {
@@ -1547,6 +1867,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_zstd_compressed_file() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![PathBuf::from("tests/data/multipart-zst.log.zst")],
+            // The compressed file is shorter than the lines it holds, so it's
+            // identified without checksumming its first line.
+            fingerprint: FingerprintConfig::DevInode,
+            ..test_default_file_config(&dir)
+        };
+
+        let received = run_file_source(&config, false, NoAcks, sleep_500_millis()).await;
+
+        let received = extract_messages_value(received);
+
+        let expected = std::fs::read_to_string("tests/data/multipart-zst.log").unwrap();
+        assert_eq!(
+            received,
+            expected.lines().map(Into::into).collect::<Vec<Value>>()
+        );
+    }
+
     #[tokio::test]
     async fn test_non_utf8_encoded_file() {
         let dir = tempdir().unwrap();
@@ -1609,6 +1951,153 @@ mod tests {
         );
     }
 
+    #[test]
+    fn file_framer_skips_to_next_delimiter() {
+        let framing = FramingConfig::NewlineDelimited {
+            newline_delimited: Default::default(),
+        };
+        let mut framer = FileFramer::new(framing.or_max_length(8).build(), Bytes::from("\n"));
+
+        let mut buf = BytesMut::from("much too");
+        framer.discard(&mut buf);
+        assert!(buf.is_empty());
+        buf.extend_from_slice(b" long\nnext\n");
+        assert_eq!(framer.decode(&mut buf).unwrap(), Some(Bytes::from("next")));
+
+        // The frames are limited to the maximum length even when not discarded.
+        buf.extend_from_slice(b"much too long\nnext\n");
+        assert_eq!(framer.decode(&mut buf).unwrap(), Some(Bytes::from("next")));
+    }
+
+    #[tokio::test]
+    async fn test_character_delimited_framing() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            framing: Some(
+                toml::from_str(
+                    r#"
+                method = "character_delimited"
+                character_delimited.delimiter = ","
+                "#,
+                )
+                .unwrap(),
+            ),
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let received = run_file_source(&config, false, NoAcks, async {
+            let mut file = File::create(&path).unwrap();
+
+            sleep_500_millis().await; // The files must be observed at their original lengths before writing to them
+
+            writeln!(&mut file, "fingerprint").unwrap();
+            write!(&mut file, "first,second").unwrap();
+            sleep_500_millis().await;
+            write!(&mut file, " half,third,").unwrap();
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let received = extract_messages_value(received);
+
+        assert_eq!(
+            received,
+            vec![
+                "fingerprint\nfirst".into(),
+                "second half".into(),
+                "third".into()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_decoding() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            decoding: DeserializerConfig::Json,
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let received = run_file_source(&config, false, NoAcks, async {
+            let mut file = File::create(&path).unwrap();
+
+            sleep_500_millis().await; // The files must be observed at their original lengths before writing to them
+
+            writeln!(&mut file, r#"{{"message":"hello","count":1}}"#).unwrap();
+            writeln!(&mut file, "not json").unwrap();
+            writeln!(&mut file, r#"{{"message":"world","count":2}}"#).unwrap();
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let logs = received
+            .into_iter()
+            .map(Event::into_log)
+            .collect::<Vec<_>>();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0][log_schema().message_key()], "hello".into());
+        assert_eq!(logs[0]["count"], 1.into());
+        assert_eq!(logs[1][log_schema().message_key()], "world".into());
+        assert_eq!(logs[1]["count"], 2.into());
+        assert_eq!(logs[1]["file"], path.to_str().unwrap().into());
+    }
+
+    #[tokio::test]
+    async fn test_length_delimited_framing_server_restart() {
+        fn write_frame(file: &mut File, frame: &[u8]) {
+            file.write_all(&(frame.len() as u32).to_be_bytes()).unwrap();
+            file.write_all(frame).unwrap();
+        }
+
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            // The binary frames have no line to fingerprint the file with.
+            fingerprint: FingerprintConfig::DevInode,
+            framing: Some(FramingConfig::LengthDelimited),
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        write_frame(&mut file, b"zeroth\nframe");
+        sleep_500_millis().await;
+
+        // The incomplete frame isn't read, nor checkpointed past.
+        {
+            let received = run_file_source(&config, true, Acks, async {
+                sleep_500_millis().await;
+                write_frame(&mut file, b"first frame");
+                file.write_all(&12u32.to_be_bytes()).unwrap();
+                file.write_all(b"second").unwrap();
+                sleep_500_millis().await;
+            })
+            .await;
+
+            let frames = extract_messages_string(received);
+            assert_eq!(frames, vec!["zeroth\nframe", "first frame"]);
+        }
+        // Restart server, read the frames from the checkpoint.
+        {
+            let received = run_file_source(&config, true, Acks, async {
+                sleep_500_millis().await;
+                file.write_all(b" frame").unwrap();
+                write_frame(&mut file, b"third frame");
+                sleep_500_millis().await;
+            })
+            .await;
+
+            let frames = extract_messages_string(received);
+            assert_eq!(frames, vec!["second frame", "third frame"]);
+        }
+    }
+
     #[tokio::test]
     async fn remove_file() {
         let n = 5;
@@ -31,6 +31,8 @@ mod disk;
 mod filesystem;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod process;
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -44,6 +46,8 @@ enum Collector {
     Host,
     Memory,
     Network,
+    #[cfg(target_os = "linux")]
+    Process,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -84,6 +88,9 @@ pub struct HostMetricsConfig {
     filesystem: filesystem::FilesystemConfig,
     #[serde(default)]
     network: network::NetworkConfig,
+    #[cfg(target_os = "linux")]
+    #[serde(default)]
+    process: process::ProcessConfig,
 }
 
 const fn default_scrape_interval() -> f64 {
@@ -165,6 +172,11 @@ impl HostMetricsConfig {
 
     fn has_collector(&self, collector: Collector) -> bool {
         match &self.collectors {
+            // The process metrics are opt-in, as there are a few for each of
+            // the many processes of a host.
+            #[cfg(target_os = "linux")]
+            None => collector != Collector::Process,
+            #[cfg(not(target_os = "linux"))]
             None => true,
             Some(collectors) => collectors.iter().any(|&c| c == collector),
         }
@@ -224,6 +236,10 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             metrics.extend(add_collector("network", self.network_metrics().await));
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Process) {
+            metrics.extend(add_collector("process", self.process_metrics().await));
+        }
         if let Ok(hostname) = &hostname {
             for metric in &mut metrics {
                 metric.insert_tag("host".into(), hostname.into());
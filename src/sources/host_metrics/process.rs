@@ -0,0 +1,388 @@
+use std::{collections::BTreeMap, io, path::Path};
+
+use chrono::{DateTime, Utc};
+use nix::unistd::{sysconf, SysconfVar};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use vector_common::btreemap;
+
+use super::{filter_result_sync, FilterList, HostMetrics};
+use crate::event::metric::Metric;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub(super) struct ProcessConfig {
+    names: FilterList,
+    cmdlines: FilterList,
+}
+
+impl HostMetrics {
+    pub async fn process_metrics(&self) -> Vec<Metric> {
+        let procfs_root = heim::os::linux::procfs_root();
+        let pids =
+            match filter_result_sync(list_pids(&procfs_root).await, "Failed to list processes.") {
+                Some(pids) => pids,
+                None => return vec![],
+            };
+        let ticks_per_second = clock_ticks_per_second();
+
+        let mut metrics = Vec::new();
+        for pid in pids {
+            let root = procfs_root.join(pid.to_string());
+            // Processes exiting while they are listed or loaded are skipped.
+            let process = match Process::load(&root).await {
+                Ok(process) => process,
+                Err(error) => {
+                    debug!(message = "Failed to load process.", %pid, %error, internal_log_rate_secs = 60);
+                    continue;
+                }
+            };
+            if !self.config.process.names.contains_str(Some(&process.name))
+                || !self
+                    .config
+                    .process
+                    .cmdlines
+                    .contains_str(process.cmdline.as_deref())
+            {
+                continue;
+            }
+            self.push_process_metrics(&mut metrics, Utc::now(), pid, &process, ticks_per_second);
+
+            // The descriptors and I/O of processes of other users can't be
+            // read without privileges, so they are left out.
+            match count_open_fds(&root).await {
+                Ok(open_fds) => metrics.push(self.gauge(
+                    "process_open_fds",
+                    Utc::now(),
+                    open_fds as f64,
+                    process_tags(pid, &process),
+                )),
+                Err(error) => {
+                    debug!(message = "Failed to count process open file descriptors.", %pid, %error, internal_log_rate_secs = 60);
+                }
+            }
+            match IoStat::load(&root).await {
+                Ok(io) => {
+                    let timestamp = Utc::now();
+                    metrics.push(self.counter(
+                        "process_io_read_bytes_total",
+                        timestamp,
+                        io.read_bytes as f64,
+                        process_tags(pid, &process),
+                    ));
+                    metrics.push(self.counter(
+                        "process_io_written_bytes_total",
+                        timestamp,
+                        io.write_bytes as f64,
+                        process_tags(pid, &process),
+                    ));
+                }
+                Err(error) => {
+                    debug!(message = "Failed to load process I/O statistics.", %pid, %error, internal_log_rate_secs = 60);
+                }
+            }
+        }
+        metrics
+    }
+
+    fn push_process_metrics(
+        &self,
+        metrics: &mut Vec<Metric>,
+        timestamp: DateTime<Utc>,
+        pid: u32,
+        process: &Process,
+        ticks_per_second: f64,
+    ) {
+        let mut tags = process_tags(pid, process);
+        tags.insert("mode".into(), "user".into());
+        metrics.push(self.counter(
+            "process_cpu_seconds_total",
+            timestamp,
+            process.user_ticks as f64 / ticks_per_second,
+            tags.clone(),
+        ));
+        tags.insert("mode".into(), "system".into());
+        metrics.push(self.counter(
+            "process_cpu_seconds_total",
+            timestamp,
+            process.system_ticks as f64 / ticks_per_second,
+            tags,
+        ));
+        // Kernel threads have no memory of their own.
+        if let Some(rss_bytes) = process.rss_bytes {
+            metrics.push(self.gauge(
+                "process_memory_rss_bytes",
+                timestamp,
+                rss_bytes as f64,
+                process_tags(pid, process),
+            ));
+        }
+        metrics.push(self.gauge(
+            "process_threads",
+            timestamp,
+            process.threads as f64,
+            process_tags(pid, process),
+        ));
+    }
+}
+
+fn process_tags(pid: u32, process: &Process) -> BTreeMap<String, String> {
+    btreemap! {
+        "pid" => pid.to_string(),
+        "name" => process.name.clone(),
+    }
+}
+
+fn clock_ticks_per_second() -> f64 {
+    match sysconf(SysconfVar::CLK_TCK) {
+        Ok(Some(ticks)) if ticks > 0 => ticks as f64,
+        // The kernel has reported times in hundredths of a second for long.
+        _ => 100.0,
+    }
+}
+
+async fn list_pids(procfs_root: &Path) -> io::Result<Vec<u32>> {
+    let mut pids = Vec::new();
+    let mut dir = fs::read_dir(procfs_root).await?;
+    while let Some(entry) = dir.next_entry().await? {
+        if let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            pids.push(pid);
+        }
+    }
+    Ok(pids)
+}
+
+async fn count_open_fds(root: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    let mut dir = fs::read_dir(root.join("fd")).await?;
+    while dir.next_entry().await?.is_some() {
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Process {
+    name: String,
+    /// The arguments of the process joined with spaces, unless it has none,
+    /// as kernel threads.
+    cmdline: Option<String>,
+    user_ticks: u64,
+    system_ticks: u64,
+    rss_bytes: Option<u64>,
+    threads: u64,
+}
+
+impl Process {
+    async fn load(root: &Path) -> io::Result<Self> {
+        let stat = fs::read_to_string(root.join("stat")).await?;
+        let status = fs::read_to_string(root.join("status")).await?;
+        let cmdline = fs::read(root.join("cmdline")).await?;
+
+        let mut process = Process::default();
+        process
+            .parse_stat(&stat)
+            .ok_or_else(|| invalid_data(root, "stat"))?;
+        process
+            .parse_status(&status)
+            .ok_or_else(|| invalid_data(root, "status"))?;
+        process.cmdline = parse_cmdline(&cmdline);
+        Ok(process)
+    }
+
+    /// Parses the CPU times out of `/proc/<pid>/stat`.
+    fn parse_stat(&mut self, stat: &str) -> Option<()> {
+        // The name of the command is in parentheses, and may hold spaces and
+        // parentheses itself, so the fields are counted from its end.
+        let mut fields = stat[stat.rfind(')')? + 1..].split_whitespace().skip(11);
+        self.user_ticks = fields.next()?.parse().ok()?;
+        self.system_ticks = fields.next()?.parse().ok()?;
+        Some(())
+    }
+
+    /// Parses the name, memory and threads out of `/proc/<pid>/status`.
+    fn parse_status(&mut self, status: &str) -> Option<()> {
+        let mut threads = None;
+        for line in status.lines() {
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key, value.trim()),
+                None => continue,
+            };
+            match key {
+                "Name" => self.name = value.to_owned(),
+                "VmRSS" => {
+                    let kilobytes: u64 = value.trim_end_matches(" kB").parse().ok()?;
+                    self.rss_bytes = Some(kilobytes * 1024);
+                }
+                "Threads" => threads = Some(value.parse().ok()?),
+                _ => {}
+            }
+        }
+        self.threads = threads?;
+        Some(())
+    }
+}
+
+fn parse_cmdline(cmdline: &[u8]) -> Option<String> {
+    let args = cmdline
+        .split(|&c| c == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>();
+    (!args.is_empty()).then(|| args.join(" "))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct IoStat {
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+impl IoStat {
+    async fn load(root: &Path) -> io::Result<Self> {
+        let io = fs::read_to_string(root.join("io")).await?;
+        Self::parse(&io).ok_or_else(|| invalid_data(root, "io"))
+    }
+
+    /// Parses the bytes read from and written to storage out of
+    /// `/proc/<pid>/io`.
+    fn parse(io: &str) -> Option<Self> {
+        let mut result = Self::default();
+        for line in io.lines() {
+            match line.split_once(": ") {
+                Some(("read_bytes", value)) => result.read_bytes = value.parse().ok()?,
+                Some(("write_bytes", value)) => result.write_bytes = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        Some(result)
+    }
+}
+
+fn invalid_data(root: &Path, filename: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "Could not parse process data file {:?}.",
+            root.join(filename)
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        super::{
+            tests::{count_name, count_tag},
+            Collector, FilterList, HostMetrics, HostMetricsConfig, PatternWrapper,
+        },
+        parse_cmdline, IoStat, Process, ProcessConfig,
+    };
+
+    #[test]
+    fn parses_process_files() {
+        let mut process = Process::default();
+        process
+            .parse_stat("42 (my (odd) name) S 1 42 42 0 -1 4194560 1 0 0 0 250 75 0 0 20 0 3 0")
+            .unwrap();
+        process
+            .parse_status(
+                "Name:\tmy (odd) name\nState:\tS (sleeping)\nVmRSS:\t    2048 kB\nThreads:\t3\n",
+            )
+            .unwrap();
+        assert_eq!(
+            process,
+            Process {
+                name: "my (odd) name".into(),
+                cmdline: None,
+                user_ticks: 250,
+                system_ticks: 75,
+                rss_bytes: Some(2 * 1024 * 1024),
+                threads: 3,
+            }
+        );
+
+        assert_eq!(
+            parse_cmdline(b"/usr/bin/vector\0--config\0/etc/vector.toml\0"),
+            Some("/usr/bin/vector --config /etc/vector.toml".into())
+        );
+        assert_eq!(parse_cmdline(b""), None);
+
+        assert_eq!(
+            IoStat::parse("rchar: 10\nwchar: 20\nread_bytes: 4096\nwrite_bytes: 8192\n"),
+            Some(IoStat {
+                read_bytes: 4096,
+                write_bytes: 8192
+            })
+        );
+    }
+
+    #[test]
+    fn process_collector_is_opt_in() {
+        assert!(!HostMetricsConfig::default().has_collector(Collector::Process));
+
+        let config: HostMetricsConfig = toml::from_str(r#"collectors = ["process"]"#).unwrap();
+        assert!(config.has_collector(Collector::Process));
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        let config: HostMetricsConfig = toml::from_str(r#"collectors = ["process"]"#).unwrap();
+        let metrics = HostMetrics::new(config).process_metrics().await;
+
+        assert!(!metrics.is_empty());
+        assert_eq!(count_tag(&metrics, "pid"), metrics.len());
+        assert_eq!(count_tag(&metrics, "name"), metrics.len());
+        assert!(count_name(&metrics, "process_cpu_seconds_total") > 0);
+        assert!(count_name(&metrics, "process_threads") > 0);
+    }
+
+    #[tokio::test]
+    async fn process_metrics_filters_on_name() {
+        let metrics = HostMetrics::new(HostMetricsConfig {
+            process: ProcessConfig {
+                names: FilterList {
+                    includes: None,
+                    excludes: Some(vec![PatternWrapper::new("*").unwrap()]),
+                },
+                cmdlines: FilterList::default(),
+            },
+            ..Default::default()
+        })
+        .process_metrics()
+        .await;
+
+        assert!(metrics.is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_metrics_filters_on_cmdline() {
+        let pid = std::process::id().to_string();
+        let exe = std::env::current_exe().unwrap();
+        let metrics = HostMetrics::new(HostMetricsConfig {
+            process: ProcessConfig {
+                names: FilterList::default(),
+                cmdlines: FilterList {
+                    includes: Some(vec![
+                        PatternWrapper::new(format!("{}*", exe.display())).unwrap()
+                    ]),
+                    excludes: None,
+                },
+            },
+            ..Default::default()
+        })
+        .process_metrics()
+        .await;
+
+        // This test process is among the processes reported.
+        assert!(metrics
+            .iter()
+            .any(|metric| metric.tags().unwrap().get("pid") == Some(&pid)));
+    }
+}
@@ -7,7 +7,7 @@ use codecs::{
     BytesDecoderConfig, BytesDeserializerConfig, JsonDeserializerConfig,
     NewlineDelimitedDecoderConfig,
 };
-use http::StatusCode;
+use http::{Method, StatusCode};
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Decoder as _;
 use warp::http::{HeaderMap, HeaderValue};
@@ -22,6 +22,7 @@ use crate::{
     serde::{bool_or_struct, default_decoding, default_framing_stream_based},
     sources::util::{
         add_query_parameters, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        HttpSourceRoute,
     },
     tls::TlsConfig,
 };
@@ -47,6 +48,24 @@ pub(super) struct SimpleHttpConfig {
     decoding: Option<DeserializerConfig>,
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    routes: Vec<HttpRouteConfig>,
+}
+
+/// A path served by the source. The source-wide `framing`, `decoding` and `auth` apply to the
+/// routes that don't set their own.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct HttpRouteConfig {
+    path: String,
+    #[serde(default = "crate::serde::default_true")]
+    strict_path: bool,
+    #[serde(default = "default_methods")]
+    methods: Vec<String>,
+    framing: Option<FramingConfig>,
+    decoding: Option<DeserializerConfig>,
+    auth: Option<HttpSourceAuthConfig>,
+    output: Option<String>,
 }
 
 inventory::submit! {
@@ -68,6 +87,7 @@ impl GenerateConfig for SimpleHttpConfig {
             framing: Some(default_framing_stream_based()),
             decoding: Some(default_decoding()),
             acknowledgements: AcknowledgementsConfig::default(),
+            routes: Vec::new(),
         })
         .unwrap()
     }
@@ -81,6 +101,10 @@ fn default_path_key() -> String {
     "path".to_string()
 }
 
+fn default_methods() -> Vec<String> {
+    vec!["POST".to_string()]
+}
+
 #[derive(Clone)]
 struct SimpleHttpSource {
     headers: Vec<String>,
@@ -173,26 +197,70 @@ impl SourceConfig for SimpleHttpConfig {
             )
         };
 
-        let decoder = DecodingConfig::new(framing, decoding).build();
-        let source = SimpleHttpSource {
+        let source = |framing, decoding| SimpleHttpSource {
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
-            decoder,
+            decoder: DecodingConfig::new(framing, decoding).build(),
         };
-        source.run(
-            self.address,
-            self.path.as_str(),
-            self.strict_path,
-            &self.tls,
-            &self.auth,
-            cx,
-            self.acknowledgements,
-        )
+
+        if self.routes.is_empty() {
+            return source(framing, decoding).run(
+                self.address,
+                self.path.as_str(),
+                self.strict_path,
+                &self.tls,
+                &self.auth,
+                cx,
+                self.acknowledgements,
+            );
+        }
+
+        let routes = self
+            .routes
+            .iter()
+            .map(|route| {
+                let methods = route
+                    .methods
+                    .iter()
+                    .map(|method| {
+                        Method::from_bytes(method.to_uppercase().as_bytes())
+                            .map_err(|_| format!("Invalid HTTP method {:?}.", method))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(HttpSourceRoute {
+                    source: source(
+                        route.framing.clone().unwrap_or_else(|| framing.clone()),
+                        route.decoding.clone().unwrap_or_else(|| decoding.clone()),
+                    ),
+                    path: route.path.clone(),
+                    strict_path: route.strict_path,
+                    methods,
+                    auth: route.auth.clone().or_else(|| self.auth.clone()),
+                    output: route.output.clone(),
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        SimpleHttpSource::run_routes(routes, self.address, &self.tls, cx, self.acknowledgements)
     }
 
     fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        if self.routes.is_empty() {
+            return vec![Output::default(DataType::Log)];
+        }
+
+        // The routes without a named output send their events to the default one.
+        let mut outputs: Vec<Output> = Vec::new();
+        for route in &self.routes {
+            let output = match &route.output {
+                Some(name) => Output::from((name.clone(), DataType::Log)),
+                None => Output::default(DataType::Log),
+            };
+            if !outputs.iter().any(|existing| existing.port == output.port) {
+                outputs.push(output);
+            }
+        }
+        outputs
     }
 
     fn source_type(&self) -> &'static str {
@@ -289,6 +357,7 @@ mod tests {
                 framing,
                 decoding,
                 acknowledgements: acknowledgements.into(),
+                routes: Vec::new(),
             }
             .build(context)
             .await
@@ -948,4 +1017,90 @@ mod tests {
 
         assert_eq!(events.len(), 1);
     }
+
+    #[tokio::test]
+    async fn routes_requests_to_outputs() {
+        use futures::StreamExt;
+        use vector_core::event::EventContainer;
+
+        trace_init();
+
+        let address = next_addr();
+        let config: SimpleHttpConfig = toml::from_str(&format!(
+            r#"
+                address = "{}"
+
+                [[routes]]
+                path = "/github"
+                methods = ["post", "put"]
+                decoding.codec = "json"
+                output = "github"
+
+                [[routes]]
+                path = "/apps"
+                auth.username = "user"
+                auth.password = "pass"
+            "#,
+            address
+        ))
+        .unwrap();
+        assert_eq!(
+            config
+                .outputs()
+                .into_iter()
+                .map(|output| output.port)
+                .collect::<Vec<_>>(),
+            vec![Some("github".to_owned()), None]
+        );
+
+        let (mut sender, default_rx) = SourceSender::new_test();
+        let github_rx = sender.add_outputs(EventStatus::Delivered, "github".to_owned());
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move {
+            config.build(context).await.unwrap().await.unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        let client = reqwest::Client::new();
+        let github = format!("http://{}/github", address);
+        let apps = format!("http://{}/apps", address);
+        let status = |request: reqwest::RequestBuilder| async move {
+            request.send().await.unwrap().status().as_u16()
+        };
+
+        assert_eq!(
+            200,
+            status(client.put(&github).body(r#"{"action":"opened"}"#)).await
+        );
+        assert_eq!(405, status(client.get(&github)).await);
+        assert_eq!(
+            404,
+            status(client.post(&format!("{}/nested", github))).await
+        );
+        assert_eq!(401, status(client.post(&apps).body("hello")).await);
+        assert_eq!(
+            200,
+            status(
+                client
+                    .post(&apps)
+                    .basic_auth("user", Some("pass"))
+                    .body("hello")
+            )
+            .await
+        );
+
+        let github_events = github_rx
+            .take(1)
+            .flat_map(|events| futures::stream::iter(events.into_events()))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(github_events[0].as_log()["action"], Value::from("opened"));
+        assert_eq!(github_events[0].as_log()["path"], Value::from("/github"));
+
+        let default_events = default_rx.take(1).collect::<Vec<_>>().await;
+        assert_eq!(
+            default_events[0].as_log()[log_schema().message_key()],
+            Value::from("hello")
+        );
+    }
 }
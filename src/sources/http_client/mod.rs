@@ -0,0 +1,351 @@
+//! The `http_client` source periodically requests an HTTP endpoint and decodes the responses into
+//! events, optionally following the pages of paginated APIs.
+
+use std::{path::PathBuf, time::Duration};
+
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::StreamExt;
+use http::{
+    header::{HeaderName, HeaderValue},
+    HeaderMap, Request,
+};
+use hyper::Body;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_stream::wrappers::IntervalStream;
+use tokio_util::codec::Decoder as _;
+use url::Url;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::Event,
+    http::{Auth, HttpClient},
+    internal_events::{
+        HttpClientBytesReceived, HttpClientEventsReceived, HttpClientHttpError,
+        HttpClientHttpResponseError, StreamClosedError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::{TlsOptions, TlsSettings},
+    SourceSender,
+};
+
+mod pagination;
+#[cfg(test)]
+mod tests;
+
+use pagination::{Checkpointer, PaginationConfig};
+
+#[derive(Debug, Snafu)]
+enum ConfigError {
+    #[snafu(display("Invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: url::ParseError,
+    },
+    #[snafu(display("Invalid header name {:?}: {}", name, source))]
+    InvalidHeaderName {
+        name: String,
+        source: http::header::InvalidHeaderName,
+    },
+    #[snafu(display("Invalid value for header {:?}: {}", name, source))]
+    InvalidHeaderValue {
+        name: String,
+        source: http::header::InvalidHeaderValue,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpClientConfig {
+    endpoint: String,
+    #[serde(default = "default_scrape_interval_secs")]
+    scrape_interval_secs: u64,
+    #[serde(default)]
+    query: IndexMap<String, Vec<String>>,
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+    auth: Option<Auth>,
+    tls: Option<TlsOptions>,
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+    pagination: Option<PaginationConfig>,
+    data_dir: Option<PathBuf>,
+}
+
+const fn default_scrape_interval_secs() -> u64 {
+    15
+}
+
+inventory::submit! {
+    SourceDescription::new::<HttpClientConfig>("http_client")
+}
+
+impl GenerateConfig for HttpClientConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "http://localhost:8080/api/logs".to_owned(),
+            scrape_interval_secs: default_scrape_interval_secs(),
+            query: IndexMap::new(),
+            headers: IndexMap::new(),
+            auth: None,
+            tls: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            pagination: None,
+            data_dir: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http_client")]
+impl SourceConfig for HttpClientConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let mut endpoint = Url::parse(&self.endpoint).context(InvalidEndpointSnafu {
+            endpoint: self.endpoint.clone(),
+        })?;
+        for (name, values) in &self.query {
+            for value in values {
+                endpoint.query_pairs_mut().append_pair(name, value);
+            }
+        }
+
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .context(InvalidHeaderNameSnafu { name: name.clone() })?;
+                let value = HeaderValue::from_str(value).context(InvalidHeaderValueSnafu {
+                    name: name.as_str(),
+                })?;
+                Ok((name, value))
+            })
+            .collect::<Result<HeaderMap, ConfigError>>()?;
+
+        let pagination = match &self.pagination {
+            Some(pagination) => {
+                let data_dir = cx
+                    .globals
+                    .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+                Some((pagination.clone(), Checkpointer::new(&data_dir).await?))
+            }
+            None => None,
+        };
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let source = HttpClientSource {
+            endpoint,
+            interval: Duration::from_secs(self.scrape_interval_secs),
+            client: HttpClient::new(tls, &cx.proxy)?,
+            headers,
+            auth: self.auth.clone(),
+            decoder: DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build(),
+            pagination,
+        };
+        Ok(Box::pin(source.run(cx.shutdown, cx.out)))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "http_client"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// A decoded response.
+struct Page {
+    events: Vec<Event>,
+    next_cursor: Option<String>,
+}
+
+struct HttpClientSource {
+    endpoint: Url,
+    interval: Duration,
+    client: HttpClient,
+    headers: HeaderMap,
+    auth: Option<Auth>,
+    decoder: Decoder,
+    pagination: Option<(PaginationConfig, Checkpointer)>,
+}
+
+impl HttpClientSource {
+    async fn run(mut self, shutdown: ShutdownSignal, mut out: SourceSender) -> Result<(), ()> {
+        let mut ticks =
+            IntervalStream::new(tokio::time::interval(self.interval)).take_until(shutdown);
+
+        while ticks.next().await.is_some() {
+            // The pages are followed until the last one, an empty one or `max_pages`, whichever
+            // comes first. The following pages are requested during the next scrape.
+            let mut pages = 0;
+            loop {
+                let url = match self.page_url() {
+                    Ok(url) => url,
+                    Err(error) => {
+                        emit!(HttpClientHttpError {
+                            error: error.into(),
+                            url: self.endpoint.as_str(),
+                        });
+                        if let Some((_, checkpointer)) = &mut self.pagination {
+                            checkpointer.set(None, 0).await;
+                        }
+                        break;
+                    }
+                };
+                let page = match self.fetch(&url).await {
+                    Some(page) => page,
+                    None => break,
+                };
+
+                // The events of a page requested again were already emitted, only its new
+                // entries are.
+                let total = page.events.len();
+                let delivered = self
+                    .pagination
+                    .as_ref()
+                    .map_or(0, |(_, checkpointer)| checkpointer.delivered());
+                let events = page.events.into_iter().skip(delivered).collect::<Vec<_>>();
+                let count = events.len();
+                if count > 0 {
+                    if let Err(error) = out.send_batch(events).await {
+                        emit!(StreamClosedError { error, count });
+                        return Err(());
+                    }
+                }
+
+                pages += 1;
+                match &mut self.pagination {
+                    Some((pagination, checkpointer)) => match page.next_cursor {
+                        Some(next_cursor) => {
+                            checkpointer.set(Some(next_cursor), 0).await;
+                            if total == 0 || pages >= pagination.max_pages {
+                                break;
+                            }
+                        }
+                        // Without a next page, the cursor of the last page is kept along with
+                        // its number of events, so that the next scrape requests it again for its
+                        // new entries rather than starting over.
+                        None => {
+                            let cursor = checkpointer.cursor().map(str::to_owned);
+                            checkpointer.set(cursor, total).await;
+                            break;
+                        }
+                    },
+                    None => break,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the URL of the page to request, starting from the endpoint when there is no cursor.
+    fn page_url(&self) -> Result<Url, url::ParseError> {
+        match &self.pagination {
+            Some((pagination, checkpointer)) => match checkpointer.cursor() {
+                Some(cursor) => pagination.page_url(&self.endpoint, cursor),
+                None => Ok(self.endpoint.clone()),
+            },
+            None => Ok(self.endpoint.clone()),
+        }
+    }
+
+    async fn fetch(&mut self, url: &Url) -> Option<Page> {
+        let mut request = Request::get(url.as_str())
+            .body(Body::empty())
+            .expect("error creating request");
+        request.headers_mut().extend(self.headers.clone());
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let (headers, body) = match self.client.send(request).await {
+            Ok(response) => {
+                let (parts, body) = response.into_parts();
+                match hyper::body::to_bytes(body).await {
+                    Ok(body) if parts.status.is_success() => (parts.headers, body),
+                    Ok(_) => {
+                        emit!(HttpClientHttpResponseError {
+                            code: parts.status,
+                            url: url.as_str(),
+                        });
+                        return None;
+                    }
+                    Err(error) => {
+                        emit!(HttpClientHttpError {
+                            error: error.into(),
+                            url: url.as_str(),
+                        });
+                        return None;
+                    }
+                }
+            }
+            Err(error) => {
+                emit!(HttpClientHttpError {
+                    error: error.into(),
+                    url: url.as_str(),
+                });
+                return None;
+            }
+        };
+
+        emit!(HttpClientBytesReceived {
+            byte_size: body.len(),
+            protocol: url.scheme(),
+            endpoint: url.as_str(),
+        });
+
+        let events = self.decode(&body);
+        emit!(HttpClientEventsReceived {
+            byte_size: events.size_of(),
+            count: events.len(),
+            url: url.as_str(),
+        });
+
+        let next_cursor = self
+            .pagination
+            .as_ref()
+            .and_then(|(pagination, _)| pagination.next_cursor(&headers, &body));
+        Some(Page {
+            events,
+            next_cursor,
+        })
+    }
+
+    /// Decodes the events of a response body. The decoding errors are reported by the decoder, and
+    /// the events decoded before an error are kept.
+    fn decode(&mut self, body: &Bytes) -> Vec<Event> {
+        let mut bytes = BytesMut::from(&body[..]);
+        let mut events = Vec::new();
+        while let Ok(Some((next, _))) = self.decoder.decode_eof(&mut bytes) {
+            events.extend(next);
+        }
+
+        let now = Utc::now();
+        for event in &mut events {
+            if let Event::Log(log) = event {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("http_client"));
+                log.try_insert(log_schema().timestamp_key(), now);
+            }
+        }
+        events
+    }
+}
@@ -0,0 +1,294 @@
+//! Pagination of the responses of the `http_client` source.
+//!
+//! The next page is described either by the `rel="next"` link of the `Link` header or by a field of
+//! the JSON response body. The cursor of the next page is persisted in the data directory so that
+//! a restarted source resumes from the page it stopped at, along with the number of events of that
+//! page that were already emitted, so that requesting the last page again only emits its new
+//! entries.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use bytes::Bytes;
+use http::{header::LINK, HeaderMap};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::internal_events::HttpClientCheckpointError;
+
+const CHECKPOINT_FILE_NAME: &str = "checkpoint";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaginationConfig {
+    #[serde(flatten)]
+    next_page: NextPage,
+    /// The maximum number of pages fetched during a single scrape.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+}
+
+const fn default_max_pages() -> usize {
+    100
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NextPage {
+    /// The URL of the next page is the `rel="next"` link of the `Link` header.
+    LinkHeader,
+    /// The token of the next page is read from a field of the JSON response, addressed by a dotted
+    /// path.
+    Field {
+        field: String,
+        /// The query parameter the token is sent as. Without it, the token is the URL of the next
+        /// page.
+        query_parameter: Option<String>,
+    },
+}
+
+impl PaginationConfig {
+    /// Extracts the cursor of the next page from a response, if any.
+    pub fn next_cursor(&self, headers: &HeaderMap, body: &Bytes) -> Option<String> {
+        match &self.next_page {
+            NextPage::LinkHeader => next_link(headers),
+            NextPage::Field { field, .. } => json_field(body, field),
+        }
+    }
+
+    /// Builds the URL of the page described by a cursor.
+    pub fn page_url(&self, endpoint: &Url, cursor: &str) -> Result<Url, url::ParseError> {
+        match &self.next_page {
+            NextPage::Field {
+                query_parameter: Some(parameter),
+                ..
+            } => {
+                let mut url = endpoint.clone();
+                let pairs = endpoint
+                    .query_pairs()
+                    .filter(|(name, _)| name != parameter)
+                    .collect::<Vec<_>>();
+                url.query_pairs_mut()
+                    .clear()
+                    .extend_pairs(pairs)
+                    .append_pair(parameter, cursor);
+                Ok(url)
+            }
+            // The links are resolved against the endpoint, as they are often relative.
+            _ => endpoint.join(cursor),
+        }
+    }
+}
+
+/// Returns the target of the `rel="next"` link of the `Link` headers.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc8288>
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let mut parts = link.split(';');
+            let target = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+            parts
+                .filter_map(|param| param.split_once('='))
+                .any(|(name, value)| {
+                    name.trim().eq_ignore_ascii_case("rel")
+                        && value
+                            .trim()
+                            .trim_matches('"')
+                            .split_ascii_whitespace()
+                            .any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+                .then(|| target.to_owned())
+        })
+}
+
+/// Returns the string or number at a dotted path of a JSON document, ignoring empty values.
+fn json_field(body: &Bytes, path: &str) -> Option<String> {
+    let document = serde_json::from_slice::<serde_json::Value>(body).ok()?;
+    let value = path
+        .split('.')
+        .try_fold(&document, |value, segment| match value {
+            serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            value => value.get(segment),
+        })?;
+    match value {
+        serde_json::Value::String(token) if !token.is_empty() => Some(token.clone()),
+        serde_json::Value::Number(token) => Some(token.to_string()),
+        _ => None,
+    }
+}
+
+/// Persists the cursor of the next page and the number of its events already emitted.
+pub struct Checkpointer {
+    path: PathBuf,
+    cursor: Option<String>,
+    delivered: usize,
+}
+
+impl Checkpointer {
+    pub async fn new(data_dir: &Path) -> io::Result<Self> {
+        let path = data_dir.join(CHECKPOINT_FILE_NAME);
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+        let mut lines = content.lines();
+        let cursor = lines
+            .next()
+            .map(|cursor| cursor.trim().to_owned())
+            .filter(|cursor| !cursor.is_empty());
+        let delivered = lines
+            .next()
+            .and_then(|delivered| delivered.trim().parse().ok())
+            .unwrap_or(0);
+        Ok(Self {
+            path,
+            cursor,
+            delivered,
+        })
+    }
+
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// The number of events of the page at the cursor that were already emitted.
+    pub const fn delivered(&self) -> usize {
+        self.delivered
+    }
+
+    /// Updates the cursor and the number of its events already emitted, or clears the cursor when
+    /// it can't be used anymore.
+    pub async fn set(&mut self, cursor: Option<String>, delivered: usize) {
+        if cursor == self.cursor && delivered == self.delivered {
+            return;
+        }
+        if let Err(error) = self.write(cursor.as_deref(), delivered).await {
+            emit!(HttpClientCheckpointError {
+                error,
+                path: &self.path,
+            });
+        }
+        self.cursor = cursor;
+        self.delivered = delivered;
+    }
+
+    async fn write(&self, cursor: Option<&str>, delivered: usize) -> io::Result<()> {
+        match (cursor, delivered) {
+            (None, 0) => match tokio::fs::remove_file(&self.path).await {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+                _ => Ok(()),
+            },
+            // The checkpoint is replaced atomically so that a crash can't leave it truncated.
+            (cursor, delivered) => {
+                let tmp_path = self.path.with_extension("tmp");
+                let content = format!("{}\n{}\n", cursor.unwrap_or_default(), delivered);
+                tokio::fs::write(&tmp_path, content).await?;
+                tokio::fs::rename(&tmp_path, &self.path).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn config(toml: &str) -> PaginationConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn parses_link_headers() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            LINK,
+            HeaderValue::from_static(
+                r#"</items?page=1>; rel="prev", </items?page=3>; rel="next last""#,
+            ),
+        );
+        assert_eq!(next_link(&headers), Some("/items?page=3".to_owned()));
+
+        headers.clear();
+        headers.append(
+            LINK,
+            HeaderValue::from_static(r#"</items?page=1>; rel=prev"#),
+        );
+        assert_eq!(next_link(&headers), None);
+    }
+
+    #[test]
+    fn reads_json_fields() {
+        let body = Bytes::from_static(br#"{"meta": {"next": "abc", "pages": [1, 2]}, "data": []}"#);
+        assert_eq!(json_field(&body, "meta.next"), Some("abc".to_owned()));
+        assert_eq!(json_field(&body, "meta.pages.1"), Some("2".to_owned()));
+        assert_eq!(json_field(&body, "meta.missing"), None);
+        assert_eq!(json_field(&body, "data"), None);
+    }
+
+    #[test]
+    fn builds_page_urls() {
+        let endpoint = Url::parse("http://localhost/api/logs?limit=10&cursor=old").unwrap();
+
+        let field = config(
+            r#"
+                type = "field"
+                field = "next"
+                query_parameter = "cursor"
+            "#,
+        );
+        assert_eq!(
+            field.page_url(&endpoint, "a b").unwrap().as_str(),
+            "http://localhost/api/logs?limit=10&cursor=a+b"
+        );
+
+        let link = config(r#"type = "link_header""#);
+        assert_eq!(
+            link.page_url(&endpoint, "/api/logs?page=2")
+                .unwrap()
+                .as_str(),
+            "http://localhost/api/logs?page=2"
+        );
+        assert_eq!(
+            link.page_url(&endpoint, "https://example.com/logs")
+                .unwrap()
+                .as_str(),
+            "https://example.com/logs"
+        );
+    }
+
+    #[tokio::test]
+    async fn persists_the_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::new(dir.path()).await.unwrap();
+        assert_eq!(checkpointer.cursor(), None);
+        checkpointer.set(Some("abc".to_owned()), 0).await;
+        assert_eq!(
+            Checkpointer::new(dir.path()).await.unwrap().cursor(),
+            Some("abc")
+        );
+
+        checkpointer.set(Some("abc".to_owned()), 3).await;
+        let restored = Checkpointer::new(dir.path()).await.unwrap();
+        assert_eq!(restored.cursor(), Some("abc"));
+        assert_eq!(restored.delivered(), 3);
+
+        checkpointer.set(None, 2).await;
+        let restored = Checkpointer::new(dir.path()).await.unwrap();
+        assert_eq!(restored.cursor(), None);
+        assert_eq!(restored.delivered(), 2);
+
+        checkpointer.set(None, 0).await;
+        assert_eq!(Checkpointer::new(dir.path()).await.unwrap().cursor(), None);
+        assert!(!dir.path().join(CHECKPOINT_FILE_NAME).exists());
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use warp::{http::Response, Filter};
+
+use super::*;
+use crate::{
+    event::Value,
+    test_util::{collect_ready, next_addr},
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<HttpClientConfig>();
+}
+
+/// Serves `/items?page=<n>` with three pages, linking to the next page in the `Link` header and
+/// in the `next` field of the body.
+fn serve_pages(address: SocketAddr) {
+    let pages = warp::path!("items")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|query: HashMap<String, String>| {
+            let page = query
+                .get("page")
+                .and_then(|page| page.parse::<usize>().ok())
+                .unwrap_or(1);
+            let mut response = Response::builder();
+            let next = if page < 3 {
+                response =
+                    response.header("Link", format!(r#"</items?page={}>; rel="next""#, page + 1));
+                format!(r#""{}""#, page + 1)
+            } else {
+                "null".to_owned()
+            };
+            response
+                .body(format!(r#"{{"page": {}, "next": {}}}"#, page, next))
+                .unwrap()
+        });
+    tokio::spawn(warp::serve(pages).run(address));
+}
+
+async fn scrape(config: &str) -> Vec<Event> {
+    let config: HttpClientConfig = toml::from_str(config).unwrap();
+    let (tx, rx) = SourceSender::new_test();
+    let source = config
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+    tokio::spawn(source);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    collect_ready(rx).await
+}
+
+fn pages(events: &[Event]) -> Vec<Value> {
+    events
+        .iter()
+        .map(|event| event.as_log()["page"].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn decodes_responses() {
+    let address = next_addr();
+    serve_pages(address);
+
+    let events = scrape(&format!(
+        r#"
+            endpoint = "http://{}/items"
+            scrape_interval_secs = 3600
+            decoding.codec = "json"
+        "#,
+        address
+    ))
+    .await;
+
+    assert_eq!(pages(&events), vec![Value::from(1)]);
+    let log = events[0].as_log();
+    assert_eq!(log[log_schema().source_type_key()], "http_client".into());
+    assert!(log.get(log_schema().timestamp_key()).is_some());
+}
+
+#[tokio::test]
+async fn follows_link_headers() {
+    let address = next_addr();
+    serve_pages(address);
+
+    let events = scrape(&format!(
+        r#"
+            endpoint = "http://{}/items"
+            scrape_interval_secs = 3600
+            decoding.codec = "json"
+            data_dir = "{}"
+            pagination.type = "link_header"
+        "#,
+        address,
+        tempfile::tempdir().unwrap().into_path().display()
+    ))
+    .await;
+
+    assert_eq!(
+        pages(&events),
+        vec![Value::from(1), Value::from(2), Value::from(3)]
+    );
+}
+
+#[tokio::test]
+async fn resumes_from_the_persisted_cursor() {
+    let address = next_addr();
+    serve_pages(address);
+    let data_dir = tempfile::tempdir().unwrap();
+
+    let config = format!(
+        r#"
+            endpoint = "http://{}/items"
+            scrape_interval_secs = 3600
+            decoding.codec = "json"
+            data_dir = "{}"
+
+            [pagination]
+            type = "field"
+            field = "next"
+            query_parameter = "page"
+            max_pages = 1
+        "#,
+        address,
+        data_dir.path().display()
+    );
+
+    assert_eq!(pages(&scrape(&config).await), vec![Value::from(1)]);
+    assert_eq!(pages(&scrape(&config).await), vec![Value::from(2)]);
+}
+
+#[tokio::test]
+async fn resumes_from_the_last_page() {
+    let address = next_addr();
+    serve_pages(address);
+    let data_dir = tempfile::tempdir().unwrap();
+
+    let config = format!(
+        r#"
+            endpoint = "http://{}/items"
+            scrape_interval_secs = 3600
+            decoding.codec = "json"
+            data_dir = "{}"
+
+            [pagination]
+            type = "field"
+            field = "next"
+            query_parameter = "page"
+        "#,
+        address,
+        data_dir.path().display()
+    );
+
+    assert_eq!(
+        pages(&scrape(&config).await),
+        vec![Value::from(1), Value::from(2), Value::from(3)]
+    );
+    // The last page is requested again, but its events were already emitted.
+    assert!(scrape(&config).await.is_empty());
+}
+
+/// Serves `/feed` as a single page holding one more entry on every request.
+fn serve_growing_page(address: SocketAddr) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let feed = warp::path!("feed").map(move || {
+        let entries = requests.fetch_add(1, Ordering::SeqCst) + 1;
+        (1..=entries)
+            .map(|entry| format!(r#"{{"entry": {}}}"#, entry))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+    tokio::spawn(warp::serve(feed).run(address));
+}
+
+#[tokio::test]
+async fn emits_only_new_entries_of_the_last_page() {
+    let address = next_addr();
+    serve_growing_page(address);
+
+    let config: HttpClientConfig = toml::from_str(&format!(
+        r#"
+            endpoint = "http://{}/feed"
+            scrape_interval_secs = 1
+            framing.method = "newline_delimited"
+            decoding.codec = "json"
+            data_dir = "{}"
+
+            [pagination]
+            type = "field"
+            field = "next"
+        "#,
+        address,
+        tempfile::tempdir().unwrap().into_path().display()
+    ))
+    .unwrap();
+    let (tx, rx) = SourceSender::new_test();
+    let source = config
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .unwrap();
+    tokio::spawn(source);
+
+    // Two scrapes, one as the source starts and one a second later.
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    let entries = collect_ready(rx)
+        .await
+        .iter()
+        .map(|event| event.as_log()["entry"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(entries, vec![Value::from(1), Value::from(2)]);
+}
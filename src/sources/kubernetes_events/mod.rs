@@ -0,0 +1,710 @@
+//! The `kubernetes_events` source collects the `Event` objects of the Kubernetes API, which
+//! report what happens to the resources of a cluster, like `OOMKilled` containers or pods
+//! failing to be scheduled.
+//!
+//! The events are listed once, then watched from the resource version of the last change seen,
+//! which is checkpointed along with the updates already emitted so that the events aren't emitted
+//! again when Vector restarts.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::SeekFrom,
+    path::PathBuf,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{future, pin_mut, StreamExt};
+use k8s_openapi::api::{core::v1::ObjectReference, events::v1::Event as KubernetesEvent};
+use kube::{
+    api::{Api, ListParams, ObjectList, WatchEvent},
+    config::{self, KubeConfigOptions},
+    error::ErrorResponse,
+    Client, Config as ClientConfig,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        EventsReceived, KubernetesEventsCheckpointError, KubernetesEventsWatchError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const COMPONENT_ID: &str = "kubernetes_events";
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.txt";
+
+/// The delay before listing or watching the events again after a failure.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// The status code of the watches whose resource version is too old to resume from.
+const GONE: u16 = 410;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesEventsConfig {
+    /// The namespaces to collect the events of, all of them if empty.
+    namespaces: Vec<String>,
+    /// The field selector to filter the events with, like `type=Warning`.
+    field_selector: Option<String>,
+    /// The label selector to filter the events with.
+    label_selector: Option<String>,
+    /// Overrides the global `data_dir`, where the checkpoints are stored.
+    data_dir: Option<PathBuf>,
+    /// The kubeconfig file to use, instead of the in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<KubernetesEventsConfig>(COMPONENT_ID)
+}
+
+impl_generate_config_from_default!(KubernetesEventsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for KubernetesEventsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let client = self.client().await?;
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let list_params = ListParams {
+            field_selector: self.field_selector.clone(),
+            label_selector: self.label_selector.clone(),
+            bookmarks: true,
+            ..Default::default()
+        };
+
+        // Each namespace is watched on its own, with its own checkpoint.
+        let watches = if self.namespaces.is_empty() {
+            vec![(Api::all(client), data_dir.join(CHECKPOINT_FILENAME))]
+        } else {
+            self.namespaces
+                .iter()
+                .map(|namespace| {
+                    (
+                        Api::namespaced(client.clone(), namespace),
+                        data_dir.join(format!("checkpoint-{}.txt", namespace)),
+                    )
+                })
+                .collect()
+        };
+
+        let SourceContext { shutdown, out, .. } = cx;
+        Ok(Box::pin(async move {
+            let tasks = watches.into_iter().map(|(api, checkpoint_path)| {
+                tokio::spawn(watch_events(
+                    api,
+                    list_params.clone(),
+                    checkpoint_path,
+                    shutdown.clone(),
+                    out.clone(),
+                ))
+            });
+            for result in future::join_all(tasks).await {
+                if !matches!(result, Ok(Ok(()))) {
+                    return Err(());
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        COMPONENT_ID
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl KubernetesEventsConfig {
+    /// Builds the client from the configured kubeconfig, or the local kubeconfig, or the
+    /// in-cluster environment, like the `kubernetes_logs` source.
+    async fn client(&self) -> crate::Result<Client> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        Ok(Client::try_from(client_config)?)
+    }
+}
+
+async fn watch_events(
+    api: Api<KubernetesEvent>,
+    list_params: ListParams,
+    checkpoint_path: PathBuf,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut checkpointer = Checkpointer::new(checkpoint_path).await.map_err(|error| {
+        emit!(KubernetesEventsCheckpointError { error });
+    })?;
+    let mut state = match checkpointer.get().await {
+        Ok(Some((resource_version, emitted))) => WatchState::new(resource_version, emitted),
+        Ok(None) => WatchState::default(),
+        Err(error) => {
+            emit!(KubernetesEventsCheckpointError { error });
+            return Err(());
+        }
+    };
+
+    loop {
+        let version = match state.resource_version.clone() {
+            Some(version) => version,
+            None => {
+                let list = tokio::select! {
+                    list = api.list(&list_params) => list,
+                    _ = &mut shutdown => return Ok(()),
+                };
+                match list {
+                    Ok(list) => {
+                        let events = state.listed(list);
+                        if send(&mut out, events).await.is_err() {
+                            return Ok(());
+                        }
+                        checkpoint(&mut checkpointer, &state).await;
+                        state.resource_version.clone().unwrap_or_default()
+                    }
+                    Err(error) => {
+                        emit!(KubernetesEventsWatchError { error });
+                        if retry_delay(&mut shutdown).await.is_err() {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+                }
+            }
+        };
+
+        let stream = tokio::select! {
+            stream = api.watch(&list_params, &version) => stream,
+            _ = &mut shutdown => return Ok(()),
+        };
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(kube::Error::Api(error)) if error.code == GONE => {
+                state.resource_version = None;
+                continue;
+            }
+            Err(error) => {
+                emit!(KubernetesEventsWatchError { error });
+                if retry_delay(&mut shutdown).await.is_err() {
+                    return Ok(());
+                }
+                continue;
+            }
+        };
+        pin_mut!(stream);
+
+        loop {
+            let watch_event = tokio::select! {
+                watch_event = stream.next() => watch_event,
+                _ = &mut shutdown => return Ok(()),
+            };
+            match watch_event {
+                Some(Ok(watch_event)) => match state.watched(watch_event) {
+                    Ok(event) => {
+                        if send(&mut out, event.into_iter().collect()).await.is_err() {
+                            return Ok(());
+                        }
+                        checkpoint(&mut checkpointer, &state).await;
+                    }
+                    // An expired resource version is already unset, so the events are listed
+                    // again.
+                    Err(error) if error.code == GONE => break,
+                    Err(error) => {
+                        emit!(KubernetesEventsWatchError {
+                            error: kube::Error::Api(error)
+                        });
+                        if retry_delay(&mut shutdown).await.is_err() {
+                            return Ok(());
+                        }
+                        break;
+                    }
+                },
+                Some(Err(error)) => {
+                    emit!(KubernetesEventsWatchError { error });
+                    if retry_delay(&mut shutdown).await.is_err() {
+                        return Ok(());
+                    }
+                    break;
+                }
+                // The server ends the watches after a timeout, so it's resumed from the last
+                // resource version seen.
+                None => break,
+            }
+        }
+    }
+}
+
+async fn send(out: &mut SourceSender, events: Vec<KubernetesEvent>) -> Result<(), ()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let events = events
+        .into_iter()
+        .map(|event| Event::from(to_log_event(event)))
+        .collect::<Vec<_>>();
+    let count = events.len();
+    emit!(EventsReceived {
+        count,
+        byte_size: events.size_of(),
+    });
+    out.send_batch(events).await.map_err(|error| {
+        emit!(StreamClosedError { error, count });
+    })
+}
+
+async fn checkpoint(checkpointer: &mut Checkpointer, state: &WatchState) {
+    if let Some(resource_version) = &state.resource_version {
+        if let Err(error) = checkpointer.set(resource_version, &state.emitted).await {
+            emit!(KubernetesEventsCheckpointError { error });
+        }
+    }
+}
+
+async fn retry_delay(shutdown: &mut ShutdownSignal) -> Result<(), ()> {
+    tokio::select! {
+        _ = tokio::time::sleep(RETRY_DELAY) => Ok(()),
+        _ = shutdown => Err(()),
+    }
+}
+
+/// Tracks the resource version to resume watching the events from, and the updates of the
+/// events already emitted.
+#[derive(Debug, Default)]
+struct WatchState {
+    /// The resource version of the last change seen, the events are listed again when unset.
+    resource_version: Option<String>,
+    /// The resource version of the last update emitted of each event, by UID. It's checkpointed
+    /// too, so that the events listed again after a restart whose checkpointed resource version
+    /// is too old to resume from are only emitted if they weren't yet.
+    emitted: HashMap<String, String>,
+}
+
+impl WatchState {
+    fn new(resource_version: String, emitted: HashMap<String, String>) -> Self {
+        Self {
+            resource_version: Some(resource_version),
+            emitted,
+        }
+    }
+
+    /// Records an update of an event, returning whether it wasn't emitted yet.
+    fn record(&mut self, event: &KubernetesEvent) -> bool {
+        let (uid, resource_version) = match (&event.metadata.uid, &event.metadata.resource_version)
+        {
+            (Some(uid), Some(resource_version)) => (uid, resource_version),
+            _ => return true,
+        };
+        match self.emitted.get(uid) {
+            Some(emitted) if emitted == resource_version => false,
+            _ => {
+                self.emitted.insert(uid.clone(), resource_version.clone());
+                true
+            }
+        }
+    }
+
+    /// Handles a list of the events, returning the updates to emit.
+    fn listed(&mut self, list: ObjectList<KubernetesEvent>) -> Vec<KubernetesEvent> {
+        self.resource_version = list.metadata.resource_version;
+
+        // Forgets the events deleted while they weren't watched.
+        let uids = list
+            .items
+            .iter()
+            .filter_map(|event| event.metadata.uid.as_ref())
+            .collect::<HashSet<_>>();
+        self.emitted.retain(|uid, _| uids.contains(uid));
+
+        list.items
+            .into_iter()
+            .filter(|event| self.record(event))
+            .collect()
+    }
+
+    /// Handles a watch event, returning the update to emit, if any.
+    fn watched(
+        &mut self,
+        watch_event: WatchEvent<KubernetesEvent>,
+    ) -> Result<Option<KubernetesEvent>, ErrorResponse> {
+        match watch_event {
+            WatchEvent::Added(event) | WatchEvent::Modified(event) => {
+                self.resource_version = event.metadata.resource_version.clone();
+                Ok(self.record(&event).then(|| event))
+            }
+            WatchEvent::Deleted(event) => {
+                self.resource_version = event.metadata.resource_version.clone();
+                if let Some(uid) = &event.metadata.uid {
+                    self.emitted.remove(uid);
+                }
+                Ok(None)
+            }
+            WatchEvent::Bookmark(bookmark) => {
+                self.resource_version = Some(bookmark.metadata.resource_version);
+                Ok(None)
+            }
+            WatchEvent::Error(error) => {
+                if error.code == GONE {
+                    self.resource_version = None;
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+fn to_log_event(event: KubernetesEvent) -> LogEvent {
+    let KubernetesEvent {
+        action,
+        deprecated_count,
+        deprecated_first_timestamp,
+        deprecated_last_timestamp,
+        event_time,
+        metadata,
+        note,
+        reason,
+        regarding,
+        related,
+        reporting_controller,
+        reporting_instance,
+        series,
+        type_,
+        ..
+    } = event;
+
+    // The events reported with the `v1` API of the core group only have the deprecated fields.
+    let first_timestamp = event_time
+        .map(|time| time.0)
+        .or_else(|| deprecated_first_timestamp.map(|time| time.0));
+    let timestamp = series
+        .as_ref()
+        .map(|series| series.last_observed_time.0)
+        .or_else(|| deprecated_last_timestamp.map(|time| time.0))
+        .or(first_timestamp)
+        .or_else(|| metadata.creation_timestamp.map(|time| time.0))
+        .unwrap_or_else(Utc::now);
+    let count = series.map(|series| series.count).or(deprecated_count);
+
+    let mut log = LogEvent::default();
+    log.insert(log_schema().message_key(), note.unwrap_or_default());
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.insert(
+        log_schema().source_type_key(),
+        Bytes::from_static(COMPONENT_ID.as_bytes()),
+    );
+    insert(&mut log, "name", metadata.name);
+    insert(&mut log, "namespace", metadata.namespace);
+    insert(&mut log, "uid", metadata.uid);
+    insert(&mut log, "resource_version", metadata.resource_version);
+    insert(&mut log, "reason", reason);
+    insert(&mut log, "type", type_);
+    insert(&mut log, "action", action);
+    insert(&mut log, "count", count.map(i64::from));
+    insert(&mut log, "first_timestamp", first_timestamp);
+    insert(&mut log, "reporting_controller", reporting_controller);
+    insert(&mut log, "reporting_instance", reporting_instance);
+    insert(&mut log, "regarding", regarding.map(object_reference));
+    insert(&mut log, "related", related.map(object_reference));
+    log
+}
+
+fn object_reference(reference: ObjectReference) -> Value {
+    let fields = [
+        ("api_version", reference.api_version),
+        ("kind", reference.kind),
+        ("name", reference.name),
+        ("namespace", reference.namespace),
+        ("uid", reference.uid),
+        ("resource_version", reference.resource_version),
+        ("field_path", reference.field_path),
+    ];
+    Value::Object(
+        fields
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| (key.to_owned(), Value::from(value))))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+fn insert(log: &mut LogEvent, key: &str, value: Option<impl Into<Value>>) {
+    if let Some(value) = value {
+        log.insert(key, value.into());
+    }
+}
+
+/// Stores the resource version to resume watching the events from, followed by the UID and the
+/// resource version of the last update emitted of each event, one per line.
+struct Checkpointer {
+    file: File,
+}
+
+impl Checkpointer {
+    async fn new(filename: PathBuf) -> Result<Self, std::io::Error> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&filename)
+            .await?;
+        Ok(Self { file })
+    }
+
+    async fn set(
+        &mut self,
+        resource_version: &str,
+        emitted: &HashMap<String, String>,
+    ) -> Result<(), std::io::Error> {
+        let mut buf = format!("{}\n", resource_version);
+        for (uid, resource_version) in emitted {
+            buf.push_str(&format!("{} {}\n", uid, resource_version));
+        }
+        self.file.seek(SeekFrom::Start(0)).await?;
+        self.file.set_len(0).await?;
+        self.file.write_all(buf.as_bytes()).await
+    }
+
+    async fn get(&mut self) -> Result<Option<(String, HashMap<String, String>)>, std::io::Error> {
+        let mut buf = String::new();
+        self.file.seek(SeekFrom::Start(0)).await?;
+        self.file.read_to_string(&mut buf).await?;
+        let mut lines = buf.lines();
+        let resource_version = match lines.next().filter(|line| !line.is_empty()) {
+            Some(resource_version) => resource_version.to_owned(),
+            None => return Ok(None),
+        };
+        let emitted = lines
+            .filter_map(|line| line.split_once(' '))
+            .map(|(uid, resource_version)| (uid.to_owned(), resource_version.to_owned()))
+            .collect();
+        Ok(Some((resource_version, emitted)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::{
+        api::events::v1::EventSeries,
+        apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta, Time},
+    };
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesEventsConfig>();
+    }
+
+    fn event(uid: &str, resource_version: &str) -> KubernetesEvent {
+        KubernetesEvent {
+            metadata: ObjectMeta {
+                name: Some(format!("{}.16f1b7a4c2d3e5f0", uid)),
+                namespace: Some("default".to_owned()),
+                uid: Some(uid.to_owned()),
+                resource_version: Some(resource_version.to_owned()),
+                ..ObjectMeta::default()
+            },
+            ..KubernetesEvent::default()
+        }
+    }
+
+    fn list(resource_version: &str, items: Vec<KubernetesEvent>) -> ObjectList<KubernetesEvent> {
+        serde_json::from_value(json!({
+            "apiVersion": "events.k8s.io/v1",
+            "kind": "EventList",
+            "metadata": { "resourceVersion": resource_version },
+            "items": items,
+        }))
+        .unwrap()
+    }
+
+    fn uids(events: &[KubernetesEvent]) -> Vec<&str> {
+        events
+            .iter()
+            .filter_map(|event| event.metadata.uid.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn deduplicates_updates() {
+        let mut state = WatchState::default();
+
+        let listed = state.listed(list("10", vec![event("a", "1"), event("b", "2")]));
+        assert_eq!(uids(&listed), vec!["a", "b"]);
+        assert_eq!(state.resource_version.as_deref(), Some("10"));
+
+        let added = state.watched(WatchEvent::Added(event("c", "11"))).unwrap();
+        assert_eq!(added.and_then(|event| event.metadata.uid), Some("c".into()));
+        assert_eq!(state.resource_version.as_deref(), Some("11"));
+
+        // The same update of an event is only emitted once.
+        let modified = state
+            .watched(WatchEvent::Modified(event("a", "1")))
+            .unwrap();
+        assert!(modified.is_none());
+        let modified = state
+            .watched(WatchEvent::Modified(event("a", "12")))
+            .unwrap();
+        assert!(modified.is_some());
+
+        // Listing again only emits the updates which weren't emitted yet.
+        let listed = state.listed(list("13", vec![event("a", "12"), event("b", "13")]));
+        assert_eq!(uids(&listed), vec!["b"]);
+        assert!(!state.emitted.contains_key("c"));
+    }
+
+    #[test]
+    fn tracks_resource_version() {
+        let mut state = WatchState::new("10".into(), HashMap::new());
+
+        let bookmark = serde_json::from_value(json!({
+            "type": "BOOKMARK",
+            "object": {
+                "apiVersion": "events.k8s.io/v1",
+                "kind": "Event",
+                "metadata": { "resourceVersion": "20" },
+            },
+        }))
+        .unwrap();
+        assert!(state.watched(bookmark).unwrap().is_none());
+        assert_eq!(state.resource_version.as_deref(), Some("20"));
+
+        let deleted = state
+            .watched(WatchEvent::Deleted(event("a", "21")))
+            .unwrap();
+        assert!(deleted.is_none());
+        assert_eq!(state.resource_version.as_deref(), Some("21"));
+
+        let error = state
+            .watched(WatchEvent::Error(ErrorResponse {
+                status: "Failure".into(),
+                message: "too old resource version: 10 (21)".into(),
+                reason: "Expired".into(),
+                code: GONE,
+            }))
+            .unwrap_err();
+        assert_eq!(error.code, GONE);
+        assert_eq!(state.resource_version, None);
+    }
+
+    #[test]
+    fn skips_events_emitted_before_restart() {
+        // The checkpointed resource version expired, so the events are listed again but only
+        // the updates which weren't emitted before the restart are emitted.
+        let emitted = HashMap::from([
+            ("a".to_owned(), "1".to_owned()),
+            ("b".to_owned(), "2".to_owned()),
+        ]);
+        let mut state = WatchState::new("10".into(), emitted);
+        state.resource_version = None;
+
+        let listed = state.listed(list(
+            "30",
+            vec![event("a", "1"), event("b", "25"), event("c", "30")],
+        ));
+        assert_eq!(uids(&listed), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn converts_events() {
+        let timestamp = Utc::now();
+        let event = KubernetesEvent {
+            action: Some("Killing".into()),
+            event_time: Some(MicroTime(timestamp - chrono::Duration::minutes(1))),
+            note: Some("Container app failed liveness probe, will be restarted".into()),
+            reason: Some("Unhealthy".into()),
+            regarding: Some(ObjectReference {
+                api_version: Some("v1".into()),
+                kind: Some("Pod".into()),
+                name: Some("app-5d8f7c9b6-x2x7q".into()),
+                namespace: Some("default".into()),
+                field_path: Some("spec.containers{app}".into()),
+                ..ObjectReference::default()
+            }),
+            reporting_controller: Some("kubelet".into()),
+            series: Some(EventSeries {
+                count: 3,
+                last_observed_time: MicroTime(timestamp),
+            }),
+            type_: Some("Warning".into()),
+            ..event("a", "1")
+        };
+
+        let log = to_log_event(event);
+        assert_eq!(
+            log[log_schema().message_key()],
+            "Container app failed liveness probe, will be restarted".into()
+        );
+        assert_eq!(log[log_schema().timestamp_key()], timestamp.into());
+        assert_eq!(log[log_schema().source_type_key()], COMPONENT_ID.into());
+        assert_eq!(log["namespace"], "default".into());
+        assert_eq!(log["uid"], "a".into());
+        assert_eq!(log["resource_version"], "1".into());
+        assert_eq!(log["reason"], "Unhealthy".into());
+        assert_eq!(log["type"], "Warning".into());
+        assert_eq!(log["action"], "Killing".into());
+        assert_eq!(log["count"], 3.into());
+        assert_eq!(log["reporting_controller"], "kubelet".into());
+        assert_eq!(log["regarding.kind"], "Pod".into());
+        assert_eq!(log["regarding.field_path"], "spec.containers{app}".into());
+        assert!(log.get("regarding.uid").is_none());
+        assert!(log.get("related").is_none());
+
+        let event = KubernetesEvent {
+            deprecated_count: Some(2),
+            deprecated_last_timestamp: Some(Time(timestamp)),
+            ..event("b", "2")
+        };
+        let log = to_log_event(event);
+        assert_eq!(log[log_schema().timestamp_key()], timestamp.into());
+        assert_eq!(log["count"], 2.into());
+        assert_eq!(log[log_schema().message_key()], "".into());
+    }
+
+    #[tokio::test]
+    async fn checkpoints_resource_version() {
+        let path = temp_dir().join(CHECKPOINT_FILENAME);
+
+        let emitted = HashMap::from([("a".to_owned(), "1234".to_owned())]);
+        let mut checkpointer = Checkpointer::new(path.clone()).await.unwrap();
+        assert_eq!(checkpointer.get().await.unwrap(), None);
+        checkpointer.set("123456", &emitted).await.unwrap();
+        checkpointer.set("1235", &emitted).await.unwrap();
+
+        let mut checkpointer = Checkpointer::new(path).await.unwrap();
+        assert_eq!(
+            checkpointer.get().await.unwrap(),
+            Some(("1235".into(), emitted))
+        );
+    }
+}
@@ -14,7 +14,11 @@ use file_source::{
     ReadFrom,
 };
 use futures_util::Stream;
-use k8s_openapi::api::core::v1::{Namespace, Pod};
+use k8s_openapi::api::{
+    apps::v1::ReplicaSet,
+    batch::v1::Job,
+    core::v1::{Namespace, Node, Pod},
+};
 use kube::{
     api::{Api, ListParams},
     config::{self, KubeConfigOptions},
@@ -37,7 +41,7 @@ use crate::{
     internal_events::{
         BytesReceived, FileSourceInternalEventsEmitter, KubernetesLifecycleError,
         KubernetesLogsEventAnnotationError, KubernetesLogsEventNamespaceAnnotationError,
-        KubernetesLogsEventsReceived, StreamClosedError,
+        KubernetesLogsEventNodeAnnotationError, KubernetesLogsEventsReceived, StreamClosedError,
     },
     kubernetes::custom_reflector,
     shutdown::ShutdownSignal,
@@ -49,6 +53,7 @@ use crate::{
 mod k8s_paths_provider;
 mod lifecycle;
 mod namespace_metadata_annotator;
+mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
@@ -60,7 +65,8 @@ use futures::{future::FutureExt, stream::StreamExt};
 use k8s_paths_provider::K8sPathsProvider;
 use lifecycle::Lifecycle;
 use namespace_metadata_annotator::NamespaceMetadataAnnotator;
-use pod_metadata_annotator::PodMetadataAnnotator;
+use node_metadata_annotator::NodeMetadataAnnotator;
+use pod_metadata_annotator::{OwnerStores, PodMetadataAnnotator};
 
 /// The key we use for `file` field.
 const FILE_KEY: &str = "file";
@@ -98,6 +104,18 @@ pub struct Config {
     /// Specifies the field names for Namespace metadata annotation.
     namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec,
 
+    /// Annotate the events with the metadata of the `Node` Vector runs at,
+    /// which requires the permissions to watch it. Disabled by default.
+    annotate_node_metadata: bool,
+
+    /// Specifies the field names for Node metadata annotation.
+    node_annotation_fields: node_metadata_annotator::FieldsSpec,
+
+    /// Resolve the workloads of the `Pod`s owned by `ReplicaSet`s and `Job`s
+    /// to the `Deployment`s and `CronJob`s owning these, which requires
+    /// watching them.
+    resolve_workload_owners: bool,
+
     /// A list of glob patterns to exclude from reading the files.
     exclude_paths_glob_patterns: Vec<PathBuf>,
 
@@ -165,6 +183,9 @@ impl Default for Config {
             data_dir: None,
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
+            annotate_node_metadata: false,
+            node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
+            resolve_workload_owners: false,
             exclude_paths_glob_patterns: default_path_exclusion(),
             max_read_bytes: default_max_read_bytes(),
             max_line_bytes: default_max_line_bytes(),
@@ -212,6 +233,10 @@ struct Source {
     auto_partial_merge: bool,
     pod_fields_spec: pod_metadata_annotator::FieldsSpec,
     namespace_fields_spec: namespace_metadata_annotator::FieldsSpec,
+    annotate_node_metadata: bool,
+    node_fields_spec: node_metadata_annotator::FieldsSpec,
+    resolve_workload_owners: bool,
+    self_node_name: String,
     field_selector: String,
     label_selector: String,
     exclude_paths: Vec<glob::Pattern>,
@@ -230,7 +255,8 @@ impl Source {
         globals: &GlobalOptions,
         key: &ComponentKey,
     ) -> crate::Result<Self> {
-        let field_selector = prepare_field_selector(config)?;
+        let self_node_name = prepare_self_node_name(config)?;
+        let field_selector = prepare_field_selector(config, &self_node_name);
         let label_selector = prepare_label_selector(config);
 
         // If the user passed a custom Kubeconfig use it, otherwise
@@ -271,6 +297,10 @@ impl Source {
             auto_partial_merge: config.auto_partial_merge,
             pod_fields_spec: config.pod_annotation_fields.clone(),
             namespace_fields_spec: config.namespace_annotation_fields.clone(),
+            annotate_node_metadata: config.annotate_node_metadata,
+            node_fields_spec: config.node_annotation_fields.clone(),
+            resolve_workload_owners: config.resolve_workload_owners,
+            self_node_name,
             field_selector,
             label_selector,
             exclude_paths,
@@ -295,6 +325,10 @@ impl Source {
             auto_partial_merge,
             pod_fields_spec,
             namespace_fields_spec,
+            annotate_node_metadata,
+            node_fields_spec,
+            resolve_workload_owners,
+            self_node_name,
             field_selector,
             label_selector,
             exclude_paths,
@@ -323,16 +357,68 @@ impl Source {
 
         // -----------------------------------------------------------------
 
-        let namespaces = Api::<Namespace>::all(client);
+        let namespaces = Api::<Namespace>::all(client.clone());
         let ns_watcher = watcher(namespaces, ListParams::default());
         let ns_store_w = reflector::store::Writer::default();
         let ns_state = ns_store_w.as_reader();
 
         tokio::spawn(custom_reflector(ns_store_w, ns_watcher, delay_deletion));
 
+        // -----------------------------------------------------------------
+
+        // Only the `Node` Vector runs at is watched, since the `Pod`s are
+        // filtered to those running at this `Node`.
+        let node_annotator = if annotate_node_metadata {
+            let nodes = Api::<Node>::all(client.clone());
+            let node_watcher = watcher(
+                nodes,
+                ListParams {
+                    field_selector: Some(format!("metadata.name={}", self_node_name)),
+                    ..Default::default()
+                },
+            );
+            let node_store_w = reflector::store::Writer::default();
+            let node_state = node_store_w.as_reader();
+
+            tokio::spawn(custom_reflector(node_store_w, node_watcher, delay_deletion));
+
+            Some(NodeMetadataAnnotator::new(node_state, node_fields_spec))
+        } else {
+            None
+        };
+
+        // -----------------------------------------------------------------
+
+        let owner_stores = if resolve_workload_owners {
+            let replicasets = Api::<ReplicaSet>::all(client.clone());
+            let replicaset_watcher = watcher(replicasets, ListParams::default());
+            let replicaset_store_w = reflector::store::Writer::default();
+            let replicaset_state = replicaset_store_w.as_reader();
+
+            tokio::spawn(custom_reflector(
+                replicaset_store_w,
+                replicaset_watcher,
+                delay_deletion,
+            ));
+
+            let jobs = Api::<Job>::all(client);
+            let job_watcher = watcher(jobs, ListParams::default());
+            let job_store_w = reflector::store::Writer::default();
+            let job_state = job_store_w.as_reader();
+
+            tokio::spawn(custom_reflector(job_store_w, job_watcher, delay_deletion));
+
+            Some(OwnerStores {
+                replicasets: replicaset_state,
+                jobs: job_state,
+            })
+        } else {
+            None
+        };
+
         let paths_provider =
             K8sPathsProvider::new(pod_state.clone(), ns_state.clone(), exclude_paths);
-        let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec);
+        let annotator = PodMetadataAnnotator::new(pod_state, owner_stores, pod_fields_spec);
         let ns_annotator = NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec);
 
         // TODO: maybe more of the parameters have to be configurable.
@@ -362,6 +448,8 @@ impl Source {
             max_line_bytes,
             // Delimiter bytes that is used to read the file line-by-line
             line_delimiter: Bytes::from("\n"),
+            // The lines are split by the `line_delimiter`.
+            frame_decoder: None,
             // The directory where to keep the checkpoints.
             data_dir,
             // This value specifies not exactly the globbing, but interval
@@ -431,6 +519,14 @@ impl Source {
                         emit!(KubernetesLogsEventNamespaceAnnotationError { event: &event });
                     }
                 }
+
+                if let Some(node_annotator) = &node_annotator {
+                    let node_info = node_annotator.annotate(&mut event, &self_node_name);
+
+                    if node_info.is_none() {
+                        emit!(KubernetesLogsEventNodeAnnotationError { event: &event });
+                    }
+                }
             }
 
             checkpoints.update(line.file_id, line.offset);
@@ -571,9 +667,9 @@ fn prepare_exclude_paths(config: &Config) -> crate::Result<Vec<glob::Pattern>> {
     Ok(exclude_paths)
 }
 
-// This function constructs the effective field selector to use, based on
-// the specified configuration.
-fn prepare_field_selector(config: &Config) -> crate::Result<String> {
+// This function obtains the name of the `Node` Vector runs at, based on the
+// specified configuration.
+fn prepare_self_node_name(config: &Config) -> crate::Result<String> {
     let self_node_name = if config.self_node_name.is_empty()
         || config.self_node_name == default_self_node_name_env_template()
     {
@@ -591,16 +687,19 @@ fn prepare_field_selector(config: &Config) -> crate::Result<String> {
         ?self_node_name
     );
 
+    Ok(self_node_name)
+}
+
+// This function constructs the effective field selector to use, based on
+// the specified configuration.
+fn prepare_field_selector(config: &Config, self_node_name: &str) -> String {
     let field_selector = format!("spec.nodeName={}", self_node_name);
 
     if config.extra_field_selector.is_empty() {
-        return Ok(field_selector);
+        return field_selector;
     }
 
-    Ok(format!(
-        "{},{}",
-        field_selector, config.extra_field_selector
-    ))
+    format!("{},{}", field_selector, config.extra_field_selector)
 }
 
 // This function constructs the effective label selector to use, based on
@@ -697,7 +796,7 @@ mod tests {
         ];
 
         for (input, expected) in cases {
-            let output = super::prepare_field_selector(&input).unwrap();
+            let output = super::prepare_field_selector(&input, &input.self_node_name);
             assert_eq!(expected, output, "expected left, actual right");
         }
     }
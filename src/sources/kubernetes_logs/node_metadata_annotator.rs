@@ -0,0 +1,203 @@
+//! Annotates events with node metadata.
+
+#![deny(missing_docs)]
+
+use k8s_openapi::{api::core::v1::Node, apimachinery::pkg::apis::meta::v1::ObjectMeta};
+use kube::runtime::reflector::{store::Store, ObjectRef};
+use serde::{Deserialize, Serialize};
+
+use crate::event::{Event, LogEvent};
+use lookup::lookup_v2::{parse_path, OwnedSegment};
+
+/// The field names of the Node metadata. The labels or annotations aren't
+/// annotated when their field name is empty, which is the default for the
+/// annotations, since they may be large and are rarely needed on every event.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct FieldsSpec {
+    pub node_labels: String,
+    pub node_annotations: String,
+}
+
+impl Default for FieldsSpec {
+    fn default() -> Self {
+        Self {
+            node_labels: "kubernetes.node_labels".to_owned(),
+            node_annotations: String::new(),
+        }
+    }
+}
+
+/// Annotate the event with node metadata.
+pub struct NodeMetadataAnnotator {
+    node_state_reader: Store<Node>,
+    fields_spec: FieldsSpec,
+}
+
+impl NodeMetadataAnnotator {
+    /// Create a new [`NodeMetadataAnnotator`].
+    pub const fn new(node_state_reader: Store<Node>, fields_spec: FieldsSpec) -> Self {
+        Self {
+            node_state_reader,
+            fields_spec,
+        }
+    }
+}
+
+impl NodeMetadataAnnotator {
+    /// Annotates an event with the information from the [`Node::metadata`].
+    /// The node is the one Vector runs at, where the pods of the events run.
+    pub fn annotate(&self, event: &mut Event, node: &str) -> Option<()> {
+        let log = event.as_mut_log();
+        let obj = ObjectRef::<Node>::new(node);
+        let resource = self.node_state_reader.get(&obj)?;
+        let node: &Node = resource.as_ref();
+
+        annotate_from_metadata(log, &self.fields_spec, &node.metadata);
+        Some(())
+    }
+}
+
+fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
+    if let Some(labels) = metadata
+        .labels
+        .as_ref()
+        .filter(|_| !fields_spec.node_labels.is_empty())
+    {
+        // Calculate and cache the prefix path.
+        let prefix_path = parse_path(&fields_spec.node_labels);
+        for (key, val) in labels.iter() {
+            let mut path = prefix_path.clone().segments;
+            path.push(OwnedSegment::Field(key.clone()));
+            log.insert(&path, val.to_owned());
+        }
+    }
+
+    if let Some(annotations) = metadata
+        .annotations
+        .as_ref()
+        .filter(|_| !fields_spec.node_annotations.is_empty())
+    {
+        let prefix_path = parse_path(&fields_spec.node_annotations);
+        for (key, val) in annotations.iter() {
+            let mut path = prefix_path.clone().segments;
+            path.push(OwnedSegment::Field(key.clone()));
+            log.insert(&path, val.to_owned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::assert_event_data_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_annotate_from_metadata() {
+        let cases = vec![
+            (
+                FieldsSpec::default(),
+                ObjectMeta::default(),
+                LogEvent::default(),
+            ),
+            (
+                FieldsSpec::default(),
+                ObjectMeta {
+                    name: Some("sandbox0-name".to_owned()),
+                    uid: Some("sandbox0-uid".to_owned()),
+                    labels: Some(
+                        vec![
+                            (
+                                "topology.kubernetes.io/zone".to_owned(),
+                                "us-east-1a".to_owned(),
+                            ),
+                            (
+                                "node.kubernetes.io/instance-type".to_owned(),
+                                "m5.large".to_owned(),
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    annotations: Some(
+                        vec![("sandbox0-annotation0".to_owned(), "val0".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert(
+                        r#"kubernetes.node_labels."topology.kubernetes.io/zone""#,
+                        "us-east-1a",
+                    );
+                    log.insert(
+                        r#"kubernetes.node_labels."node.kubernetes.io/instance-type""#,
+                        "m5.large",
+                    );
+                    log
+                },
+            ),
+            (
+                FieldsSpec {
+                    node_labels: "labels".to_owned(),
+                    node_annotations: "annotations".to_owned(),
+                },
+                ObjectMeta {
+                    name: Some("sandbox0-name".to_owned()),
+                    uid: Some("sandbox0-uid".to_owned()),
+                    labels: Some(
+                        vec![("sandbox0-label0".to_owned(), "val0".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    annotations: Some(
+                        vec![("sandbox0-annotation0".to_owned(), "val1".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert("labels.\"sandbox0-label0\"", "val0");
+                    log.insert("annotations.\"sandbox0-annotation0\"", "val1");
+                    log
+                },
+            ),
+            (
+                FieldsSpec {
+                    node_labels: "".to_owned(),
+                    node_annotations: "annotations".to_owned(),
+                },
+                ObjectMeta {
+                    name: Some("sandbox0-name".to_owned()),
+                    labels: Some(
+                        vec![("sandbox0-label0".to_owned(), "val0".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    annotations: Some(
+                        vec![("sandbox0-annotation0".to_owned(), "val1".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert("annotations.\"sandbox0-annotation0\"", "val1");
+                    log
+                },
+            ),
+        ];
+
+        for (fields_spec, metadata, expected) in cases.into_iter() {
+            let mut log = LogEvent::default();
+            annotate_from_metadata(&mut log, &fields_spec, &metadata);
+            assert_event_data_eq!(log, expected);
+        }
+    }
+}
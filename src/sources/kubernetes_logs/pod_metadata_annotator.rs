@@ -3,8 +3,12 @@
 #![deny(missing_docs)]
 
 use k8s_openapi::{
-    api::core::v1::{Container, ContainerStatus, Pod, PodSpec, PodStatus},
-    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    api::{
+        apps::v1::ReplicaSet,
+        batch::v1::Job,
+        core::v1::{Container, ContainerStatus, Pod, PodSpec, PodStatus},
+    },
+    apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference},
 };
 use kube::runtime::reflector::{store::Store, ObjectRef};
 use serde::{Deserialize, Serialize};
@@ -25,6 +29,8 @@ pub struct FieldsSpec {
     pub pod_annotations: String,
     pub pod_node_name: String,
     pub pod_owner: String,
+    pub workload_kind: String,
+    pub workload_name: String,
     pub container_name: String,
     pub container_id: String,
     pub container_image: String,
@@ -42,6 +48,8 @@ impl Default for FieldsSpec {
             pod_annotations: "kubernetes.pod_annotations".to_owned(),
             pod_node_name: "kubernetes.pod_node_name".to_owned(),
             pod_owner: "kubernetes.pod_owner".to_owned(),
+            workload_kind: "kubernetes.workload_kind".to_owned(),
+            workload_name: "kubernetes.workload_name".to_owned(),
             container_name: "kubernetes.container_name".to_owned(),
             container_id: "kubernetes.container_id".to_owned(),
             container_image: "kubernetes.container_image".to_owned(),
@@ -49,17 +57,32 @@ impl Default for FieldsSpec {
     }
 }
 
+/// The stores of the controllers which own the pods through an intermediate
+/// object, used to resolve the workloads of the pods.
+pub struct OwnerStores {
+    /// The `ReplicaSet`s, owned by `Deployment`s.
+    pub replicasets: Store<ReplicaSet>,
+    /// The `Job`s, owned by `CronJob`s.
+    pub jobs: Store<Job>,
+}
+
 /// Annotate the event with pod metadata.
 pub struct PodMetadataAnnotator {
     pods_state_reader: Store<Pod>,
+    owner_stores: Option<OwnerStores>,
     fields_spec: FieldsSpec,
 }
 
 impl PodMetadataAnnotator {
     /// Create a new [`PodMetadataAnnotator`].
-    pub const fn new(pods_state_reader: Store<Pod>, fields_spec: FieldsSpec) -> Self {
+    pub const fn new(
+        pods_state_reader: Store<Pod>,
+        owner_stores: Option<OwnerStores>,
+        fields_spec: FieldsSpec,
+    ) -> Self {
         Self {
             pods_state_reader,
+            owner_stores,
             fields_spec,
         }
     }
@@ -78,6 +101,12 @@ impl PodMetadataAnnotator {
 
         annotate_from_file_info(log, &self.fields_spec, &file_info);
         annotate_from_metadata(log, &self.fields_spec, &pod.metadata);
+        annotate_workload(
+            log,
+            &self.fields_spec,
+            &pod.metadata,
+            self.owner_stores.as_ref(),
+        );
 
         let container;
         if let Some(ref pod_spec) = pod.spec {
@@ -158,6 +187,52 @@ fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata
     }
 }
 
+/// Annotates the event with the workload of the pod, which is the controller
+/// owning the pod, or the controller owning this one when it's a `ReplicaSet`
+/// or a `Job`, provided the owner stores are available.
+fn annotate_workload(
+    log: &mut LogEvent,
+    fields_spec: &FieldsSpec,
+    metadata: &ObjectMeta,
+    owner_stores: Option<&OwnerStores>,
+) {
+    let owner = match controller(&metadata.owner_references) {
+        Some(owner) => owner,
+        None => return,
+    };
+
+    let parent = owner_stores.and_then(|stores| {
+        let namespace = metadata.namespace.as_deref()?;
+        match (owner.api_version.as_str(), owner.kind.as_str()) {
+            ("apps/v1", "ReplicaSet") => {
+                let obj = ObjectRef::<ReplicaSet>::new(&owner.name).within(namespace);
+                let replicaset = stores.replicasets.get(&obj)?;
+                controller(&replicaset.metadata.owner_references).cloned()
+            }
+            ("batch/v1", "Job") => {
+                let obj = ObjectRef::<Job>::new(&owner.name).within(namespace);
+                let job = stores.jobs.get(&obj)?;
+                controller(&job.metadata.owner_references).cloned()
+            }
+            _ => None,
+        }
+    });
+    let workload = parent.as_ref().unwrap_or(owner);
+
+    log.insert(fields_spec.workload_kind.as_str(), workload.kind.to_owned());
+    log.insert(fields_spec.workload_name.as_str(), workload.name.to_owned());
+}
+
+/// Returns the managing controller of an object, or its first owner if none is
+/// marked as such.
+fn controller(owner_references: &Option<Vec<OwnerReference>>) -> Option<&OwnerReference> {
+    let owner_references = owner_references.as_ref()?;
+    owner_references
+        .iter()
+        .find(|owner| owner.controller == Some(true))
+        .or_else(|| owner_references.first())
+}
+
 fn annotate_from_pod_spec(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_spec: &PodSpec) {
     for (key, val) in [(&fields_spec.pod_node_name, &pod_spec.node_name)].iter() {
         if let Some(val) = val {
@@ -207,6 +282,7 @@ fn annotate_from_container(log: &mut LogEvent, fields_spec: &FieldsSpec, contain
 #[cfg(test)]
 mod tests {
     use k8s_openapi::api::core::v1::PodIP;
+    use kube::runtime::{reflector::store::Writer, watcher};
     use vector_common::assert_event_data_eq;
 
     use super::*;
@@ -343,6 +419,106 @@ mod tests {
         }
     }
 
+    fn owner(api_version: &str, kind: &str, name: &str) -> OwnerReference {
+        OwnerReference {
+            api_version: api_version.to_owned(),
+            kind: kind.to_owned(),
+            name: name.to_owned(),
+            controller: Some(true),
+            ..OwnerReference::default()
+        }
+    }
+
+    fn owned_metadata(name: &str, owner: OwnerReference) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_owned()),
+            namespace: Some("sandbox0-ns".to_owned()),
+            owner_references: Some(vec![owner]),
+            ..ObjectMeta::default()
+        }
+    }
+
+    fn owner_stores() -> OwnerStores {
+        let mut replicasets = Writer::default();
+        replicasets.apply_watcher_event(&watcher::Event::Restarted(vec![ReplicaSet {
+            metadata: owned_metadata(
+                "sandbox0-5d8f7c9b6",
+                owner("apps/v1", "Deployment", "sandbox0"),
+            ),
+            ..ReplicaSet::default()
+        }]));
+        let mut jobs = Writer::default();
+        jobs.apply_watcher_event(&watcher::Event::Restarted(vec![Job {
+            metadata: owned_metadata(
+                "sandbox1-27573780",
+                owner("batch/v1", "CronJob", "sandbox1"),
+            ),
+            ..Job::default()
+        }]));
+        OwnerStores {
+            replicasets: replicasets.as_reader(),
+            jobs: jobs.as_reader(),
+        }
+    }
+
+    #[test]
+    fn test_annotate_workload() {
+        let owner_stores = owner_stores();
+        let cases = vec![
+            (ObjectMeta::default(), Some(&owner_stores), None),
+            (
+                owned_metadata(
+                    "sandbox0-5d8f7c9b6-x2x7q",
+                    owner("apps/v1", "ReplicaSet", "sandbox0-5d8f7c9b6"),
+                ),
+                Some(&owner_stores),
+                Some(("Deployment", "sandbox0")),
+            ),
+            (
+                owned_metadata(
+                    "sandbox1-27573780-p8v4k",
+                    owner("batch/v1", "Job", "sandbox1-27573780"),
+                ),
+                Some(&owner_stores),
+                Some(("CronJob", "sandbox1")),
+            ),
+            (
+                owned_metadata("sandbox2-0", owner("apps/v1", "StatefulSet", "sandbox2")),
+                Some(&owner_stores),
+                Some(("StatefulSet", "sandbox2")),
+            ),
+            // The owner is the workload when it's unknown, or without stores.
+            (
+                owned_metadata(
+                    "sandbox3-7c9b6d8f5-k2l9m",
+                    owner("apps/v1", "ReplicaSet", "sandbox3-7c9b6d8f5"),
+                ),
+                Some(&owner_stores),
+                Some(("ReplicaSet", "sandbox3-7c9b6d8f5")),
+            ),
+            (
+                owned_metadata(
+                    "sandbox0-5d8f7c9b6-x2x7q",
+                    owner("apps/v1", "ReplicaSet", "sandbox0-5d8f7c9b6"),
+                ),
+                None,
+                Some(("ReplicaSet", "sandbox0-5d8f7c9b6")),
+            ),
+        ];
+
+        for (metadata, owner_stores, expected) in cases.into_iter() {
+            let mut log = LogEvent::default();
+            annotate_workload(&mut log, &FieldsSpec::default(), &metadata, owner_stores);
+
+            let mut expected_log = LogEvent::default();
+            if let Some((kind, name)) = expected {
+                expected_log.insert("kubernetes.workload_kind", kind);
+                expected_log.insert("kubernetes.workload_name", name);
+            }
+            assert_event_data_eq!(log, expected_log);
+        }
+    }
+
     #[test]
     fn test_annotate_from_file_info() {
         let cases = vec![(
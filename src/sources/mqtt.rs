@@ -0,0 +1,419 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use vector_core::ByteSizeOf;
+
+use super::util::finalizer::OrderedFinalizer;
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig,
+        SourceContext, SourceDescription,
+    },
+    event::{BatchNotifier, Event},
+    internal_events::{
+        BytesReceived, MqttAckError, MqttConnectionError, MqttEventsReceived, MqttSubscribeError,
+        StreamClosedError,
+    },
+    mqtt::{
+        MqttClient, MqttConfigError, MqttConnectionConfig, MqttEvent, MqttEventLoop, MqttMessage,
+        MqttQos,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// The delay before polling the event loop again after a connection error, which reconnects.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("MQTT Config Error: {}", source))]
+    Config { source: MqttConfigError },
+    #[snafu(display("At least one topic is required"))]
+    NoTopics,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct MqttSourceConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    /// The topics to subscribe to, which may contain the `+` and `#` wildcards.
+    topics: Vec<String>,
+    #[serde(default)]
+    qos: MqttQos,
+    /// How long MQTT 5 brokers keep the session once disconnected.
+    #[serde(default = "default_session_expiry_secs")]
+    session_expiry_secs: u32,
+    #[serde(default = "default_topic_key")]
+    topic_key: String,
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_session_expiry_secs() -> u32 {
+    24 * 60 * 60
+}
+
+fn default_topic_key() -> String {
+    "topic".to_owned()
+}
+
+inventory::submit! {
+    SourceDescription::new::<MqttSourceConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            port = 1883
+            topics = ["vector/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics.into());
+        }
+        let acknowledgements =
+            cx.do_acknowledgements(&self.acknowledgements) && self.qos == MqttQos::AtLeastOnce;
+        // The session is kept by the broker so that the messages published while disconnected
+        // and the unacknowledged ones are delivered once reconnected.
+        let (client, eventloop) = self
+            .connection
+            .build(
+                &format!("vector-{}", cx.key.id()),
+                Some(self.session_expiry_secs),
+                acknowledgements,
+            )
+            .context(ConfigSnafu)?;
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build();
+
+        Ok(Box::pin(mqtt_source(
+            client,
+            eventloop,
+            self.topics.clone(),
+            self.qos,
+            self.topic_key.clone(),
+            decoder,
+            cx.shutdown,
+            cx.out,
+            acknowledgements,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+async fn mqtt_source(
+    client: MqttClient,
+    mut eventloop: MqttEventLoop,
+    topics: Vec<String>,
+    qos: MqttQos,
+    topic_key: String,
+    decoder: Decoder,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let shutdown = shutdown.shared();
+    // MQTT requires the messages to be acknowledged in the order they were received.
+    let finalizer = acknowledgements.then(|| {
+        let client = client.clone();
+        OrderedFinalizer::new(shutdown.clone(), move |message: MqttMessage| {
+            let client = client.clone();
+            async move {
+                if let Err(error) = client.ack(&message).await {
+                    emit!(MqttAckError { error });
+                }
+            }
+        })
+    });
+
+    loop {
+        let event = tokio::select! {
+            _ = shutdown.clone() => break,
+            event = eventloop.poll() => event,
+        };
+
+        match event {
+            Ok(MqttEvent::Connected) => {
+                // The subscriptions are lost along with the session when the broker doesn't keep
+                // it, so they are renewed on every connection.
+                for topic in &topics {
+                    if let Err(error) = client.try_subscribe(topic, qos) {
+                        emit!(MqttSubscribeError { error, topic });
+                    }
+                }
+            }
+            Ok(MqttEvent::Message(message)) => {
+                emit!(BytesReceived {
+                    byte_size: message.payload.len(),
+                    protocol: "tcp",
+                });
+                let events = decode_message(&message, &decoder, &topic_key).await;
+                let count = events.len();
+
+                match &finalizer {
+                    Some(finalizer) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        let events = events
+                            .into_iter()
+                            .map(|event| event.with_batch_notifier(&batch));
+                        if let Err(error) = out.send_batch(events).await {
+                            emit!(StreamClosedError { error, count });
+                            return Err(());
+                        }
+                        finalizer.add(message, receiver);
+                    }
+                    None => {
+                        if let Err(error) = out.send_batch(events).await {
+                            emit!(StreamClosedError { error, count });
+                            return Err(());
+                        }
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(error) => {
+                emit!(MqttConnectionError { error });
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn decode_message(message: &MqttMessage, decoder: &Decoder, topic_key: &str) -> Vec<Event> {
+    let mut stream = FramedRead::new(message.payload.as_ref(), decoder.clone());
+    let mut events = Vec::new();
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((decoded, _byte_size)) => events.extend(decoded),
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    emit!(MqttEventsReceived {
+        byte_size: events.size_of(),
+        count: events.len(),
+        topic: &message.topic,
+    });
+
+    let now = Utc::now();
+    for event in &mut events {
+        if let Event::Log(log) = event {
+            log.try_insert(log_schema().source_type_key(), Bytes::from("mqtt"));
+            log.try_insert(log_schema().timestamp_key(), now);
+            log.insert(topic_key, message.topic.clone());
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[tokio::test]
+    async fn requires_topics() {
+        let config: MqttSourceConfig = toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = []"#,
+        )
+        .unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use stream_cancel::{Trigger, Tripwire};
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{collect_n, random_string, trace_init},
+    };
+
+    fn mqtt_host() -> String {
+        std::env::var("MQTT_HOST").unwrap_or_else(|_| "127.0.0.1".to_owned())
+    }
+
+    fn make_config(topic: &str, client_id: &str, protocol: &str) -> MqttSourceConfig {
+        toml::from_str(&format!(
+            r#"
+            host = "{}"
+            client_id = "{}"
+            protocol = "{}"
+            topics = ["{}/+"]"#,
+            mqtt_host(),
+            client_id,
+            protocol,
+            topic
+        ))
+        .unwrap()
+    }
+
+    /// Publishes the messages with QoS 1 once the source had the time to subscribe.
+    async fn publish(topic: &str, messages: &[&str]) {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let config: MqttConnectionConfig =
+            toml::from_str(&format!(r#"host = "{}""#, mqtt_host())).unwrap();
+        let (client, mut eventloop) = config
+            .build(&format!("publisher-{}", random_string(10)), None, false)
+            .unwrap();
+        for message in messages {
+            client
+                .publish(
+                    topic.to_owned(),
+                    MqttQos::AtLeastOnce,
+                    false,
+                    Bytes::from(message.to_string()),
+                )
+                .await
+                .unwrap();
+        }
+        let mut acknowledged = 0;
+        while acknowledged < messages.len() {
+            if let MqttEvent::Acknowledged(_) = eventloop.poll().await.unwrap() {
+                acknowledged += 1;
+            }
+        }
+    }
+
+    fn spawn_source(
+        config: &MqttSourceConfig,
+        status: EventStatus,
+    ) -> (
+        impl futures::Stream<Item = Event> + Unpin,
+        Trigger,
+        Tripwire,
+    ) {
+        let (client, eventloop) = config
+            .connection
+            .build("unused", Some(config.session_expiry_secs), true)
+            .unwrap();
+        let (trigger, shutdown, done) = ShutdownSignal::new_wired();
+        let (tx, rx) = SourceSender::new_test_finalize(status);
+        tokio::spawn(mqtt_source(
+            client,
+            eventloop,
+            config.topics.clone(),
+            config.qos,
+            config.topic_key.clone(),
+            DecodingConfig::new(config.framing.clone(), config.decoding.clone()).build(),
+            shutdown,
+            tx,
+            true,
+        ));
+        (rx, trigger, done)
+    }
+
+    async fn consumes_messages(protocol: &str) {
+        trace_init();
+        let topic = format!("vector/{}", random_string(10));
+        let config = make_config(&topic, &random_string(10), protocol);
+
+        let (rx, trigger, done) = spawn_source(&config, EventStatus::Delivered);
+        publish(&format!("{}/sensor", topic), &["first", "second"]).await;
+
+        let events = collect_n(rx, 2).await;
+        drop(trigger);
+        done.await;
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first", "second"]);
+        assert_eq!(
+            events[0].as_log()["topic"],
+            format!("{}/sensor", topic).into()
+        );
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key()],
+            "mqtt".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn mqtt_consumes_messages_v3_1_1() {
+        consumes_messages("3.1.1").await;
+    }
+
+    #[tokio::test]
+    async fn mqtt_consumes_messages_v5() {
+        consumes_messages("5").await;
+    }
+
+    #[tokio::test]
+    async fn mqtt_redelivers_rejected_messages() {
+        trace_init();
+        let topic = format!("vector/{}", random_string(10));
+        let config = make_config(&topic, &random_string(10), "3.1.1");
+
+        let (rx, trigger, done) = spawn_source(&config, EventStatus::Rejected);
+        publish(&format!("{}/sensor", topic), &["rejected"]).await;
+        assert_eq!(collect_n(rx, 1).await.len(), 1);
+        drop(trigger);
+        done.await;
+
+        // The session is resumed with the same client id, and the message that wasn't
+        // acknowledged is delivered again.
+        let (rx, trigger, done) = spawn_source(&config, EventStatus::Delivered);
+        let events = collect_n(rx, 1).await;
+        drop(trigger);
+        done.await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "rejected".into()
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! The IANA information elements decoded into typed fields, shared by NetFlow v9 and IPFIX.
+//!
+//! <https://www.iana.org/assignments/ipfix/ipfix.xhtml>
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{TimeZone, Utc};
+
+use crate::event::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum FieldType {
+    Unsigned,
+    Ipv4Address,
+    Ipv6Address,
+    MacAddress,
+    String,
+    DateTimeSeconds,
+    DateTimeMilliseconds,
+    Octets,
+}
+
+/// The name and type of the information elements known to the source, the other ones being
+/// output as hexadecimal strings.
+pub(super) const fn information_element(id: u16) -> Option<(&'static str, FieldType)> {
+    use FieldType::*;
+
+    Some(match id {
+        1 => ("octet_delta_count", Unsigned),
+        2 => ("packet_delta_count", Unsigned),
+        3 => ("delta_flow_count", Unsigned),
+        4 => ("protocol_identifier", Unsigned),
+        5 => ("ip_class_of_service", Unsigned),
+        6 => ("tcp_control_bits", Unsigned),
+        7 => ("source_transport_port", Unsigned),
+        8 => ("source_ipv4_address", Ipv4Address),
+        9 => ("source_ipv4_prefix_length", Unsigned),
+        10 => ("ingress_interface", Unsigned),
+        11 => ("destination_transport_port", Unsigned),
+        12 => ("destination_ipv4_address", Ipv4Address),
+        13 => ("destination_ipv4_prefix_length", Unsigned),
+        14 => ("egress_interface", Unsigned),
+        15 => ("ip_next_hop_ipv4_address", Ipv4Address),
+        16 => ("bgp_source_as_number", Unsigned),
+        17 => ("bgp_destination_as_number", Unsigned),
+        18 => ("bgp_next_hop_ipv4_address", Ipv4Address),
+        19 => ("post_mcast_packet_delta_count", Unsigned),
+        20 => ("post_mcast_octet_delta_count", Unsigned),
+        21 => ("flow_end_sys_up_time", Unsigned),
+        22 => ("flow_start_sys_up_time", Unsigned),
+        23 => ("post_octet_delta_count", Unsigned),
+        24 => ("post_packet_delta_count", Unsigned),
+        25 => ("minimum_ip_total_length", Unsigned),
+        26 => ("maximum_ip_total_length", Unsigned),
+        27 => ("source_ipv6_address", Ipv6Address),
+        28 => ("destination_ipv6_address", Ipv6Address),
+        29 => ("source_ipv6_prefix_length", Unsigned),
+        30 => ("destination_ipv6_prefix_length", Unsigned),
+        31 => ("flow_label_ipv6", Unsigned),
+        32 => ("icmp_type_code_ipv4", Unsigned),
+        33 => ("igmp_type", Unsigned),
+        34 => ("sampling_interval", Unsigned),
+        35 => ("sampling_algorithm", Unsigned),
+        36 => ("flow_active_timeout", Unsigned),
+        37 => ("flow_idle_timeout", Unsigned),
+        38 => ("engine_type", Unsigned),
+        39 => ("engine_id", Unsigned),
+        40 => ("exported_octet_total_count", Unsigned),
+        41 => ("exported_message_total_count", Unsigned),
+        42 => ("exported_flow_record_total_count", Unsigned),
+        52 => ("minimum_ttl", Unsigned),
+        53 => ("maximum_ttl", Unsigned),
+        54 => ("fragment_identification", Unsigned),
+        55 => ("post_ip_class_of_service", Unsigned),
+        56 => ("source_mac_address", MacAddress),
+        57 => ("post_destination_mac_address", MacAddress),
+        58 => ("vlan_id", Unsigned),
+        59 => ("post_vlan_id", Unsigned),
+        60 => ("ip_version", Unsigned),
+        61 => ("flow_direction", Unsigned),
+        62 => ("ip_next_hop_ipv6_address", Ipv6Address),
+        63 => ("bgp_next_hop_ipv6_address", Ipv6Address),
+        64 => ("ipv6_extension_headers", Unsigned),
+        70 => ("mpls_top_label_stack_section", Octets),
+        80 => ("destination_mac_address", MacAddress),
+        81 => ("post_source_mac_address", MacAddress),
+        82 => ("interface_name", String),
+        83 => ("interface_description", String),
+        85 => ("octet_total_count", Unsigned),
+        86 => ("packet_total_count", Unsigned),
+        88 => ("fragment_offset", Unsigned),
+        89 => ("forwarding_status", Unsigned),
+        90 => ("mpls_vpn_route_distinguisher", Octets),
+        94 => ("application_description", String),
+        95 => ("application_id", Octets),
+        96 => ("application_name", String),
+        136 => ("flow_end_reason", Unsigned),
+        148 => ("flow_id", Unsigned),
+        150 => ("flow_start_seconds", DateTimeSeconds),
+        151 => ("flow_end_seconds", DateTimeSeconds),
+        152 => ("flow_start_milliseconds", DateTimeMilliseconds),
+        153 => ("flow_end_milliseconds", DateTimeMilliseconds),
+        160 => ("system_init_time_milliseconds", DateTimeMilliseconds),
+        176 => ("icmp_type_ipv4", Unsigned),
+        177 => ("icmp_code_ipv4", Unsigned),
+        178 => ("icmp_type_ipv6", Unsigned),
+        179 => ("icmp_code_ipv6", Unsigned),
+        180 => ("udp_source_port", Unsigned),
+        181 => ("udp_destination_port", Unsigned),
+        182 => ("tcp_source_port", Unsigned),
+        183 => ("tcp_destination_port", Unsigned),
+        192 => ("ip_ttl", Unsigned),
+        225 => ("post_nat_source_ipv4_address", Ipv4Address),
+        226 => ("post_nat_destination_ipv4_address", Ipv4Address),
+        227 => ("post_napt_source_transport_port", Unsigned),
+        228 => ("post_napt_destination_transport_port", Unsigned),
+        234 => ("ingress_vrf_id", Unsigned),
+        235 => ("egress_vrf_id", Unsigned),
+        _ => return None,
+    })
+}
+
+/// The name of the scope fields of NetFlow v9 options templates, which have their own numbering.
+pub(super) const fn v9_scope(id: u16) -> Option<&'static str> {
+    match id {
+        1 => Some("scope_system"),
+        2 => Some("scope_interface"),
+        3 => Some("scope_line_card"),
+        4 => Some("scope_cache"),
+        5 => Some("scope_template"),
+        _ => None,
+    }
+}
+
+/// Decodes a field, falling back to its hexadecimal representation when its length doesn't fit
+/// its type.
+pub(super) fn decode(field_type: FieldType, bytes: &[u8]) -> Value {
+    match (field_type, bytes.len()) {
+        (FieldType::Unsigned, 1..=8) => unsigned(bytes),
+        (FieldType::Ipv4Address, 4) => {
+            let octets: [u8; 4] = bytes.try_into().expect("length is checked");
+            Ipv4Addr::from(octets).to_string().into()
+        }
+        (FieldType::Ipv6Address, 16) => {
+            let octets: [u8; 16] = bytes.try_into().expect("length is checked");
+            Ipv6Addr::from(octets).to_string().into()
+        }
+        (FieldType::MacAddress, 6) => bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(":")
+            .into(),
+        (FieldType::String, _) => String::from_utf8_lossy(bytes)
+            .trim_end_matches('\0')
+            .to_owned()
+            .into(),
+        (FieldType::DateTimeSeconds, 4) => match Utc.timestamp_opt(read_u64(bytes) as i64, 0) {
+            chrono::LocalResult::Single(timestamp) => timestamp.into(),
+            _ => hex(bytes),
+        },
+        (FieldType::DateTimeMilliseconds, 8) => {
+            match Utc.timestamp_millis_opt(read_u64(bytes) as i64) {
+                chrono::LocalResult::Single(timestamp) => timestamp.into(),
+                _ => hex(bytes),
+            }
+        }
+        _ => hex(bytes),
+    }
+}
+
+/// Decodes an unsigned integer of any length up to 8 bytes, as sent with reduced-size encoding.
+pub(super) fn unsigned(bytes: &[u8]) -> Value {
+    let value = read_u64(bytes);
+    match i64::try_from(value) {
+        Ok(value) => value.into(),
+        // Can't be represented as an integer `Value`.
+        Err(_) => (value as f64).into(),
+    }
+}
+
+pub(super) fn hex(bytes: &[u8]) -> Value {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>()
+        .into()
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0, |value, &byte| (value << 8) | u64::from(byte))
+}
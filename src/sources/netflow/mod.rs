@@ -0,0 +1,277 @@
+use std::{
+    io,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::future;
+use serde::{Deserialize, Serialize};
+use tokio::net::UdpSocket;
+use vector_core::ByteSizeOf;
+
+use self::templates::TemplateCache;
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::Event,
+    internal_events::{
+        BytesReceived, EventsReceived, NetflowParseError, NetflowTemplatesPersistError, SocketMode,
+        SocketReceiveError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp::{self, MulticastConfig},
+    SourceSender,
+};
+
+mod fields;
+pub(crate) mod parser;
+mod templates;
+
+/// The largest UDP payload.
+const MAX_DATAGRAM_LENGTH: usize = 65535;
+
+/// How often the templates are written to the data directory, if they changed.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    address: SocketAddr,
+    receive_buffer_bytes: Option<usize>,
+    multicast: Option<MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
+    data_dir: Option<PathBuf>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<NetflowConfig>("netflow")
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:2055".parse().unwrap(),
+            receive_buffer_bytes: None,
+            multicast: None,
+            reuse_port_sockets: None,
+            data_dir: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "netflow")]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        // The templates are shared by the sockets, as the datagrams of an exporter can be
+        // received by any of them.
+        let templates = Arc::new(Mutex::new(TemplateCache::load(&data_dir)?));
+        let sockets = udp::bind_listeners(
+            self.address,
+            self.receive_buffer_bytes,
+            self.multicast.as_ref(),
+            self.reuse_port_sockets,
+        )?;
+        info!(message = "Listening.", address = %self.address, sockets = sockets.len());
+
+        let shutdown = cx.shutdown;
+        let out = cx.out;
+        Ok(Box::pin(async move {
+            let tasks = sockets.into_iter().map(|socket| {
+                tokio::spawn(receive(
+                    socket,
+                    Arc::clone(&templates),
+                    shutdown.clone(),
+                    out.clone(),
+                ))
+            });
+            let receivers = future::join_all(tasks);
+            tokio::pin!(receivers);
+            let mut persist = tokio::time::interval(PERSIST_INTERVAL);
+            let results = loop {
+                tokio::select! {
+                    results = &mut receivers => break results,
+                    _ = persist.tick() => persist_templates(&templates).await,
+                }
+            };
+            // The templates received since the last write are kept for the next run.
+            persist_templates(&templates).await;
+
+            for result in results {
+                if !matches!(result, Ok(Ok(()))) {
+                    return Err(());
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "netflow"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn receive(
+    socket: UdpSocket,
+    templates: Arc<Mutex<TemplateCache>>,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut buf = vec![0; MAX_DATAGRAM_LENGTH];
+    loop {
+        let (byte_size, address) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => recv.map_err(|error| {
+                let error = codecs::decoding::Error::FramingError(error.into());
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error: &error
+                })
+            })?,
+            _ = &mut shutdown => return Ok(()),
+        };
+        emit!(BytesReceived {
+            byte_size,
+            protocol: "udp",
+        });
+
+        let exporter = address.ip();
+        let logs = parser::parse(
+            &buf[..byte_size],
+            exporter,
+            &mut templates.lock().expect("poisoned lock"),
+        );
+        let mut events = match logs {
+            Ok(logs) => logs.into_iter().map(Event::from).collect::<Vec<_>>(),
+            Err(error) => {
+                emit!(NetflowParseError {
+                    error: &error,
+                    exporter,
+                });
+                continue;
+            }
+        };
+        if events.is_empty() {
+            continue;
+        }
+
+        for event in &mut events {
+            event
+                .as_mut_log()
+                .insert(log_schema().source_type_key(), Bytes::from("netflow"));
+        }
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+        tokio::select! {
+            result = out.send_batch(events) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count });
+                    return Ok(());
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+/// Writes the templates to the data directory if they changed, on a blocking thread so as not to
+/// stall the runtime, and without holding the lock the sockets parse the datagrams with.
+async fn persist_templates(templates: &Mutex<TemplateCache>) {
+    let snapshot = match templates.lock().expect("poisoned lock").snapshot() {
+        Some(snapshot) => snapshot,
+        None => return,
+    };
+    let path = snapshot.path().to_owned();
+    let result = tokio::task::spawn_blocking(move || snapshot.write())
+        .await
+        .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+    if let Err(error) = result {
+        templates.lock().expect("poisoned lock").set_changed();
+        emit!(NetflowTemplatesPersistError { error, path: &path });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::{
+        config::ComponentKey,
+        test_util::{collect_n, next_addr},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_flow_records() {
+        let address = next_addr();
+        let data_dir = tempfile::tempdir().unwrap();
+        let config: NetflowConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            data_dir = "{}"
+            "#,
+            address,
+            data_dir.path().display()
+        ))
+        .unwrap();
+        let (tx, rx) = SourceSender::new_test();
+        let key = ComponentKey::from("default");
+        let (cx, mut shutdown) = SourceContext::new_shutdown(&key, tx);
+        let source = tokio::spawn(config.build(cx).await.unwrap());
+
+        // An IPFIX message with a template set and a data set of two records.
+        let mut packet = vec![0, 10, 0, 52, 0x62, 0xbc, 0xc3, 0x18, 0, 0, 0, 1, 0, 0, 0, 1];
+        packet.extend_from_slice(&[0, 2, 0, 16, 1, 0, 0, 2, 0, 8, 0, 4, 0, 2, 0, 4]);
+        packet.extend_from_slice(&[
+            1, 0, 0, 20, 10, 0, 0, 1, 0, 0, 0, 5, 10, 0, 0, 2, 0, 0, 0, 6,
+        ]);
+        let socket = std::net::UdpSocket::bind(next_addr()).unwrap();
+        socket.send_to(&packet, address).unwrap();
+
+        let events = collect_n(rx, 2).await;
+        let log = events[0].as_log();
+        assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(log["packet_delta_count"], 5.into());
+        assert_eq!(log[log_schema().source_type_key()], "netflow".into());
+        assert_eq!(events[1].as_log()["source_ipv4_address"], "10.0.0.2".into());
+
+        // The template is kept for the next run once the source is shut down.
+        let deadline = Instant::now() + Duration::from_secs(10);
+        assert!(shutdown.shutdown_source(&key, deadline).await);
+        source.await.unwrap().unwrap();
+        assert!(data_dir
+            .path()
+            .join("default")
+            .join("templates.json")
+            .exists());
+    }
+}
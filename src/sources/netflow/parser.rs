@@ -0,0 +1,649 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::Snafu;
+
+use super::{
+    fields::{self, FieldType},
+    templates::{Template, TemplateCache, TemplateField, TemplateKey, VARIABLE_LENGTH},
+};
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+    internal_events::NetflowTemplateNotFoundError,
+};
+
+const NETFLOW_V5: u16 = 5;
+const NETFLOW_V9: u16 = 9;
+const IPFIX: u16 = 10;
+
+const V5_HEADER_LENGTH: usize = 24;
+const V5_RECORD_LENGTH: usize = 48;
+
+const V9_TEMPLATE_FLOWSET_ID: u16 = 0;
+const V9_OPTIONS_TEMPLATE_FLOWSET_ID: u16 = 1;
+const IPFIX_TEMPLATE_SET_ID: u16 = 2;
+const IPFIX_OPTIONS_TEMPLATE_SET_ID: u16 = 3;
+/// The sets with a lower ID are reserved, the higher ones hold data records.
+const MIN_DATA_SET_ID: u16 = 256;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub(crate) enum ParseError {
+    #[snafu(display("packet is truncated"))]
+    Truncated,
+    #[snafu(display("unsupported version {}", version))]
+    UnsupportedVersion { version: u16 },
+    #[snafu(display("invalid message length {}", length))]
+    InvalidMessageLength { length: u16 },
+    #[snafu(display("invalid length of set {}: {}", set_id, length))]
+    InvalidSetLength { set_id: u16, length: u16 },
+    #[snafu(display("invalid template {}", template_id))]
+    InvalidTemplate { template_id: u16 },
+}
+
+/// Parses a NetFlow v5, NetFlow v9 or IPFIX packet into a log per flow record, updating the
+/// templates of the exporter with the ones the packet holds.
+///
+/// The data records whose template is unknown are skipped.
+pub(super) fn parse(
+    packet: &[u8],
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> Result<Vec<LogEvent>, ParseError> {
+    let version = Reader::new(packet).u16()?;
+    match version {
+        NETFLOW_V5 => parse_v5(packet, exporter),
+        NETFLOW_V9 => parse_v9(packet, exporter, templates),
+        IPFIX => parse_ipfix(packet, exporter, templates),
+        version => Err(ParseError::UnsupportedVersion { version }),
+    }
+}
+
+fn parse_v5(packet: &[u8], exporter: IpAddr) -> Result<Vec<LogEvent>, ParseError> {
+    // https://www.cisco.com/c/en/us/td/docs/net_mgmt/netflow_collection_engine/3-6/user/guide/format.html
+    let mut reader = Reader::new(packet);
+    reader.skip(2)?;
+    let count = reader.u16()? as usize;
+    let _sys_uptime = reader.u32()?;
+    let unix_secs = reader.u32()?;
+    let unix_nsecs = reader.u32()?;
+    let _flow_sequence = reader.u32()?;
+    let engine_type = reader.u8()?;
+    let engine_id = reader.u8()?;
+    // The two highest bits hold the sampling mode.
+    let sampling_interval = reader.u16()? & 0x3fff;
+
+    if packet.len() < V5_HEADER_LENGTH + count * V5_RECORD_LENGTH {
+        return Err(ParseError::Truncated);
+    }
+    let timestamp = Utc
+        .timestamp_opt(i64::from(unix_secs), unix_nsecs)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    (0..count)
+        .map(|_| {
+            let mut log = new_log(NETFLOW_V5, exporter, timestamp);
+            log.insert("engine_type", engine_type);
+            log.insert("engine_id", engine_id);
+            log.insert("sampling_interval", sampling_interval);
+
+            log.insert("source_ipv4_address", reader.ipv4()?);
+            log.insert("destination_ipv4_address", reader.ipv4()?);
+            log.insert("ip_next_hop_ipv4_address", reader.ipv4()?);
+            log.insert("ingress_interface", reader.u16()?);
+            log.insert("egress_interface", reader.u16()?);
+            log.insert("packet_delta_count", reader.u32()?);
+            log.insert("octet_delta_count", reader.u32()?);
+            log.insert("flow_start_sys_up_time", reader.u32()?);
+            log.insert("flow_end_sys_up_time", reader.u32()?);
+            log.insert("source_transport_port", reader.u16()?);
+            log.insert("destination_transport_port", reader.u16()?);
+            reader.skip(1)?;
+            log.insert("tcp_control_bits", reader.u8()?);
+            log.insert("protocol_identifier", reader.u8()?);
+            log.insert("ip_class_of_service", reader.u8()?);
+            log.insert("bgp_source_as_number", reader.u16()?);
+            log.insert("bgp_destination_as_number", reader.u16()?);
+            log.insert("source_ipv4_prefix_length", reader.u8()?);
+            log.insert("destination_ipv4_prefix_length", reader.u8()?);
+            reader.skip(2)?;
+            Ok(log)
+        })
+        .collect()
+}
+
+fn parse_v9(
+    packet: &[u8],
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> Result<Vec<LogEvent>, ParseError> {
+    // https://www.rfc-editor.org/rfc/rfc3954
+    let mut reader = Reader::new(packet);
+    reader.skip(2)?;
+    let _count = reader.u16()?;
+    let _sys_uptime = reader.u32()?;
+    let unix_secs = reader.u32()?;
+    let _sequence = reader.u32()?;
+    let source_id = reader.u32()?;
+    let timestamp = Utc
+        .timestamp_opt(i64::from(unix_secs), 0)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    let mut logs = Vec::new();
+    while reader.remaining() > 0 {
+        let (flowset_id, mut flowset) = reader.set()?;
+        let key = |template_id| TemplateKey {
+            exporter,
+            version: NETFLOW_V9,
+            observation_domain_id: source_id,
+            template_id,
+        };
+        match flowset_id {
+            V9_TEMPLATE_FLOWSET_ID => {
+                // The flowsets are padded to 4 bytes.
+                while flowset.remaining() >= 4 {
+                    let template_id = flowset.u16()?;
+                    let field_count = flowset.u16()?;
+                    let fields = (0..field_count)
+                        .map(|_| flowset.v9_field(false))
+                        .collect::<Result<_, _>>()?;
+                    let template = Template {
+                        fields,
+                        options: false,
+                    };
+                    validate(&template, template_id)?;
+                    templates.insert(key(template_id), template);
+                }
+            }
+            V9_OPTIONS_TEMPLATE_FLOWSET_ID => {
+                while flowset.remaining() >= 6 {
+                    let template_id = flowset.u16()?;
+                    let scope_length = flowset.u16()? as usize;
+                    let options_length = flowset.u16()? as usize;
+                    let mut fields = (0..scope_length / 4)
+                        .map(|_| flowset.v9_field(true))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for _ in 0..options_length / 4 {
+                        fields.push(flowset.v9_field(false)?);
+                    }
+                    let template = Template {
+                        fields,
+                        options: true,
+                    };
+                    validate(&template, template_id)?;
+                    templates.insert(key(template_id), template);
+                }
+            }
+            MIN_DATA_SET_ID.. => match templates.get(&key(flowset_id)) {
+                Some(template) => logs.extend(parse_data_set(
+                    flowset, template, flowset_id, NETFLOW_V9, exporter, source_id, timestamp,
+                )?),
+                None => emit!(NetflowTemplateNotFoundError {
+                    exporter,
+                    observation_domain_id: source_id,
+                    template_id: flowset_id,
+                }),
+            },
+            _ => (),
+        }
+    }
+    Ok(logs)
+}
+
+fn parse_ipfix(
+    packet: &[u8],
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> Result<Vec<LogEvent>, ParseError> {
+    // https://www.rfc-editor.org/rfc/rfc7011
+    let mut reader = Reader::new(packet);
+    reader.skip(2)?;
+    let length = reader.u16()? as usize;
+    let export_time = reader.u32()?;
+    let _sequence = reader.u32()?;
+    let domain = reader.u32()?;
+    // The length covers the header, which the message can't be shorter than.
+    if length < 16 {
+        return Err(ParseError::InvalidMessageLength {
+            length: length as u16,
+        });
+    }
+    if packet.len() < length {
+        return Err(ParseError::Truncated);
+    }
+    // Ignores what follows the message in the datagram.
+    let mut reader = Reader::new(&packet[16..length]);
+    let timestamp = Utc
+        .timestamp_opt(i64::from(export_time), 0)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    let mut logs = Vec::new();
+    while reader.remaining() > 0 {
+        let (set_id, mut set) = reader.set()?;
+        let key = |template_id| TemplateKey {
+            exporter,
+            version: IPFIX,
+            observation_domain_id: domain,
+            template_id,
+        };
+        match set_id {
+            IPFIX_TEMPLATE_SET_ID | IPFIX_OPTIONS_TEMPLATE_SET_ID => {
+                let options = set_id == IPFIX_OPTIONS_TEMPLATE_SET_ID;
+                while set.remaining() >= 4 {
+                    let template_id = set.u16()?;
+                    let field_count = set.u16()?;
+                    if field_count == 0 {
+                        // A template withdrawal, for all the templates with the ID of the set.
+                        if template_id == set_id {
+                            templates.remove_domain(exporter, IPFIX, domain);
+                        } else {
+                            templates.remove(&key(template_id));
+                        }
+                        continue;
+                    }
+                    if options {
+                        let _scope_field_count = set.u16()?;
+                    }
+                    let fields = (0..field_count)
+                        .map(|_| set.ipfix_field())
+                        .collect::<Result<_, _>>()?;
+                    let template = Template { fields, options };
+                    validate(&template, template_id)?;
+                    templates.insert(key(template_id), template);
+                }
+            }
+            MIN_DATA_SET_ID.. => match templates.get(&key(set_id)) {
+                Some(template) => logs.extend(parse_data_set(
+                    set, template, set_id, IPFIX, exporter, domain, timestamp,
+                )?),
+                None => emit!(NetflowTemplateNotFoundError {
+                    exporter,
+                    observation_domain_id: domain,
+                    template_id: set_id,
+                }),
+            },
+            _ => (),
+        }
+    }
+    Ok(logs)
+}
+
+fn validate(template: &Template, template_id: u16) -> Result<(), ParseError> {
+    if template_id < MIN_DATA_SET_ID || template.min_record_length() == 0 {
+        Err(ParseError::InvalidTemplate { template_id })
+    } else {
+        Ok(())
+    }
+}
+
+fn parse_data_set(
+    mut set: Reader<'_>,
+    template: &Template,
+    template_id: u16,
+    version: u16,
+    exporter: IpAddr,
+    domain: u32,
+    timestamp: DateTime<Utc>,
+) -> Result<Vec<LogEvent>, ParseError> {
+    let min_length = template.min_record_length();
+    let mut logs = Vec::new();
+    // What remains after the last record is padding.
+    while set.remaining() >= min_length {
+        let mut log = new_log(version, exporter, timestamp);
+        log.insert("observation_domain_id", domain);
+        log.insert("template_id", template_id);
+        log.insert(
+            "record_type",
+            if template.options { "options" } else { "data" },
+        );
+        for field in &template.fields {
+            let length = match field.length {
+                VARIABLE_LENGTH => set.variable_length()?,
+                length => length as usize,
+            };
+            let bytes = set.take(length)?;
+            let (name, value) = decode_field(field, bytes);
+            log.insert(name.as_str(), value);
+        }
+        logs.push(log);
+    }
+    Ok(logs)
+}
+
+fn decode_field(field: &TemplateField, bytes: &[u8]) -> (String, Value) {
+    if let Some(enterprise) = field.enterprise {
+        return (
+            format!("enterprise_{}_{}", enterprise, field.id),
+            fields::hex(bytes),
+        );
+    }
+    if field.v9_scope {
+        return match fields::v9_scope(field.id) {
+            Some(name) => (name.to_owned(), fields::decode(FieldType::Unsigned, bytes)),
+            None => (format!("scope_{}", field.id), fields::hex(bytes)),
+        };
+    }
+    match fields::information_element(field.id) {
+        Some((name, field_type)) => (name.to_owned(), fields::decode(field_type, bytes)),
+        None => (format!("field_{}", field.id), fields::hex(bytes)),
+    }
+}
+
+fn new_log(version: u16, exporter: IpAddr, timestamp: DateTime<Utc>) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert("version", version);
+    log.insert("exporter", exporter.to_string());
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log
+}
+
+/// Reads the big-endian fields of the packets, failing on truncated ones.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    const fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ParseError> {
+        if self.data.len() < length {
+            return Err(ParseError::Truncated);
+        }
+        let (taken, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn skip(&mut self, length: usize) -> Result<(), ParseError> {
+        self.take(length).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ParseError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn ipv4(&mut self) -> Result<String, ParseError> {
+        Ok(Ipv4Addr::from(self.u32()?).to_string())
+    }
+
+    /// Reads the header of a set, or flowset, returning its ID and its content.
+    fn set(&mut self) -> Result<(u16, Reader<'a>), ParseError> {
+        let set_id = self.u16()?;
+        let length = self.u16()?;
+        if length < 4 {
+            return Err(ParseError::InvalidSetLength { set_id, length });
+        }
+        Ok((set_id, Reader::new(self.take(length as usize - 4)?)))
+    }
+
+    fn v9_field(&mut self, v9_scope: bool) -> Result<TemplateField, ParseError> {
+        Ok(TemplateField {
+            id: self.u16()?,
+            length: self.u16()?,
+            enterprise: None,
+            v9_scope,
+        })
+    }
+
+    fn ipfix_field(&mut self) -> Result<TemplateField, ParseError> {
+        let id = self.u16()?;
+        let length = self.u16()?;
+        // The highest bit of the ID tells whether an enterprise number follows.
+        let enterprise = if id & 0x8000 != 0 {
+            Some(self.u32()?)
+        } else {
+            None
+        };
+        Ok(TemplateField {
+            id: id & 0x7fff,
+            length,
+            enterprise,
+            v9_scope: false,
+        })
+    }
+
+    /// Reads the length of a variable-length field, on one byte or three for the longer ones.
+    fn variable_length(&mut self) -> Result<usize, ParseError> {
+        match self.u8()? {
+            255 => Ok(self.u16()? as usize),
+            length => Ok(length as usize),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORTER: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+
+    fn v5_packet() -> Vec<u8> {
+        let mut packet = vec![
+            0, 5, // version
+            0, 1, // count
+            0, 0, 0x03, 0xe8, // sys_uptime
+            0x62, 0xbc, 0xc3, 0x18, // unix_secs
+            0, 0, 0, 0, // unix_nsecs
+            0, 0, 0, 1, // flow_sequence
+            1, 2, // engine_type, engine_id
+            0x40, 0x0a, // sampling mode and interval
+        ];
+        packet.extend_from_slice(&[
+            10, 0, 0, 1, // srcaddr
+            10, 0, 0, 2, // dstaddr
+            0, 0, 0, 0, // nexthop
+            0, 3, 0, 4, // input, output
+            0, 0, 0, 10, // dPkts
+            0, 0, 0x05, 0xdc, // dOctets
+            0, 0, 0, 100, // first
+            0, 0, 0, 200, // last
+            0x1f, 0x90, 0, 80, // srcport, dstport
+            0, 0x18, 6, 0, // pad1, tcp_flags, prot, tos
+            0, 1, 0, 2, // src_as, dst_as
+            24, 16, 0, 0, // src_mask, dst_mask, pad2
+        ]);
+        packet
+    }
+
+    #[test]
+    fn parses_v5() {
+        let mut templates = TemplateCache::default();
+        let logs = parse(&v5_packet(), EXPORTER, &mut templates).unwrap();
+        assert_eq!(logs.len(), 1);
+        let log = &logs[0];
+        assert_eq!(log["version"], 5.into());
+        assert_eq!(log["exporter"], "192.0.2.1".into());
+        assert_eq!(log["sampling_interval"], 10.into());
+        assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(log["destination_ipv4_address"], "10.0.0.2".into());
+        assert_eq!(log["packet_delta_count"], 10.into());
+        assert_eq!(log["octet_delta_count"], 1500.into());
+        assert_eq!(log["source_transport_port"], 8080.into());
+        assert_eq!(log["destination_transport_port"], 80.into());
+        assert_eq!(log["tcp_control_bits"], 0x18.into());
+        assert_eq!(log["protocol_identifier"], 6.into());
+        assert_eq!(log["source_ipv4_prefix_length"], 24.into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(0x62bc_c318, 0).into()
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_v5() {
+        let packet = v5_packet();
+        let mut templates = TemplateCache::default();
+        assert_eq!(
+            parse(&packet[..packet.len() - 1], EXPORTER, &mut templates),
+            Err(ParseError::Truncated)
+        );
+    }
+
+    fn v9_header() -> Vec<u8> {
+        vec![
+            0, 9, // version
+            0, 1, // count
+            0, 0, 0x03, 0xe8, // sys_uptime
+            0x62, 0xbc, 0xc3, 0x18, // unix_secs
+            0, 0, 0, 1, // sequence
+            0, 0, 0, 7, // source_id
+        ]
+    }
+
+    #[test]
+    fn parses_v9_with_templates() {
+        let mut templates = TemplateCache::default();
+
+        let mut packet = v9_header();
+        packet.extend_from_slice(&[
+            0, 0, 0, 20, // template flowset
+            1, 0, 0, 3, // template 256 with 3 fields
+            0, 8, 0, 4, // source_ipv4_address
+            0, 2, 0, 4, // packet_delta_count
+            0, 7, 0, 2, // source_transport_port
+        ]);
+        packet.extend_from_slice(&[
+            1, 0, 0, 16, // data flowset of template 256
+            10, 0, 0, 1, 0, 0, 0, 42, 0, 53, // record
+            0, 0, // padding
+        ]);
+        let logs = parse(&packet, EXPORTER, &mut templates).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0]["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(logs[0]["packet_delta_count"], 42.into());
+        assert_eq!(logs[0]["source_transport_port"], 53.into());
+        assert_eq!(logs[0]["observation_domain_id"], 7.into());
+        assert_eq!(logs[0]["template_id"], 256.into());
+        assert_eq!(logs[0]["record_type"], "data".into());
+
+        // The template is remembered for the next packets of the exporter only.
+        let mut packet = v9_header();
+        packet.extend_from_slice(&[1, 0, 0, 14, 10, 0, 0, 2, 0, 0, 0, 1, 0, 54]);
+        let logs = parse(&packet, EXPORTER, &mut templates).unwrap();
+        assert_eq!(logs[0]["source_ipv4_address"], "10.0.0.2".into());
+        let other_exporter = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2));
+        assert!(parse(&packet, other_exporter, &mut templates)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn parses_v9_options() {
+        let mut templates = TemplateCache::default();
+        let mut packet = v9_header();
+        packet.extend_from_slice(&[
+            0, 1, 0, 20, // options template flowset
+            1, 1, 0, 4, 0, 4, // template 257, scope and options lengths
+            0, 1, 0, 4, // scope_system
+            0, 34, 0, 4, // sampling_interval
+            0, 0, // padding
+        ]);
+        packet.extend_from_slice(&[
+            1, 1, 0, 12, // data flowset of template 257
+            0, 0, 0, 1, 0, 0, 0, 100,
+        ]);
+        let logs = parse(&packet, EXPORTER, &mut templates).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0]["scope_system"], 1.into());
+        assert_eq!(logs[0]["sampling_interval"], 100.into());
+        assert_eq!(logs[0]["record_type"], "options".into());
+    }
+
+    fn ipfix_packet(sets: &[u8]) -> Vec<u8> {
+        let length = (16 + sets.len()) as u16;
+        let mut packet = vec![0, 10];
+        packet.extend_from_slice(&length.to_be_bytes());
+        packet.extend_from_slice(&[
+            0x62, 0xbc, 0xc3, 0x18, // export_time
+            0, 0, 0, 1, // sequence
+            0, 0, 0, 3, // observation_domain_id
+        ]);
+        packet.extend_from_slice(sets);
+        packet
+    }
+
+    #[test]
+    fn parses_ipfix() {
+        let mut templates = TemplateCache::default();
+        let packet = ipfix_packet(&[
+            0, 2, 0, 28, // template set
+            1, 0, 0, 4, // template 256 with 4 fields
+            0, 27, 0, 16, // source_ipv6_address
+            0, 96, 0xff, 0xff, // application_name, variable length
+            0x80, 1, 0, 2, // enterprise field 1
+            0, 0, 0x72, 0x79, // enterprise number 29305
+            0, 152, 0, 8, // flow_start_milliseconds
+        ]);
+        assert!(parse(&packet, EXPORTER, &mut templates).unwrap().is_empty());
+
+        let mut data = vec![1, 0, 0, 35];
+        data.extend_from_slice(&[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        data.extend_from_slice(&[4, b'h', b't', b't', b'p']);
+        data.extend_from_slice(&[0xab, 0xcd]);
+        data.extend_from_slice(&1_656_539_928_000_u64.to_be_bytes());
+        let logs = parse(&ipfix_packet(&data), EXPORTER, &mut templates).unwrap();
+        assert_eq!(logs.len(), 1);
+        let log = &logs[0];
+        assert_eq!(log["version"], 10.into());
+        assert_eq!(log["observation_domain_id"], 3.into());
+        assert_eq!(log["source_ipv6_address"], "2001:db8::1".into());
+        assert_eq!(log["application_name"], "http".into());
+        assert_eq!(log["enterprise_29305_1"], "abcd".into());
+        assert_eq!(
+            log["flow_start_milliseconds"],
+            Utc.timestamp_millis(1_656_539_928_000).into()
+        );
+    }
+
+    #[test]
+    fn withdraws_ipfix_templates() {
+        let mut templates = TemplateCache::default();
+        let template = ipfix_packet(&[0, 2, 0, 12, 1, 0, 0, 1, 0, 8, 0, 4]);
+        let data = ipfix_packet(&[1, 0, 0, 8, 10, 0, 0, 1]);
+        parse(&template, EXPORTER, &mut templates).unwrap();
+        assert_eq!(parse(&data, EXPORTER, &mut templates).unwrap().len(), 1);
+
+        let withdrawal = ipfix_packet(&[0, 2, 0, 8, 1, 0, 0, 0]);
+        parse(&withdrawal, EXPORTER, &mut templates).unwrap();
+        assert!(parse(&data, EXPORTER, &mut templates).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_ipfix_length_shorter_than_header() {
+        let mut templates = TemplateCache::default();
+        let mut packet = ipfix_packet(&[]);
+        packet[2..4].copy_from_slice(&[0, 0]);
+        assert_eq!(
+            parse(&packet, EXPORTER, &mut templates),
+            Err(ParseError::InvalidMessageLength { length: 0 })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let mut templates = TemplateCache::default();
+        assert_eq!(
+            parse(&[0, 1, 0, 0], EXPORTER, &mut templates),
+            Err(ParseError::UnsupportedVersion { version: 1 })
+        );
+    }
+}
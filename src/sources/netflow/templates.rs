@@ -0,0 +1,255 @@
+use std::{
+    io,
+    net::IpAddr,
+    path::{Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+const TEMPLATES_FILE_NAME: &str = "templates.json";
+
+/// The maximum number of templates kept, so that exporters announcing ever new templates, or
+/// spoofing the addresses of many exporters, can't grow the cache without bound.
+const MAX_TEMPLATES: usize = 10_000;
+
+/// Identifies a template among the ones of all the exporters, as the template IDs are only unique
+/// within an observation domain of an exporter.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(super) struct TemplateKey {
+    pub(super) exporter: IpAddr,
+    pub(super) version: u16,
+    pub(super) observation_domain_id: u32,
+    pub(super) template_id: u16,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(super) struct Template {
+    pub(super) fields: Vec<TemplateField>,
+    /// Whether the template describes options records, rather than flow records.
+    #[serde(default)]
+    pub(super) options: bool,
+}
+
+/// The length of the variable-length IPFIX fields in templates.
+pub(super) const VARIABLE_LENGTH: u16 = u16::MAX;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(super) struct TemplateField {
+    pub(super) id: u16,
+    pub(super) length: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) enterprise: Option<u32>,
+    /// Whether the field is a scope of a NetFlow v9 options template, whose types have their own
+    /// numbering.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(super) v9_scope: bool,
+}
+
+impl Template {
+    /// The minimum length of the records, with the variable-length fields being empty.
+    pub(super) fn min_record_length(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => length as usize,
+            })
+            .sum()
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct PersistedTemplate {
+    #[serde(flatten)]
+    key: TemplateKey,
+    #[serde(flatten)]
+    template: Template,
+}
+
+/// The templates received from the exporters, persisted in the data directory so that the data
+/// records sent after a restart can be decoded without waiting for the templates to be sent again.
+///
+/// Once full, the template announced the longest ago is dropped for each new one, as exporters
+/// announce the templates they use periodically.
+#[derive(Debug, Default)]
+pub(super) struct TemplateCache {
+    templates: IndexMap<TemplateKey, Template>,
+    path: Option<PathBuf>,
+    changed: bool,
+}
+
+impl TemplateCache {
+    pub(super) fn load(data_dir: &Path) -> io::Result<Self> {
+        let path = data_dir.join(TEMPLATES_FILE_NAME);
+        let templates = match std::fs::read(&path) {
+            Ok(content) => serde_json::from_slice::<Vec<PersistedTemplate>>(&content)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+                .into_iter()
+                .map(|persisted| (persisted.key, persisted.template))
+                .collect(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => IndexMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            templates,
+            path: Some(path),
+            changed: false,
+        })
+    }
+
+    pub(super) fn get(&self, key: &TemplateKey) -> Option<&Template> {
+        self.templates.get(key)
+    }
+
+    pub(super) fn insert(&mut self, key: TemplateKey, template: Template) {
+        // The template is moved last, as the most recently announced one.
+        match self.templates.shift_remove(&key) {
+            Some(previous) => self.changed |= previous != template,
+            None => {
+                if self.templates.len() >= MAX_TEMPLATES {
+                    self.templates.shift_remove_index(0);
+                }
+                self.changed = true;
+            }
+        }
+        self.templates.insert(key, template);
+    }
+
+    pub(super) fn remove(&mut self, key: &TemplateKey) {
+        self.changed |= self.templates.shift_remove(key).is_some();
+    }
+
+    /// Removes the templates of an observation domain, as withdrawn all at once by IPFIX exporters.
+    pub(super) fn remove_domain(&mut self, exporter: IpAddr, version: u16, domain: u32) {
+        let count = self.templates.len();
+        self.templates.retain(|key, _| {
+            key.exporter != exporter
+                || key.version != version
+                || key.observation_domain_id != domain
+        });
+        self.changed |= self.templates.len() != count;
+    }
+
+    /// Takes a copy of the templates to write to the data directory, if they changed since the
+    /// last one. The copy is written with [`TemplatesSnapshot::write`], without holding on to the
+    /// cache.
+    pub(super) fn snapshot(&mut self) -> Option<TemplatesSnapshot> {
+        let path = match &self.path {
+            Some(path) if self.changed => path.clone(),
+            _ => return None,
+        };
+        let templates = self
+            .templates
+            .iter()
+            .map(|(key, template)| PersistedTemplate {
+                key: *key,
+                template: template.clone(),
+            })
+            .collect();
+        self.changed = false;
+        Some(TemplatesSnapshot { path, templates })
+    }
+
+    /// Flags the templates as changed, so that the next snapshot takes them again after the last
+    /// one couldn't be written.
+    pub(super) fn set_changed(&mut self) {
+        self.changed = true;
+    }
+}
+
+/// A copy of the templates, to be written to the data directory.
+pub(super) struct TemplatesSnapshot {
+    path: PathBuf,
+    templates: Vec<PersistedTemplate>,
+}
+
+impl TemplatesSnapshot {
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the templates, blocking on the file system.
+    pub(super) fn write(&self) -> io::Result<()> {
+        let content = serde_json::to_vec(&self.templates)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        // The file is replaced atomically so that a crash can't leave it truncated.
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persists_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = TemplateKey {
+            exporter: "192.0.2.1".parse().unwrap(),
+            version: 10,
+            observation_domain_id: 1,
+            template_id: 256,
+        };
+        let template = Template {
+            fields: vec![
+                TemplateField {
+                    id: 8,
+                    length: 4,
+                    enterprise: None,
+                    v9_scope: false,
+                },
+                TemplateField {
+                    id: 1,
+                    length: VARIABLE_LENGTH,
+                    enterprise: Some(29305),
+                    v9_scope: false,
+                },
+            ],
+            options: false,
+        };
+
+        let mut templates = TemplateCache::load(dir.path()).unwrap();
+        assert_eq!(templates.get(&key), None);
+        templates.insert(key, template.clone());
+        templates.snapshot().unwrap().write().unwrap();
+        assert!(templates.snapshot().is_none());
+
+        let mut templates = TemplateCache::load(dir.path()).unwrap();
+        assert_eq!(templates.get(&key), Some(&template));
+
+        templates.remove_domain(key.exporter, 10, 1);
+        templates.snapshot().unwrap().write().unwrap();
+        assert_eq!(TemplateCache::load(dir.path()).unwrap().get(&key), None);
+    }
+
+    #[test]
+    fn drops_the_oldest_templates_once_full() {
+        let key = |template_id| TemplateKey {
+            exporter: "192.0.2.1".parse().unwrap(),
+            version: 10,
+            observation_domain_id: 1,
+            template_id,
+        };
+        let template = Template {
+            fields: vec![],
+            options: false,
+        };
+
+        let mut templates = TemplateCache::default();
+        for template_id in 0..MAX_TEMPLATES as u16 {
+            templates.insert(key(template_id), template.clone());
+        }
+        // Announced again, the first template is kept over the second one.
+        templates.insert(key(0), template.clone());
+        templates.insert(key(MAX_TEMPLATES as u16), template);
+
+        assert_eq!(templates.templates.len(), MAX_TEMPLATES);
+        assert!(templates.get(&key(0)).is_some());
+        assert!(templates.get(&key(1)).is_none());
+        assert!(templates.get(&key(MAX_TEMPLATES as u16)).is_some());
+    }
+}
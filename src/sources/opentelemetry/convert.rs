@@ -0,0 +1,878 @@
+//! Conversion of OTLP payloads to Vector events.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+use ordered_float::NotNan;
+use vector_core::{
+    event::metric::{Bucket, MetricSketch, Quantile},
+    metrics::AgentDDSketch,
+};
+
+use super::ExponentialHistogramMode;
+use crate::{
+    config::log_schema,
+    event::{
+        metric::MetricTags, Event, LogEvent, Metric, MetricKind, MetricValue, TraceEvent, Value,
+    },
+    internal_events::OpentelemetryExponentialHistogramRangeError,
+    proto::opentelemetry::{
+        common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+        logs::v1::{LogRecord, ResourceLogs},
+        metrics::v1::{
+            exponential_histogram_data_point::Buckets, metric, number_data_point,
+            AggregationTemporality, ExponentialHistogramDataPoint, HistogramDataPoint,
+            Metric as OtlpMetric, NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{span, status, ResourceSpans, Span},
+    },
+};
+
+const SOURCE_TYPE: &str = "opentelemetry";
+
+/// Set on data points that hold no value, such as Prometheus staleness markers.
+const FLAG_NO_RECORDED_VALUE: u32 = 1;
+
+pub(super) fn logs(resource_logs: Vec<ResourceLogs>) -> Vec<Event> {
+    resource_logs
+        .into_iter()
+        .flat_map(|resource_logs| {
+            let resource = resource_logs.resource.map(resource_value);
+            resource_logs
+                .scope_logs
+                .into_iter()
+                .flat_map(move |scope_logs| {
+                    let resource = resource.clone();
+                    let scope = scope_logs.scope.map(scope_value);
+                    scope_logs
+                        .log_records
+                        .into_iter()
+                        .map(move |record| log_event(record, resource.clone(), scope.clone()))
+                })
+        })
+        .collect()
+}
+
+fn log_event(record: LogRecord, resource: Option<Value>, scope: Option<Value>) -> Event {
+    let mut log = LogEvent::default();
+
+    if let Some(body) = record.body.and_then(any_value) {
+        log.insert(log_schema().message_key(), body);
+    }
+    // The observed time is the best we have when the producer didn't set the time.
+    if let Some(timestamp) =
+        timestamp(record.time_unix_nano).or_else(|| timestamp(record.observed_time_unix_nano))
+    {
+        log.insert(log_schema().timestamp_key(), timestamp);
+    }
+    if let Some(observed_timestamp) = timestamp(record.observed_time_unix_nano) {
+        log.insert("observed_timestamp", observed_timestamp);
+    }
+    if !record.severity_text.is_empty() {
+        log.insert("severity_text", record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert("severity_number", record.severity_number);
+    }
+    if let Some(trace_id) = id(&record.trace_id) {
+        log.insert("trace_id", trace_id);
+    }
+    if let Some(span_id) = id(&record.span_id) {
+        log.insert("span_id", span_id);
+    }
+    if record.flags != 0 {
+        log.insert("flags", record.flags);
+    }
+    if !record.attributes.is_empty() {
+        log.insert("attributes", attributes(record.attributes));
+    }
+    if record.dropped_attributes_count != 0 {
+        log.insert("dropped_attributes_count", record.dropped_attributes_count);
+    }
+    if let Some(resource) = resource {
+        log.insert("resources", resource);
+    }
+    if let Some(scope) = scope {
+        log.insert("scope", scope);
+    }
+    log.insert(log_schema().source_type_key(), Value::from(SOURCE_TYPE));
+
+    log.into()
+}
+
+pub(super) fn traces(resource_spans: Vec<ResourceSpans>) -> Vec<Event> {
+    resource_spans
+        .into_iter()
+        .flat_map(|resource_spans| {
+            let resource = resource_spans.resource.map(resource_value);
+            resource_spans
+                .scope_spans
+                .into_iter()
+                .flat_map(move |scope_spans| {
+                    let resource = resource.clone();
+                    let scope = scope_spans.scope.map(scope_value);
+                    scope_spans
+                        .spans
+                        .into_iter()
+                        .map(move |span| trace_event(span, resource.clone(), scope.clone()))
+                })
+        })
+        .collect()
+}
+
+fn trace_event(span: Span, resource: Option<Value>, scope: Option<Value>) -> Event {
+    let kind = match span.kind() {
+        span::SpanKind::Unspecified => None,
+        span::SpanKind::Internal => Some("internal"),
+        span::SpanKind::Server => Some("server"),
+        span::SpanKind::Client => Some("client"),
+        span::SpanKind::Producer => Some("producer"),
+        span::SpanKind::Consumer => Some("consumer"),
+    };
+
+    let mut fields = BTreeMap::new();
+    insert_some(&mut fields, "trace_id", id(&span.trace_id));
+    insert_some(&mut fields, "span_id", id(&span.span_id));
+    insert_some(&mut fields, "parent_span_id", id(&span.parent_span_id));
+    insert_some(&mut fields, "trace_state", non_empty(span.trace_state));
+    fields.insert("name".to_owned(), span.name.into());
+    insert_some(&mut fields, "kind", kind.map(Value::from));
+    insert_some(
+        &mut fields,
+        "start_timestamp",
+        timestamp(span.start_time_unix_nano).map(Value::from),
+    );
+    insert_some(
+        &mut fields,
+        "end_timestamp",
+        timestamp(span.end_time_unix_nano).map(Value::from),
+    );
+    fields.insert("attributes".to_owned(), attributes(span.attributes));
+    fields.insert(
+        "dropped_attributes_count".to_owned(),
+        span.dropped_attributes_count.into(),
+    );
+    fields.insert(
+        "events".to_owned(),
+        span.events
+            .into_iter()
+            .map(|event| {
+                let mut fields = BTreeMap::new();
+                fields.insert("name".to_owned(), event.name.into());
+                insert_some(
+                    &mut fields,
+                    "timestamp",
+                    timestamp(event.time_unix_nano).map(Value::from),
+                );
+                fields.insert("attributes".to_owned(), attributes(event.attributes));
+                fields.insert(
+                    "dropped_attributes_count".to_owned(),
+                    event.dropped_attributes_count.into(),
+                );
+                Value::from(fields)
+            })
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    fields.insert(
+        "dropped_events_count".to_owned(),
+        span.dropped_events_count.into(),
+    );
+    fields.insert(
+        "links".to_owned(),
+        span.links
+            .into_iter()
+            .map(|link| {
+                let mut fields = BTreeMap::new();
+                insert_some(&mut fields, "trace_id", id(&link.trace_id));
+                insert_some(&mut fields, "span_id", id(&link.span_id));
+                insert_some(&mut fields, "trace_state", non_empty(link.trace_state));
+                fields.insert("attributes".to_owned(), attributes(link.attributes));
+                fields.insert(
+                    "dropped_attributes_count".to_owned(),
+                    link.dropped_attributes_count.into(),
+                );
+                Value::from(fields)
+            })
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    fields.insert(
+        "dropped_links_count".to_owned(),
+        span.dropped_links_count.into(),
+    );
+    if let Some(status) = span.status {
+        let code = match status.code() {
+            status::StatusCode::Unset => "unset",
+            status::StatusCode::Ok => "ok",
+            status::StatusCode::Error => "error",
+        };
+        let mut status_fields = BTreeMap::new();
+        status_fields.insert("code".to_owned(), code.into());
+        insert_some(&mut status_fields, "message", non_empty(status.message));
+        fields.insert("status".to_owned(), status_fields.into());
+    }
+    insert_some(&mut fields, "resources", resource);
+    insert_some(&mut fields, "scope", scope);
+    fields.insert(
+        log_schema().source_type_key().to_owned(),
+        SOURCE_TYPE.into(),
+    );
+
+    TraceEvent::from(fields).into()
+}
+
+pub(super) fn metrics(
+    resource_metrics: Vec<ResourceMetrics>,
+    exponential_histograms: ExponentialHistogramMode,
+) -> Vec<Event> {
+    resource_metrics
+        .into_iter()
+        .flat_map(|resource_metrics| {
+            let resource_tags = resource_metrics
+                .resource
+                .map(|resource| tags(&resource.attributes))
+                .unwrap_or_default();
+            resource_metrics
+                .scope_metrics
+                .into_iter()
+                .flat_map(|scope_metrics| scope_metrics.metrics)
+                .flat_map(move |metric| {
+                    convert_metric(metric, &resource_tags, exponential_histograms)
+                })
+        })
+        .map(Event::from)
+        .collect()
+}
+
+fn convert_metric(
+    metric: OtlpMetric,
+    resource_tags: &MetricTags,
+    exponential_histograms: ExponentialHistogramMode,
+) -> Vec<Metric> {
+    let name = metric.name;
+    let build = |kind: MetricKind, value: MetricValue, attributes: &[KeyValue], time: u64| {
+        let mut tags = resource_tags.clone();
+        tags.extend(self::tags(attributes));
+        Metric::new(name.clone(), kind, value)
+            .with_timestamp(timestamp(time))
+            .with_tags((!tags.is_empty()).then(|| tags))
+    };
+
+    match metric.data {
+        Some(metric::Data::Gauge(gauge)) => number_points(gauge.data_points)
+            .map(|(point, value)| {
+                build(
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                    &point.attributes,
+                    point.time_unix_nano,
+                )
+            })
+            .collect(),
+        Some(metric::Data::Sum(sum)) => {
+            let kind = metric_kind(sum.aggregation_temporality());
+            number_points(sum.data_points)
+                .map(|(point, value)| {
+                    // Sums that may go down can't be represented by counters.
+                    let value = if sum.is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    build(kind, value, &point.attributes, point.time_unix_nano)
+                })
+                .collect()
+        }
+        Some(metric::Data::Histogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality());
+            histogram
+                .data_points
+                .into_iter()
+                .filter(has_value(|point: &HistogramDataPoint| point.flags))
+                .map(|point| {
+                    build(
+                        kind,
+                        histogram_value(&point),
+                        &point.attributes,
+                        point.time_unix_nano,
+                    )
+                })
+                .collect()
+        }
+        Some(metric::Data::ExponentialHistogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality());
+            histogram
+                .data_points
+                .into_iter()
+                .filter(has_value(|point: &ExponentialHistogramDataPoint| {
+                    point.flags
+                }))
+                .filter_map(|point| {
+                    match exponential_histogram_value(&point, exponential_histograms) {
+                        Some(value) => {
+                            Some(build(kind, value, &point.attributes, point.time_unix_nano))
+                        }
+                        None => {
+                            emit!(OpentelemetryExponentialHistogramRangeError {
+                                name: &name,
+                                scale: point.scale,
+                            });
+                            None
+                        }
+                    }
+                })
+                .collect()
+        }
+        Some(metric::Data::Summary(summary)) => summary
+            .data_points
+            .into_iter()
+            .filter(has_value(|point: &SummaryDataPoint| point.flags))
+            .map(|point| {
+                build(
+                    MetricKind::Absolute,
+                    summary_value(&point),
+                    &point.attributes,
+                    point.time_unix_nano,
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Delta temporality reports the change since the last report, which is what incremental
+/// metrics are.
+const fn metric_kind(temporality: AggregationTemporality) -> MetricKind {
+    match temporality {
+        AggregationTemporality::Delta => MetricKind::Incremental,
+        AggregationTemporality::Cumulative | AggregationTemporality::Unspecified => {
+            MetricKind::Absolute
+        }
+    }
+}
+
+fn has_value<P>(flags: impl Fn(&P) -> u32) -> impl Fn(&P) -> bool {
+    move |point| flags(point) & FLAG_NO_RECORDED_VALUE == 0
+}
+
+fn number_points(points: Vec<NumberDataPoint>) -> impl Iterator<Item = (NumberDataPoint, f64)> {
+    points
+        .into_iter()
+        .filter(has_value(|point: &NumberDataPoint| point.flags))
+        .filter_map(|point| {
+            let value = match point.value? {
+                number_data_point::Value::AsDouble(value) => value,
+                number_data_point::Value::AsInt(value) => value as f64,
+            };
+            Some((point, value))
+        })
+}
+
+fn histogram_value(point: &HistogramDataPoint) -> MetricValue {
+    // The last bucket count has no explicit bound, it holds everything above the last one.
+    MetricValue::AggregatedHistogram {
+        buckets: point
+            .explicit_bounds
+            .iter()
+            .zip(&point.bucket_counts)
+            .map(|(&upper_limit, &count)| Bucket {
+                upper_limit,
+                count: saturating_u32(count),
+            })
+            .collect(),
+        count: saturating_u32(point.count),
+        sum: point.sum,
+    }
+}
+
+fn exponential_histogram_value(
+    point: &ExponentialHistogramDataPoint,
+    mode: ExponentialHistogramMode,
+) -> Option<MetricValue> {
+    let buckets = exponential_buckets(point)?;
+    Some(match mode {
+        ExponentialHistogramMode::AggregatedHistogram => MetricValue::AggregatedHistogram {
+            buckets,
+            count: saturating_u32(point.count),
+            sum: point.sum,
+        },
+        ExponentialHistogramMode::Sketch => {
+            let mut sketch = AgentDDSketch::with_agent_defaults();
+            sketch.insert_interpolate_buckets(buckets);
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            }
+        }
+    })
+}
+
+/// Lays out the buckets of an exponential histogram in ascending order. The bucket at index `i`
+/// holds the values in `(base^i, base^(i + 1)]`, or their negation for negative buckets, where
+/// `base = 2^(2^-scale)`.
+///
+/// Returns `None` if the scale or a bucket index is out of range.
+fn exponential_buckets(point: &ExponentialHistogramDataPoint) -> Option<Vec<Bucket>> {
+    let base = 2f64.powf(2f64.powi(point.scale.checked_neg()?));
+    if !base.is_finite() || base <= 1.0 {
+        return None;
+    }
+    let bounds = |buckets: &Option<Buckets>| {
+        buckets
+            .iter()
+            .flat_map(|buckets| {
+                buckets
+                    .bucket_counts
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, &count)| {
+                        let index = i32::try_from(i)
+                            .ok()
+                            .and_then(|i| buckets.offset.checked_add(i))?;
+                        Some((index, count))
+                    })
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+
+    let negative = bounds(&point.negative)?
+        .into_iter()
+        .rev()
+        .map(|(index, count)| Bucket {
+            upper_limit: -base.powi(index),
+            count: saturating_u32(count),
+        });
+    let zero = (point.zero_count > 0).then(|| Bucket {
+        upper_limit: 0.0,
+        count: saturating_u32(point.zero_count),
+    });
+    let positive = bounds(&point.positive)?
+        .into_iter()
+        .map(|(index, count)| {
+            Some(Bucket {
+                upper_limit: base.powi(index.checked_add(1)?),
+                count: saturating_u32(count),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(negative.chain(zero).chain(positive).collect())
+}
+
+fn summary_value(point: &SummaryDataPoint) -> MetricValue {
+    MetricValue::AggregatedSummary {
+        quantiles: point
+            .quantile_values
+            .iter()
+            .map(|quantile| Quantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        count: saturating_u32(point.count),
+        sum: point.sum,
+    }
+}
+
+fn saturating_u32(count: u64) -> u32 {
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+fn resource_value(resource: Resource) -> Value {
+    attributes(resource.attributes)
+}
+
+fn scope_value(scope: InstrumentationScope) -> Value {
+    let mut fields = BTreeMap::new();
+    insert_some(&mut fields, "name", non_empty(scope.name));
+    insert_some(&mut fields, "version", non_empty(scope.version));
+    if !scope.attributes.is_empty() {
+        fields.insert("attributes".to_owned(), attributes(scope.attributes));
+    }
+    fields.into()
+}
+
+fn attributes(attributes: Vec<KeyValue>) -> Value {
+    attributes
+        .into_iter()
+        .filter_map(|KeyValue { key, value }| Some((key, value.and_then(any_value)?)))
+        .collect::<BTreeMap<_, _>>()
+        .into()
+}
+
+/// Metric tags are flat strings, so nested values are rendered as JSON.
+fn tags(attributes: &[KeyValue]) -> MetricTags {
+    attributes
+        .iter()
+        .filter_map(|KeyValue { key, value }| {
+            let value = match value.as_ref()?.value.as_ref()? {
+                any_value::Value::StringValue(value) => value.clone(),
+                any_value::Value::BoolValue(value) => value.to_string(),
+                any_value::Value::IntValue(value) => value.to_string(),
+                any_value::Value::DoubleValue(value) => value.to_string(),
+                any_value::Value::BytesValue(value) => hex::encode(value),
+                _ => serde_json::to_string(&any_value(value.clone()?)?).ok()?,
+            };
+            Some((key.clone(), value))
+        })
+        .collect()
+}
+
+fn any_value(value: AnyValue) -> Option<Value> {
+    Some(match value.value? {
+        any_value::Value::StringValue(value) => value.into(),
+        any_value::Value::BoolValue(value) => value.into(),
+        any_value::Value::IntValue(value) => value.into(),
+        any_value::Value::DoubleValue(value) => NotNan::new(value).ok()?.into(),
+        any_value::Value::BytesValue(value) => bytes::Bytes::from(value).into(),
+        any_value::Value::ArrayValue(array) => array
+            .values
+            .into_iter()
+            .map(|value| any_value(value).unwrap_or(Value::Null))
+            .collect::<Vec<_>>()
+            .into(),
+        any_value::Value::KvlistValue(list) => attributes(list.values),
+    })
+}
+
+/// Trace and span IDs are rendered in hex, as in the W3C trace context.
+fn id(id: &[u8]) -> Option<Value> {
+    (!id.is_empty()).then(|| hex::encode(id).into())
+}
+
+fn non_empty(value: String) -> Option<Value> {
+    (!value.is_empty()).then(|| value.into())
+}
+
+/// A zero time means the time is unknown.
+fn timestamp(nanos: u64) -> Option<DateTime<Utc>> {
+    (nanos != 0).then(|| Utc.timestamp_nanos(nanos as i64))
+}
+
+fn insert_some(fields: &mut BTreeMap<String, Value>, key: &str, value: Option<Value>) {
+    if let Some(value) = value {
+        fields.insert(key.to_owned(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::opentelemetry::{
+        common::v1::ArrayValue,
+        logs::v1::ScopeLogs,
+        metrics::v1::{ExponentialHistogram, Gauge, ScopeMetrics, Sum},
+        trace::v1::{ScopeSpans, Status},
+    };
+
+    fn string(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.to_owned())),
+        })
+    }
+
+    fn key_value(key: &str, value: Option<AnyValue>) -> KeyValue {
+        KeyValue {
+            key: key.to_owned(),
+            value,
+        }
+    }
+
+    fn resource() -> Option<Resource> {
+        Some(Resource {
+            attributes: vec![key_value("service.name", string("checkout"))],
+            dropped_attributes_count: 0,
+        })
+    }
+
+    fn metric_event(metric: OtlpMetric) -> Metric {
+        let mut events = metrics(
+            vec![ResourceMetrics {
+                resource: resource(),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![metric],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+            ExponentialHistogramMode::AggregatedHistogram,
+        );
+        assert_eq!(events.len(), 1);
+        events.remove(0).into_metric()
+    }
+
+    fn number_point(value: f64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![key_value("host", string("a"))],
+            start_time_unix_nano: 0,
+            time_unix_nano: 1_579_134_612_000_000_000,
+            exemplars: vec![],
+            flags: 0,
+            value: Some(number_data_point::Value::AsDouble(value)),
+        }
+    }
+
+    #[test]
+    fn converts_log_records() {
+        let events = logs(vec![ResourceLogs {
+            resource: resource(),
+            scope_logs: vec![ScopeLogs {
+                scope: Some(InstrumentationScope {
+                    name: "http".to_owned(),
+                    version: String::new(),
+                    attributes: vec![],
+                    dropped_attributes_count: 0,
+                }),
+                log_records: vec![LogRecord {
+                    time_unix_nano: 1_579_134_612_000_000_011,
+                    observed_time_unix_nano: 0,
+                    severity_number: 9,
+                    severity_text: "INFO".to_owned(),
+                    body: string("request served"),
+                    attributes: vec![key_value(
+                        "tags",
+                        Some(AnyValue {
+                            value: Some(any_value::Value::ArrayValue(ArrayValue {
+                                values: vec![string("a").unwrap()],
+                            })),
+                        }),
+                    )],
+                    dropped_attributes_count: 0,
+                    flags: 1,
+                    trace_id: vec![0xab; 16],
+                    span_id: vec![0x01; 8],
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }]);
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "request served".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp_nanos(1_579_134_612_000_000_011).into()
+        );
+        assert_eq!(log["severity_text"], "INFO".into());
+        assert_eq!(log["severity_number"], 9.into());
+        assert_eq!(log["trace_id"], "ab".repeat(16).into());
+        assert_eq!(log["span_id"], "0101010101010101".into());
+        assert_eq!(log["attributes.tags[0]"], "a".into());
+        assert_eq!(log["resources.\"service.name\""], "checkout".into());
+        assert_eq!(log["scope.name"], "http".into());
+        assert!(!log.contains("observed_timestamp"));
+    }
+
+    #[test]
+    fn converts_sums_by_temporality() {
+        let metric = metric_event(OtlpMetric {
+            name: "requests".to_owned(),
+            description: String::new(),
+            unit: String::new(),
+            data: Some(metric::Data::Sum(Sum {
+                data_points: vec![number_point(3.0)],
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+                is_monotonic: true,
+            })),
+        });
+
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 3.0 });
+        assert_eq!(metric.tag_value("host"), Some("a".to_owned()));
+        assert_eq!(
+            metric.tag_value("service.name"),
+            Some("checkout".to_owned())
+        );
+
+        let metric = metric_event(OtlpMetric {
+            name: "connections".to_owned(),
+            description: String::new(),
+            unit: String::new(),
+            data: Some(metric::Data::Sum(Sum {
+                data_points: vec![number_point(-2.0)],
+                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                is_monotonic: false,
+            })),
+        });
+
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        assert_eq!(metric.value(), &MetricValue::Gauge { value: -2.0 });
+    }
+
+    #[test]
+    fn skips_points_without_value() {
+        let mut point = number_point(1.0);
+        point.flags = FLAG_NO_RECORDED_VALUE;
+        let events = metrics(
+            vec![ResourceMetrics {
+                resource: None,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![OtlpMetric {
+                        name: "up".to_owned(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(metric::Data::Gauge(Gauge {
+                            data_points: vec![point],
+                        })),
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+            ExponentialHistogramMode::AggregatedHistogram,
+        );
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn converts_exponential_histograms() {
+        let metric = metric_event(OtlpMetric {
+            name: "latency".to_owned(),
+            description: String::new(),
+            unit: "ms".to_owned(),
+            data: Some(metric::Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![ExponentialHistogramDataPoint {
+                    attributes: vec![],
+                    start_time_unix_nano: 0,
+                    time_unix_nano: 0,
+                    count: 7,
+                    sum: 10.0,
+                    scale: 0,
+                    zero_count: 1,
+                    positive: Some(Buckets {
+                        offset: 0,
+                        bucket_counts: vec![2, 3],
+                    }),
+                    negative: Some(Buckets {
+                        offset: 1,
+                        bucket_counts: vec![1],
+                    }),
+                    flags: 0,
+                    exemplars: vec![],
+                }],
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+            })),
+        });
+
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: -2.0,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 0.0,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 2
+                    },
+                    Bucket {
+                        upper_limit: 4.0,
+                        count: 3
+                    },
+                ],
+                count: 7,
+                sum: 10.0,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_exponential_histograms_out_of_range() {
+        let point = |scale, offset, bucket_counts| ExponentialHistogramDataPoint {
+            attributes: vec![],
+            start_time_unix_nano: 0,
+            time_unix_nano: 0,
+            count: 2,
+            sum: 1.0,
+            scale,
+            zero_count: 0,
+            positive: Some(Buckets {
+                offset,
+                bucket_counts,
+            }),
+            negative: None,
+            flags: 0,
+            exemplars: vec![],
+        };
+
+        assert!(exponential_buckets(&point(i32::MIN, 0, vec![1])).is_none());
+        assert!(exponential_buckets(&point(i32::MAX, 0, vec![1])).is_none());
+        assert!(exponential_buckets(&point(0, i32::MAX - 1, vec![1, 1])).is_none());
+        assert!(exponential_buckets(&point(0, i32::MAX, vec![1])).is_none());
+        assert!(exponential_buckets(&point(0, i32::MIN, vec![1])).is_some());
+
+        let events = metrics(
+            vec![ResourceMetrics {
+                resource: None,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![OtlpMetric {
+                        name: "latency".to_owned(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(metric::Data::ExponentialHistogram(ExponentialHistogram {
+                            data_points: vec![point(i32::MIN, 0, vec![1])],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                        })),
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+            ExponentialHistogramMode::Sketch,
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn converts_spans() {
+        let events = traces(vec![ResourceSpans {
+            resource: resource(),
+            scope_spans: vec![ScopeSpans {
+                scope: None,
+                spans: vec![Span {
+                    trace_id: vec![0x01; 16],
+                    span_id: vec![0x02; 8],
+                    trace_state: String::new(),
+                    parent_span_id: vec![],
+                    name: "GET /".to_owned(),
+                    kind: span::SpanKind::Server as i32,
+                    start_time_unix_nano: 1_579_134_612_000_000_000,
+                    end_time_unix_nano: 1_579_134_612_500_000_000,
+                    attributes: vec![key_value("http.method", string("GET"))],
+                    dropped_attributes_count: 0,
+                    events: vec![],
+                    dropped_events_count: 0,
+                    links: vec![],
+                    dropped_links_count: 0,
+                    status: Some(Status {
+                        message: String::new(),
+                        code: status::StatusCode::Error as i32,
+                    }),
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }]);
+
+        assert_eq!(events.len(), 1);
+        let trace = events[0].as_trace();
+        assert_eq!(trace.get("trace_id"), Some(&"01".repeat(16).into()));
+        assert_eq!(trace.get("parent_span_id"), None);
+        assert_eq!(trace.get("name"), Some(&"GET /".into()));
+        assert_eq!(trace.get("kind"), Some(&"server".into()));
+        assert_eq!(trace.get("status.code"), Some(&"error".into()));
+        assert_eq!(trace.get("attributes.\"http.method\""), Some(&"GET".into()));
+    }
+}
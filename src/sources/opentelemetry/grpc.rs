@@ -0,0 +1,141 @@
+use std::net::SocketAddr;
+
+use futures::{FutureExt, StreamExt};
+use tonic::{
+    transport::{server::Connected as _, Server},
+    Request, Response, Status,
+};
+use tracing_futures::Instrument;
+use vector_core::{event::Event, ByteSizeOf};
+
+use super::{convert, DeliveryError, Pipeline, LOGS, METRICS, TRACES};
+use crate::{
+    internal_events::{EventsReceived, TcpBytesReceived},
+    proto::opentelemetry::collector::{
+        logs::v1::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        metrics::v1::{
+            metrics_service_server::{MetricsService, MetricsServiceServer},
+            ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        },
+        trace::v1::{
+            trace_service_server::{TraceService, TraceServiceServer},
+            ExportTraceServiceRequest, ExportTraceServiceResponse,
+        },
+    },
+    shutdown::{ShutdownSignal, ShutdownSignalToken},
+    sources::util::AfterReadExt as _,
+    tls::MaybeTlsSettings,
+};
+
+/// Serves the OTLP logs, metrics and trace services.
+#[derive(Clone)]
+struct Service {
+    pipeline: Pipeline,
+}
+
+impl Service {
+    async fn send(&self, output: &str, events: Vec<Event>) -> Result<(), Status> {
+        emit!(EventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        self.pipeline
+            .send(output, events)
+            .await
+            .map_err(|error| match error {
+                DeliveryError::Closed => Status::unavailable(error.to_string()),
+                DeliveryError::Errored => Status::internal(error.to_string()),
+                DeliveryError::Rejected => Status::data_loss(error.to_string()),
+            })
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let events = convert::logs(request.into_inner().resource_logs);
+        self.send(LOGS, events).await?;
+
+        Ok(Response::new(ExportLogsServiceResponse {
+            partial_success: None,
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let events = convert::metrics(
+            request.into_inner().resource_metrics,
+            self.pipeline.exponential_histograms,
+        );
+        self.send(METRICS, events).await?;
+
+        Ok(Response::new(ExportMetricsServiceResponse {
+            partial_success: None,
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportTraceServiceRequest>,
+    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+        let events = convert::traces(request.into_inner().resource_spans);
+        self.send(TRACES, events).await?;
+
+        Ok(Response::new(ExportTraceServiceResponse {
+            partial_success: None,
+        }))
+    }
+}
+
+pub(super) async fn run(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    pipeline: Pipeline,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let span = crate::trace::current_span();
+    let service = Service { pipeline };
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream().map(|result| {
+        result.map(|socket| {
+            let peer_addr = socket.connect_info().remote_addr;
+            socket.after_read(move |byte_size| {
+                emit!(TcpBytesReceived {
+                    byte_size,
+                    peer_addr,
+                })
+            })
+        })
+    });
+
+    Server::builder()
+        .trace_fn(move |_| span.clone())
+        .add_service(LogsServiceServer::new(service.clone()).accept_gzip())
+        .add_service(MetricsServiceServer::new(service.clone()).accept_gzip())
+        .add_service(TraceServiceServer::new(service).accept_gzip())
+        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .in_current_span()
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
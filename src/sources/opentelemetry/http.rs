@@ -0,0 +1,202 @@
+use bytes::Bytes;
+use futures::FutureExt;
+use http::StatusCode;
+use prost::Message;
+use vector_core::{event::Event, ByteSizeOf};
+use warp::{filters::BoxedFilter, path, reply::Response, Filter, Rejection};
+
+use super::{convert, DeliveryError, Pipeline, LOGS, METRICS, TRACES};
+use crate::{
+    internal_events::{HttpBytesReceived, HttpEventsReceived, OpentelemetryProtoDecodeError},
+    proto::opentelemetry::collector::{
+        logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+        metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+        trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
+    },
+    shutdown::ShutdownSignal,
+    sources::util::{decode, ErrorMessage},
+    tls::MaybeTlsListener,
+};
+
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+pub(super) async fn run(
+    listener: MaybeTlsListener,
+    protocol: &'static str,
+    pipeline: Pipeline,
+    shutdown: ShutdownSignal,
+) {
+    let span = crate::trace::current_span();
+    let routes = filters(protocol, pipeline)
+        .with(warp::trace(move |_info| span.clone()))
+        .recover(|rejection: Rejection| async move {
+            if let Some(error) = rejection.find::<ErrorMessage>() {
+                let json = warp::reply::json(error);
+                Ok(warp::reply::with_status(json, error.status_code()))
+            } else {
+                // other internal error - will return 500 internal server error
+                Err(rejection)
+            }
+        });
+
+    warp::serve(routes)
+        .serve_incoming_with_graceful_shutdown(listener.accept_stream(), shutdown.map(|_| ()))
+        .await;
+}
+
+fn filters(protocol: &'static str, pipeline: Pipeline) -> BoxedFilter<(Response,)> {
+    let logs = route(
+        path!("v1" / "logs"),
+        protocol,
+        pipeline.clone(),
+        |request: ExportLogsServiceRequest, _| {
+            (
+                LOGS,
+                convert::logs(request.resource_logs),
+                ExportLogsServiceResponse {
+                    partial_success: None,
+                }
+                .encode_to_vec(),
+            )
+        },
+    );
+    let metrics = route(
+        path!("v1" / "metrics"),
+        protocol,
+        pipeline.clone(),
+        |request: ExportMetricsServiceRequest, pipeline: &Pipeline| {
+            (
+                METRICS,
+                convert::metrics(request.resource_metrics, pipeline.exponential_histograms),
+                ExportMetricsServiceResponse {
+                    partial_success: None,
+                }
+                .encode_to_vec(),
+            )
+        },
+    );
+    let traces = route(
+        path!("v1" / "traces"),
+        protocol,
+        pipeline,
+        |request: ExportTraceServiceRequest, _| {
+            (
+                TRACES,
+                convert::traces(request.resource_spans),
+                ExportTraceServiceResponse {
+                    partial_success: None,
+                }
+                .encode_to_vec(),
+            )
+        },
+    );
+
+    logs.or(metrics).unify().or(traces).unify().boxed()
+}
+
+/// Builds the route of one signal. `convert` returns the output the events go to along with the
+/// encoded response.
+fn route<R, F>(
+    path: impl Filter<Extract = (), Error = Rejection> + Clone + Send + Sync + 'static,
+    protocol: &'static str,
+    pipeline: Pipeline,
+    convert: F,
+) -> BoxedFilter<(Response,)>
+where
+    R: Message + Default + Send + 'static,
+    F: Fn(R, &Pipeline) -> (&'static str, Vec<Event>, Vec<u8>) + Clone + Send + Sync + 'static,
+{
+    warp::post()
+        .and(path)
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::body::bytes())
+        .and_then(
+            move |path: warp::path::FullPath,
+                  content_type: Option<String>,
+                  encoding: Option<String>,
+                  body: Bytes| {
+                let pipeline = pipeline.clone();
+                let convert = convert.clone();
+                async move {
+                    emit!(HttpBytesReceived {
+                        byte_size: body.len(),
+                        http_path: path.as_str(),
+                        protocol,
+                    });
+
+                    let request = decode_request::<R>(
+                        path.as_str(),
+                        content_type.as_deref(),
+                        &encoding,
+                        body,
+                    )?;
+                    let (output, events, response) = convert(request, &pipeline);
+
+                    emit!(HttpEventsReceived {
+                        count: events.len(),
+                        byte_size: events.size_of(),
+                        http_path: path.as_str(),
+                        protocol,
+                    });
+
+                    pipeline.send(output, events).await.map_err(|error| {
+                        let status = match error {
+                            DeliveryError::Closed => StatusCode::SERVICE_UNAVAILABLE,
+                            DeliveryError::Errored => StatusCode::INTERNAL_SERVER_ERROR,
+                            DeliveryError::Rejected => StatusCode::BAD_REQUEST,
+                        };
+                        warp::reject::custom(ErrorMessage::new(status, error.to_string()))
+                    })?;
+
+                    Ok::<_, Rejection>(
+                        warp::http::Response::builder()
+                            .header("content-type", PROTOBUF_CONTENT_TYPE)
+                            .body(response.into())
+                            .expect("valid response"),
+                    )
+                }
+            },
+        )
+        .boxed()
+}
+
+/// Compares the media type only, as clients may add parameters such as `charset`.
+fn is_protobuf(content_type: &str) -> bool {
+    content_type.split(';').next().map_or(false, |media_type| {
+        media_type
+            .trim()
+            .eq_ignore_ascii_case(PROTOBUF_CONTENT_TYPE)
+    })
+}
+
+/// OTLP over HTTP also allows JSON payloads, which aren't supported yet.
+fn decode_request<R: Message + Default>(
+    path: &str,
+    content_type: Option<&str>,
+    encoding: &Option<String>,
+    body: Bytes,
+) -> Result<R, Rejection> {
+    if !content_type.map_or(false, is_protobuf) {
+        return Err(warp::reject::custom(ErrorMessage::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!(
+                "Unsupported content type, expected `{}`.",
+                PROTOBUF_CONTENT_TYPE
+            ),
+        )));
+    }
+
+    let body = decode(encoding, body).map_err(warp::reject::custom)?;
+    R::decode(body).map_err(|error| {
+        emit!(OpentelemetryProtoDecodeError {
+            error: &error,
+            http_path: path,
+        });
+        warp::reject::custom(ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Failed to decode request: {}.", error),
+        ))
+    })
+}
@@ -0,0 +1,317 @@
+mod convert;
+mod grpc;
+mod http;
+
+use std::net::SocketAddr;
+
+use futures::{future, FutureExt, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use vector_core::event::{BatchNotifier, BatchStatus, Event};
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
+        SourceContext, SourceDescription,
+    },
+    internal_events::StreamClosedError,
+    serde::bool_or_struct,
+    sources::Source,
+    tls::{MaybeTlsSettings, TlsConfig},
+    SourceSender,
+};
+
+pub const LOGS: &str = "logs";
+pub const METRICS: &str = "metrics";
+pub const TRACES: &str = "traces";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    grpc: Option<ListenerConfig>,
+    http: Option<ListenerConfig>,
+    #[serde(default)]
+    exponential_histograms: ExponentialHistogramMode,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct ListenerConfig {
+    address: SocketAddr,
+    tls: Option<TlsConfig>,
+}
+
+/// How exponential histograms are converted, as Vector has no equivalent metric type.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExponentialHistogramMode {
+    /// Converts each bucket to the bucket of an aggregated histogram, bounded by the exponential
+    /// bucket boundaries.
+    AggregatedHistogram,
+    /// Interpolates the buckets into a sketch.
+    Sketch,
+}
+
+impl Default for ExponentialHistogramMode {
+    fn default() -> Self {
+        Self::AggregatedHistogram
+    }
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: Some(ListenerConfig {
+                address: "0.0.0.0:4317".parse().unwrap(),
+                tls: None,
+            }),
+            http: Some(ListenerConfig {
+                address: "0.0.0.0:4318".parse().unwrap(),
+                tls: None,
+            }),
+            exponential_histograms: Default::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        if self.grpc.is_none() && self.http.is_none() {
+            return Err("At least one of `grpc` or `http` must be configured.".into());
+        }
+
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let pipeline = Pipeline {
+            out: cx.out,
+            acknowledgements,
+            exponential_histograms: self.exponential_histograms,
+        };
+
+        let mut servers = Vec::new();
+        if let Some(grpc) = &self.grpc {
+            let tls = MaybeTlsSettings::from_config(&grpc.tls, true)?;
+            servers.push(
+                grpc::run(grpc.address, tls, pipeline.clone(), cx.shutdown.clone())
+                    .map_err(|error| error!(message = "Source future failed.", %error))
+                    .boxed(),
+            );
+        }
+        if let Some(http) = &self.http {
+            let tls = MaybeTlsSettings::from_config(&http.tls, true)?;
+            let listener = tls.bind(&http.address).await?;
+            servers.push(
+                http::run(listener, tls.http_protocol_name(), pipeline, cx.shutdown)
+                    .map(Ok)
+                    .boxed(),
+            );
+        }
+
+        Ok(Box::pin(future::try_join_all(servers).map_ok(|_| ())))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![
+            Output::from((LOGS, DataType::Log)),
+            Output::from((METRICS, DataType::Metric)),
+            Output::from((TRACES, DataType::Trace)),
+        ]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.grpc
+            .iter()
+            .chain(self.http.iter())
+            .map(|listener| Resource::tcp(listener.address))
+            .collect()
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum DeliveryError {
+    #[snafu(display("Source is shutting down."))]
+    Closed,
+    #[snafu(display("Error delivering contents to sink."))]
+    Errored,
+    #[snafu(display("Contents failed to deliver to sink."))]
+    Rejected,
+}
+
+/// Forwards the converted events of both protocols to the outputs of the source.
+#[derive(Clone)]
+struct Pipeline {
+    out: SourceSender,
+    acknowledgements: bool,
+    exponential_histograms: ExponentialHistogramMode,
+}
+
+impl Pipeline {
+    /// Sends the events to the given output, waiting for their delivery when acknowledgements
+    /// are enabled.
+    async fn send(&self, output: &str, mut events: Vec<Event>) -> Result<(), DeliveryError> {
+        let count = events.len();
+        let receiver = BatchNotifier::maybe_apply_to_events(self.acknowledgements, &mut events);
+
+        self.out
+            .clone()
+            .send_batch_named(output, events)
+            .await
+            .map_err(|error| {
+                emit!(StreamClosedError { error, count });
+                DeliveryError::Closed
+            })?;
+
+        match receiver {
+            None => Ok(()),
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Ok(()),
+                BatchStatus::Errored => Err(DeliveryError::Errored),
+                BatchStatus::Rejected => Err(DeliveryError::Rejected),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use prost::Message;
+    use vector_core::{
+        config::log_schema,
+        event::{EventContainer, EventStatus},
+    };
+
+    use super::*;
+    use crate::{
+        proto::opentelemetry::{
+            collector::logs::v1::{
+                logs_service_client::LogsServiceClient, ExportLogsServiceRequest,
+            },
+            common::v1::{any_value, AnyValue},
+            logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        },
+        test_util::{self, next_addr},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    fn logs_request(message: &str) -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: None,
+                scope_logs: vec![ScopeLogs {
+                    scope: None,
+                    log_records: vec![LogRecord {
+                        body: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue(message.to_owned())),
+                        }),
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_grpc_and_http() {
+        let grpc_address = next_addr();
+        let http_address = next_addr();
+        let config = OpentelemetryConfig {
+            grpc: Some(ListenerConfig {
+                address: grpc_address,
+                tls: None,
+            }),
+            http: Some(ListenerConfig {
+                address: http_address,
+                tls: None,
+            }),
+            exponential_histograms: Default::default(),
+            acknowledgements: Default::default(),
+        };
+
+        let (mut sender, _) = SourceSender::new_test();
+        let mut logs = sender.add_outputs(EventStatus::Delivered, LOGS.to_owned());
+        let source = config
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        test_util::wait_for_tcp(grpc_address).await;
+        test_util::wait_for_tcp(http_address).await;
+
+        LogsServiceClient::connect(format!("http://{}", grpc_address))
+            .await
+            .unwrap()
+            .export(logs_request("over grpc"))
+            .await
+            .unwrap();
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/v1/logs", http_address))
+            .header("content-type", "application/x-protobuf; charset=utf-8")
+            .body(logs_request("over http").encode_to_vec())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let mut messages = Vec::new();
+        for _ in 0..2 {
+            let events = logs.next().await.unwrap();
+            for event in events.into_events() {
+                messages.push(event.as_log()[log_schema().message_key()].to_string_lossy());
+            }
+        }
+        assert_eq!(messages, vec!["over grpc", "over http"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_other_content_types() {
+        let address = next_addr();
+        let config = OpentelemetryConfig {
+            grpc: None,
+            http: Some(ListenerConfig { address, tls: None }),
+            exponential_histograms: Default::default(),
+            acknowledgements: Default::default(),
+        };
+
+        let (sender, _) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        test_util::wait_for_tcp(address).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/v1/logs", address))
+            .header("content-type", "application/json")
+            .body("{}")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 415);
+    }
+}
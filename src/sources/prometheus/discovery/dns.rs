@@ -0,0 +1,142 @@
+//! Discovery of targets through DNS SRV, A or AAAA records.
+//!
+//! <https://prometheus.io/docs/prometheus/latest/configuration/configuration/#dns_sd_config>
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::sync::watch;
+use trust_dns_resolver::{error::ResolveError, TokioAsyncResolver};
+
+use super::{default_refresh_interval_secs, next_refresh, Labels, ADDRESS_LABEL};
+use crate::internal_events::PrometheusDiscoveryError;
+
+const NAME_LABEL: &str = "__meta_dns_name";
+const SRV_RECORD_TARGET_LABEL: &str = "__meta_dns_srv_record_target";
+const SRV_RECORD_PORT_LABEL: &str = "__meta_dns_srv_record_port";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DnsDiscoveryConfig {
+    names: Vec<String>,
+    #[serde(default)]
+    record_type: RecordType,
+    /// The port of the targets, required for A and AAAA records.
+    port: Option<u16>,
+    #[serde(default = "default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RecordType {
+    #[derivative(Default)]
+    Srv,
+    A,
+    Aaaa,
+}
+
+#[derive(Debug, Snafu)]
+enum DnsDiscoveryError {
+    #[snafu(display(
+        "A port is required to discover targets through {:?} records",
+        record_type
+    ))]
+    MissingPort { record_type: RecordType },
+    #[snafu(display("Failed to look up {:?}: {}", name, source))]
+    Lookup { name: String, source: ResolveError },
+}
+
+impl DnsDiscoveryConfig {
+    /// Validates the configuration and builds a resolver using the system configuration.
+    pub fn resolver(&self) -> crate::Result<TokioAsyncResolver> {
+        if self.record_type != RecordType::Srv && self.port.is_none() {
+            return Err(DnsDiscoveryError::MissingPort {
+                record_type: self.record_type,
+            }
+            .into());
+        }
+        Ok(TokioAsyncResolver::tokio_from_system_conf()?)
+    }
+}
+
+pub async fn run(
+    config: DnsDiscoveryConfig,
+    resolver: TokioAsyncResolver,
+    tx: watch::Sender<Vec<Labels>>,
+) {
+    let interval = Duration::from_secs(config.refresh_interval_secs);
+    let mut targets = vec![Vec::new(); config.names.len()];
+
+    loop {
+        // The targets of a name are kept while it fails to resolve.
+        for (name, name_targets) in config.names.iter().zip(targets.iter_mut()) {
+            match lookup(&resolver, name, config.record_type, config.port).await {
+                Ok(found) => *name_targets = found,
+                Err(error) => emit!(PrometheusDiscoveryError {
+                    error: error.into(),
+                    mechanism: "dns",
+                }),
+            }
+        }
+
+        if tx.send(targets.concat()).is_err() || !next_refresh(&tx, interval).await {
+            break;
+        }
+    }
+}
+
+async fn lookup(
+    resolver: &TokioAsyncResolver,
+    name: &str,
+    record_type: RecordType,
+    port: Option<u16>,
+) -> Result<Vec<Labels>, DnsDiscoveryError> {
+    let lookup_error = |source| DnsDiscoveryError::Lookup {
+        name: name.to_owned(),
+        source,
+    };
+    let target = |address: String| {
+        let mut labels = Labels::new();
+        labels.insert(ADDRESS_LABEL.to_owned(), address);
+        labels.insert(NAME_LABEL.to_owned(), name.to_owned());
+        labels
+    };
+    let port = port.unwrap_or_default();
+
+    Ok(match record_type {
+        RecordType::Srv => resolver
+            .srv_lookup(name)
+            .await
+            .map_err(lookup_error)?
+            .iter()
+            .map(|srv| {
+                let host = srv.target().to_utf8();
+                let host = host.trim_end_matches('.');
+                let mut labels = target(format!("{}:{}", host, srv.port()));
+                labels.insert(SRV_RECORD_TARGET_LABEL.to_owned(), host.to_owned());
+                labels.insert(SRV_RECORD_PORT_LABEL.to_owned(), srv.port().to_string());
+                labels
+            })
+            .collect(),
+        RecordType::A => resolver
+            .ipv4_lookup(name)
+            .await
+            .map_err(lookup_error)?
+            .iter()
+            .map(|ip| target(SocketAddr::new(IpAddr::V4(*ip), port).to_string()))
+            .collect(),
+        RecordType::Aaaa => resolver
+            .ipv6_lookup(name)
+            .await
+            .map_err(lookup_error)?
+            .iter()
+            .map(|ip| target(SocketAddr::new(IpAddr::V6(*ip), port).to_string()))
+            .collect(),
+    })
+}
@@ -0,0 +1,177 @@
+//! Discovery of targets listed in files using the `file_sd` format of Prometheus.
+//!
+//! <https://prometheus.io/docs/prometheus/latest/configuration/configuration/#file_sd_config>
+
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::watch;
+
+use super::{default_refresh_interval_secs, next_refresh, Labels, ADDRESS_LABEL};
+use crate::internal_events::PrometheusDiscoveryError;
+
+const FILEPATH_LABEL: &str = "__meta_filepath";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileDiscoveryConfig {
+    /// The files to read the targets from. Glob patterns are supported.
+    paths: Vec<PathBuf>,
+    #[serde(default = "default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+}
+
+#[derive(Debug, Snafu)]
+enum FileDiscoveryError {
+    #[snafu(display("Invalid glob pattern {:?}: {}", pattern, source))]
+    InvalidPattern {
+        pattern: PathBuf,
+        source: glob::PatternError,
+    },
+    #[snafu(display("Failed to read {:?}: {}", path, source))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to parse {:?}: {}", path, source))]
+    ParseJson {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Failed to parse {:?}: {}", path, source))]
+    ParseYaml {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+}
+
+/// A group of targets sharing the same labels.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TargetGroup {
+    targets: Vec<String>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+}
+
+pub async fn run(config: FileDiscoveryConfig, tx: watch::Sender<Vec<Labels>>) {
+    let interval = Duration::from_secs(config.refresh_interval_secs);
+    let mut targets = BTreeMap::new();
+
+    loop {
+        // The targets of the files that can't be read anymore are kept until they are fixed.
+        let mut current = BTreeMap::new();
+        for path in expand_paths(&config.paths) {
+            match read_targets(&path).await {
+                Ok(file_targets) => {
+                    current.insert(path, file_targets);
+                }
+                Err(error) => {
+                    emit!(PrometheusDiscoveryError {
+                        error: error.into(),
+                        mechanism: "file",
+                    });
+                    if let Some(previous) = targets.remove(&path) {
+                        current.insert(path, previous);
+                    }
+                }
+            }
+        }
+        targets = current;
+
+        if tx
+            .send(targets.values().flatten().cloned().collect())
+            .is_err()
+            || !next_refresh(&tx, interval).await
+        {
+            break;
+        }
+    }
+}
+
+fn expand_paths(patterns: &[PathBuf]) -> Vec<PathBuf> {
+    patterns
+        .iter()
+        .flat_map(|pattern| {
+            match glob::glob(&pattern.to_string_lossy()).context(InvalidPatternSnafu {
+                pattern: pattern.clone(),
+            }) {
+                Ok(paths) => paths.filter_map(Result::ok).collect(),
+                Err(error) => {
+                    emit!(PrometheusDiscoveryError {
+                        error: error.into(),
+                        mechanism: "file",
+                    });
+                    Vec::new()
+                }
+            }
+        })
+        .collect()
+}
+
+async fn read_targets(path: &PathBuf) -> Result<Vec<Labels>, FileDiscoveryError> {
+    let content = tokio::fs::read(path)
+        .await
+        .context(ReadSnafu { path: path.clone() })?;
+    let groups: Vec<TargetGroup> = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => {
+            serde_json::from_slice(&content).context(ParseJsonSnafu { path: path.clone() })?
+        }
+        _ => serde_yaml::from_slice(&content).context(ParseYamlSnafu { path: path.clone() })?,
+    };
+
+    Ok(groups
+        .into_iter()
+        .flat_map(|group| {
+            let path = path.to_string_lossy().into_owned();
+            group.targets.into_iter().map(move |target| {
+                let mut labels = group.labels.clone();
+                labels.insert(ADDRESS_LABEL.to_owned(), target);
+                labels.insert(FILEPATH_LABEL.to_owned(), path.clone());
+                labels
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_json_and_yaml_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("targets.json");
+        let yaml_path = dir.path().join("targets.yml");
+        std::fs::File::create(&json_path)
+            .unwrap()
+            .write_all(
+                br#"[{"targets": ["10.0.0.1:9100", "10.0.0.2:9100"], "labels": {"job": "node"}}]"#,
+            )
+            .unwrap();
+        std::fs::File::create(&yaml_path)
+            .unwrap()
+            .write_all(b"- targets: ['10.0.0.3:8080']\n")
+            .unwrap();
+
+        let config: FileDiscoveryConfig = toml::from_str(&format!(
+            r#"paths = ["{}/*"]"#,
+            dir.path().to_string_lossy()
+        ))
+        .unwrap();
+        let (tx, mut rx) = watch::channel(Vec::new());
+        tokio::spawn(run(config, tx));
+        rx.changed().await.unwrap();
+
+        let targets = rx.borrow().clone();
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets[0][ADDRESS_LABEL], "10.0.0.1:9100");
+        assert_eq!(targets[0]["job"], "node");
+        assert_eq!(targets[0][FILEPATH_LABEL], json_path.to_string_lossy());
+        assert_eq!(targets[2][ADDRESS_LABEL], "10.0.0.3:8080");
+        assert!(!targets[2].contains_key("job"));
+    }
+}
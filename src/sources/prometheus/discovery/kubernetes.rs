@@ -0,0 +1,406 @@
+//! Discovery of Kubernetes pods and services annotated with `prometheus.io/scrape: "true"`.
+//!
+//! The `prometheus.io/port`, `prometheus.io/path` and `prometheus.io/scheme` annotations override
+//! the port, path and scheme of the targets. Without a port annotation, each declared container
+//! or service port is a target.
+
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash, path::PathBuf};
+
+use futures::{stream, StreamExt};
+use k8s_openapi::api::core::v1::{Pod, Service};
+use kube::{
+    api::{Api, ListParams, ObjectMeta},
+    config::{self, KubeConfigOptions},
+    runtime::{reflector, watcher},
+    Client, Config as ClientConfig, Resource,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::sync::watch;
+
+use super::{Labels, ADDRESS_LABEL, METRICS_PATH_LABEL, SCHEME_LABEL};
+use crate::internal_events::PrometheusDiscoveryError;
+
+const SCRAPE_ANNOTATION: &str = "prometheus.io/scrape";
+const PORT_ANNOTATION: &str = "prometheus.io/port";
+const PATH_ANNOTATION: &str = "prometheus.io/path";
+const SCHEME_ANNOTATION: &str = "prometheus.io/scheme";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesDiscoveryConfig {
+    #[serde(default)]
+    role: Role,
+    /// The namespaces to discover targets in, all of them if empty.
+    #[serde(default)]
+    namespaces: Vec<String>,
+    kube_config_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[derivative(Default)]
+    Pod,
+    Service,
+}
+
+impl KubernetesDiscoveryConfig {
+    /// Builds the client from the configured kubeconfig, or the local kubeconfig, or the
+    /// in-cluster environment, like the `kubernetes_logs` source.
+    pub async fn client(&self) -> crate::Result<Client> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        Ok(Client::try_from(client_config)?)
+    }
+}
+
+pub async fn run(
+    config: KubernetesDiscoveryConfig,
+    client: Client,
+    tx: watch::Sender<Vec<Labels>>,
+) {
+    match config.role {
+        Role::Pod => discover(apis::<Pod>(client, &config.namespaces), tx, pod_targets).await,
+        Role::Service => {
+            discover(
+                apis::<Service>(client, &config.namespaces),
+                tx,
+                service_targets,
+            )
+            .await
+        }
+    }
+}
+
+/// Builds an API per configured namespace, so that permissions on those namespaces are enough,
+/// or a single API for all of them if none is configured.
+fn apis<K>(client: Client, namespaces: &[String]) -> Vec<Api<K>>
+where
+    K: Resource,
+    K::DynamicType: Default,
+{
+    if namespaces.is_empty() {
+        vec![Api::all(client)]
+    } else {
+        namespaces
+            .iter()
+            .map(|namespace| Api::namespaced(client.clone(), namespace))
+            .collect()
+    }
+}
+
+/// Watches the resources and sends the targets of all of them on each change.
+async fn discover<K, F>(apis: Vec<Api<K>>, tx: watch::Sender<Vec<Labels>>, targets: F)
+where
+    K: Resource + Clone + Debug + DeserializeOwned + Send + Sync + 'static,
+    K::DynamicType: Clone + Default + Eq + Hash,
+    F: Fn(&K) -> Vec<Labels>,
+{
+    let mut stores = Vec::with_capacity(apis.len());
+    let mut stream = stream::select_all(apis.into_iter().map(|api| {
+        let writer = reflector::store::Writer::default();
+        stores.push(writer.as_reader());
+        reflector(writer, watcher(api, ListParams::default())).boxed()
+    }));
+
+    loop {
+        tokio::select! {
+            _ = tx.closed() => break,
+            event = stream.next() => match event {
+                Some(Ok(_)) => {
+                    let discovered = stores
+                        .iter()
+                        .flat_map(|store| store.state())
+                        .flat_map(|resource| targets(&resource))
+                        .collect();
+                    if tx.send(discovered).is_err() {
+                        break;
+                    }
+                }
+                // The watcher recovers by listing the resources again.
+                Some(Err(error)) => emit!(PrometheusDiscoveryError {
+                    error: error.into(),
+                    mechanism: "kubernetes",
+                }),
+                None => break,
+            },
+        }
+    }
+}
+
+fn pod_targets(pod: &Pod) -> Vec<Labels> {
+    let metadata = &pod.metadata;
+    let annotations = metadata.annotations.clone().unwrap_or_default();
+    let ip = match pod
+        .status
+        .as_ref()
+        .and_then(|status| status.pod_ip.as_ref())
+    {
+        Some(ip) if is_scraped(&annotations) => ip,
+        _ => return Vec::new(),
+    };
+
+    let mut labels = resource_labels("pod", metadata, &annotations);
+    labels.insert("pod".to_owned(), metadata.name.clone().unwrap_or_default());
+    labels.insert("__meta_kubernetes_pod_ip".to_owned(), ip.clone());
+    if let Some(node_name) = pod.spec.as_ref().and_then(|spec| spec.node_name.clone()) {
+        labels.insert("__meta_kubernetes_pod_node_name".to_owned(), node_name);
+    }
+    if let Some(phase) = pod.status.as_ref().and_then(|status| status.phase.clone()) {
+        labels.insert("__meta_kubernetes_pod_phase".to_owned(), phase);
+    }
+
+    if let Some(port) = annotations.get(PORT_ANNOTATION) {
+        return vec![target(labels, format!("{}:{}", ip, port))];
+    }
+
+    pod.spec
+        .iter()
+        .flat_map(|spec| &spec.containers)
+        .flat_map(|container| {
+            container.ports.iter().flatten().map(|port| {
+                let mut labels = labels.clone();
+                labels.insert(
+                    "__meta_kubernetes_pod_container_name".to_owned(),
+                    container.name.clone(),
+                );
+                labels.insert(
+                    "__meta_kubernetes_pod_container_port_number".to_owned(),
+                    port.container_port.to_string(),
+                );
+                if let Some(name) = &port.name {
+                    labels.insert(
+                        "__meta_kubernetes_pod_container_port_name".to_owned(),
+                        name.clone(),
+                    );
+                }
+                target(labels, format!("{}:{}", ip, port.container_port))
+            })
+        })
+        .collect()
+}
+
+fn service_targets(service: &Service) -> Vec<Labels> {
+    let metadata = &service.metadata;
+    let annotations = metadata.annotations.clone().unwrap_or_default();
+    if !is_scraped(&annotations) {
+        return Vec::new();
+    }
+
+    let name = metadata.name.clone().unwrap_or_default();
+    let namespace = metadata.namespace.clone().unwrap_or_default();
+    let host = format!("{}.{}.svc", name, namespace);
+    let mut labels = resource_labels("service", metadata, &annotations);
+    labels.insert("service".to_owned(), name);
+
+    if let Some(port) = annotations.get(PORT_ANNOTATION) {
+        return vec![target(labels, format!("{}:{}", host, port))];
+    }
+
+    service
+        .spec
+        .iter()
+        .flat_map(|spec| spec.ports.iter().flatten())
+        .map(|port| {
+            let mut labels = labels.clone();
+            if let Some(name) = &port.name {
+                labels.insert(
+                    "__meta_kubernetes_service_port_name".to_owned(),
+                    name.clone(),
+                );
+            }
+            target(labels, format!("{}:{}", host, port.port))
+        })
+        .collect()
+}
+
+fn is_scraped(annotations: &BTreeMap<String, String>) -> bool {
+    annotations.get(SCRAPE_ANNOTATION).map(String::as_str) == Some("true")
+}
+
+/// Builds the labels shared by all the targets of a resource: its namespace, along with its
+/// Kubernetes labels and annotations as meta labels, and the path and scheme to scrape.
+fn resource_labels(
+    role: &str,
+    metadata: &ObjectMeta,
+    annotations: &BTreeMap<String, String>,
+) -> Labels {
+    let mut labels = Labels::new();
+    let namespace = metadata.namespace.clone().unwrap_or_default();
+    labels.insert("__meta_kubernetes_namespace".to_owned(), namespace.clone());
+    labels.insert("namespace".to_owned(), namespace);
+    labels.insert(
+        format!("__meta_kubernetes_{}_name", role),
+        metadata.name.clone().unwrap_or_default(),
+    );
+    for (name, value) in metadata.labels.iter().flatten() {
+        labels.insert(
+            format!(
+                "__meta_kubernetes_{}_label_{}",
+                role,
+                sanitize_label_name(name)
+            ),
+            value.clone(),
+        );
+    }
+    for (name, value) in annotations {
+        labels.insert(
+            format!(
+                "__meta_kubernetes_{}_annotation_{}",
+                role,
+                sanitize_label_name(name)
+            ),
+            value.clone(),
+        );
+    }
+    if let Some(path) = annotations.get(PATH_ANNOTATION) {
+        labels.insert(METRICS_PATH_LABEL.to_owned(), path.clone());
+    }
+    if let Some(scheme) = annotations.get(SCHEME_ANNOTATION) {
+        labels.insert(SCHEME_LABEL.to_owned(), scheme.clone());
+    }
+    labels
+}
+
+fn target(mut labels: Labels, address: String) -> Labels {
+    labels.insert(ADDRESS_LABEL.to_owned(), address);
+    labels
+}
+
+/// Kubernetes label and annotation names may contain characters that aren't valid in label names.
+fn sanitize_label_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::core::v1::{
+        Container, ContainerPort, PodSpec, PodStatus, ServicePort, ServiceSpec,
+    };
+
+    use super::*;
+
+    fn metadata(annotations: &[(&str, &str)]) -> ObjectMeta {
+        ObjectMeta {
+            name: Some("web-0".to_owned()),
+            namespace: Some("default".to_owned()),
+            labels: Some(
+                [("app.kubernetes.io/name".to_owned(), "web".to_owned())]
+                    .into_iter()
+                    .collect(),
+            ),
+            annotations: Some(
+                annotations
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn pod(annotations: &[(&str, &str)]) -> Pod {
+        Pod {
+            metadata: metadata(annotations),
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "web".to_owned(),
+                    ports: Some(vec![
+                        ContainerPort {
+                            container_port: 8080,
+                            name: Some("http".to_owned()),
+                            ..Default::default()
+                        },
+                        ContainerPort {
+                            container_port: 9102,
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                pod_ip: Some("10.0.0.1".to_owned()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn skips_pods_not_annotated() {
+        assert!(pod_targets(&pod(&[])).is_empty());
+        assert!(pod_targets(&pod(&[(SCRAPE_ANNOTATION, "false")])).is_empty());
+    }
+
+    #[test]
+    fn discovers_annotated_pods() {
+        let targets = pod_targets(&pod(&[
+            (SCRAPE_ANNOTATION, "true"),
+            (PORT_ANNOTATION, "9102"),
+            (PATH_ANNOTATION, "/stats"),
+        ]));
+        assert_eq!(targets.len(), 1);
+        let target = &targets[0];
+        assert_eq!(target[ADDRESS_LABEL], "10.0.0.1:9102");
+        assert_eq!(target[METRICS_PATH_LABEL], "/stats");
+        assert_eq!(target["namespace"], "default");
+        assert_eq!(target["pod"], "web-0");
+        assert_eq!(
+            target["__meta_kubernetes_pod_label_app_kubernetes_io_name"],
+            "web"
+        );
+    }
+
+    #[test]
+    fn discovers_each_container_port() {
+        let targets = pod_targets(&pod(&[(SCRAPE_ANNOTATION, "true")]));
+        let addresses = targets
+            .iter()
+            .map(|target| target[ADDRESS_LABEL].as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(addresses, vec!["10.0.0.1:8080", "10.0.0.1:9102"]);
+        assert_eq!(
+            targets[0]["__meta_kubernetes_pod_container_port_name"],
+            "http"
+        );
+    }
+
+    #[test]
+    fn discovers_annotated_services() {
+        let service = Service {
+            metadata: metadata(&[(SCRAPE_ANNOTATION, "true")]),
+            spec: Some(ServiceSpec {
+                ports: Some(vec![ServicePort {
+                    port: 80,
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            status: None,
+        };
+        let targets = service_targets(&service);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0][ADDRESS_LABEL], "web-0.default.svc:80");
+        assert_eq!(targets[0]["service"], "web-0");
+    }
+
+    #[test]
+    fn sanitizes_label_names() {
+        assert_eq!(
+            sanitize_label_name("app.kubernetes.io/name"),
+            "app_kubernetes_io_name"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Discovery of the targets scraped by the `prometheus_scrape` source.
+//!
+//! Like in Prometheus, a discovered target is described by its labels: the `__address__` to
+//! scrape, optionally its `__scheme__`, `__metrics_path__` and `__param_<name>` query parameters,
+//! and meta labels prefixed with `__meta_` that can be used by the relabel rules. The labels left
+//! after relabeling that don't start with `__` are attached as tags to the scraped metrics.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use futures::{future::BoxFuture, FutureExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+mod dns;
+mod file;
+mod kubernetes;
+
+pub const ADDRESS_LABEL: &str = "__address__";
+const SCHEME_LABEL: &str = "__scheme__";
+const METRICS_PATH_LABEL: &str = "__metrics_path__";
+const PARAM_LABEL_PREFIX: &str = "__param_";
+const RESERVED_LABEL_PREFIX: &str = "__";
+
+/// The labels of a discovered target.
+pub type Labels = BTreeMap<String, String>;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DiscoveryConfig {
+    File(file::FileDiscoveryConfig),
+    Dns(dns::DnsDiscoveryConfig),
+    Kubernetes(kubernetes::KubernetesDiscoveryConfig),
+}
+
+impl DiscoveryConfig {
+    /// Builds the discovery task along with the receiver of the targets it discovers. The task
+    /// runs until the receiver is dropped.
+    pub async fn build(
+        &self,
+    ) -> crate::Result<(watch::Receiver<Vec<Labels>>, BoxFuture<'static, ()>)> {
+        let (tx, rx) = watch::channel(Vec::new());
+        let task = match self {
+            Self::File(config) => file::run(config.clone(), tx).boxed(),
+            Self::Dns(config) => dns::run(config.clone(), config.resolver()?, tx).boxed(),
+            Self::Kubernetes(config) => {
+                kubernetes::run(config.clone(), config.client().await?, tx).boxed()
+            }
+        };
+        Ok((rx, task))
+    }
+}
+
+pub(super) const fn default_refresh_interval_secs() -> u64 {
+    30
+}
+
+/// Waits for the next refresh of a polling discovery, returning `false` once the targets are no
+/// longer received.
+async fn next_refresh(tx: &watch::Sender<Vec<Labels>>, interval: Duration) -> bool {
+    tokio::select! {
+        _ = tx.closed() => false,
+        _ = tokio::time::sleep(interval) => true,
+    }
+}
+
+/// Builds the URL to scrape from the labels of a target, without the query parameters.
+pub fn target_url(labels: &Labels) -> Option<(String, Vec<(String, String)>)> {
+    let address = labels.get(ADDRESS_LABEL)?;
+    let scheme = labels
+        .get(SCHEME_LABEL)
+        .map(String::as_str)
+        .unwrap_or("http");
+    let path = labels
+        .get(METRICS_PATH_LABEL)
+        .map(String::as_str)
+        .unwrap_or("/metrics");
+    let params = labels
+        .iter()
+        .filter_map(|(name, value)| {
+            name.strip_prefix(PARAM_LABEL_PREFIX)
+                .map(|param| (param.to_owned(), value.clone()))
+        })
+        .collect();
+    Some((format!("{}://{}{}", scheme, address, path), params))
+}
+
+/// Returns the labels of a target that are attached as tags.
+pub fn target_tags(labels: Labels) -> Labels {
+    labels
+        .into_iter()
+        .filter(|(name, _)| !name.starts_with(RESERVED_LABEL_PREFIX))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_target_urls() {
+        let labels: Labels = [
+            (ADDRESS_LABEL, "10.0.0.1:9100"),
+            (SCHEME_LABEL, "https"),
+            ("__param_module", "http_2xx"),
+            ("job", "node"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect();
+
+        assert_eq!(
+            target_url(&labels),
+            Some((
+                "https://10.0.0.1:9100/metrics".to_owned(),
+                vec![("module".to_owned(), "http_2xx".to_owned())]
+            ))
+        );
+        assert_eq!(
+            target_tags(labels),
+            [("job".to_owned(), "node".to_owned())]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn requires_an_address() {
+        assert_eq!(target_url(&Labels::new()), None);
+    }
+}
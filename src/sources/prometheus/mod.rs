@@ -1,3 +1,5 @@
+mod discovery;
 pub(crate) mod parser;
+mod relabel;
 mod remote_write;
 mod scrape;
@@ -0,0 +1,301 @@
+//! Relabeling of discovered targets, following the `relabel_config` of Prometheus.
+//!
+//! <https://prometheus.io/docs/prometheus/latest/configuration/configuration/#relabel_config>
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum RelabelError {
+    #[snafu(display("Invalid relabel regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("The `{}` relabel action requires a `target_label`", action))]
+    MissingTargetLabel { action: &'static str },
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RelabelAction {
+    /// Sets `target_label` to `replacement` if the concatenated source labels match `regex`.
+    #[derivative(Default)]
+    Replace,
+    /// Drops the targets whose concatenated source labels don't match `regex`.
+    Keep,
+    /// Drops the targets whose concatenated source labels match `regex`.
+    Drop,
+    /// Copies the labels whose name matches `regex` to the label named after `replacement`.
+    Labelmap,
+    /// Removes the labels whose name matches `regex`.
+    Labeldrop,
+    /// Removes the labels whose name doesn't match `regex`.
+    Labelkeep,
+}
+
+impl RelabelAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Keep => "keep",
+            Self::Drop => "drop",
+            Self::Labelmap => "labelmap",
+            Self::Labeldrop => "labeldrop",
+            Self::Labelkeep => "labelkeep",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    #[serde(default)]
+    source_labels: Vec<String>,
+    #[serde(default = "default_separator")]
+    separator: String,
+    #[serde(default = "default_regex")]
+    regex: String,
+    target_label: Option<String>,
+    #[serde(default = "default_replacement")]
+    replacement: String,
+    #[serde(default)]
+    action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+/// A compiled relabel rule.
+#[derive(Clone, Debug)]
+struct Rule {
+    source_labels: Vec<String>,
+    separator: String,
+    regex: Regex,
+    target_label: String,
+    replacement: String,
+    action: RelabelAction,
+}
+
+/// Applies the relabel rules in order to the labels of a target.
+#[derive(Clone, Debug, Default)]
+pub struct Relabeler {
+    rules: Vec<Rule>,
+}
+
+impl Relabeler {
+    pub fn new(configs: &[RelabelConfig]) -> Result<Self, RelabelError> {
+        let rules = configs
+            .iter()
+            .map(|config| {
+                // Prometheus anchors the regular expressions on both ends.
+                let regex =
+                    Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu {
+                        regex: config.regex.clone(),
+                    })?;
+                let target_label = match (config.action, &config.target_label) {
+                    (_, Some(label)) => label.clone(),
+                    (RelabelAction::Replace, None) => {
+                        return Err(RelabelError::MissingTargetLabel {
+                            action: config.action.as_str(),
+                        })
+                    }
+                    (_, None) => String::new(),
+                };
+                Ok(Rule {
+                    source_labels: config.source_labels.clone(),
+                    separator: config.separator.clone(),
+                    regex,
+                    target_label,
+                    replacement: config.replacement.clone(),
+                    action: config.action,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Returns the relabeled labels, or `None` if the target is dropped.
+    pub fn relabel(
+        &self,
+        mut labels: BTreeMap<String, String>,
+    ) -> Option<BTreeMap<String, String>> {
+        for rule in &self.rules {
+            let value = rule
+                .source_labels
+                .iter()
+                .map(|label| labels.get(label).map(String::as_str).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(&rule.separator);
+
+            match rule.action {
+                RelabelAction::Replace => {
+                    if let Some(captures) = rule.regex.captures(&value) {
+                        let mut replaced = String::new();
+                        captures.expand(&rule.replacement, &mut replaced);
+                        if replaced.is_empty() {
+                            labels.remove(&rule.target_label);
+                        } else {
+                            labels.insert(rule.target_label.clone(), replaced);
+                        }
+                    }
+                }
+                RelabelAction::Keep if !rule.regex.is_match(&value) => return None,
+                RelabelAction::Drop if rule.regex.is_match(&value) => return None,
+                RelabelAction::Keep | RelabelAction::Drop => {}
+                RelabelAction::Labelmap => {
+                    let mapped = labels
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            rule.regex.captures(name).map(|captures| {
+                                let mut mapped = String::new();
+                                captures.expand(&rule.replacement, &mut mapped);
+                                (mapped, value.clone())
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    labels.extend(mapped);
+                }
+                RelabelAction::Labeldrop => labels.retain(|name, _| !rule.regex.is_match(name)),
+                RelabelAction::Labelkeep => labels.retain(|name, _| rule.regex.is_match(name)),
+            }
+        }
+        Some(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relabel(rules: &str, input: &[(&str, &str)]) -> Option<BTreeMap<String, String>> {
+        #[derive(Deserialize)]
+        struct Rules {
+            rules: Vec<RelabelConfig>,
+        }
+        let rules: Rules = toml::from_str(rules).unwrap();
+        Relabeler::new(&rules.rules).unwrap().relabel(labels(input))
+    }
+
+    fn labels(labels: &[(&str, &str)]) -> BTreeMap<String, String> {
+        labels
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn replace() {
+        let relabeled = relabel(
+            r#"
+                [[rules]]
+                source_labels = ["__meta_kubernetes_namespace", "__meta_kubernetes_pod_name"]
+                separator = "/"
+                regex = "(.+)/(.+)"
+                target_label = "pod"
+                replacement = "$1-$2"
+            "#,
+            &[
+                ("__meta_kubernetes_namespace", "default"),
+                ("__meta_kubernetes_pod_name", "web-0"),
+            ],
+        );
+        assert_eq!(relabeled.unwrap()["pod"], "default-web-0");
+    }
+
+    #[test]
+    fn replace_is_anchored() {
+        let relabeled = relabel(
+            r#"
+                [[rules]]
+                source_labels = ["job"]
+                regex = "api"
+                target_label = "team"
+                replacement = "backend"
+            "#,
+            &[("job", "api-gateway")],
+        );
+        assert_eq!(relabeled, Some(labels(&[("job", "api-gateway")])));
+    }
+
+    #[test]
+    fn keep_and_drop() {
+        let rules = r#"
+            [[rules]]
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+            regex = "true"
+            action = "keep"
+
+            [[rules]]
+            source_labels = ["env"]
+            regex = "dev"
+            action = "drop"
+        "#;
+        assert!(relabel(rules, &[("env", "prod")]).is_none());
+        assert!(relabel(
+            rules,
+            &[
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_scrape",
+                    "true"
+                ),
+                ("env", "dev")
+            ]
+        )
+        .is_none());
+        assert!(relabel(
+            rules,
+            &[
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_scrape",
+                    "true"
+                ),
+                ("env", "prod")
+            ]
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn labelmap_and_labeldrop() {
+        let relabeled = relabel(
+            r#"
+                [[rules]]
+                regex = "__meta_kubernetes_pod_label_(.+)"
+                action = "labelmap"
+
+                [[rules]]
+                regex = "__meta_.*"
+                action = "labeldrop"
+            "#,
+            &[
+                ("__address__", "10.0.0.1:9100"),
+                ("__meta_kubernetes_pod_label_app", "web"),
+                ("__meta_kubernetes_pod_name", "web-0"),
+            ],
+        );
+        assert_eq!(
+            relabeled,
+            Some(labels(&[("__address__", "10.0.0.1:9100"), ("app", "web")]))
+        );
+    }
+
+    #[test]
+    fn replace_requires_target_label() {
+        let config: RelabelConfig = toml::from_str(r#"source_labels = ["job"]"#).unwrap();
+        assert!(matches!(
+            Relabeler::new(&[config]),
+            Err(RelabelError::MissingTargetLabel { .. })
+        ));
+    }
+}
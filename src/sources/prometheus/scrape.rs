@@ -4,18 +4,24 @@ use std::{
     time::{Duration, Instant},
 };
 
-use futures::{stream, FutureExt, StreamExt, TryFutureExt};
+use futures::{future::BoxFuture, stream, FutureExt, StreamExt, TryFutureExt};
 use hyper::{Body, Request};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use tokio::sync::watch;
 use tokio_stream::wrappers::IntervalStream;
 use vector_core::ByteSizeOf;
 
-use super::parser;
+use super::{
+    discovery::{self, DiscoveryConfig, Labels},
+    parser,
+    relabel::{RelabelConfig, Relabeler},
+};
 use crate::{
     config::{
         self, GenerateConfig, Output, ProxyConfig, SourceConfig, SourceContext, SourceDescription,
     },
+    event::Metric,
     http::{Auth, HttpClient},
     internal_events::{
         BytesReceived, PrometheusEventsReceived, PrometheusHttpError, PrometheusHttpResponseError,
@@ -39,13 +45,19 @@ static NOT_FOUND_NO_PATH: &str = "No path is set on the endpoint and we got a 40
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
     BothEndpointsAndHosts,
+    #[snafu(display("At least one of `endpoints` or `discovery` must be set"))]
+    NoTargets,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 struct PrometheusScrapeConfig {
     // Deprecated name
-    #[serde(alias = "hosts")]
+    #[serde(default, alias = "hosts")]
     endpoints: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    discovery: Vec<DiscoveryConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    relabel: Vec<RelabelConfig>,
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
     instance_tag: Option<String>,
@@ -73,6 +85,8 @@ impl GenerateConfig for PrometheusScrapeConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
+            discovery: Vec::new(),
+            relabel: Vec::new(),
             scrape_interval_secs: default_scrape_interval_secs(),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -89,41 +103,34 @@ impl GenerateConfig for PrometheusScrapeConfig {
 #[typetag::serde(name = "prometheus_scrape")]
 impl SourceConfig for PrometheusScrapeConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
-        let urls = self
+        let endpoints = self
             .endpoints
             .iter()
             .map(|s| s.parse::<http::Uri>().context(sources::UriParseSnafu))
-            .map(|r| {
-                r.map(|uri| {
-                    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
-                    if let Some(query) = uri.query() {
-                        serializer.extend_pairs(url::form_urlencoded::parse(query.as_bytes()));
-                    };
-                    if let Some(query) = &self.query {
-                        for (k, l) in query {
-                            for v in l {
-                                serializer.append_pair(k, v);
-                            }
-                        }
-                    };
-                    let mut builder = http::Uri::builder();
-                    if let Some(scheme) = uri.scheme() {
-                        builder = builder.scheme(scheme.clone());
-                    };
-                    if let Some(authority) = uri.authority() {
-                        builder = builder.authority(authority.clone());
-                    };
-                    builder = builder.path_and_query(match serializer.finish() {
-                        query if !query.is_empty() => format!("{}?{}", uri.path(), query),
-                        _ => uri.path().to_string(),
-                    });
-                    builder.build().expect("error building URI")
-                })
-            })
+            .map(|r| r.map(|uri| with_query(&uri, &[], &self.query)))
             .collect::<Result<Vec<http::Uri>, sources::BuildError>>()?;
+        if endpoints.is_empty() && self.discovery.is_empty() {
+            return Err(ConfigError::NoTargets.into());
+        }
+
+        let mut discovered = Vec::with_capacity(self.discovery.len());
+        let mut discovery_tasks = Vec::with_capacity(self.discovery.len());
+        for discovery in &self.discovery {
+            let (targets, task) = discovery.build().await?;
+            discovered.push(targets);
+            discovery_tasks.push(task);
+        }
+        let targets = Targets {
+            endpoints,
+            discovered,
+            relabeler: Relabeler::new(&self.relabel)?,
+            query: self.query.clone(),
+        };
+
         let tls = TlsSettings::from_options(&self.tls)?;
         Ok(prometheus(
-            urls,
+            targets,
+            discovery_tasks,
             self.instance_tag.clone(),
             self.endpoint_tag.clone(),
             self.honor_labels,
@@ -155,8 +162,12 @@ impl SourceConfig for PrometheusScrapeConfig {
 struct PrometheusCompatConfig {
     // Clone of PrometheusScrapeConfig to work around serde bug
     // https://github.com/serde-rs/serde/issues/1504
-    #[serde(alias = "hosts")]
+    #[serde(default, alias = "hosts")]
     endpoints: Vec<String>,
+    #[serde(default)]
+    discovery: Vec<DiscoveryConfig>,
+    #[serde(default)]
+    relabel: Vec<RelabelConfig>,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
     #[serde(default = "crate::serde::default_false")]
@@ -176,6 +187,8 @@ impl SourceConfig for PrometheusCompatConfig {
         // https://github.com/serde-rs/serde/issues/1504
         let config = PrometheusScrapeConfig {
             endpoints: self.endpoints.clone(),
+            discovery: self.discovery.clone(),
+            relabel: self.relabel.clone(),
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
             honor_labels: self.honor_labels,
@@ -220,8 +233,104 @@ struct EndpointInfo {
     honor_label: bool,
 }
 
+/// The targets to scrape: the configured endpoints, and the targets currently discovered.
+struct Targets {
+    endpoints: Vec<http::Uri>,
+    discovered: Vec<watch::Receiver<Vec<Labels>>>,
+    relabeler: Relabeler,
+    query: Option<HashMap<String, Vec<String>>>,
+}
+
+// ScrapeTarget stores the URL to scrape along with the tags to insert into the scraped metrics,
+// the labels of discovered targets.
+struct ScrapeTarget {
+    url: http::Uri,
+    tags: Labels,
+}
+
+impl Targets {
+    fn current(&self) -> Vec<ScrapeTarget> {
+        let endpoints = self.endpoints.iter().map(|url| ScrapeTarget {
+            url: url.clone(),
+            tags: Labels::new(),
+        });
+        let discovered = self
+            .discovered
+            .iter()
+            .flat_map(|targets| targets.borrow().clone())
+            .filter_map(|labels| self.relabeler.relabel(labels))
+            .filter_map(|labels| {
+                let (url, params) = discovery::target_url(&labels)?;
+                match url.parse::<http::Uri>() {
+                    Ok(uri) => Some(ScrapeTarget {
+                        url: with_query(&uri, &params, &self.query),
+                        tags: discovery::target_tags(labels),
+                    }),
+                    Err(error) => {
+                        warn!(
+                            message = "Invalid discovered target, skipping.",
+                            url = %url,
+                            %error,
+                            internal_log_rate_secs = 30,
+                        );
+                        None
+                    }
+                }
+            });
+        endpoints.chain(discovered).collect()
+    }
+}
+
+/// Merges the query parameters of the URL with the given parameters and the configured query.
+fn with_query(
+    uri: &http::Uri,
+    params: &[(String, String)],
+    query: &Option<HashMap<String, Vec<String>>>,
+) -> http::Uri {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    if let Some(query) = uri.query() {
+        serializer.extend_pairs(url::form_urlencoded::parse(query.as_bytes()));
+    };
+    serializer.extend_pairs(params);
+    if let Some(query) = query {
+        for (k, l) in query {
+            for v in l {
+                serializer.append_pair(k, v);
+            }
+        }
+    };
+    let mut builder = http::Uri::builder();
+    if let Some(scheme) = uri.scheme() {
+        builder = builder.scheme(scheme.clone());
+    };
+    if let Some(authority) = uri.authority() {
+        builder = builder.authority(authority.clone());
+    };
+    builder = builder.path_and_query(match serializer.finish() {
+        query if !query.is_empty() => format!("{}?{}", uri.path(), query),
+        _ => uri.path().to_string(),
+    });
+    builder.build().expect("error building URI")
+}
+
+/// Inserts a tag into a scraped metric. Unless the scraped labels are honored, the label the
+/// metric was scraped with is kept as `exported_<tag>`.
+fn insert_target_tag(metric: &mut Metric, tag: &str, value: &str, honor_label: bool) {
+    match (honor_label, metric.tag_value(tag)) {
+        (false, Some(old_value)) => {
+            metric.insert_tag(format!("exported_{}", tag), old_value);
+            metric.insert_tag(tag.to_owned(), value.to_owned());
+        }
+        (true, Some(_)) => {}
+        (_, None) => {
+            metric.insert_tag(tag.to_owned(), value.to_owned());
+        }
+    }
+}
+
 fn prometheus(
-    urls: Vec<http::Uri>,
+    targets: Targets,
+    discovery_tasks: Vec<BoxFuture<'static, ()>>,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
     honor_labels: bool,
@@ -233,11 +342,16 @@ fn prometheus(
     mut out: SourceSender,
 ) -> sources::Source {
     Box::pin(async move {
+        // The discovery stops along with the source, once the targets are dropped.
+        for task in discovery_tasks {
+            tokio::spawn(task);
+        }
+
         let mut stream = IntervalStream::new(tokio::time::interval(Duration::from_secs(interval)))
             .take_until(shutdown)
-            .map(move |_| stream::iter(urls.clone()))
+            .map(move |_| stream::iter(targets.current()))
             .flatten()
-            .map(move |url| {
+            .map(move |ScrapeTarget { url, tags }| {
                 let client =
                     HttpClient::new(tls.clone(), &proxy).expect("Building HTTP client failed");
 
@@ -287,6 +401,7 @@ fn prometheus(
                     .filter_map(move |response| {
                         let instance_info = instance_info.clone();
                         let endpoint_info = endpoint_info.clone();
+                        let tags = tags.clone();
 
                         ready(match response {
                             Ok((header, body)) if header.status == hyper::StatusCode::OK => {
@@ -312,25 +427,12 @@ fn prometheus(
                                                 honor_label,
                                             }) = &instance_info
                                             {
-                                                match (honor_label, metric.tag_value(tag)) {
-                                                    (false, Some(old_instance)) => {
-                                                        metric.insert_tag(
-                                                            format!("exported_{}", tag),
-                                                            old_instance,
-                                                        );
-                                                        metric.insert_tag(
-                                                            tag.clone(),
-                                                            instance.clone(),
-                                                        );
-                                                    }
-                                                    (true, Some(_)) => {}
-                                                    (_, None) => {
-                                                        metric.insert_tag(
-                                                            tag.clone(),
-                                                            instance.clone(),
-                                                        );
-                                                    }
-                                                }
+                                                insert_target_tag(
+                                                    metric,
+                                                    tag,
+                                                    instance,
+                                                    *honor_label,
+                                                );
                                             }
                                             if let Some(EndpointInfo {
                                                 tag,
@@ -338,25 +440,15 @@ fn prometheus(
                                                 honor_label,
                                             }) = &endpoint_info
                                             {
-                                                match (honor_label, metric.tag_value(tag)) {
-                                                    (false, Some(old_endpoint)) => {
-                                                        metric.insert_tag(
-                                                            format!("exported_{}", tag),
-                                                            old_endpoint,
-                                                        );
-                                                        metric.insert_tag(
-                                                            tag.clone(),
-                                                            endpoint.clone(),
-                                                        );
-                                                    }
-                                                    (true, Some(_)) => {}
-                                                    (_, None) => {
-                                                        metric.insert_tag(
-                                                            tag.clone(),
-                                                            endpoint.clone(),
-                                                        );
-                                                    }
-                                                }
+                                                insert_target_tag(
+                                                    metric,
+                                                    tag,
+                                                    endpoint,
+                                                    *honor_label,
+                                                );
+                                            }
+                                            for (tag, value) in &tags {
+                                                insert_target_tag(metric, tag, value, honor_labels);
                                             }
                                             event
                                         }))
@@ -459,6 +551,8 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            discovery: Vec::new(),
+            relabel: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -513,6 +607,8 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            discovery: Vec::new(),
+            relabel: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -580,6 +676,8 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics?key1=val1", in_addr)],
+            discovery: Vec::new(),
+            relabel: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -638,6 +736,90 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_prometheus_file_discovery() {
+        let in_addr = next_addr();
+
+        let dummy_endpoint = warp::path!("metrics").map(|| {
+            r#"
+                promhttp_metric_handler_requests_total{job="exported", code="200"} 100 1612411516789
+            "#
+        });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+
+        let dir = tempfile::tempdir().unwrap();
+        let targets_path = dir.path().join("targets.json");
+        std::fs::write(
+            &targets_path,
+            format!(
+                r#"[{{"targets": ["{}", "10.0.0.1:9100"], "labels": {{"job": "api"}}}}]"#,
+                in_addr
+            ),
+        )
+        .unwrap();
+
+        let config: PrometheusScrapeConfig = toml::from_str(&format!(
+            r#"
+                scrape_interval_secs = 1
+
+                [[discovery]]
+                type = "file"
+                paths = ["{}"]
+
+                [[relabel]]
+                source_labels = ["__address__"]
+                regex = "10\\..*"
+                action = "drop"
+
+                [[relabel]]
+                source_labels = ["job"]
+                target_label = "service"
+                replacement = "svc-$1"
+            "#,
+            targets_path.to_string_lossy()
+        ))
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+
+        tokio::spawn(source);
+        // The first scrapes may happen before the targets are discovered, so the test waits for
+        // the first metric to come in.
+        let mut rx = rx;
+        let first = tokio::time::timeout(Duration::from_secs(10), rx.next())
+            .await
+            .expect("targets should be discovered and scraped")
+            .unwrap();
+        let mut events = vec![first];
+        events.extend(test_util::collect_ready(rx).await);
+
+        for event in events {
+            let metric = event.into_metric();
+            assert_eq!(metric.tag_value("job"), Some("api".to_owned()));
+            assert_eq!(
+                metric.tag_value("exported_job"),
+                Some("exported".to_owned())
+            );
+            assert_eq!(metric.tag_value("service"), Some("svc-api".to_owned()));
+            assert_eq!(metric.tag_value("__address__"), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_requires_targets() {
+        let config: PrometheusScrapeConfig = toml::from_str("").unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_prometheus_routing() {
         let in_addr = next_addr();
@@ -693,6 +875,8 @@ mod test {
                 endpoint_tag: None,
                 honor_labels: false,
                 query: None,
+                discovery: Vec::new(),
+                relabel: Vec::new(),
                 scrape_interval_secs: 1,
                 tls: None,
                 auth: None,
@@ -776,6 +960,8 @@ mod integration_tests {
     async fn scrapes_metrics() {
         let config = PrometheusScrapeConfig {
             endpoints: vec!["http://localhost:9090/metrics".into()],
+            discovery: Vec::new(),
+            relabel: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
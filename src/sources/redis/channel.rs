@@ -40,9 +40,15 @@ pub async fn subscribe(
         while let Some(msg) = pubsub_stream.next().await {
             match msg.get_payload::<String>() {
                 Ok(line) => {
-                    if let Err(()) =
-                        handle_line(line, &key, redis_key.as_deref(), decoder.clone(), &mut out)
-                            .await
+                    if let Err(()) = handle_line(
+                        line,
+                        &key,
+                        redis_key.as_deref(),
+                        decoder.clone(),
+                        None,
+                        &mut out,
+                    )
+                    .await
                     {
                         break;
                     }
@@ -44,9 +44,15 @@ pub async fn watch(
             match res {
                 Err(error) => emit!(RedisReceiveEventError::from(error)),
                 Ok(line) => {
-                    if let Err(()) =
-                        handle_line(line, &key, redis_key.as_deref(), decoder.clone(), &mut out)
-                            .await
+                    if let Err(()) = handle_line(
+                        line,
+                        &key,
+                        redis_key.as_deref(),
+                        decoder.clone(),
+                        None,
+                        &mut out,
+                    )
+                    .await
                     {
                         break;
                     }
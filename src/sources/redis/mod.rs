@@ -1,12 +1,14 @@
+use std::sync::Arc;
+
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{
-        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
-        SourceDescription,
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig,
+        SourceContext, SourceDescription,
     },
-    event::Event,
+    event::{BatchNotifier, Event},
     internal_events::{BytesReceived, EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     SourceSender,
 };
@@ -25,6 +27,7 @@ use vector_core::ByteSizeOf;
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -32,13 +35,14 @@ enum BuildError {
     Client { source: redis::RedisError },
 }
 
-#[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
 #[derivative(Default)]
 #[serde(rename_all = "lowercase")]
 pub enum DataTypeConfig {
     #[derivative(Default)]
     List,
     Channel,
+    Stream,
 }
 
 #[derive(Copy, Clone, Debug, Default, Derivative, Deserialize, Serialize, Eq, PartialEq)]
@@ -56,12 +60,32 @@ pub enum Method {
     Rpop,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group, created at the end of the stream if it doesn't exist.
+    group: String,
+    /// The name of the consumer within the group, defaulting to the host name.
+    consumer: Option<String>,
+    /// The field of the entries holding the message.
+    #[serde(default = "default_stream_field")]
+    field: String,
+    /// The entries pending for longer than this in the group are claimed on startup, taking over
+    /// the entries of the consumers that went away.
+    claim_min_idle_secs: Option<u64>,
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
 #[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
     #[serde(default)]
     data_type: DataTypeConfig,
     list: Option<ListOption>,
+    stream: Option<StreamOption>,
     url: String,
     key: String,
     redis_key: Option<String>,
@@ -71,6 +95,8 @@ pub struct RedisSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -97,7 +123,8 @@ inventory::submit! {
 impl SourceConfig for RedisSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build();
-        redis_source(self, decoder, cx.shutdown, cx.out).await
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        redis_source(self, decoder, cx.shutdown, cx.out, acknowledgements).await
     }
 
     fn outputs(&self) -> Vec<Output> {
@@ -109,7 +136,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.data_type == DataTypeConfig::Stream
     }
 }
 
@@ -118,6 +145,7 @@ async fn redis_source(
     decoder: Decoder,
     shutdown: ShutdownSignal,
     out: SourceSender,
+    acknowledgements: bool,
 ) -> crate::Result<super::Source> {
     if config.key.is_empty() {
         return Err("`key` cannot be empty.".into());
@@ -150,6 +178,23 @@ async fn redis_source(
             )
             .await
         }
+        DataTypeConfig::Stream => {
+            let stream = config
+                .stream
+                .clone()
+                .ok_or("`stream.group` is required for the `stream` data type.")?;
+            stream::consume(
+                client,
+                config.key.clone(),
+                config.redis_key.clone(),
+                stream,
+                decoder,
+                shutdown,
+                out,
+                acknowledgements,
+            )
+            .await
+        }
     }
 }
 
@@ -165,6 +210,7 @@ async fn handle_line(
     key: &str,
     redis_key: Option<&str>,
     decoder: Decoder,
+    batch: Option<&Arc<BatchNotifier>>,
     out: &mut SourceSender,
 ) -> Result<(), ()> {
     let now = Utc::now();
@@ -191,7 +237,10 @@ async fn handle_line(
                             event.as_mut_log().insert(redis_key, key);
                         }
                     }
-                    event
+                    match batch {
+                        Some(batch) => event.with_batch_notifier(batch),
+                        None => event,
+                    }
                 });
 
                 if let Err(error) = out.send_batch(events).await {
@@ -219,6 +268,22 @@ mod test {
     fn generate_config() {
         crate::test_util::test_generate_config::<RedisSourceConfig>();
     }
+
+    #[test]
+    fn only_streams_can_acknowledge() {
+        for (data_type, can_acknowledge) in [("list", false), ("channel", false), ("stream", true)]
+        {
+            let config: RedisSourceConfig = toml::from_str(&format!(
+                r#"
+                url = "redis://127.0.0.1:6379/0"
+                key = "vector"
+                data_type = "{}""#,
+                data_type
+            ))
+            .unwrap();
+            assert_eq!(config.can_acknowledge(), can_acknowledge);
+        }
+    }
 }
 
 #[cfg(feature = "redis-integration-tests")]
@@ -227,11 +292,15 @@ mod integration_test {
     use super::*;
     use crate::config::log_schema;
     use crate::{
+        event::EventStatus,
         shutdown::ShutdownSignal,
         test_util::{collect_n, random_string},
         SourceSender,
     };
-    use redis::AsyncCommands;
+    use redis::{
+        streams::{StreamPendingReply, StreamReadOptions, StreamReadReply},
+        AsyncCommands,
+    };
 
     const REDIS_SERVER: &str = "redis://redis:6379/0";
 
@@ -250,9 +319,11 @@ mod integration_test {
             }),
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
+            stream: None,
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         let _: i32 = conn.rpush(&key, "1").await.unwrap();
@@ -266,6 +337,7 @@ mod integration_test {
                 crate::codecs::Decoder::default(),
                 ShutdownSignal::noop(),
                 tx,
+                false,
             )
             .await
             .unwrap(),
@@ -292,9 +364,11 @@ mod integration_test {
             }),
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
+            stream: None,
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         let _: i32 = conn.rpush(&key, "1").await.unwrap();
@@ -308,6 +382,7 @@ mod integration_test {
                 crate::codecs::Decoder::default(),
                 ShutdownSignal::noop(),
                 tx,
+                false,
             )
             .await
             .unwrap(),
@@ -331,9 +406,11 @@ mod integration_test {
             list: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
+            stream: None,
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         debug!("Receiving event.");
@@ -344,6 +421,7 @@ mod integration_test {
                 crate::codecs::Decoder::default(),
                 ShutdownSignal::noop(),
                 tx,
+                false,
             )
             .await
             .unwrap(),
@@ -375,4 +453,107 @@ mod integration_test {
             );
         }
     }
+
+    fn stream_config(
+        key: &str,
+        group: &str,
+        claim_min_idle_secs: Option<u64>,
+    ) -> RedisSourceConfig {
+        RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                group: group.to_owned(),
+                consumer: Some("vector".to_owned()),
+                field: default_stream_field(),
+                claim_min_idle_secs,
+            }),
+            url: REDIS_SERVER.to_owned(),
+            key: key.to_owned(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            acknowledgements: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_acknowledges_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        let group = "vector-group";
+        let _: () = conn.xgroup_create_mkstream(&key, group, "0").await.unwrap();
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        let config = stream_config(&key, group, None);
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        tokio::spawn(
+            redis_source(
+                &config,
+                crate::codecs::Decoder::default(),
+                ShutdownSignal::noop(),
+                tx,
+                true,
+            )
+            .await
+            .unwrap(),
+        );
+        let events = collect_n(rx, 3).await;
+
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "1".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "2".into());
+        assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let pending: StreamPendingReply = conn.xpending(&key, group).await.unwrap();
+        assert_eq!(pending.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_claims_pending_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        let group = "vector-group";
+        let _: () = conn.xgroup_create_mkstream(&key, group, "0").await.unwrap();
+        for message in ["1", "2"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+        // Another consumer reads the entries without acknowledging them.
+        let _: StreamReadReply = conn
+            .xread_options(
+                &[&key],
+                &[">"],
+                &StreamReadOptions::default().group(group, "gone"),
+            )
+            .await
+            .unwrap();
+
+        let config = stream_config(&key, group, Some(0));
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        tokio::spawn(
+            redis_source(
+                &config,
+                crate::codecs::Decoder::default(),
+                ShutdownSignal::noop(),
+                tx,
+                true,
+            )
+            .await
+            .unwrap(),
+        );
+        let events = collect_n(rx, 2).await;
+
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "1".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "2".into());
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let pending: StreamPendingReply = conn.xpending(&key, group).await.unwrap();
+        assert_eq!(pending.count(), 0);
+    }
 }
@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use futures::FutureExt;
+use redis::{
+    aio::ConnectionManager,
+    streams::{
+        StreamClaimOptions, StreamId, StreamPendingCountReply, StreamReadOptions, StreamReadReply,
+    },
+    AsyncCommands, ErrorKind, RedisError, RedisResult,
+};
+use snafu::{ResultExt, Snafu};
+
+use super::{handle_line, StreamOption};
+use crate::{
+    codecs,
+    event::BatchNotifier,
+    internal_events::RedisReceiveEventError,
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    sources::{util::finalizer::UnorderedFinalizer, Source},
+    SourceSender,
+};
+
+/// The maximum number of entries read or claimed at once.
+const BATCH_SIZE: usize = 100;
+/// How long a read waits for new entries before being issued again.
+const BLOCK_MS: usize = 5000;
+
+/// The delays between the reads failing in a row, until the server is reachable again.
+const fn fresh_backoff() -> ExponentialBackoff {
+    ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(30))
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: RedisError },
+    #[snafu(display("Failed to claim pending entries: {}", source))]
+    Claim { source: RedisError },
+}
+
+pub async fn consume(
+    client: redis::Client,
+    key: String,
+    redis_key: Option<String>,
+    stream: StreamOption,
+    decoder: codecs::Decoder,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    acknowledgements: bool,
+) -> crate::Result<Source> {
+    let consumer = match stream.consumer {
+        Some(consumer) => consumer,
+        None => crate::get_hostname()?,
+    };
+
+    // The reads block their connection, so the entries are acknowledged through another one.
+    let mut conn = client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu)?;
+    let ack_conn = client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu)?;
+
+    create_group(&mut conn, &key, &stream.group).await?;
+    if let Some(min_idle_secs) = stream.claim_min_idle_secs {
+        claim_pending(
+            &mut conn,
+            &key,
+            &stream.group,
+            &consumer,
+            min_idle_secs * 1000,
+        )
+        .await
+        .context(ClaimSnafu)?;
+    }
+
+    let shutdown = shutdown.shared();
+    let finalizer = acknowledgements.then(|| {
+        let key = key.clone();
+        let group = stream.group.clone();
+        UnorderedFinalizer::new(shutdown.clone(), move |id: String| {
+            let mut conn = ack_conn.clone();
+            let key = key.clone();
+            let group = group.clone();
+            async move {
+                if let Err(error) = conn.xack::<_, _, _, i64>(&key, &group, &[id]).await {
+                    emit!(RedisReceiveEventError::from(error));
+                }
+            }
+        })
+    });
+
+    let fut = async move {
+        // The entries delivered to this consumer but never acknowledged are read again first,
+        // starting from the beginning of its pending list, then the new entries are read.
+        let mut pending = Some("0".to_owned());
+        let mut backoff = fresh_backoff();
+        loop {
+            let mut options = StreamReadOptions::default()
+                .group(&stream.group, &consumer)
+                .count(BATCH_SIZE);
+            if !acknowledgements {
+                options = options.noack();
+            }
+            let id = match &pending {
+                Some(id) => id.clone(),
+                None => {
+                    options = options.block(BLOCK_MS);
+                    ">".to_owned()
+                }
+            };
+            let reply: RedisResult<Option<StreamReadReply>> = tokio::select! {
+                reply = conn.xread_options(&[&key], &[&id], &options) => reply,
+                _ = shutdown.clone() => break,
+            };
+            let entries: Vec<StreamId> = match reply {
+                Ok(reply) => {
+                    backoff = fresh_backoff();
+                    reply
+                        .map(|reply| reply.keys.into_iter().flat_map(|key| key.ids).collect())
+                        .unwrap_or_default()
+                }
+                Err(error) => {
+                    emit!(RedisReceiveEventError::from(error));
+                    tokio::select! {
+                        _ = tokio::time::sleep(backoff.next().unwrap()) => continue,
+                        _ = shutdown.clone() => break,
+                    }
+                }
+            };
+
+            if pending.is_some() {
+                pending = entries.last().map(|entry| entry.id.clone());
+            }
+
+            for entry in entries {
+                if let Err(()) = handle_entry(
+                    entry,
+                    &key,
+                    redis_key.as_deref(),
+                    &stream.field,
+                    decoder.clone(),
+                    finalizer.as_ref(),
+                    &mut out,
+                )
+                .await
+                {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    };
+    Ok(Box::pin(fut))
+}
+
+async fn handle_entry(
+    entry: StreamId,
+    key: &str,
+    redis_key: Option<&str>,
+    field: &str,
+    decoder: codecs::Decoder,
+    finalizer: Option<&UnorderedFinalizer<String>>,
+    out: &mut SourceSender,
+) -> Result<(), ()> {
+    let line = match entry.get::<String>(field) {
+        Some(line) => line,
+        None => {
+            emit!(RedisReceiveEventError::from(RedisError::from((
+                ErrorKind::TypeError,
+                "Stream entry has no message field",
+                format!("entry {} has no valid `{}` field", entry.id, field),
+            ))));
+            // Acknowledged right away, as the entry can never be processed.
+            if let Some(finalizer) = finalizer {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                finalizer.add(entry.id, receiver);
+                drop(batch);
+            }
+            return Ok(());
+        }
+    };
+
+    match finalizer {
+        Some(finalizer) => {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            handle_line(line, key, redis_key, decoder, Some(&batch), out).await?;
+            finalizer.add(entry.id, receiver);
+            Ok(())
+        }
+        None => handle_line(line, key, redis_key, decoder, None, out).await,
+    }
+}
+
+async fn create_group(conn: &mut ConnectionManager, key: &str, group: &str) -> crate::Result<()> {
+    match conn
+        .xgroup_create_mkstream::<_, _, _, ()>(key, group, "$")
+        .await
+    {
+        // The group already exists.
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        result => Ok(result.context(CreateGroupSnafu)?),
+    }
+}
+
+/// Claims the entries of the group that were delivered to other consumers but not acknowledged
+/// for at least `min_idle_ms`, so they are read again along with the pending entries of this one.
+async fn claim_pending(
+    conn: &mut ConnectionManager,
+    key: &str,
+    group: &str,
+    consumer: &str,
+    min_idle_ms: u64,
+) -> RedisResult<()> {
+    let mut start = "-".to_owned();
+    loop {
+        let reply: StreamPendingCountReply = conn
+            .xpending_count(key, group, &start, "+", BATCH_SIZE)
+            .await?;
+        let ids = reply
+            .ids
+            .iter()
+            .filter(|pending| {
+                pending.consumer != consumer && pending.last_delivered_ms as u64 >= min_idle_ms
+            })
+            .map(|pending| pending.id.clone())
+            .collect::<Vec<_>>();
+        if !ids.is_empty() {
+            let claimed: Vec<String> = conn
+                .xclaim_options(
+                    key,
+                    group,
+                    consumer,
+                    min_idle_ms,
+                    &ids,
+                    StreamClaimOptions::default().with_justid(),
+                )
+                .await?;
+            debug!(message = "Claimed pending entries.", count = claimed.len());
+        }
+
+        match reply.ids.last() {
+            Some(last) if reply.ids.len() == BATCH_SIZE => start = next_id(&last.id),
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// The smallest entry ID following `id`, as ranges are inclusive.
+fn next_id(id: &str) -> String {
+    match id.split_once('-') {
+        Some((ms, seq)) => match seq.parse::<u64>() {
+            Ok(seq) => format!("{}-{}", ms, seq + 1),
+            Err(_) => id.to_owned(),
+        },
+        None => id.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_id;
+
+    #[test]
+    fn next_entry_id() {
+        assert_eq!(next_id("1526919030474-55"), "1526919030474-56");
+        assert_eq!(next_id("0-0"), "0-1");
+    }
+}
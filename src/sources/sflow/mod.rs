@@ -0,0 +1,223 @@
+use std::{net::SocketAddr, num::NonZeroUsize};
+
+use bytes::{Bytes, BytesMut};
+use futures::future;
+use serde::{Deserialize, Serialize};
+use tokio::net::UdpSocket;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::Event,
+    internal_events::{
+        BytesReceived, SflowEventsReceived, SflowParseError, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp::{self, MulticastConfig},
+    SourceSender,
+};
+
+pub mod parser;
+pub use parser::{parse_sflow_datagram, SflowParser};
+
+pub mod schema;
+pub use schema::SflowEventSchema;
+
+/// The largest UDP payload.
+const MAX_DATAGRAM_LENGTH: usize = 65535;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SflowConfig {
+    address: SocketAddr,
+    receive_buffer_bytes: Option<usize>,
+    multicast: Option<MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<SflowConfig>("sflow")
+}
+
+impl GenerateConfig for SflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:6343".parse().unwrap(),
+            receive_buffer_bytes: None,
+            multicast: None,
+            reuse_port_sockets: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sflow")]
+impl SourceConfig for SflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let mut schema = SflowEventSchema::new();
+        schema
+            .sflow_root_data_schema_mut()
+            .set_timestamp(log_schema().timestamp_key());
+
+        let sockets = udp::bind_listeners(
+            self.address,
+            self.receive_buffer_bytes,
+            self.multicast.as_ref(),
+            self.reuse_port_sockets,
+        )?;
+        info!(message = "Listening.", address = %self.address, sockets = sockets.len());
+
+        let shutdown = cx.shutdown;
+        let out = cx.out;
+        Ok(Box::pin(async move {
+            let tasks = sockets.into_iter().map(|socket| {
+                tokio::spawn(receive(
+                    socket,
+                    schema.clone(),
+                    shutdown.clone(),
+                    out.clone(),
+                ))
+            });
+            for result in future::join_all(tasks).await {
+                if !matches!(result, Ok(Ok(()))) {
+                    return Err(());
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log | DataType::Metric)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "sflow"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn receive(
+    socket: UdpSocket,
+    schema: SflowEventSchema,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut buf = BytesMut::with_capacity(MAX_DATAGRAM_LENGTH);
+    loop {
+        buf.resize(MAX_DATAGRAM_LENGTH, 0);
+        let byte_size = tokio::select! {
+            recv = socket.recv_from(&mut buf) => recv.map_err(|error| {
+                let error = codecs::decoding::Error::FramingError(error.into());
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error: &error
+                })
+            })?.0,
+            _ = &mut shutdown => return Ok(()),
+        };
+        emit!(BytesReceived {
+            byte_size,
+            protocol: "udp",
+        });
+
+        let mut events = match parse_sflow_datagram(&schema, buf.split_to(byte_size).freeze()) {
+            Ok(events) => events,
+            Err(err) => {
+                emit!(SflowParseError {
+                    error: format!("sFlow datagram decode error {}.", err).as_str()
+                });
+                continue;
+            }
+        };
+        if events.is_empty() {
+            continue;
+        }
+
+        for event in &mut events {
+            if let Event::Log(log) = event {
+                log.insert(log_schema().source_type_key(), Bytes::from("sflow"));
+            }
+        }
+        let count = events.len();
+        emit!(SflowEventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+        tokio::select! {
+            result = out.send_batch(events) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count });
+                    return Ok(());
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect_n, next_addr};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SflowConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_samples() {
+        let address = next_addr();
+        let config: SflowConfig = toml::from_str(&format!(r#"address = "{}""#, address)).unwrap();
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        // A datagram with a flow sample of an IPv4 UDP packet header, and a counter sample of
+        // processor counters.
+        let words = |values: &[u32]| {
+            values
+                .iter()
+                .flat_map(|value| value.to_be_bytes())
+                .collect::<Vec<_>>()
+        };
+        let mut datagram = words(&[5, 1, 0x7f00_0001, 0, 1, 1000, 2]);
+        datagram.extend(words(&[1, 84, 1, 4, 512, 1024, 0, 1, 2, 1, 1, 44]));
+        datagram.extend(words(&[11, 60, 0, 28, 0x4500_003c, 0, 0x4011_0000]));
+        datagram.extend(words(&[0x0a00_0001, 0x0a00_0002, 0x0035_0035, 0x0028_0000]));
+        datagram.extend(words(&[
+            2, 48, 1, 0, 1, 1001, 28, 150, 200, 250, 0, 1024, 0, 512,
+        ]));
+        let socket = std::net::UdpSocket::bind(next_addr()).unwrap();
+        socket.send_to(&datagram, address).unwrap();
+
+        let events = collect_n(rx, 6).await;
+        let log = events[0].as_log();
+        assert_eq!(log["agentAddress"], "127.0.0.1".into());
+        assert_eq!(log["sampledHeader.ip.sourceAddress"], "10.0.0.1".into());
+        assert_eq!(log["sampledHeader.transport.destinationPort"], 53.into());
+        assert_eq!(log[log_schema().source_type_key()], "sflow".into());
+        let metric = events[1].as_metric();
+        assert_eq!(metric.name(), "processor_cpu_5s_percent");
+        assert_eq!(
+            metric.value(),
+            &crate::event::MetricValue::Gauge { value: 1.5 }
+        );
+    }
+}
@@ -0,0 +1,779 @@
+use std::{
+    fmt::Debug,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use lookup::lookup_v2::OwnedPath;
+use snafu::Snafu;
+
+use super::schema::SflowEventSchema;
+use crate::{
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event, LogEvent, Value,
+    },
+    internal_events::SflowParseError,
+    Result,
+};
+
+const SFLOW_VERSION: u32 = 5;
+
+const FLOW_SAMPLE: u32 = 1;
+const COUNTER_SAMPLE: u32 = 2;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+const EXPANDED_COUNTER_SAMPLE: u32 = 4;
+
+const RAW_PACKET_HEADER: u32 = 1;
+
+const GENERIC_INTERFACE_COUNTERS: u32 = 1;
+const ETHERNET_INTERFACE_COUNTERS: u32 = 2;
+const PROCESSOR_COUNTERS: u32 = 1001;
+
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+const HEADER_PROTOCOL_IPV4: u32 = 11;
+const HEADER_PROTOCOL_IPV6: u32 = 12;
+
+const ETHER_TYPE_IPV4: u16 = 0x0800;
+const ETHER_TYPE_VLAN: u16 = 0x8100;
+const ETHER_TYPE_IPV6: u16 = 0x86dd;
+
+const IP_PROTOCOL_TCP: u8 = 6;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+const METRIC_NAMESPACE: &str = "sflow";
+
+#[derive(Debug, Snafu)]
+enum SflowParserError {
+    #[snafu(display("Truncated sFlow data"))]
+    TruncatedData,
+    #[snafu(display("Unsupported sFlow version: {}", version))]
+    UnsupportedVersion { version: u32 },
+    #[snafu(display("Unsupported sFlow agent address type: {}", address_type))]
+    UnsupportedAddressType { address_type: u32 },
+}
+
+/// The header of a datagram, shared by all its samples.
+struct DatagramHeader {
+    agent_address: IpAddr,
+    sub_agent_id: u32,
+    sequence_number: u32,
+    uptime: u32,
+}
+
+/// Parses an sFlow v5 datagram, the flow samples into logs and the counter samples into metrics.
+pub fn parse_sflow_datagram(
+    event_schema: &SflowEventSchema,
+    datagram: Bytes,
+) -> Result<Vec<Event>> {
+    let mut reader = XdrReader::new(&datagram);
+    let version = reader.u32()?;
+    if version != SFLOW_VERSION {
+        return Err(SflowParserError::UnsupportedVersion { version }.into());
+    }
+    let header = DatagramHeader {
+        agent_address: reader.ip_address()?,
+        sub_agent_id: reader.u32()?,
+        sequence_number: reader.u32()?,
+        uptime: reader.u32()?,
+    };
+    // The agents only send their uptime.
+    let timestamp = Utc::now();
+
+    let sample_count = reader.u32()?;
+    let mut events = Vec::new();
+    for _ in 0..sample_count {
+        let (enterprise, format, sample) = reader.record()?;
+        if enterprise != 0 {
+            continue;
+        }
+        match format {
+            FLOW_SAMPLE | EXPANDED_FLOW_SAMPLE => {
+                let mut log_event = LogEvent::default();
+                SflowParser::new(event_schema, &mut log_event).parse_flow_sample(
+                    &header,
+                    timestamp,
+                    sample,
+                    format == EXPANDED_FLOW_SAMPLE,
+                );
+                events.push(Event::from(log_event));
+            }
+            COUNTER_SAMPLE | EXPANDED_COUNTER_SAMPLE => {
+                match parse_counter_sample(
+                    &header,
+                    timestamp,
+                    XdrReader::new(sample),
+                    format == EXPANDED_COUNTER_SAMPLE,
+                ) {
+                    Ok(metrics) => events.extend(metrics.into_iter().map(Event::from)),
+                    Err(err) => emit!(SflowParseError {
+                        error: err.to_string().as_str()
+                    }),
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(events)
+}
+
+pub struct SflowParser<'a> {
+    event_schema: &'a SflowEventSchema,
+    parent_key_path: OwnedPath,
+    log_event: &'a mut LogEvent,
+}
+
+impl<'a> SflowParser<'a> {
+    pub fn new(event_schema: &'a SflowEventSchema, log_event: &'a mut LogEvent) -> Self {
+        Self {
+            event_schema,
+            parent_key_path: Vec::new().into(),
+            log_event,
+        }
+    }
+
+    fn insert<V>(&mut self, key: &'static str, value: V) -> Option<Value>
+    where
+        V: Into<Value> + Debug,
+    {
+        let mut node_path = self.parent_key_path.clone();
+        node_path.push_field(key);
+        self.log_event.insert(&node_path, value)
+    }
+
+    fn parse_flow_sample(
+        &mut self,
+        header: &DatagramHeader,
+        timestamp: DateTime<Utc>,
+        sample: &[u8],
+        expanded: bool,
+    ) {
+        let schema = self.event_schema.sflow_root_data_schema();
+        self.insert(schema.agent_address(), header.agent_address.to_string());
+        self.insert(schema.sub_agent_id(), header.sub_agent_id);
+        self.insert(schema.datagram_sequence_number(), header.sequence_number);
+        self.insert(schema.uptime(), header.uptime);
+        self.insert(schema.timestamp(), timestamp);
+        self.insert(schema.sample_type(), "flow");
+
+        if let Err(err) = self.parse_flow_sample_data(XdrReader::new(sample), expanded) {
+            emit!(SflowParseError {
+                error: err.to_string().as_str()
+            });
+            self.insert(schema.error(), err.to_string());
+            self.insert(schema.raw_data(), base64::encode(sample));
+        }
+    }
+
+    fn parse_flow_sample_data(&mut self, mut reader: XdrReader<'_>, expanded: bool) -> Result<()> {
+        let schema = self.event_schema.sflow_flow_sample_schema();
+        self.insert(schema.sequence_number(), reader.u32()?);
+        let (source_id_type, source_id_index) = reader.source_id(expanded)?;
+        self.insert(schema.source_id_type(), source_id_type);
+        self.insert(schema.source_id_index(), source_id_index);
+        self.insert(schema.sampling_rate(), reader.u32()?);
+        self.insert(schema.sample_pool(), reader.u32()?);
+        self.insert(schema.drops(), reader.u32()?);
+        if let Some(input) = reader.interface(expanded)? {
+            self.insert(schema.input_interface(), input);
+        }
+        if let Some(output) = reader.interface(expanded)? {
+            self.insert(schema.output_interface(), output);
+        }
+
+        let record_count = reader.u32()?;
+        for _ in 0..record_count {
+            let (enterprise, format, record) = reader.record()?;
+            if enterprise == 0 && format == RAW_PACKET_HEADER {
+                self.parent_key_path.push_field(schema.sampled_header());
+                let result = self.parse_sampled_header(XdrReader::new(record));
+                self.parent_key_path.segments.pop();
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_sampled_header(&mut self, mut reader: XdrReader<'_>) -> Result<()> {
+        let schema = self.event_schema.sampled_header_schema();
+        let header_protocol = reader.u32()?;
+        self.insert(schema.header_protocol(), header_protocol);
+        self.insert(schema.frame_length(), reader.u32()?);
+        self.insert(schema.stripped(), reader.u32()?);
+        let header = reader.opaque()?;
+        self.insert(schema.header_length(), header.len() as u32);
+
+        let decoded = match header_protocol {
+            HEADER_PROTOCOL_ETHERNET => self.parse_ethernet_header(header),
+            HEADER_PROTOCOL_IPV4 => self.parse_ipv4_header(header),
+            HEADER_PROTOCOL_IPV6 => self.parse_ipv6_header(header),
+            _ => false,
+        };
+        if !decoded {
+            self.insert(schema.raw_data(), base64::encode(header));
+        }
+        Ok(())
+    }
+
+    /// The headers are truncated by the agents, so they are decoded as far as they go, returning
+    /// whether the outermost one could be.
+    fn parse_ethernet_header(&mut self, header: &[u8]) -> bool {
+        if header.len() < 14 {
+            return false;
+        }
+        let schema = self.event_schema.ethernet_header_schema();
+        self.parent_key_path
+            .push_field(self.event_schema.sampled_header_schema().ethernet());
+
+        self.insert(schema.destination_mac(), to_mac_address(&header[0..6]));
+        self.insert(schema.source_mac(), to_mac_address(&header[6..12]));
+        let mut ether_type = u16::from_be_bytes([header[12], header[13]]);
+        let mut offset = 14;
+        if ether_type == ETHER_TYPE_VLAN && header.len() >= 18 {
+            self.insert(
+                schema.vlan_id(),
+                u16::from_be_bytes([header[14], header[15]]) & 0x0fff,
+            );
+            ether_type = u16::from_be_bytes([header[16], header[17]]);
+            offset = 18;
+        }
+        self.insert(schema.ether_type(), ether_type);
+
+        self.parent_key_path.segments.pop();
+
+        match ether_type {
+            ETHER_TYPE_IPV4 => self.parse_ipv4_header(&header[offset..]),
+            ETHER_TYPE_IPV6 => self.parse_ipv6_header(&header[offset..]),
+            _ => false,
+        };
+        true
+    }
+
+    fn parse_ipv4_header(&mut self, header: &[u8]) -> bool {
+        let header_length = header
+            .first()
+            .map_or(0, |byte| usize::from(byte & 0x0f) * 4);
+        if header.len() < 20 || header[0] >> 4 != 4 || header_length < 20 {
+            return false;
+        }
+        let schema = self.event_schema.ip_header_schema();
+        self.parent_key_path
+            .push_field(self.event_schema.sampled_header_schema().ip());
+
+        let protocol = header[9];
+        self.insert(schema.version(), 4);
+        self.insert(schema.tos(), header[1]);
+        self.insert(
+            schema.total_length(),
+            u16::from_be_bytes([header[2], header[3]]),
+        );
+        self.insert(schema.ttl(), header[8]);
+        self.insert(schema.protocol(), protocol);
+        self.insert(
+            schema.source_address(),
+            Ipv4Addr::new(header[12], header[13], header[14], header[15]).to_string(),
+        );
+        self.insert(
+            schema.destination_address(),
+            Ipv4Addr::new(header[16], header[17], header[18], header[19]).to_string(),
+        );
+
+        self.parent_key_path.segments.pop();
+
+        // Only the first fragment holds the transport header.
+        let fragment_offset = u16::from_be_bytes([header[6], header[7]]) & 0x1fff;
+        if fragment_offset == 0 && header.len() >= header_length {
+            self.parse_transport_header(protocol, &header[header_length..]);
+        }
+        true
+    }
+
+    fn parse_ipv6_header(&mut self, header: &[u8]) -> bool {
+        if header.len() < 40 || header[0] >> 4 != 6 {
+            return false;
+        }
+        let schema = self.event_schema.ip_header_schema();
+        self.parent_key_path
+            .push_field(self.event_schema.sampled_header_schema().ip());
+
+        // The extension headers aren't followed, so this is the protocol of the transport header
+        // only when there are none.
+        let protocol = header[6];
+        self.insert(schema.version(), 6);
+        self.insert(schema.tos(), ((header[0] & 0x0f) << 4) | (header[1] >> 4));
+        self.insert(
+            schema.total_length(),
+            u32::from(u16::from_be_bytes([header[4], header[5]])) + 40,
+        );
+        self.insert(schema.ttl(), header[7]);
+        self.insert(schema.protocol(), protocol);
+        let source_address: [u8; 16] = header[8..24].try_into().expect("length is checked");
+        self.insert(
+            schema.source_address(),
+            Ipv6Addr::from(source_address).to_string(),
+        );
+        let destination_address: [u8; 16] = header[24..40].try_into().expect("length is checked");
+        self.insert(
+            schema.destination_address(),
+            Ipv6Addr::from(destination_address).to_string(),
+        );
+
+        self.parent_key_path.segments.pop();
+
+        self.parse_transport_header(protocol, &header[40..]);
+        true
+    }
+
+    fn parse_transport_header(&mut self, protocol: u8, header: &[u8]) {
+        let min_length = match protocol {
+            IP_PROTOCOL_TCP => 14,
+            IP_PROTOCOL_UDP => 4,
+            _ => return,
+        };
+        if header.len() < min_length {
+            return;
+        }
+        let schema = self.event_schema.transport_header_schema();
+        self.parent_key_path
+            .push_field(self.event_schema.sampled_header_schema().transport());
+
+        self.insert(
+            schema.source_port(),
+            u16::from_be_bytes([header[0], header[1]]),
+        );
+        self.insert(
+            schema.destination_port(),
+            u16::from_be_bytes([header[2], header[3]]),
+        );
+        if protocol == IP_PROTOCOL_TCP {
+            // The NS flag is the lowest bit of the byte before the other ones.
+            self.insert(
+                schema.tcp_flags(),
+                u16::from(header[13]) | (u16::from(header[12] & 0x01) << 8),
+            );
+        }
+
+        self.parent_key_path.segments.pop();
+    }
+}
+
+fn parse_counter_sample(
+    header: &DatagramHeader,
+    timestamp: DateTime<Utc>,
+    mut reader: XdrReader<'_>,
+    expanded: bool,
+) -> std::result::Result<Vec<Metric>, SflowParserError> {
+    let _sequence_number = reader.u32()?;
+    let (source_id_type, source_id_index) = reader.source_id(expanded)?;
+    let mut tags = MetricTags::new();
+    tags.insert("agent_address".into(), header.agent_address.to_string());
+    tags.insert("sub_agent_id".into(), header.sub_agent_id.to_string());
+    tags.insert("source_id_type".into(), source_id_type.to_string());
+    tags.insert("source_id_index".into(), source_id_index.to_string());
+    let mut metrics = CounterMetrics {
+        tags,
+        timestamp,
+        metrics: Vec::new(),
+    };
+
+    let record_count = reader.u32()?;
+    for _ in 0..record_count {
+        let (enterprise, format, record) = reader.record()?;
+        let mut record = XdrReader::new(record);
+        match (enterprise, format) {
+            (0, GENERIC_INTERFACE_COUNTERS) => metrics.generic_interface(&mut record)?,
+            (0, ETHERNET_INTERFACE_COUNTERS) => metrics.ethernet_interface(&mut record)?,
+            (0, PROCESSOR_COUNTERS) => metrics.processor(&mut record)?,
+            _ => (),
+        }
+    }
+    Ok(metrics.metrics)
+}
+
+struct CounterMetrics {
+    tags: MetricTags,
+    timestamp: DateTime<Utc>,
+    metrics: Vec<Metric>,
+}
+
+impl CounterMetrics {
+    fn counter(&mut self, name: &str, value: u64) {
+        self.push(
+            name,
+            MetricValue::Counter {
+                value: value as f64,
+            },
+        );
+    }
+
+    fn gauge(&mut self, name: &str, value: f64) {
+        self.push(name, MetricValue::Gauge { value });
+    }
+
+    fn push(&mut self, name: &str, value: MetricValue) {
+        self.metrics.push(
+            Metric::new(name, MetricKind::Absolute, value)
+                .with_namespace(Some(METRIC_NAMESPACE))
+                .with_tags(Some(self.tags.clone()))
+                .with_timestamp(Some(self.timestamp)),
+        );
+    }
+
+    fn generic_interface(
+        &mut self,
+        reader: &mut XdrReader<'_>,
+    ) -> std::result::Result<(), SflowParserError> {
+        let _if_index = reader.u32()?;
+        let _if_type = reader.u32()?;
+        self.gauge("interface_speed_bits_per_second", reader.u64()? as f64);
+        let _if_direction = reader.u32()?;
+        let if_status = reader.u32()?;
+        self.gauge("interface_admin_up", f64::from(if_status & 0x01));
+        self.gauge("interface_oper_up", f64::from((if_status >> 1) & 0x01));
+        self.counter("interface_in_octets_total", reader.u64()?);
+        for name in [
+            "interface_in_unicast_packets_total",
+            "interface_in_multicast_packets_total",
+            "interface_in_broadcast_packets_total",
+            "interface_in_discards_total",
+            "interface_in_errors_total",
+            "interface_in_unknown_protocols_total",
+        ] {
+            self.counter(name, reader.u32()?.into());
+        }
+        self.counter("interface_out_octets_total", reader.u64()?);
+        for name in [
+            "interface_out_unicast_packets_total",
+            "interface_out_multicast_packets_total",
+            "interface_out_broadcast_packets_total",
+            "interface_out_discards_total",
+            "interface_out_errors_total",
+        ] {
+            self.counter(name, reader.u32()?.into());
+        }
+        self.gauge("interface_promiscuous_mode", reader.u32()?.into());
+        Ok(())
+    }
+
+    fn ethernet_interface(
+        &mut self,
+        reader: &mut XdrReader<'_>,
+    ) -> std::result::Result<(), SflowParserError> {
+        for name in [
+            "ethernet_alignment_errors_total",
+            "ethernet_fcs_errors_total",
+            "ethernet_single_collision_frames_total",
+            "ethernet_multiple_collision_frames_total",
+            "ethernet_sqe_test_errors_total",
+            "ethernet_deferred_transmissions_total",
+            "ethernet_late_collisions_total",
+            "ethernet_excessive_collisions_total",
+            "ethernet_internal_mac_transmit_errors_total",
+            "ethernet_carrier_sense_errors_total",
+            "ethernet_frame_too_longs_total",
+            "ethernet_internal_mac_receive_errors_total",
+            "ethernet_symbol_errors_total",
+        ] {
+            self.counter(name, reader.u32()?.into());
+        }
+        Ok(())
+    }
+
+    fn processor(
+        &mut self,
+        reader: &mut XdrReader<'_>,
+    ) -> std::result::Result<(), SflowParserError> {
+        // The loads are sent in hundredths of percent.
+        for name in [
+            "processor_cpu_5s_percent",
+            "processor_cpu_1m_percent",
+            "processor_cpu_5m_percent",
+        ] {
+            self.gauge(name, f64::from(reader.u32()?) / 100.0);
+        }
+        self.gauge("processor_memory_total_bytes", reader.u64()? as f64);
+        self.gauge("processor_memory_free_bytes", reader.u64()? as f64);
+        Ok(())
+    }
+}
+
+fn to_mac_address(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Reads the XDR encoded data of the datagrams, failing on truncated ones.
+struct XdrReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> XdrReader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, length: usize) -> std::result::Result<&'a [u8], SflowParserError> {
+        if self.data.len() < length {
+            return Err(SflowParserError::TruncatedData);
+        }
+        let (taken, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> std::result::Result<u32, SflowParserError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> std::result::Result<u64, SflowParserError> {
+        Ok((u64::from(self.u32()?) << 32) | u64::from(self.u32()?))
+    }
+
+    /// Reads variable-length opaque data, which is padded to 4 bytes.
+    fn opaque(&mut self) -> std::result::Result<&'a [u8], SflowParserError> {
+        let length = self.u32()? as usize;
+        let data = self.take(length)?;
+        self.take((4 - length % 4) % 4)?;
+        Ok(data)
+    }
+
+    fn ip_address(&mut self) -> std::result::Result<IpAddr, SflowParserError> {
+        match self.u32()? {
+            1 => Ok(IpAddr::V4(Ipv4Addr::from(self.u32()?))),
+            2 => {
+                let octets: [u8; 16] = self.take(16)?.try_into().expect("length is checked");
+                Ok(IpAddr::V6(Ipv6Addr::from(octets)))
+            }
+            address_type => Err(SflowParserError::UnsupportedAddressType { address_type }),
+        }
+    }
+
+    /// Reads a sample or a record, returning its enterprise, its format and its data.
+    fn record(&mut self) -> std::result::Result<(u32, u32, &'a [u8]), SflowParserError> {
+        let data_format = self.u32()?;
+        let data = self.opaque()?;
+        Ok((data_format >> 12, data_format & 0x0fff, data))
+    }
+
+    /// Reads the type and index of the source of a sample, which are packed in the compact
+    /// samples.
+    fn source_id(&mut self, expanded: bool) -> std::result::Result<(u32, u32), SflowParserError> {
+        if expanded {
+            Ok((self.u32()?, self.u32()?))
+        } else {
+            let source_id = self.u32()?;
+            Ok((source_id >> 24, source_id & 0x00ff_ffff))
+        }
+    }
+
+    /// Reads the index of an interface of a flow sample, if the packets went through a single one.
+    fn interface(&mut self, expanded: bool) -> std::result::Result<Option<u32>, SflowParserError> {
+        let (format, value) = if expanded {
+            (self.u32()?, self.u32()?)
+        } else {
+            let interface = self.u32()?;
+            (interface >> 30, interface & 0x3fff_ffff)
+        };
+        Ok((format == 0).then(|| value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datagram(samples: &[(u32, Vec<u8>)]) -> Bytes {
+        let mut datagram = Vec::new();
+        for value in [5, 1, 0xc0_00_02_01, 0, 42, 60_000, samples.len() as u32] {
+            datagram.extend_from_slice(&u32::to_be_bytes(value));
+        }
+        for (format, data) in samples {
+            datagram.extend_from_slice(&format.to_be_bytes());
+            datagram.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            datagram.extend_from_slice(data);
+        }
+        datagram.into()
+    }
+
+    fn words(values: &[u32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    fn flow_sample(header_protocol: u32, header: &[u8]) -> Vec<u8> {
+        let mut record = words(&[header_protocol, 1518, 4, header.len() as u32]);
+        record.extend_from_slice(header);
+        record.resize(record.len() + (4 - header.len() % 4) % 4, 0);
+
+        let mut sample = words(&[7, 3, 1024, 8192, 0, 3, 0x4000_0000, 1, RAW_PACKET_HEADER]);
+        sample.extend_from_slice(&(record.len() as u32).to_be_bytes());
+        sample.extend_from_slice(&record);
+        sample
+    }
+
+    fn ethernet_ipv4_tcp_header() -> Vec<u8> {
+        let mut header = vec![
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // destination MAC
+            0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, // source MAC
+            0x81, 0x00, 0x00, 0x0a, // VLAN 10
+            0x08, 0x00, // IPv4
+        ];
+        header.extend_from_slice(&[
+            0x45, 0x10, 0x05, 0xdc, 0, 0, 0x40, 0, 64, 6, 0, 0, // IPv4 header
+            10, 0, 0, 1, // source address
+            10, 0, 0, 2, // destination address
+        ]);
+        header.extend_from_slice(&[
+            0xc3, 0x50, 0x01, 0xbb, 0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x12, // TCP header
+        ]);
+        header
+    }
+
+    #[test]
+    fn test_parse_sflow_flow_sample() {
+        let schema = SflowEventSchema::new();
+        let events = parse_sflow_datagram(
+            &schema,
+            datagram(&[(FLOW_SAMPLE, flow_sample(1, &ethernet_ipv4_tcp_header()))]),
+        )
+        .unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["agentAddress"], "192.0.2.1".into());
+        assert_eq!(log["datagramSequenceNumber"], 42.into());
+        assert_eq!(log["sampleType"], "flow".into());
+        assert_eq!(log["sourceIdType"], 0.into());
+        assert_eq!(log["sourceIdIndex"], 3.into());
+        assert_eq!(log["samplingRate"], 1024.into());
+        assert_eq!(log["inputInterface"], 3.into());
+        assert!(!log.contains("outputInterface"));
+        assert_eq!(log["sampledHeader.frameLength"], 1518.into());
+        assert_eq!(
+            log["sampledHeader.ethernet.sourceMac"],
+            "66:77:88:99:aa:bb".into()
+        );
+        assert_eq!(log["sampledHeader.ethernet.vlanId"], 10.into());
+        assert_eq!(log["sampledHeader.ethernet.etherType"], 0x0800.into());
+        assert_eq!(log["sampledHeader.ip.version"], 4.into());
+        assert_eq!(log["sampledHeader.ip.sourceAddress"], "10.0.0.1".into());
+        assert_eq!(
+            log["sampledHeader.ip.destinationAddress"],
+            "10.0.0.2".into()
+        );
+        assert_eq!(log["sampledHeader.ip.protocol"], 6.into());
+        assert_eq!(log["sampledHeader.ip.ttl"], 64.into());
+        assert_eq!(log["sampledHeader.transport.sourcePort"], 50000.into());
+        assert_eq!(log["sampledHeader.transport.destinationPort"], 443.into());
+        assert_eq!(log["sampledHeader.transport.tcpFlags"], 0x12.into());
+        assert!(!log.contains("sampledHeader.rawData"));
+    }
+
+    #[test]
+    fn test_parse_sflow_ipv6_header() {
+        let mut header = vec![0x60, 0, 0, 0, 0, 8, 17, 255];
+        header.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        header.extend_from_slice(&"2001:db8::1".parse::<Ipv6Addr>().unwrap().octets());
+        header.extend_from_slice(&[0, 53, 0x30, 0x39, 0, 8, 0, 0]);
+        let schema = SflowEventSchema::new();
+        let events = parse_sflow_datagram(
+            &schema,
+            datagram(&[(FLOW_SAMPLE, flow_sample(HEADER_PROTOCOL_IPV6, &header))]),
+        )
+        .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["sampledHeader.ip.version"], 6.into());
+        assert_eq!(log["sampledHeader.ip.sourceAddress"], "::1".into());
+        assert_eq!(
+            log["sampledHeader.ip.destinationAddress"],
+            "2001:db8::1".into()
+        );
+        assert_eq!(log["sampledHeader.ip.totalLength"], 48.into());
+        assert_eq!(log["sampledHeader.transport.destinationPort"], 12345.into());
+    }
+
+    #[test]
+    fn test_parse_sflow_unknown_header_protocol() {
+        let schema = SflowEventSchema::new();
+        let events = parse_sflow_datagram(
+            &schema,
+            datagram(&[(FLOW_SAMPLE, flow_sample(7, &[1, 2, 3]))]),
+        )
+        .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["sampledHeader.headerProtocol"], 7.into());
+        assert_eq!(log["sampledHeader.rawData"], "AQID".into());
+    }
+
+    #[test]
+    fn test_parse_sflow_truncated_flow_sample() {
+        let schema = SflowEventSchema::new();
+        let events =
+            parse_sflow_datagram(&schema, datagram(&[(FLOW_SAMPLE, words(&[7, 3]))])).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["error"], "Truncated sFlow data".into());
+        assert_eq!(log["rawData"], "AAAABwAAAAM=".into());
+    }
+
+    #[test]
+    fn test_parse_sflow_counter_sample() {
+        let mut interface = words(&[3, 6]);
+        interface.extend_from_slice(&10_000_000_000_u64.to_be_bytes());
+        interface.extend_from_slice(&words(&[1, 3]));
+        interface.extend_from_slice(&123_456_u64.to_be_bytes());
+        interface.extend_from_slice(&words(&[100, 1, 2, 3, 4, 5]));
+        interface.extend_from_slice(&654_321_u64.to_be_bytes());
+        interface.extend_from_slice(&words(&[200, 6, 7, 8, 9, 0]));
+
+        let mut sample = words(&[1, 3, 2, GENERIC_INTERFACE_COUNTERS]);
+        sample.extend_from_slice(&(interface.len() as u32).to_be_bytes());
+        sample.extend_from_slice(&interface);
+        // An enterprise record, which is skipped.
+        sample.extend_from_slice(&words(&[(9 << 12) | 1, 4, 0]));
+
+        let schema = SflowEventSchema::new();
+        let events = parse_sflow_datagram(&schema, datagram(&[(COUNTER_SAMPLE, sample)])).unwrap();
+        let metrics = events
+            .iter()
+            .map(|event| event.as_metric())
+            .collect::<Vec<_>>();
+        assert_eq!(metrics.len(), 17);
+
+        let in_octets = metrics
+            .iter()
+            .find(|metric| metric.name() == "interface_in_octets_total")
+            .unwrap();
+        assert_eq!(in_octets.namespace(), Some("sflow"));
+        assert_eq!(
+            in_octets.value(),
+            &MetricValue::Counter { value: 123_456.0 }
+        );
+        let tags = in_octets.tags().unwrap();
+        assert_eq!(tags["agent_address"], "192.0.2.1");
+        assert_eq!(tags["source_id_index"], "3");
+
+        let speed = metrics
+            .iter()
+            .find(|metric| metric.name() == "interface_speed_bits_per_second")
+            .unwrap();
+        assert_eq!(speed.value(), &MetricValue::Gauge { value: 1e10 });
+        let oper_up = metrics
+            .iter()
+            .find(|metric| metric.name() == "interface_oper_up")
+            .unwrap();
+        assert_eq!(oper_up.value(), &MetricValue::Gauge { value: 1.0 });
+    }
+
+    #[test]
+    fn test_parse_sflow_unsupported_version() {
+        let schema = SflowEventSchema::new();
+        let error = parse_sflow_datagram(&schema, Bytes::from(words(&[4]))).unwrap_err();
+        assert_eq!(error.to_string(), "Unsupported sFlow version: 4");
+    }
+}
@@ -0,0 +1,367 @@
+#[derive(Default, Debug, Clone)]
+pub struct SflowEventSchema {
+    sflow_root_data_schema: SflowRootDataSchema,
+    sflow_flow_sample_schema: SflowFlowSampleSchema,
+    sampled_header_schema: SampledHeaderSchema,
+    ethernet_header_schema: EthernetHeaderSchema,
+    ip_header_schema: IpHeaderSchema,
+    transport_header_schema: TransportHeaderSchema,
+}
+
+impl SflowEventSchema {
+    pub const fn sflow_root_data_schema(&self) -> &SflowRootDataSchema {
+        &self.sflow_root_data_schema
+    }
+
+    pub const fn sflow_flow_sample_schema(&self) -> &SflowFlowSampleSchema {
+        &self.sflow_flow_sample_schema
+    }
+
+    pub const fn sampled_header_schema(&self) -> &SampledHeaderSchema {
+        &self.sampled_header_schema
+    }
+
+    pub const fn ethernet_header_schema(&self) -> &EthernetHeaderSchema {
+        &self.ethernet_header_schema
+    }
+
+    pub const fn ip_header_schema(&self) -> &IpHeaderSchema {
+        &self.ip_header_schema
+    }
+
+    pub const fn transport_header_schema(&self) -> &TransportHeaderSchema {
+        &self.transport_header_schema
+    }
+
+    pub fn sflow_root_data_schema_mut(&mut self) -> &mut SflowRootDataSchema {
+        &mut self.sflow_root_data_schema
+    }
+
+    pub fn new() -> Self {
+        Self {
+            sflow_root_data_schema: SflowRootDataSchema::default(),
+            sflow_flow_sample_schema: SflowFlowSampleSchema::default(),
+            sampled_header_schema: SampledHeaderSchema::default(),
+            ethernet_header_schema: EthernetHeaderSchema::default(),
+            ip_header_schema: IpHeaderSchema::default(),
+            transport_header_schema: TransportHeaderSchema::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SflowRootDataSchema {
+    agent_address: &'static str,
+    sub_agent_id: &'static str,
+    datagram_sequence_number: &'static str,
+    uptime: &'static str,
+    timestamp: &'static str,
+    sample_type: &'static str,
+    error: &'static str,
+    raw_data: &'static str,
+}
+
+impl Default for SflowRootDataSchema {
+    fn default() -> Self {
+        Self {
+            agent_address: "agentAddress",
+            sub_agent_id: "subAgentId",
+            datagram_sequence_number: "datagramSequenceNumber",
+            uptime: "uptime",
+            timestamp: "timestamp",
+            sample_type: "sampleType",
+            error: "error",
+            raw_data: "rawData",
+        }
+    }
+}
+
+impl SflowRootDataSchema {
+    pub const fn agent_address(&self) -> &'static str {
+        self.agent_address
+    }
+
+    pub const fn sub_agent_id(&self) -> &'static str {
+        self.sub_agent_id
+    }
+
+    pub const fn datagram_sequence_number(&self) -> &'static str {
+        self.datagram_sequence_number
+    }
+
+    pub const fn uptime(&self) -> &'static str {
+        self.uptime
+    }
+
+    pub const fn timestamp(&self) -> &'static str {
+        self.timestamp
+    }
+
+    pub const fn sample_type(&self) -> &'static str {
+        self.sample_type
+    }
+
+    pub const fn error(&self) -> &'static str {
+        self.error
+    }
+
+    pub const fn raw_data(&self) -> &'static str {
+        self.raw_data
+    }
+
+    pub fn set_timestamp(&mut self, val: &'static str) -> &mut Self {
+        self.timestamp = val;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SflowFlowSampleSchema {
+    sequence_number: &'static str,
+    source_id_type: &'static str,
+    source_id_index: &'static str,
+    sampling_rate: &'static str,
+    sample_pool: &'static str,
+    drops: &'static str,
+    input_interface: &'static str,
+    output_interface: &'static str,
+    sampled_header: &'static str,
+}
+
+impl Default for SflowFlowSampleSchema {
+    fn default() -> Self {
+        Self {
+            sequence_number: "sequenceNumber",
+            source_id_type: "sourceIdType",
+            source_id_index: "sourceIdIndex",
+            sampling_rate: "samplingRate",
+            sample_pool: "samplePool",
+            drops: "drops",
+            input_interface: "inputInterface",
+            output_interface: "outputInterface",
+            sampled_header: "sampledHeader",
+        }
+    }
+}
+
+impl SflowFlowSampleSchema {
+    pub const fn sequence_number(&self) -> &'static str {
+        self.sequence_number
+    }
+
+    pub const fn source_id_type(&self) -> &'static str {
+        self.source_id_type
+    }
+
+    pub const fn source_id_index(&self) -> &'static str {
+        self.source_id_index
+    }
+
+    pub const fn sampling_rate(&self) -> &'static str {
+        self.sampling_rate
+    }
+
+    pub const fn sample_pool(&self) -> &'static str {
+        self.sample_pool
+    }
+
+    pub const fn drops(&self) -> &'static str {
+        self.drops
+    }
+
+    pub const fn input_interface(&self) -> &'static str {
+        self.input_interface
+    }
+
+    pub const fn output_interface(&self) -> &'static str {
+        self.output_interface
+    }
+
+    pub const fn sampled_header(&self) -> &'static str {
+        self.sampled_header
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SampledHeaderSchema {
+    header_protocol: &'static str,
+    frame_length: &'static str,
+    stripped: &'static str,
+    header_length: &'static str,
+    ethernet: &'static str,
+    ip: &'static str,
+    transport: &'static str,
+    raw_data: &'static str,
+}
+
+impl Default for SampledHeaderSchema {
+    fn default() -> Self {
+        Self {
+            header_protocol: "headerProtocol",
+            frame_length: "frameLength",
+            stripped: "stripped",
+            header_length: "headerLength",
+            ethernet: "ethernet",
+            ip: "ip",
+            transport: "transport",
+            raw_data: "rawData",
+        }
+    }
+}
+
+impl SampledHeaderSchema {
+    pub const fn header_protocol(&self) -> &'static str {
+        self.header_protocol
+    }
+
+    pub const fn frame_length(&self) -> &'static str {
+        self.frame_length
+    }
+
+    pub const fn stripped(&self) -> &'static str {
+        self.stripped
+    }
+
+    pub const fn header_length(&self) -> &'static str {
+        self.header_length
+    }
+
+    pub const fn ethernet(&self) -> &'static str {
+        self.ethernet
+    }
+
+    pub const fn ip(&self) -> &'static str {
+        self.ip
+    }
+
+    pub const fn transport(&self) -> &'static str {
+        self.transport
+    }
+
+    pub const fn raw_data(&self) -> &'static str {
+        self.raw_data
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EthernetHeaderSchema {
+    source_mac: &'static str,
+    destination_mac: &'static str,
+    ether_type: &'static str,
+    vlan_id: &'static str,
+}
+
+impl Default for EthernetHeaderSchema {
+    fn default() -> Self {
+        Self {
+            source_mac: "sourceMac",
+            destination_mac: "destinationMac",
+            ether_type: "etherType",
+            vlan_id: "vlanId",
+        }
+    }
+}
+
+impl EthernetHeaderSchema {
+    pub const fn source_mac(&self) -> &'static str {
+        self.source_mac
+    }
+
+    pub const fn destination_mac(&self) -> &'static str {
+        self.destination_mac
+    }
+
+    pub const fn ether_type(&self) -> &'static str {
+        self.ether_type
+    }
+
+    pub const fn vlan_id(&self) -> &'static str {
+        self.vlan_id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IpHeaderSchema {
+    version: &'static str,
+    source_address: &'static str,
+    destination_address: &'static str,
+    protocol: &'static str,
+    tos: &'static str,
+    ttl: &'static str,
+    total_length: &'static str,
+}
+
+impl Default for IpHeaderSchema {
+    fn default() -> Self {
+        Self {
+            version: "version",
+            source_address: "sourceAddress",
+            destination_address: "destinationAddress",
+            protocol: "protocol",
+            tos: "tos",
+            ttl: "ttl",
+            total_length: "totalLength",
+        }
+    }
+}
+
+impl IpHeaderSchema {
+    pub const fn version(&self) -> &'static str {
+        self.version
+    }
+
+    pub const fn source_address(&self) -> &'static str {
+        self.source_address
+    }
+
+    pub const fn destination_address(&self) -> &'static str {
+        self.destination_address
+    }
+
+    pub const fn protocol(&self) -> &'static str {
+        self.protocol
+    }
+
+    pub const fn tos(&self) -> &'static str {
+        self.tos
+    }
+
+    pub const fn ttl(&self) -> &'static str {
+        self.ttl
+    }
+
+    pub const fn total_length(&self) -> &'static str {
+        self.total_length
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransportHeaderSchema {
+    source_port: &'static str,
+    destination_port: &'static str,
+    tcp_flags: &'static str,
+}
+
+impl Default for TransportHeaderSchema {
+    fn default() -> Self {
+        Self {
+            source_port: "sourcePort",
+            destination_port: "destinationPort",
+            tcp_flags: "tcpFlags",
+        }
+    }
+}
+
+impl TransportHeaderSchema {
+    pub const fn source_port(&self) -> &'static str {
+        self.source_port
+    }
+
+    pub const fn destination_port(&self) -> &'static str {
+        self.destination_port
+    }
+
+    pub const fn tcp_flags(&self) -> &'static str {
+        self.tcp_flags
+    }
+}
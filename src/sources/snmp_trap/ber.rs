@@ -0,0 +1,291 @@
+//! The subset of the Basic Encoding Rules used by SNMP messages.
+
+use snafu::Snafu;
+
+pub(super) const INTEGER: u8 = 0x02;
+pub(super) const OCTET_STRING: u8 = 0x04;
+pub(super) const NULL: u8 = 0x05;
+pub(super) const OBJECT_IDENTIFIER: u8 = 0x06;
+pub(super) const SEQUENCE: u8 = 0x30;
+pub(super) const IP_ADDRESS: u8 = 0x40;
+pub(super) const COUNTER32: u8 = 0x41;
+pub(super) const GAUGE32: u8 = 0x42;
+pub(super) const TIME_TICKS: u8 = 0x43;
+pub(super) const OPAQUE: u8 = 0x44;
+pub(super) const COUNTER64: u8 = 0x46;
+pub(super) const NO_SUCH_OBJECT: u8 = 0x80;
+pub(super) const NO_SUCH_INSTANCE: u8 = 0x81;
+pub(super) const END_OF_MIB_VIEW: u8 = 0x82;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub(crate) enum BerError {
+    #[snafu(display("Truncated BER data"))]
+    Truncated,
+    #[snafu(display("Unsupported BER length"))]
+    UnsupportedLength,
+    #[snafu(display("Expected tag 0x{:02x}, found 0x{:02x}", expected, found))]
+    UnexpectedTag { expected: u8, found: u8 },
+    #[snafu(display("Invalid integer of {} bytes", length))]
+    InvalidInteger { length: usize },
+    #[snafu(display("Invalid object identifier"))]
+    InvalidObjectIdentifier,
+}
+
+/// Reads the TLVs of some BER data one after the other.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub(super) const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub(super) fn peek_tag(&self) -> Result<u8, BerError> {
+        self.data.first().copied().ok_or(BerError::Truncated)
+    }
+
+    /// Reads a TLV, returning its tag, its contents and the whole encoded TLV.
+    pub(super) fn read(&mut self) -> Result<(u8, &'a [u8], &'a [u8]), BerError> {
+        let tag = self.peek_tag()?;
+        let first = *self.data.get(1).ok_or(BerError::Truncated)?;
+        let (length, header) = if first & 0x80 == 0 {
+            (first as usize, 2)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 {
+                return Err(BerError::UnsupportedLength);
+            }
+            let bytes = self.data.get(2..2 + count).ok_or(BerError::Truncated)?;
+            let length = bytes
+                .iter()
+                .fold(0usize, |length, byte| length << 8 | *byte as usize);
+            (length, 2 + count)
+        };
+        let end = header.checked_add(length).ok_or(BerError::Truncated)?;
+        if end > self.data.len() {
+            return Err(BerError::Truncated);
+        }
+        let (tlv, rest) = self.data.split_at(end);
+        self.data = rest;
+        Ok((tag, &tlv[header..], tlv))
+    }
+
+    pub(super) fn expect(&mut self, expected: u8) -> Result<&'a [u8], BerError> {
+        let (found, contents, _) = self.read()?;
+        if found != expected {
+            return Err(BerError::UnexpectedTag { expected, found });
+        }
+        Ok(contents)
+    }
+
+    pub(super) fn sequence(&mut self) -> Result<Decoder<'a>, BerError> {
+        self.expect(SEQUENCE).map(Decoder::new)
+    }
+
+    pub(super) fn integer(&mut self) -> Result<i64, BerError> {
+        self.expect(INTEGER).and_then(decode_integer)
+    }
+
+    pub(super) fn octet_string(&mut self) -> Result<&'a [u8], BerError> {
+        self.expect(OCTET_STRING)
+    }
+
+    pub(super) fn object_identifier(&mut self) -> Result<Vec<u32>, BerError> {
+        self.expect(OBJECT_IDENTIFIER).and_then(decode_oid)
+    }
+}
+
+pub(super) fn decode_integer(contents: &[u8]) -> Result<i64, BerError> {
+    if contents.is_empty() || contents.len() > 8 {
+        return Err(BerError::InvalidInteger {
+            length: contents.len(),
+        });
+    }
+    let sign = if contents[0] & 0x80 != 0 { -1i64 } else { 0 };
+    Ok(contents
+        .iter()
+        .fold(sign, |value, byte| value << 8 | *byte as i64))
+}
+
+/// Decodes the unsigned integers of the application types, which are encoded like integers
+/// but may need an extra leading zero byte.
+pub(super) fn decode_unsigned(contents: &[u8]) -> Result<u64, BerError> {
+    let contents = match contents {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        contents => contents,
+    };
+    if contents.is_empty() || contents.len() > 8 {
+        return Err(BerError::InvalidInteger {
+            length: contents.len(),
+        });
+    }
+    Ok(contents
+        .iter()
+        .fold(0u64, |value, byte| value << 8 | *byte as u64))
+}
+
+pub(super) fn decode_oid(contents: &[u8]) -> Result<Vec<u32>, BerError> {
+    let mut oid = Vec::with_capacity(contents.len() + 1);
+    let mut value = 0u32;
+    for (index, byte) in contents.iter().enumerate() {
+        if value > u32::MAX >> 7 {
+            return Err(BerError::InvalidObjectIdentifier);
+        }
+        value = value << 7 | (byte & 0x7f) as u32;
+        if byte & 0x80 != 0 {
+            if index + 1 == contents.len() {
+                return Err(BerError::InvalidObjectIdentifier);
+            }
+            continue;
+        }
+        if oid.is_empty() {
+            let first = (value / 40).min(2);
+            oid.push(first);
+            oid.push(value - first * 40);
+        } else {
+            oid.push(value);
+        }
+        value = 0;
+    }
+    if oid.is_empty() {
+        return Err(BerError::InvalidObjectIdentifier);
+    }
+    Ok(oid)
+}
+
+pub(super) fn encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut tlv = Vec::with_capacity(contents.len() + 6);
+    tlv.push(tag);
+    let length = contents.len();
+    if length < 0x80 {
+        tlv.push(length as u8);
+    } else {
+        let bytes = (length as u32).to_be_bytes();
+        let skip = bytes.iter().take_while(|byte| **byte == 0).count();
+        tlv.push(0x80 | (4 - skip) as u8);
+        tlv.extend_from_slice(&bytes[skip..]);
+    }
+    tlv.extend_from_slice(contents);
+    tlv
+}
+
+pub(super) fn encode_integer(value: i64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    // Drops the leading bytes which only repeat the sign of the next one.
+    let mut start = 0;
+    while start < 7 {
+        let (byte, next) = (bytes[start], bytes[start + 1]);
+        if (byte == 0 && next & 0x80 == 0) || (byte == 0xff && next & 0x80 != 0) {
+            start += 1;
+        } else {
+            break;
+        }
+    }
+    encode(INTEGER, &bytes[start..])
+}
+
+pub(super) fn encode_unsigned(tag: u8, value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|byte| **byte == 0).count().min(7);
+    if bytes[skip] & 0x80 != 0 {
+        let mut contents = vec![0];
+        contents.extend_from_slice(&bytes[skip..]);
+        encode(tag, &contents)
+    } else {
+        encode(tag, &bytes[skip..])
+    }
+}
+
+pub(super) fn encode_oid(oid: &[u32]) -> Vec<u8> {
+    let mut contents = Vec::with_capacity(oid.len() + 4);
+    let first = oid.first().copied().unwrap_or(0) * 40 + oid.get(1).copied().unwrap_or(0);
+    for value in std::iter::once(first).chain(oid.iter().skip(2).copied()) {
+        let mut shift = 28;
+        while shift > 0 && value >> shift == 0 {
+            shift -= 7;
+        }
+        while shift > 0 {
+            contents.push(0x80 | (value >> shift) as u8 & 0x7f);
+            shift -= 7;
+        }
+        contents.push(value as u8 & 0x7f);
+    }
+    encode(OBJECT_IDENTIFIER, &contents)
+}
+
+pub(super) fn oid_to_string(oid: &[u32]) -> String {
+    oid.iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_round_trip() {
+        for value in [0, 1, 127, 128, 255, 256, -1, -128, -129, i64::MAX, i64::MIN] {
+            let tlv = encode_integer(value);
+            assert_eq!(Decoder::new(&tlv).integer(), Ok(value), "{}", value);
+        }
+        assert_eq!(encode_integer(128), vec![0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(encode_integer(-128), vec![0x02, 0x01, 0x80]);
+    }
+
+    #[test]
+    fn unsigned_integers_round_trip() {
+        for value in [0, 127, 128, u32::MAX as u64, u64::MAX] {
+            let tlv = encode_unsigned(COUNTER64, value);
+            assert_eq!(
+                Decoder::new(&tlv).expect(COUNTER64).map(decode_unsigned),
+                Ok(Ok(value))
+            );
+        }
+    }
+
+    #[test]
+    fn oids_round_trip() {
+        let oid = vec![1, 3, 6, 1, 4, 1, 8072, 2, 3, 0, 1];
+        let tlv = encode_oid(&oid);
+        assert_eq!(
+            tlv,
+            vec![0x06, 0x0b, 0x2b, 0x06, 0x01, 0x04, 0x01, 0xbf, 0x08, 0x02, 0x03, 0x00, 0x01]
+        );
+        assert_eq!(Decoder::new(&tlv).object_identifier(), Ok(oid));
+        assert_eq!(oid_to_string(&[1, 3, 6, 1]), "1.3.6.1");
+    }
+
+    #[test]
+    fn long_lengths() {
+        let contents = vec![0x61; 300];
+        let tlv = encode(OCTET_STRING, &contents);
+        assert_eq!(&tlv[..4], &[0x04, 0x82, 0x01, 0x2c]);
+        let mut decoder = Decoder::new(&tlv);
+        assert_eq!(decoder.octet_string(), Ok(&contents[..]));
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn truncated_data() {
+        let tlv = encode(OCTET_STRING, b"public");
+        assert_eq!(
+            Decoder::new(&tlv[..5]).octet_string(),
+            Err(BerError::Truncated)
+        );
+        assert_eq!(
+            Decoder::new(&tlv).integer(),
+            Err(BerError::UnexpectedTag {
+                expected: INTEGER,
+                found: OCTET_STRING
+            })
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! Decodes and encodes the SNMP messages, described in RFC 1157, RFC 3416 and RFC 3412.
+
+use std::net::Ipv4Addr;
+
+use snafu::Snafu;
+
+use super::ber::{self, BerError, Decoder};
+
+pub(super) const VERSION_1: i64 = 0;
+pub(super) const VERSION_2C: i64 = 1;
+pub(super) const VERSION_3: i64 = 3;
+
+pub(super) const GET_REQUEST: u8 = 0xa0;
+pub(super) const RESPONSE: u8 = 0xa2;
+pub(super) const V1_TRAP: u8 = 0xa4;
+pub(super) const INFORM_REQUEST: u8 = 0xa6;
+pub(super) const V2_TRAP: u8 = 0xa7;
+pub(super) const REPORT: u8 = 0xa8;
+
+pub(super) const AUTH_FLAG: u8 = 0x01;
+pub(super) const PRIV_FLAG: u8 = 0x02;
+pub(super) const REPORTABLE_FLAG: u8 = 0x04;
+
+/// The User-based Security Model.
+const USM_SECURITY_MODEL: i64 = 3;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub(crate) enum MessageError {
+    #[snafu(context(false), display("{}", source))]
+    Ber { source: BerError },
+    #[snafu(display("Unsupported SNMP version {}", version))]
+    UnsupportedVersion { version: i64 },
+    #[snafu(display("Unsupported security model {}", model))]
+    UnsupportedSecurityModel { model: i64 },
+    #[snafu(display("Unexpected PDU type 0x{:02x}", tag))]
+    UnexpectedPdu { tag: u8 },
+    #[snafu(display("Invalid IP address of {} bytes", length))]
+    InvalidIpAddress { length: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum VarBindValue {
+    Integer(i64),
+    Unsigned(u64),
+    OctetString(Vec<u8>),
+    Null,
+    ObjectIdentifier(Vec<u32>),
+    IpAddress(Ipv4Addr),
+    Opaque(Vec<u8>),
+    /// The `noSuchObject`, `noSuchInstance` and `endOfMibView` exceptions.
+    Exception,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct VarBind {
+    pub(super) oid: Vec<u32>,
+    pub(super) value: VarBindValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct V1Trap {
+    pub(super) enterprise: Vec<u32>,
+    pub(super) agent_address: Ipv4Addr,
+    pub(super) generic_trap: i64,
+    pub(super) specific_trap: i64,
+    pub(super) time_stamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Pdu {
+    pub(super) tag: u8,
+    pub(super) request_id: i64,
+    pub(super) v1_trap: Option<V1Trap>,
+    pub(super) varbinds: Vec<VarBind>,
+    /// The encoded variable bindings, sent back in the responses to informs.
+    pub(super) encoded_varbinds: Vec<u8>,
+}
+
+/// A message of SNMPv1 or SNMPv2c.
+#[derive(Debug, PartialEq)]
+pub(super) struct CommunityMessage<'a> {
+    pub(super) version: i64,
+    pub(super) community: &'a [u8],
+    pub(super) pdu: Pdu,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct SecurityParameters<'a> {
+    pub(super) engine_id: &'a [u8],
+    pub(super) engine_boots: u32,
+    pub(super) engine_time: u32,
+    pub(super) user_name: &'a [u8],
+    pub(super) auth_params: &'a [u8],
+    pub(super) priv_params: &'a [u8],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum ScopedPduData<'a> {
+    /// The encoded scoped PDU.
+    Plaintext(&'a [u8]),
+    Encrypted(&'a [u8]),
+}
+
+/// A message of SNMPv3.
+#[derive(Debug, PartialEq)]
+pub(super) struct V3Message<'a> {
+    pub(super) msg_id: i64,
+    pub(super) max_size: i64,
+    pub(super) flags: u8,
+    pub(super) security: SecurityParameters<'a>,
+    /// The offset of the authentication parameters in the message, to zero them when
+    /// computing its digest.
+    pub(super) auth_params_offset: usize,
+    pub(super) data: ScopedPduData<'a>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Message<'a> {
+    Community(CommunityMessage<'a>),
+    V3(V3Message<'a>),
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct ScopedPdu {
+    pub(super) context_engine_id: Vec<u8>,
+    pub(super) context_name: Vec<u8>,
+    pub(super) pdu: Pdu,
+}
+
+pub(super) fn decode_message(packet: &[u8]) -> Result<Message<'_>, MessageError> {
+    let mut message = Decoder::new(packet).sequence()?;
+    match message.integer()? {
+        version @ (VERSION_1 | VERSION_2C) => {
+            let community = message.octet_string()?;
+            let pdu = decode_pdu(&mut message)?;
+            Ok(Message::Community(CommunityMessage {
+                version,
+                community,
+                pdu,
+            }))
+        }
+        VERSION_3 => {
+            let mut header = message.sequence()?;
+            let msg_id = header.integer()?;
+            let max_size = header.integer()?;
+            let flags = header.octet_string()?.first().copied().unwrap_or(0);
+            let model = header.integer()?;
+            if model != USM_SECURITY_MODEL {
+                return Err(MessageError::UnsupportedSecurityModel { model });
+            }
+
+            let mut security = Decoder::new(message.octet_string()?).sequence()?;
+            let engine_id = security.octet_string()?;
+            let engine_boots = security.integer()? as u32;
+            let engine_time = security.integer()? as u32;
+            let user_name = security.octet_string()?;
+            let auth_params = security.octet_string()?;
+            let priv_params = security.octet_string()?;
+            let auth_params_offset = auth_params.as_ptr() as usize - packet.as_ptr() as usize;
+
+            let data = if message.peek_tag()? == ber::OCTET_STRING {
+                ScopedPduData::Encrypted(message.octet_string()?)
+            } else {
+                ScopedPduData::Plaintext(message.read()?.2)
+            };
+            Ok(Message::V3(V3Message {
+                msg_id,
+                max_size,
+                flags,
+                security: SecurityParameters {
+                    engine_id,
+                    engine_boots,
+                    engine_time,
+                    user_name,
+                    auth_params,
+                    priv_params,
+                },
+                auth_params_offset,
+                data,
+            }))
+        }
+        version => Err(MessageError::UnsupportedVersion { version }),
+    }
+}
+
+/// Decodes a scoped PDU, ignoring the padding of the decrypted ones after it.
+pub(super) fn decode_scoped_pdu(data: &[u8]) -> Result<ScopedPdu, MessageError> {
+    let mut scoped_pdu = Decoder::new(data).sequence()?;
+    let context_engine_id = scoped_pdu.octet_string()?.to_vec();
+    let context_name = scoped_pdu.octet_string()?.to_vec();
+    let pdu = decode_pdu(&mut scoped_pdu)?;
+    Ok(ScopedPdu {
+        context_engine_id,
+        context_name,
+        pdu,
+    })
+}
+
+fn decode_pdu(decoder: &mut Decoder<'_>) -> Result<Pdu, MessageError> {
+    let (tag, contents, _) = decoder.read()?;
+    let mut pdu = Decoder::new(contents);
+    let (request_id, v1_trap) = match tag {
+        V1_TRAP => {
+            let enterprise = pdu.object_identifier()?;
+            let agent_address = decode_ip_address(pdu.expect(ber::IP_ADDRESS)?)?;
+            let generic_trap = pdu.integer()?;
+            let specific_trap = pdu.integer()?;
+            let time_stamp = ber::decode_unsigned(pdu.expect(ber::TIME_TICKS)?)?;
+            let v1_trap = V1Trap {
+                enterprise,
+                agent_address,
+                generic_trap,
+                specific_trap,
+                time_stamp,
+            };
+            (0, Some(v1_trap))
+        }
+        GET_REQUEST..=REPORT => {
+            let request_id = pdu.integer()?;
+            // The error status and index.
+            pdu.integer()?;
+            pdu.integer()?;
+            (request_id, None)
+        }
+        tag => return Err(MessageError::UnexpectedPdu { tag }),
+    };
+
+    let (_, contents, encoded_varbinds) = pdu.read()?;
+    let mut list = Decoder::new(contents);
+    let mut varbinds = Vec::new();
+    while !list.is_empty() {
+        let mut varbind = list.sequence()?;
+        let oid = varbind.object_identifier()?;
+        let (tag, contents, _) = varbind.read()?;
+        let value = match tag {
+            ber::INTEGER => VarBindValue::Integer(ber::decode_integer(contents)?),
+            ber::OCTET_STRING => VarBindValue::OctetString(contents.to_vec()),
+            ber::NULL => VarBindValue::Null,
+            ber::OBJECT_IDENTIFIER => VarBindValue::ObjectIdentifier(ber::decode_oid(contents)?),
+            ber::IP_ADDRESS => VarBindValue::IpAddress(decode_ip_address(contents)?),
+            ber::COUNTER32 | ber::GAUGE32 | ber::TIME_TICKS | ber::COUNTER64 => {
+                VarBindValue::Unsigned(ber::decode_unsigned(contents)?)
+            }
+            ber::OPAQUE => VarBindValue::Opaque(contents.to_vec()),
+            ber::NO_SUCH_OBJECT | ber::NO_SUCH_INSTANCE | ber::END_OF_MIB_VIEW => {
+                VarBindValue::Exception
+            }
+            found => {
+                return Err(BerError::UnexpectedTag {
+                    expected: ber::NULL,
+                    found,
+                }
+                .into())
+            }
+        };
+        varbinds.push(VarBind { oid, value });
+    }
+
+    Ok(Pdu {
+        tag,
+        request_id,
+        v1_trap,
+        varbinds,
+        encoded_varbinds: encoded_varbinds.to_vec(),
+    })
+}
+
+fn decode_ip_address(contents: &[u8]) -> Result<Ipv4Addr, MessageError> {
+    <[u8; 4]>::try_from(contents)
+        .map(Ipv4Addr::from)
+        .map_err(|_| MessageError::InvalidIpAddress {
+            length: contents.len(),
+        })
+}
+
+/// Encodes a PDU of the SNMPv2 format, without error.
+pub(super) fn encode_pdu(tag: u8, request_id: i64, encoded_varbinds: &[u8]) -> Vec<u8> {
+    let mut contents = ber::encode_integer(request_id);
+    contents.extend(ber::encode_integer(0));
+    contents.extend(ber::encode_integer(0));
+    contents.extend_from_slice(encoded_varbinds);
+    ber::encode(tag, &contents)
+}
+
+pub(super) fn encode_community_message(version: i64, community: &[u8], pdu: &[u8]) -> Vec<u8> {
+    let mut contents = ber::encode_integer(version);
+    contents.extend(ber::encode(ber::OCTET_STRING, community));
+    contents.extend_from_slice(pdu);
+    ber::encode(ber::SEQUENCE, &contents)
+}
+
+pub(super) fn encode_scoped_pdu(
+    context_engine_id: &[u8],
+    context_name: &[u8],
+    pdu: &[u8],
+) -> Vec<u8> {
+    let mut contents = ber::encode(ber::OCTET_STRING, context_engine_id);
+    contents.extend(ber::encode(ber::OCTET_STRING, context_name));
+    contents.extend_from_slice(pdu);
+    ber::encode(ber::SEQUENCE, &contents)
+}
+
+pub(super) fn encode_v3_message(
+    msg_id: i64,
+    max_size: i64,
+    flags: u8,
+    security: &SecurityParameters<'_>,
+    data: ScopedPduData<'_>,
+) -> Vec<u8> {
+    let mut header = ber::encode_integer(msg_id);
+    header.extend(ber::encode_integer(max_size));
+    header.extend(ber::encode(ber::OCTET_STRING, &[flags]));
+    header.extend(ber::encode_integer(USM_SECURITY_MODEL));
+
+    let mut parameters = ber::encode(ber::OCTET_STRING, security.engine_id);
+    parameters.extend(ber::encode_integer(security.engine_boots.into()));
+    parameters.extend(ber::encode_integer(security.engine_time.into()));
+    parameters.extend(ber::encode(ber::OCTET_STRING, security.user_name));
+    parameters.extend(ber::encode(ber::OCTET_STRING, security.auth_params));
+    parameters.extend(ber::encode(ber::OCTET_STRING, security.priv_params));
+
+    let mut contents = ber::encode_integer(VERSION_3);
+    contents.extend(ber::encode(ber::SEQUENCE, &header));
+    contents.extend(ber::encode(
+        ber::OCTET_STRING,
+        &ber::encode(ber::SEQUENCE, &parameters),
+    ));
+    match data {
+        ScopedPduData::Plaintext(scoped_pdu) => contents.extend_from_slice(scoped_pdu),
+        ScopedPduData::Encrypted(data) => contents.extend(ber::encode(ber::OCTET_STRING, data)),
+    }
+    ber::encode(ber::SEQUENCE, &contents)
+}
+
+/// Encodes a variable binding list.
+pub(super) fn encode_varbinds(varbinds: &[(Vec<u32>, Vec<u8>)]) -> Vec<u8> {
+    let contents = varbinds
+        .iter()
+        .flat_map(|(oid, value)| {
+            let mut varbind = ber::encode_oid(oid);
+            varbind.extend_from_slice(value);
+            ber::encode(ber::SEQUENCE, &varbind)
+        })
+        .collect::<Vec<_>>();
+    ber::encode(ber::SEQUENCE, &contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_v1_traps() {
+        let mut pdu = ber::encode_oid(&[1, 3, 6, 1, 4, 1, 8072]);
+        pdu.extend(ber::encode(ber::IP_ADDRESS, &[192, 0, 2, 1]));
+        pdu.extend(ber::encode_integer(6));
+        pdu.extend(ber::encode_integer(17));
+        pdu.extend(ber::encode_unsigned(ber::TIME_TICKS, 4200));
+        pdu.extend(encode_varbinds(&[(
+            vec![1, 3, 6, 1, 4, 1, 8072, 1],
+            ber::encode(ber::OCTET_STRING, b"hot"),
+        )]));
+        let packet = encode_community_message(VERSION_1, b"public", &ber::encode(V1_TRAP, &pdu));
+
+        let message = match decode_message(&packet).unwrap() {
+            Message::Community(message) => message,
+            message => panic!("unexpected message {:?}", message),
+        };
+        assert_eq!(message.version, VERSION_1);
+        assert_eq!(message.community, b"public");
+        assert_eq!(
+            message.pdu.v1_trap,
+            Some(V1Trap {
+                enterprise: vec![1, 3, 6, 1, 4, 1, 8072],
+                agent_address: Ipv4Addr::new(192, 0, 2, 1),
+                generic_trap: 6,
+                specific_trap: 17,
+                time_stamp: 4200,
+            })
+        );
+        assert_eq!(
+            message.pdu.varbinds,
+            vec![VarBind {
+                oid: vec![1, 3, 6, 1, 4, 1, 8072, 1],
+                value: VarBindValue::OctetString(b"hot".to_vec()),
+            }]
+        );
+    }
+
+    #[test]
+    fn decodes_v2c_informs() {
+        let varbinds = encode_varbinds(&[
+            (
+                vec![1, 3, 6, 1, 2, 1, 1, 3, 0],
+                ber::encode_unsigned(ber::TIME_TICKS, 100),
+            ),
+            (
+                vec![1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0],
+                ber::encode_oid(&[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+            ),
+            (
+                vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 2],
+                ber::encode_integer(2),
+            ),
+            (
+                vec![1, 3, 6, 1, 2, 1, 31, 1, 1, 1, 6, 2],
+                ber::encode_unsigned(ber::COUNTER64, u64::MAX),
+            ),
+            (
+                vec![1, 3, 6, 1, 2, 1, 4, 20, 1, 1],
+                ber::encode(ber::NO_SUCH_INSTANCE, &[]),
+            ),
+        ]);
+        let packet = encode_community_message(
+            VERSION_2C,
+            b"private",
+            &encode_pdu(INFORM_REQUEST, 1234, &varbinds),
+        );
+
+        let message = match decode_message(&packet).unwrap() {
+            Message::Community(message) => message,
+            message => panic!("unexpected message {:?}", message),
+        };
+        assert_eq!(message.pdu.tag, INFORM_REQUEST);
+        assert_eq!(message.pdu.request_id, 1234);
+        assert_eq!(message.pdu.encoded_varbinds, varbinds);
+        let values = message
+            .pdu
+            .varbinds
+            .into_iter()
+            .map(|varbind| varbind.value)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                VarBindValue::Unsigned(100),
+                VarBindValue::ObjectIdentifier(vec![1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+                VarBindValue::Integer(2),
+                VarBindValue::Unsigned(u64::MAX),
+                VarBindValue::Exception,
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_v3_messages() {
+        let scoped_pdu = encode_scoped_pdu(
+            b"engine",
+            b"",
+            &encode_pdu(V2_TRAP, 7, &encode_varbinds(&[])),
+        );
+        let security = SecurityParameters {
+            engine_id: b"engine",
+            engine_boots: 2,
+            engine_time: 3600,
+            user_name: b"admin",
+            auth_params: &[0; 12],
+            priv_params: &[],
+        };
+        let packet = encode_v3_message(
+            42,
+            65507,
+            AUTH_FLAG,
+            &security,
+            ScopedPduData::Plaintext(&scoped_pdu),
+        );
+
+        let message = match decode_message(&packet).unwrap() {
+            Message::V3(message) => message,
+            message => panic!("unexpected message {:?}", message),
+        };
+        assert_eq!(message.msg_id, 42);
+        assert_eq!(message.flags, AUTH_FLAG);
+        assert_eq!(message.security, security);
+        assert_eq!(
+            &packet[message.auth_params_offset..message.auth_params_offset + 12],
+            &[0; 12]
+        );
+        let data = match message.data {
+            ScopedPduData::Plaintext(data) => data,
+            data => panic!("unexpected data {:?}", data),
+        };
+        let scoped_pdu = decode_scoped_pdu(data).unwrap();
+        assert_eq!(scoped_pdu.context_engine_id, b"engine");
+        assert_eq!(scoped_pdu.pdu.tag, V2_TRAP);
+        assert_eq!(scoped_pdu.pdu.request_id, 7);
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let packet = encode_community_message(2, b"public", &encode_pdu(V2_TRAP, 1, &[]));
+        assert_eq!(
+            decode_message(&packet),
+            Err(MessageError::UnsupportedVersion { version: 2 })
+        );
+    }
+}
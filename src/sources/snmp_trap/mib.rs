@@ -0,0 +1,431 @@
+//! Translates OIDs to the names defined by MIB modules.
+//!
+//! Only the OID assignments of the modules are read, and the names are resolved across all
+//! the modules loaded, so the `IMPORTS` are ignored.
+
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use super::ber::oid_to_string;
+
+/// The macros whose values are OID assignments.
+const OID_MACROS: &[&str] = &[
+    "AGENT-CAPABILITIES",
+    "MODULE-COMPLIANCE",
+    "MODULE-IDENTITY",
+    "NOTIFICATION-GROUP",
+    "NOTIFICATION-TYPE",
+    "OBJECT-GROUP",
+    "OBJECT-IDENTITY",
+    "OBJECT-TYPE",
+];
+
+/// The names of SNMPv2-SMI and SNMPv2-MIB the other modules build on, so they don't need to
+/// be loaded.
+const WELL_KNOWN_NAMES: &[(&str, &[u32])] = &[
+    ("ccitt", &[0]),
+    ("zeroDotZero", &[0, 0]),
+    ("iso", &[1]),
+    ("org", &[1, 3]),
+    ("dod", &[1, 3, 6]),
+    ("internet", &[1, 3, 6, 1]),
+    ("directory", &[1, 3, 6, 1, 1]),
+    ("mgmt", &[1, 3, 6, 1, 2]),
+    ("mib-2", &[1, 3, 6, 1, 2, 1]),
+    ("system", &[1, 3, 6, 1, 2, 1, 1]),
+    ("sysUpTime", &[1, 3, 6, 1, 2, 1, 1, 3]),
+    ("transmission", &[1, 3, 6, 1, 2, 1, 10]),
+    ("experimental", &[1, 3, 6, 1, 3]),
+    ("private", &[1, 3, 6, 1, 4]),
+    ("enterprises", &[1, 3, 6, 1, 4, 1]),
+    ("security", &[1, 3, 6, 1, 5]),
+    ("snmpV2", &[1, 3, 6, 1, 6]),
+    ("snmpDomains", &[1, 3, 6, 1, 6, 1]),
+    ("snmpProxys", &[1, 3, 6, 1, 6, 2]),
+    ("snmpModules", &[1, 3, 6, 1, 6, 3]),
+    ("snmpTrapOID", &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1]),
+    ("snmpTraps", &[1, 3, 6, 1, 6, 3, 1, 1, 5]),
+    ("coldStart", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 1]),
+    ("warmStart", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 2]),
+    ("linkDown", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+    ("linkUp", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 4]),
+    ("authenticationFailure", &[1, 3, 6, 1, 6, 3, 1, 1, 5, 5]),
+    ("joint-iso-ccitt", &[2]),
+];
+
+/// An OID assignment, relative to the OID of its parent.
+#[derive(Debug, PartialEq)]
+struct Assignment {
+    name: String,
+    parent: String,
+    sub_ids: Vec<u32>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Mib {
+    names: HashMap<Vec<u32>, String>,
+}
+
+impl Mib {
+    /// Loads the MIB modules of files, or of all the files of directories.
+    pub(super) fn load(paths: &[PathBuf]) -> crate::Result<Self> {
+        let mut assignments = Vec::new();
+        for path in paths {
+            let files = if path.is_dir() {
+                let mut files = fs::read_dir(path)
+                    .map_err(|error| format!("Could not read MIB directory {:?}: {}", path, error))?
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.is_file())
+                    .collect::<Vec<_>>();
+                files.sort();
+                files
+            } else {
+                vec![path.clone()]
+            };
+            for file in files {
+                let text = fs::read(&file)
+                    .map_err(|error| format!("Could not read MIB file {:?}: {}", file, error))?;
+                assignments.extend(parse(&String::from_utf8_lossy(&text)));
+            }
+        }
+        Ok(Self::resolve(assignments))
+    }
+
+    fn resolve(mut assignments: Vec<Assignment>) -> Self {
+        let mut oids = WELL_KNOWN_NAMES
+            .iter()
+            .map(|(name, oid)| (name.to_string(), oid.to_vec()))
+            .collect::<HashMap<_, _>>();
+        // The values like `{ 1 3 6 }` are relative to the root, named with an empty name.
+        oids.insert(String::new(), Vec::new());
+        // The assignments may refer to names assigned later, or in another module.
+        loop {
+            let count = assignments.len();
+            assignments.retain(|assignment| match oids.get(&assignment.parent) {
+                Some(parent) => {
+                    let mut oid = parent.clone();
+                    oid.extend_from_slice(&assignment.sub_ids);
+                    oids.insert(assignment.name.clone(), oid);
+                    false
+                }
+                None => true,
+            });
+            if assignments.is_empty() || assignments.len() == count {
+                break;
+            }
+        }
+        for assignment in &assignments {
+            debug!(
+                message = "Could not resolve the OID of a MIB name.",
+                name = %assignment.name,
+                parent = %assignment.parent,
+            );
+        }
+        Self {
+            names: oids
+                .into_iter()
+                .filter(|(name, _)| !name.is_empty())
+                .map(|(name, oid)| (oid, name))
+                .collect(),
+        }
+    }
+
+    /// Translates an OID to the name of its longest known prefix, followed by the remaining
+    /// sub-identifiers.
+    pub(super) fn translate(&self, oid: &[u32]) -> String {
+        (1..=oid.len())
+            .rev()
+            .find_map(|length| {
+                self.names.get(&oid[..length]).map(|name| {
+                    if length == oid.len() {
+                        name.clone()
+                    } else {
+                        format!("{}.{}", name, oid_to_string(&oid[length..]))
+                    }
+                })
+            })
+            .unwrap_or_else(|| oid_to_string(oid))
+    }
+}
+
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("--") {
+            // A comment ends at the end of its line, or at the next `--`.
+            let end = comment
+                .find(|c| c == '\n' || c == '\r')
+                .unwrap_or(comment.len());
+            let end = comment[..end].find("--").map_or(end, |end| end + 2);
+            rest = &comment[end..];
+        } else if let Some(string) = rest.strip_prefix('"') {
+            let end = string.find('"').map_or(string.len(), |end| end + 1);
+            tokens.push("\"\"");
+            rest = &string[end..];
+        } else if rest.starts_with("::=") {
+            tokens.push("::=");
+            rest = &rest[3..];
+        } else {
+            let word = rest
+                .char_indices()
+                .find(|(index, c)| {
+                    !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                        || rest[*index..].starts_with("--")
+                })
+                .map_or(rest.len(), |(index, _)| index);
+            let length = word.max(rest.chars().next().map_or(1, char::len_utf8));
+            tokens.push(&rest[..length]);
+            rest = &rest[length..];
+        }
+    }
+    tokens
+}
+
+fn is_value_name(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+/// Parses the OID assignments of a MIB module.
+fn parse(text: &str) -> Vec<Assignment> {
+    let tokens = tokenize(text);
+    let mut assignments = Vec::new();
+    let mut index = 0;
+    while index + 1 < tokens.len() {
+        let name = tokens[index];
+        if !is_value_name(name) {
+            index += 1;
+            continue;
+        }
+        let next = tokens[index + 1];
+        if next == "OBJECT"
+            && tokens.get(index + 2) == Some(&"IDENTIFIER")
+            && tokens.get(index + 3) == Some(&"::=")
+        {
+            index = parse_value(&tokens, index + 4, name, &mut assignments);
+        } else if OID_MACROS.contains(&next) {
+            match tokens[index..].iter().position(|token| *token == "::=") {
+                Some(position) => {
+                    index = parse_value(&tokens, index + position + 1, name, &mut assignments)
+                }
+                None => break,
+            }
+        } else if next == "TRAP-TYPE" {
+            // The SNMPv1 traps are identified by their enterprise and a number.
+            let end = tokens[index..]
+                .iter()
+                .position(|token| *token == "::=")
+                .map_or(tokens.len(), |position| index + position);
+            let enterprise = tokens[index..end]
+                .iter()
+                .position(|token| *token == "ENTERPRISE")
+                .and_then(|position| tokens.get(index + position + 1));
+            let number = tokens.get(end + 1).and_then(|token| token.parse().ok());
+            if let (Some(enterprise), Some(number)) = (enterprise, number) {
+                assignments.push(Assignment {
+                    name: name.to_owned(),
+                    parent: (*enterprise).to_owned(),
+                    sub_ids: vec![0, number],
+                });
+            }
+            index = end + 1;
+        } else {
+            index += 1;
+        }
+    }
+    assignments
+}
+
+/// Parses an OID value like `{ parent 1 }` or `{ iso org(3) dod(6) 1 }`, returning the index
+/// of the token after it.
+fn parse_value(
+    tokens: &[&str],
+    mut index: usize,
+    name: &str,
+    assignments: &mut Vec<Assignment>,
+) -> usize {
+    if tokens.get(index) != Some(&"{") {
+        return index;
+    }
+    index += 1;
+    let mut parent = None;
+    let mut sub_ids = Vec::new();
+    while let Some(token) = tokens.get(index) {
+        index += 1;
+        if *token == "}" {
+            if let Some(parent) = parent {
+                assignments.push(Assignment {
+                    name: name.to_owned(),
+                    parent,
+                    sub_ids,
+                });
+            }
+            break;
+        } else if let Ok(sub_id) = token.parse::<u32>() {
+            sub_ids.push(sub_id);
+            parent.get_or_insert_with(String::new);
+        } else if tokens.get(index) == Some(&"(") {
+            // A named number, like `org(3)`, also assigns its name.
+            let sub_id = tokens.get(index + 1).and_then(|token| token.parse().ok());
+            index += 3;
+            match (sub_id, &parent) {
+                (Some(sub_id), Some(parent)) => {
+                    sub_ids.push(sub_id);
+                    assignments.push(Assignment {
+                        name: (*token).to_owned(),
+                        parent: parent.clone(),
+                        sub_ids: sub_ids.clone(),
+                    });
+                }
+                (Some(sub_id), None) => {
+                    parent = Some(String::new());
+                    sub_ids.push(sub_id);
+                }
+                (None, _) => return index,
+            }
+        } else if parent.is_none() {
+            parent = Some((*token).to_owned());
+        } else {
+            return index;
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: &str = r#"
+VECTOR-TEST-MIB DEFINITIONS ::= BEGIN
+
+IMPORTS
+    MODULE-IDENTITY, OBJECT-TYPE, NOTIFICATION-TYPE, enterprises
+        FROM SNMPv2-SMI;
+
+vectorTestMib MODULE-IDENTITY
+    LAST-UPDATED "202205010000Z"
+    ORGANIZATION "Vector" -- an inline -- comment
+    CONTACT-INFO "vector@example.com"
+    DESCRIPTION  "A test module ::= { nothing }."
+    ::= { enterprises 99999 }
+
+-- The table of fans.
+fanTable OBJECT-TYPE
+    SYNTAX      SEQUENCE OF FanEntry
+    MAX-ACCESS  not-accessible
+    STATUS      current
+    DESCRIPTION "The fans."
+    ::= { vectorTestObjects 1 }
+
+FanEntry ::= SEQUENCE {
+    fanIndex    INTEGER,
+    fanVendor   OBJECT IDENTIFIER
+}
+
+vectorTestObjects OBJECT IDENTIFIER ::= { vectorTestMib 1 }
+
+fanEntry OBJECT-TYPE
+    SYNTAX      FanEntry
+    MAX-ACCESS  not-accessible
+    STATUS      current
+    DESCRIPTION "A fan."
+    INDEX       { fanIndex }
+    ::= { fanTable 1 }
+
+fanSpeed OBJECT-TYPE
+    SYNTAX      INTEGER (0..65535)
+    MAX-ACCESS  read-only
+    STATUS      current
+    DESCRIPTION "The speed of the fan."
+    ::= { fanEntry 2 }
+
+fanFailed NOTIFICATION-TYPE
+    OBJECTS     { fanSpeed }
+    STATUS      current
+    DESCRIPTION "A fan failed."
+    ::= { vectorTestMib 0 1 }
+
+fanFailedV1 TRAP-TYPE
+    ENTERPRISE  vectorTestMib
+    VARIABLES   { fanSpeed }
+    DESCRIPTION "A fan failed."
+    ::= 3
+
+END
+"#;
+
+    #[test]
+    fn parses_assignments() {
+        let assignments = parse(MIB);
+        let names = assignments
+            .iter()
+            .map(|assignment| assignment.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "vectorTestMib",
+                "fanTable",
+                "vectorTestObjects",
+                "fanEntry",
+                "fanSpeed",
+                "fanFailed",
+                "fanFailedV1"
+            ]
+        );
+        assert_eq!(
+            assignments[5],
+            Assignment {
+                name: "fanFailed".to_owned(),
+                parent: "vectorTestMib".to_owned(),
+                sub_ids: vec![0, 1],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_named_numbers() {
+        let assignments = parse("internet OBJECT IDENTIFIER ::= { iso org(3) dod(6) 1 }");
+        assert_eq!(
+            assignments,
+            vec![
+                Assignment {
+                    name: "org".to_owned(),
+                    parent: "iso".to_owned(),
+                    sub_ids: vec![3],
+                },
+                Assignment {
+                    name: "dod".to_owned(),
+                    parent: "iso".to_owned(),
+                    sub_ids: vec![3, 6],
+                },
+                Assignment {
+                    name: "internet".to_owned(),
+                    parent: "iso".to_owned(),
+                    sub_ids: vec![3, 6, 1],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn translates_oids() {
+        let mib = Mib::resolve(parse(MIB));
+        assert_eq!(
+            mib.translate(&[1, 3, 6, 1, 4, 1, 99999, 1, 1, 1, 2, 7]),
+            "fanSpeed.7"
+        );
+        assert_eq!(mib.translate(&[1, 3, 6, 1, 4, 1, 99999, 0, 1]), "fanFailed");
+        assert_eq!(
+            mib.translate(&[1, 3, 6, 1, 4, 1, 99999, 0, 3]),
+            "fanFailedV1"
+        );
+        assert_eq!(
+            mib.translate(&[1, 3, 6, 1, 4, 1, 12345, 1]),
+            "enterprises.12345.1"
+        );
+        assert_eq!(mib.translate(&[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]), "linkDown");
+        assert_eq!(mib.translate(&[3, 1]), "3.1");
+    }
+}
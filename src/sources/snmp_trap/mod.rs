@@ -0,0 +1,1073 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::future;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::net::UdpSocket;
+use vector_core::ByteSizeOf;
+
+use self::{
+    ber::oid_to_string,
+    message::{
+        CommunityMessage, Message, MessageError, Pdu, ScopedPdu, ScopedPduData, SecurityParameters,
+        V3Message, VarBindValue,
+    },
+    mib::Mib,
+    usm::{LocalizedKeys, Usm, UsmError},
+};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        BytesReceived, EventsReceived, SnmpTrapReceiveError, SnmpTrapResponseError, SocketMode,
+        SocketReceiveError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp::{self, MulticastConfig},
+    SourceSender,
+};
+
+mod ber;
+mod message;
+mod mib;
+mod usm;
+
+pub use usm::{AuthProtocol, PrivProtocol, UserConfig};
+
+/// The largest UDP payload.
+const MAX_DATAGRAM_LENGTH: usize = 65535;
+
+/// `sysUpTime.0`, the first variable binding of the SNMPv2 notifications.
+const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+/// `snmpTrapOID.0`, the second variable binding of the SNMPv2 notifications.
+const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+/// `snmpTraps`, the prefix of the OIDs of the SNMPv1 generic traps.
+const SNMP_TRAPS: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 5];
+/// `usmStatsUnknownEngineIDs.0`, reported to the senders discovering the engine ID.
+const USM_STATS_UNKNOWN_ENGINE_IDS: &[u32] = &[1, 3, 6, 1, 6, 3, 15, 1, 1, 4, 0];
+/// `usmStatsNotInTimeWindows.0`, reported to the senders whose boots and time are out of date.
+const USM_STATS_NOT_IN_TIME_WINDOWS: &[u32] = &[1, 3, 6, 1, 6, 3, 15, 1, 1, 2, 0];
+
+/// The largest `snmpEngineBoots`, which the engine stays at once reached.
+const MAX_ENGINE_BOOTS: u32 = 2_147_483_647;
+/// How far the time of the messages sent to the engine can be from its own, in seconds.
+const TIME_WINDOW_SECS: i64 = 150;
+const ENGINE_FILE_NAME: &str = "engine.json";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpTrapConfig {
+    address: SocketAddr,
+    receive_buffer_bytes: Option<usize>,
+    multicast: Option<MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
+    /// The communities accepted in the SNMPv1 and SNMPv2c messages, or all of them when empty.
+    #[serde(default)]
+    communities: Vec<String>,
+    /// The users the SNMPv3 messages are accepted from.
+    #[serde(default)]
+    users: Vec<UserConfig>,
+    /// The hexadecimal SNMP engine ID of the source, to which the SNMPv3 informs are sent.
+    engine_id: Option<String>,
+    /// The MIB files, or directories of MIB files, used to translate the OIDs to names.
+    #[serde(default)]
+    mib_paths: Vec<PathBuf>,
+    host_key: Option<String>,
+    /// The directory the boots of the engine are counted in.
+    data_dir: Option<PathBuf>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<SnmpTrapConfig>("snmp_trap")
+}
+
+impl GenerateConfig for SnmpTrapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:162".parse().unwrap(),
+            receive_buffer_bytes: None,
+            multicast: None,
+            reuse_port_sockets: None,
+            communities: Vec::new(),
+            users: Vec::new(),
+            engine_id: None,
+            mib_paths: Vec::new(),
+            host_key: None,
+            data_dir: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "snmp_trap")]
+impl SourceConfig for SnmpTrapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let engine_id = match &self.engine_id {
+            Some(engine_id) => hex::decode(engine_id)
+                .map_err(|error| format!("Invalid engine ID {:?}: {}", engine_id, error))?,
+            None => default_engine_id(),
+        };
+        if !(5..=32).contains(&engine_id.len()) {
+            return Err("The engine ID must be 5 to 32 bytes long.".into());
+        }
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let receiver = Receiver {
+            communities: self
+                .communities
+                .iter()
+                .map(|community| community.as_bytes().to_vec())
+                .collect(),
+            usm: Usm::new(&self.users)?,
+            engine: Arc::new(Engine::load(engine_id, &data_dir)?),
+            mib: if self.mib_paths.is_empty() {
+                None
+            } else {
+                Some(Arc::new(Mib::load(&self.mib_paths)?))
+            },
+            host_key: self
+                .host_key
+                .clone()
+                .unwrap_or_else(|| log_schema().host_key().to_string()),
+        };
+
+        let sockets = udp::bind_listeners(
+            self.address,
+            self.receive_buffer_bytes,
+            self.multicast.as_ref(),
+            self.reuse_port_sockets,
+        )?;
+        info!(message = "Listening.", address = %self.address, sockets = sockets.len());
+
+        let shutdown = cx.shutdown;
+        let out = cx.out;
+        Ok(Box::pin(async move {
+            let tasks = sockets.into_iter().map(|socket| {
+                tokio::spawn(receive(
+                    socket,
+                    receiver.clone(),
+                    shutdown.clone(),
+                    out.clone(),
+                ))
+            });
+            for result in future::join_all(tasks).await {
+                if !matches!(result, Ok(Ok(()))) {
+                    return Err(());
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "snmp_trap"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// An engine ID in the text format of RFC 3411, made of the hostname.
+fn default_engine_id() -> Vec<u8> {
+    let mut engine_id = vec![0x80, 0x00, 0x1f, 0x88, 0x04];
+    let hostname = crate::get_hostname().unwrap_or_default();
+    engine_id.extend(format!("vector-{}", hostname).bytes().take(27));
+    engine_id
+}
+
+#[derive(Debug, Snafu)]
+pub(crate) enum ReceiveError {
+    #[snafu(context(false), display("{}", source))]
+    Message { source: MessageError },
+    #[snafu(context(false), display("{}", source))]
+    Usm { source: UsmError },
+    #[snafu(display("Unknown community {:?}", community))]
+    UnknownCommunity { community: String },
+}
+
+impl ReceiveError {
+    pub(crate) const fn error_code(&self) -> &'static str {
+        match self {
+            Self::Message { .. } => "invalid_message",
+            Self::Usm { .. } => "authentication_failed",
+            Self::UnknownCommunity { .. } => "unknown_community",
+        }
+    }
+
+    pub(crate) const fn error_type(&self) -> &'static str {
+        use crate::internal_events::prelude::error_type;
+
+        match self {
+            Self::Message { .. } => error_type::PARSER_FAILED,
+            Self::Usm { .. } | Self::UnknownCommunity { .. } => error_type::CONDITION_FAILED,
+        }
+    }
+}
+
+/// The SNMP engine of the source, authoritative for the SNMPv3 informs, shared by the sockets.
+#[derive(Debug)]
+struct Engine {
+    id: Vec<u8>,
+    boots: u32,
+    started: Instant,
+    /// The salt of the next encrypted response, incremented for each one so that it's never
+    /// reused with the same key.
+    salt: AtomicU64,
+    unknown_engine_ids: AtomicU64,
+    not_in_time_windows: AtomicU64,
+}
+
+#[derive(Deserialize, Serialize)]
+struct PersistedEngine {
+    id: String,
+    boots: u32,
+}
+
+impl Engine {
+    /// Loads the engine, counting one more boot than the last time the engine of the same ID was
+    /// loaded from the data directory, as the boots and time of the messages sent to the engine
+    /// must be greater than the ones of the messages it could have received before.
+    fn load(id: Vec<u8>, data_dir: &Path) -> io::Result<Self> {
+        let path = data_dir.join(ENGINE_FILE_NAME);
+        let persisted = match std::fs::read(&path) {
+            Ok(content) => Some(
+                serde_json::from_slice::<PersistedEngine>(&content)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            ),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        };
+        let hex_id = hex::encode(&id);
+        let boots = match persisted {
+            Some(persisted) if persisted.id == hex_id => {
+                persisted.boots.saturating_add(1).min(MAX_ENGINE_BOOTS)
+            }
+            _ => 1,
+        };
+        let content = serde_json::to_vec(&PersistedEngine { id: hex_id, boots })
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        // The file is replaced atomically so that a crash can't leave it truncated.
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, &path)?;
+
+        Ok(Self {
+            id,
+            boots,
+            started: Instant::now(),
+            salt: AtomicU64::new(rand::random()),
+            unknown_engine_ids: AtomicU64::new(0),
+            not_in_time_windows: AtomicU64::new(0),
+        })
+    }
+
+    fn time(&self) -> u32 {
+        self.started.elapsed().as_secs() as u32
+    }
+
+    fn next_salt(&self) -> [u8; 8] {
+        self.salt.fetch_add(1, Ordering::Relaxed).to_be_bytes()
+    }
+
+    /// Whether an authenticated message sent to the engine is timely, as described in RFC 3414
+    /// section 3.2, so that it can't be replayed after the time window.
+    fn in_time_window(&self, boots: u32, time: u32) -> bool {
+        self.boots != MAX_ENGINE_BOOTS
+            && boots == self.boots
+            && (i64::from(time) - i64::from(self.time())).abs() <= TIME_WINDOW_SECS
+    }
+}
+
+/// Decodes the messages received on a socket, to events and to the responses to send back.
+#[derive(Clone, Debug)]
+struct Receiver {
+    communities: Vec<Vec<u8>>,
+    usm: Usm,
+    engine: Arc<Engine>,
+    mib: Option<Arc<Mib>>,
+    host_key: String,
+}
+
+impl Receiver {
+    fn receive(
+        &mut self,
+        packet: &[u8],
+        peer: SocketAddr,
+    ) -> Result<(Option<LogEvent>, Option<Vec<u8>>), ReceiveError> {
+        match message::decode_message(packet)? {
+            Message::Community(message) => self.receive_community(message, peer),
+            Message::V3(message) => self.receive_v3(packet, message, peer),
+        }
+    }
+
+    fn receive_community(
+        &self,
+        message: CommunityMessage<'_>,
+        peer: SocketAddr,
+    ) -> Result<(Option<LogEvent>, Option<Vec<u8>>), ReceiveError> {
+        let community = String::from_utf8_lossy(message.community);
+        if !self.communities.is_empty()
+            && !self
+                .communities
+                .iter()
+                .any(|accepted| accepted == message.community)
+        {
+            return Err(ReceiveError::UnknownCommunity {
+                community: community.into_owned(),
+            });
+        }
+
+        let version = if message.version == message::VERSION_1 {
+            "1"
+        } else {
+            "2c"
+        };
+        let mut log = self.notification_log(&message.pdu, version, peer)?;
+        log.insert("community", community.into_owned());
+
+        let response = (message.pdu.tag == message::INFORM_REQUEST).then(|| {
+            message::encode_community_message(
+                message.version,
+                message.community,
+                &message::encode_pdu(
+                    message::RESPONSE,
+                    message.pdu.request_id,
+                    &message.pdu.encoded_varbinds,
+                ),
+            )
+        });
+        Ok((Some(log), response))
+    }
+
+    fn receive_v3(
+        &mut self,
+        packet: &[u8],
+        message: V3Message<'_>,
+        peer: SocketAddr,
+    ) -> Result<(Option<LogEvent>, Option<Vec<u8>>), ReceiveError> {
+        let security = &message.security;
+        let reportable = message.flags & message::REPORTABLE_FLAG != 0;
+
+        // The senders of informs discover the engine ID first, with an empty one.
+        if security.engine_id.is_empty() {
+            let count = self
+                .engine
+                .unknown_engine_ids
+                .fetch_add(1, Ordering::Relaxed)
+                + 1;
+            let report = reportable
+                .then(|| self.report(&message, USM_STATS_UNKNOWN_ENGINE_IDS, count, None))
+                .transpose()?;
+            return Ok((None, report));
+        }
+
+        let user = String::from_utf8_lossy(security.user_name).into_owned();
+        let keys = self.usm.keys(&user, security.engine_id)?;
+        let auth = message.flags & message::AUTH_FLAG != 0;
+        let privacy = message.flags & message::PRIV_FLAG != 0;
+        if auth != keys.auth.is_some() || privacy != keys.privacy.is_some() {
+            return Err(UsmError::UnsupportedSecurityLevel { user }.into());
+        }
+
+        if let Some((protocol, key)) = &keys.auth {
+            let offset = message.auth_params_offset;
+            let length = security.auth_params.len();
+            let mut zeroed = packet.to_vec();
+            zeroed[offset..offset + length].fill(0);
+            let digest = usm::authenticate(*protocol, key, &zeroed)?;
+            if length != digest.len() || !openssl::memcmp::eq(&digest, security.auth_params) {
+                return Err(UsmError::WrongDigest { user }.into());
+            }
+            self.usm.remember(&user, security.engine_id, &keys);
+
+            // The senders synchronize with the boots and time of the engine through the report.
+            if security.engine_id == self.engine.id
+                && !self
+                    .engine
+                    .in_time_window(security.engine_boots, security.engine_time)
+            {
+                let count = self
+                    .engine
+                    .not_in_time_windows
+                    .fetch_add(1, Ordering::Relaxed)
+                    + 1;
+                let report = reportable
+                    .then(|| {
+                        self.report(&message, USM_STATS_NOT_IN_TIME_WINDOWS, count, Some(&keys))
+                    })
+                    .transpose()?;
+                return Ok((None, report));
+            }
+        }
+
+        let scoped_pdu = match (message.data, &keys.privacy) {
+            (ScopedPduData::Encrypted(data), Some((protocol, key))) => {
+                let plaintext = usm::decrypt(
+                    *protocol,
+                    key,
+                    security.engine_boots,
+                    security.engine_time,
+                    security.priv_params,
+                    data,
+                )?;
+                message::decode_scoped_pdu(&plaintext)?
+            }
+            (ScopedPduData::Plaintext(data), None) => message::decode_scoped_pdu(data)?,
+            _ => return Err(UsmError::UnsupportedSecurityLevel { user }.into()),
+        };
+
+        // The informs must be sent to the engine of the source, the traps come from the
+        // engine of their sender.
+        let pdu = &scoped_pdu.pdu;
+        if pdu.tag == message::INFORM_REQUEST && security.engine_id != self.engine.id {
+            let count = self
+                .engine
+                .unknown_engine_ids
+                .fetch_add(1, Ordering::Relaxed)
+                + 1;
+            let report = reportable
+                .then(|| self.report(&message, USM_STATS_UNKNOWN_ENGINE_IDS, count, None))
+                .transpose()?;
+            return Ok((None, report));
+        }
+
+        let mut log = self.notification_log(pdu, "3", peer)?;
+        log.insert("user", user);
+        log.insert(
+            "security_level",
+            match (auth, privacy) {
+                (true, true) => "authPriv",
+                (true, false) => "authNoPriv",
+                _ => "noAuthNoPriv",
+            },
+        );
+        log.insert(
+            "context_engine_id",
+            hex::encode(&scoped_pdu.context_engine_id),
+        );
+        log.insert(
+            "context_name",
+            String::from_utf8_lossy(&scoped_pdu.context_name).into_owned(),
+        );
+
+        let response = if pdu.tag == message::INFORM_REQUEST {
+            Some(self.response(&message, &keys, &scoped_pdu)?)
+        } else {
+            None
+        };
+        Ok((Some(log), response))
+    }
+
+    /// Encodes the response to an inform, with the security level of the inform.
+    fn response(
+        &self,
+        message: &V3Message<'_>,
+        keys: &LocalizedKeys,
+        scoped_pdu: &ScopedPdu,
+    ) -> Result<Vec<u8>, ReceiveError> {
+        let plaintext = message::encode_scoped_pdu(
+            &scoped_pdu.context_engine_id,
+            &scoped_pdu.context_name,
+            &message::encode_pdu(
+                message::RESPONSE,
+                scoped_pdu.pdu.request_id,
+                &scoped_pdu.pdu.encoded_varbinds,
+            ),
+        );
+        let (boots, time) = (self.engine.boots, self.engine.time());
+        let salt = self.engine.next_salt();
+        let (encrypted, priv_params) = match &keys.privacy {
+            Some((protocol, key)) => (
+                Some(usm::encrypt(
+                    *protocol, key, boots, time, &salt, &plaintext,
+                )?),
+                &salt[..],
+            ),
+            None => (None, &[][..]),
+        };
+        let auth_params = keys
+            .auth
+            .as_ref()
+            .map(|(protocol, _)| vec![0; protocol.parameters_length()])
+            .unwrap_or_default();
+
+        let mut response = message::encode_v3_message(
+            message.msg_id,
+            message.max_size,
+            message.flags & (message::AUTH_FLAG | message::PRIV_FLAG),
+            &SecurityParameters {
+                engine_id: &self.engine.id,
+                engine_boots: boots,
+                engine_time: time,
+                user_name: message.security.user_name,
+                auth_params: &auth_params,
+                priv_params,
+            },
+            match &encrypted {
+                Some(encrypted) => ScopedPduData::Encrypted(encrypted),
+                None => ScopedPduData::Plaintext(&plaintext),
+            },
+        );
+        sign(&mut response, keys)?;
+        Ok(response)
+    }
+
+    /// Encodes a report of the counter of an error, along with the engine ID, boots and time of
+    /// the source, authenticated with the keys of the user if given.
+    fn report(
+        &self,
+        message: &V3Message<'_>,
+        oid: &[u32],
+        count: u64,
+        keys: Option<&LocalizedKeys>,
+    ) -> Result<Vec<u8>, ReceiveError> {
+        let request_id = match message.data {
+            ScopedPduData::Plaintext(data) => message::decode_scoped_pdu(data)
+                .map(|scoped_pdu| scoped_pdu.pdu.request_id)
+                .unwrap_or(0),
+            ScopedPduData::Encrypted(_) => 0,
+        };
+        let varbinds = message::encode_varbinds(&[(
+            oid.to_vec(),
+            ber::encode_unsigned(ber::COUNTER32, count & 0xffff_ffff),
+        )]);
+        let scoped_pdu = message::encode_scoped_pdu(
+            &self.engine.id,
+            &[],
+            &message::encode_pdu(message::REPORT, request_id, &varbinds),
+        );
+        let auth_params = keys
+            .and_then(|keys| keys.auth.as_ref())
+            .map(|(protocol, _)| vec![0; protocol.parameters_length()])
+            .unwrap_or_default();
+        let mut report = message::encode_v3_message(
+            message.msg_id,
+            message.max_size,
+            if auth_params.is_empty() {
+                0
+            } else {
+                message::AUTH_FLAG
+            },
+            &SecurityParameters {
+                engine_id: &self.engine.id,
+                engine_boots: self.engine.boots,
+                engine_time: self.engine.time(),
+                user_name: message.security.user_name,
+                auth_params: &auth_params,
+                priv_params: &[],
+            },
+            ScopedPduData::Plaintext(&scoped_pdu),
+        );
+        if let Some(keys) = keys {
+            sign(&mut report, keys)?;
+        }
+        Ok(report)
+    }
+
+    /// Builds the log of a trap or an inform, with the fields common to all the versions.
+    fn notification_log(
+        &self,
+        pdu: &Pdu,
+        version: &'static str,
+        peer: SocketAddr,
+    ) -> Result<LogEvent, ReceiveError> {
+        let mut log = LogEvent::default();
+        log.insert("version", version);
+        let mut varbinds = pdu.varbinds.iter().peekable();
+        match (pdu.tag, &pdu.v1_trap) {
+            (message::V1_TRAP, Some(trap)) => {
+                log.insert("pdu_type", "trap");
+                log.insert("enterprise", self.translate(&trap.enterprise));
+                log.insert("agent_address", trap.agent_address.to_string());
+                log.insert("generic_trap", trap.generic_trap);
+                log.insert("specific_trap", trap.specific_trap);
+                log.insert("uptime", trap.time_stamp as i64);
+                // The OID of the equivalent SNMPv2 notification, as described in RFC 3584.
+                let trap_oid = if trap.generic_trap == 6 {
+                    let mut oid = trap.enterprise.clone();
+                    oid.extend([0, trap.specific_trap as u32]);
+                    oid
+                } else {
+                    let mut oid = SNMP_TRAPS.to_vec();
+                    oid.push(trap.generic_trap as u32 + 1);
+                    oid
+                };
+                log.insert("trap_oid", self.translate(&trap_oid));
+            }
+            (message::V2_TRAP | message::INFORM_REQUEST, _) => {
+                let pdu_type = if pdu.tag == message::V2_TRAP {
+                    "trap"
+                } else {
+                    "inform"
+                };
+                log.insert("pdu_type", pdu_type);
+                if let Some(varbind) = varbinds.next_if(|varbind| varbind.oid == SYS_UP_TIME) {
+                    log.insert("uptime", self.value(&varbind.value));
+                }
+                if let Some(varbind) = varbinds.next_if(|varbind| varbind.oid == SNMP_TRAP_OID) {
+                    log.insert("trap_oid", self.value(&varbind.value));
+                }
+            }
+            (tag, _) => return Err(MessageError::UnexpectedPdu { tag }.into()),
+        }
+        log.insert(
+            "varbinds",
+            varbinds
+                .map(|varbind| (self.translate(&varbind.oid), self.value(&varbind.value)))
+                .collect::<BTreeMap<_, _>>(),
+        );
+        log.insert(self.host_key.as_str(), peer.ip().to_string());
+        log.insert(log_schema().source_type_key(), Bytes::from("snmp_trap"));
+        log.insert(log_schema().timestamp_key(), Utc::now());
+        Ok(log)
+    }
+
+    fn translate(&self, oid: &[u32]) -> String {
+        match &self.mib {
+            Some(mib) => mib.translate(oid),
+            None => oid_to_string(oid),
+        }
+    }
+
+    fn value(&self, value: &VarBindValue) -> Value {
+        match value {
+            VarBindValue::Integer(value) => (*value).into(),
+            VarBindValue::Unsigned(value) => match i64::try_from(*value) {
+                Ok(value) => value.into(),
+                Err(_) => (*value as f64).into(),
+            },
+            VarBindValue::OctetString(value) => match std::str::from_utf8(value) {
+                Ok(text) if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+                    text.into()
+                }
+                _ => hex::encode(value).into(),
+            },
+            VarBindValue::ObjectIdentifier(oid) => self.translate(oid).into(),
+            VarBindValue::IpAddress(address) => address.to_string().into(),
+            VarBindValue::Opaque(value) => hex::encode(value).into(),
+            VarBindValue::Null | VarBindValue::Exception => Value::Null,
+        }
+    }
+}
+
+/// Fills the authentication parameters of an encoded SNMPv3 message, if the user authenticates
+/// their messages.
+fn sign(encoded: &mut [u8], keys: &LocalizedKeys) -> Result<(), ReceiveError> {
+    if let Some((protocol, key)) = &keys.auth {
+        let offset = match message::decode_message(encoded)? {
+            Message::V3(message) => message.auth_params_offset,
+            Message::Community(_) => unreachable!("encoded an SNMPv3 message"),
+        };
+        let digest = usm::authenticate(*protocol, key, encoded)?;
+        encoded[offset..offset + digest.len()].copy_from_slice(&digest);
+    }
+    Ok(())
+}
+
+async fn receive(
+    socket: UdpSocket,
+    mut receiver: Receiver,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut buf = vec![0; MAX_DATAGRAM_LENGTH];
+    loop {
+        let (byte_size, peer) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => recv.map_err(|error| {
+                let error = codecs::decoding::Error::FramingError(error.into());
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error: &error
+                })
+            })?,
+            _ = &mut shutdown => return Ok(()),
+        };
+        emit!(BytesReceived {
+            byte_size,
+            protocol: "udp",
+        });
+
+        let (log, response) = match receiver.receive(&buf[..byte_size], peer) {
+            Ok(received) => received,
+            Err(error) => {
+                emit!(SnmpTrapReceiveError {
+                    error: &error,
+                    peer
+                });
+                continue;
+            }
+        };
+        if let Some(response) = response {
+            if let Err(error) = socket.send_to(&response, peer).await {
+                emit!(SnmpTrapResponseError { error, peer });
+            }
+        }
+        let event = match log {
+            Some(log) => Event::from(log),
+            None => continue,
+        };
+
+        emit!(EventsReceived {
+            count: 1,
+            byte_size: event.size_of(),
+        });
+        tokio::select! {
+            result = out.send_event(event) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count: 1 });
+                    return Ok(());
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect_n, collect_ready, next_addr};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpTrapConfig>();
+    }
+
+    async fn start(
+        config: &str,
+    ) -> (
+        SocketAddr,
+        impl futures::Stream<Item = Event> + Unpin,
+        tempfile::TempDir,
+    ) {
+        let address = next_addr();
+        let data_dir = tempfile::tempdir().unwrap();
+        let config: SnmpTrapConfig = toml::from_str(&format!(
+            "address = \"{}\"\ndata_dir = \"{}\"\n{}",
+            address,
+            data_dir.path().display(),
+            config
+        ))
+        .unwrap();
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        (address, rx, data_dir)
+    }
+
+    fn inform_varbinds() -> Vec<u8> {
+        message::encode_varbinds(&[
+            (
+                SYS_UP_TIME.to_vec(),
+                ber::encode_unsigned(ber::TIME_TICKS, 4200),
+            ),
+            (
+                SNMP_TRAP_OID.to_vec(),
+                ber::encode_oid(&[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+            ),
+            (
+                vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 3],
+                ber::encode(ber::OCTET_STRING, b"eth0"),
+            ),
+        ])
+    }
+
+    #[tokio::test]
+    async fn acknowledges_v2c_informs() {
+        let (address, rx, _data_dir) = start(r#"communities = ["public"]"#).await;
+        let socket = UdpSocket::bind(next_addr()).await.unwrap();
+
+        // A message of an unknown community is discarded.
+        for community in [&b"private"[..], b"public"] {
+            let packet = message::encode_community_message(
+                message::VERSION_2C,
+                community,
+                &message::encode_pdu(message::INFORM_REQUEST, 42, &inform_varbinds()),
+            );
+            socket.send_to(&packet, address).await.unwrap();
+        }
+
+        let mut buf = vec![0; MAX_DATAGRAM_LENGTH];
+        let length = socket.recv(&mut buf).await.unwrap();
+        let response = match message::decode_message(&buf[..length]).unwrap() {
+            Message::Community(response) => response,
+            response => panic!("unexpected response {:?}", response),
+        };
+        assert_eq!(response.community, b"public");
+        assert_eq!(response.pdu.tag, message::RESPONSE);
+        assert_eq!(response.pdu.request_id, 42);
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log["version"], "2c".into());
+        assert_eq!(log["pdu_type"], "inform".into());
+        assert_eq!(log["community"], "public".into());
+        assert_eq!(log["uptime"], 4200.into());
+        assert_eq!(log["trap_oid"], "1.3.6.1.6.3.1.1.5.3".into());
+        assert_eq!(
+            log["varbinds"],
+            Value::from(BTreeMap::from([(
+                "1.3.6.1.2.1.2.2.1.2.3".to_owned(),
+                Value::from("eth0")
+            )]))
+        );
+        assert_eq!(log[log_schema().source_type_key()], "snmp_trap".into());
+    }
+
+    const V3_CONFIG: &str = r#"
+        engine_id = "800000000401020304"
+        [[users]]
+        name = "admin"
+        auth_protocol = "sha"
+        auth_password = "authpassword"
+        priv_protocol = "aes"
+        priv_password = "privpassword"
+        "#;
+
+    fn v3_keys(engine_id: &[u8]) -> LocalizedKeys {
+        Usm::new(&[UserConfig {
+            name: "admin".to_owned(),
+            auth_protocol: Some(AuthProtocol::Sha),
+            auth_password: Some("authpassword".to_owned()),
+            priv_protocol: Some(PrivProtocol::Aes),
+            priv_password: Some("privpassword".to_owned()),
+        }])
+        .unwrap()
+        .keys("admin", engine_id)
+        .unwrap()
+    }
+
+    /// Discovers the engine ID, boots and time of the source.
+    async fn discover(socket: &UdpSocket, address: SocketAddr) -> (Vec<u8>, u32, u32) {
+        let discovery = message::encode_v3_message(
+            1,
+            65507,
+            message::REPORTABLE_FLAG,
+            &SecurityParameters {
+                engine_id: &[],
+                engine_boots: 0,
+                engine_time: 0,
+                user_name: &[],
+                auth_params: &[],
+                priv_params: &[],
+            },
+            ScopedPduData::Plaintext(&message::encode_scoped_pdu(
+                &[],
+                &[],
+                &message::encode_pdu(message::GET_REQUEST, 1, &message::encode_varbinds(&[])),
+            )),
+        );
+        socket.send_to(&discovery, address).await.unwrap();
+        let mut buf = vec![0; MAX_DATAGRAM_LENGTH];
+        let length = socket.recv(&mut buf).await.unwrap();
+        let report = match message::decode_message(&buf[..length]).unwrap() {
+            Message::V3(report) => report,
+            report => panic!("unexpected report {:?}", report),
+        };
+        (
+            report.security.engine_id.to_vec(),
+            report.security.engine_boots,
+            report.security.engine_time,
+        )
+    }
+
+    /// Encodes an encrypted and authenticated inform.
+    fn v3_inform(engine_id: &[u8], boots: u32, time: u32) -> Vec<u8> {
+        let keys = v3_keys(engine_id);
+        let (priv_protocol, priv_key) = keys.privacy.clone().unwrap();
+        let salt = [0, 0, 0, 0, 0, 0, 0, 1];
+        let scoped_pdu = message::encode_scoped_pdu(
+            engine_id,
+            &[],
+            &message::encode_pdu(message::INFORM_REQUEST, 7, &inform_varbinds()),
+        );
+        let encrypted =
+            usm::encrypt(priv_protocol, &priv_key, boots, time, &salt, &scoped_pdu).unwrap();
+        let mut inform = message::encode_v3_message(
+            2,
+            65507,
+            message::AUTH_FLAG | message::PRIV_FLAG | message::REPORTABLE_FLAG,
+            &SecurityParameters {
+                engine_id,
+                engine_boots: boots,
+                engine_time: time,
+                user_name: b"admin",
+                auth_params: &[0; 12],
+                priv_params: &salt,
+            },
+            ScopedPduData::Encrypted(&encrypted),
+        );
+        sign(&mut inform, &keys).unwrap();
+        inform
+    }
+
+    #[tokio::test]
+    async fn acknowledges_v3_informs() {
+        let (address, rx, _data_dir) = start(V3_CONFIG).await;
+        let socket = UdpSocket::bind(next_addr()).await.unwrap();
+        let mut buf = vec![0; MAX_DATAGRAM_LENGTH];
+
+        let (engine_id, boots, time) = discover(&socket, address).await;
+        assert_eq!(engine_id, hex::decode("800000000401020304").unwrap());
+        assert_eq!(boots, 1);
+
+        socket
+            .send_to(&v3_inform(&engine_id, boots, time), address)
+            .await
+            .unwrap();
+        let length = socket.recv(&mut buf).await.unwrap();
+        let response = match message::decode_message(&buf[..length]).unwrap() {
+            Message::V3(response) => response,
+            response => panic!("unexpected response {:?}", response),
+        };
+        assert_eq!(response.msg_id, 2);
+        assert_eq!(response.flags, message::AUTH_FLAG | message::PRIV_FLAG);
+        let data = match response.data {
+            ScopedPduData::Encrypted(data) => data,
+            data => panic!("unexpected data {:?}", data),
+        };
+        let (priv_protocol, priv_key) = v3_keys(&engine_id).privacy.unwrap();
+        let plaintext = usm::decrypt(
+            priv_protocol,
+            &priv_key,
+            response.security.engine_boots,
+            response.security.engine_time,
+            response.security.priv_params,
+            data,
+        )
+        .unwrap();
+        let scoped_pdu = message::decode_scoped_pdu(&plaintext).unwrap();
+        assert_eq!(scoped_pdu.pdu.tag, message::RESPONSE);
+        assert_eq!(scoped_pdu.pdu.request_id, 7);
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log["version"], "3".into());
+        assert_eq!(log["user"], "admin".into());
+        assert_eq!(log["security_level"], "authPriv".into());
+        assert_eq!(log["trap_oid"], "1.3.6.1.6.3.1.1.5.3".into());
+    }
+
+    #[tokio::test]
+    async fn reports_v3_informs_out_of_time_window() {
+        let (address, rx, _data_dir) = start(V3_CONFIG).await;
+        let socket = UdpSocket::bind(next_addr()).await.unwrap();
+        let mut buf = vec![0; MAX_DATAGRAM_LENGTH];
+
+        let (engine_id, boots, time) = discover(&socket, address).await;
+        for (boots, time) in [(boots + 1, time), (boots, time + 1000)] {
+            socket
+                .send_to(&v3_inform(&engine_id, boots, time), address)
+                .await
+                .unwrap();
+            let length = socket.recv(&mut buf).await.unwrap();
+            let report = match message::decode_message(&buf[..length]).unwrap() {
+                Message::V3(report) => report,
+                report => panic!("unexpected report {:?}", report),
+            };
+            assert_eq!(report.flags, message::AUTH_FLAG);
+            let scoped_pdu = match report.data {
+                ScopedPduData::Plaintext(data) => message::decode_scoped_pdu(data).unwrap(),
+                data => panic!("unexpected data {:?}", data),
+            };
+            assert_eq!(scoped_pdu.pdu.tag, message::REPORT);
+            assert_eq!(
+                scoped_pdu.pdu.varbinds[0].oid,
+                USM_STATS_NOT_IN_TIME_WINDOWS
+            );
+        }
+
+        // Only the inform in the time window is received.
+        let (_, boots, time) = discover(&socket, address).await;
+        socket
+            .send_to(&v3_inform(&engine_id, boots, time), address)
+            .await
+            .unwrap();
+        socket.recv(&mut buf).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(collect_ready(rx).await.len(), 1);
+    }
+
+    #[test]
+    fn counts_engine_boots() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let engine_id = hex::decode("800000000401020304").unwrap();
+        assert_eq!(
+            Engine::load(engine_id.clone(), data_dir.path())
+                .unwrap()
+                .boots,
+            1
+        );
+        assert_eq!(Engine::load(engine_id, data_dir.path()).unwrap().boots, 2);
+        // The boots of another engine are counted from the start.
+        let engine_id = hex::decode("800000000405060708").unwrap();
+        assert_eq!(Engine::load(engine_id, data_dir.path()).unwrap().boots, 1);
+    }
+
+    #[tokio::test]
+    async fn translates_v1_traps() {
+        let (address, rx, _data_dir) = start("").await;
+        let mut pdu = ber::encode_oid(&[1, 3, 6, 1, 4, 1, 8072]);
+        pdu.extend(ber::encode(ber::IP_ADDRESS, &[192, 0, 2, 1]));
+        pdu.extend(ber::encode_integer(2));
+        pdu.extend(ber::encode_integer(0));
+        pdu.extend(ber::encode_unsigned(ber::TIME_TICKS, 100));
+        pdu.extend(message::encode_varbinds(&[(
+            vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 3],
+            ber::encode_integer(3),
+        )]));
+        let packet = message::encode_community_message(
+            message::VERSION_1,
+            b"public",
+            &ber::encode(message::V1_TRAP, &pdu),
+        );
+        let socket = UdpSocket::bind(next_addr()).await.unwrap();
+        socket.send_to(&packet, address).await.unwrap();
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log["version"], "1".into());
+        assert_eq!(log["agent_address"], "192.0.2.1".into());
+        assert_eq!(log["generic_trap"], 2.into());
+        assert_eq!(log["trap_oid"], "1.3.6.1.6.3.1.1.5.3".into());
+        assert_eq!(
+            log["varbinds"],
+            Value::from(BTreeMap::from([(
+                "1.3.6.1.2.1.2.2.1.1.3".to_owned(),
+                Value::from(3)
+            )]))
+        );
+        assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+    }
+}
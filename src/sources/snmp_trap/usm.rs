@@ -0,0 +1,397 @@
+//! The User-based Security Model of SNMPv3, described in RFC 3414, RFC 3826 and RFC 7860.
+
+use std::collections::HashMap;
+
+use openssl::{
+    hash::{Hasher, MessageDigest},
+    pkey::PKey,
+    sign::Signer,
+    symm::{Cipher, Crypter, Mode},
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub(crate) enum UsmError {
+    #[snafu(display("Unknown user {:?}", user))]
+    UnknownUser { user: String },
+    #[snafu(display("Unsupported security level for user {:?}", user))]
+    UnsupportedSecurityLevel { user: String },
+    #[snafu(display("Wrong digest for user {:?}", user))]
+    WrongDigest { user: String },
+    #[snafu(display("Invalid privacy parameters"))]
+    InvalidPrivacyParameters,
+    #[snafu(display("Cryptographic operation failed: {}", source))]
+    Crypto { source: openssl::error::ErrorStack },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthProtocol {
+    Md5,
+    Sha,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl AuthProtocol {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Md5 => MessageDigest::md5(),
+            Self::Sha => MessageDigest::sha1(),
+            Self::Sha224 => MessageDigest::sha224(),
+            Self::Sha256 => MessageDigest::sha256(),
+            Self::Sha384 => MessageDigest::sha384(),
+            Self::Sha512 => MessageDigest::sha512(),
+        }
+    }
+
+    /// The length of the truncated HMAC put in the authentication parameters.
+    pub(super) const fn parameters_length(self) -> usize {
+        match self {
+            Self::Md5 | Self::Sha => 12,
+            Self::Sha224 => 16,
+            Self::Sha256 => 24,
+            Self::Sha384 => 32,
+            Self::Sha512 => 48,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivProtocol {
+    /// DES in CBC mode.
+    Des,
+    /// AES-128 in CFB mode.
+    Aes,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    /// The name of the user.
+    pub name: String,
+    /// The protocol used to authenticate the messages of the user.
+    pub auth_protocol: Option<AuthProtocol>,
+    /// The password of the user, from which the authentication key is derived.
+    pub auth_password: Option<String>,
+    /// The protocol used to encrypt the messages of the user, which requires authentication.
+    pub priv_protocol: Option<PrivProtocol>,
+    /// The password from which the privacy key is derived.
+    pub priv_password: Option<String>,
+}
+
+/// The keys of a user, derived from their passwords but not localized to an engine yet.
+#[derive(Clone, Debug)]
+struct User {
+    auth: Option<(AuthProtocol, Vec<u8>)>,
+    privacy: Option<(PrivProtocol, Vec<u8>)>,
+}
+
+/// The keys of a user localized to an engine.
+#[derive(Clone, Debug)]
+pub(super) struct LocalizedKeys {
+    pub(super) auth: Option<(AuthProtocol, Vec<u8>)>,
+    pub(super) privacy: Option<(PrivProtocol, Vec<u8>)>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct Usm {
+    users: HashMap<String, User>,
+    /// The keys localized to the engines that authenticated messages were received from, so that
+    /// the messages of unknown engines can't grow it.
+    localized: HashMap<(String, Vec<u8>), LocalizedKeys>,
+}
+
+impl Usm {
+    pub(super) fn new(users: &[UserConfig]) -> crate::Result<Self> {
+        let users = users
+            .iter()
+            .map(|user| {
+                let auth = match (user.auth_protocol, &user.auth_password) {
+                    (None, None) => None,
+                    (Some(protocol), Some(password)) if password.len() >= 8 => {
+                        Some((protocol, password_to_key(protocol, password)?))
+                    }
+                    (Some(_), Some(_)) => {
+                        return Err(format!(
+                            "The authentication password of user {:?} must have at least 8 characters.",
+                            user.name
+                        )
+                        .into())
+                    }
+                    _ => {
+                        return Err(format!(
+                            "User {:?} needs both an authentication protocol and password.",
+                            user.name
+                        )
+                        .into())
+                    }
+                };
+                let privacy = match (user.priv_protocol, &user.priv_password, &auth) {
+                    (None, None, _) => None,
+                    (Some(protocol), Some(password), Some((auth_protocol, _)))
+                        if password.len() >= 8 =>
+                    {
+                        Some((protocol, password_to_key(*auth_protocol, password)?))
+                    }
+                    (Some(_), Some(_), Some(_)) => {
+                        return Err(format!(
+                            "The privacy password of user {:?} must have at least 8 characters.",
+                            user.name
+                        )
+                        .into())
+                    }
+                    _ => {
+                        return Err(format!(
+                            "User {:?} needs an authentication protocol, a privacy protocol and a privacy password to use privacy.",
+                            user.name
+                        )
+                        .into())
+                    }
+                };
+                Ok((user.name.clone(), User { auth, privacy }))
+            })
+            .collect::<crate::Result<_>>()?;
+        Ok(Self {
+            users,
+            localized: HashMap::new(),
+        })
+    }
+
+    /// Returns the keys of a user localized to an engine, computing them unless remembered.
+    pub(super) fn keys(
+        &self,
+        user_name: &str,
+        engine_id: &[u8],
+    ) -> Result<LocalizedKeys, UsmError> {
+        let user = self
+            .users
+            .get(user_name)
+            .ok_or_else(|| UsmError::UnknownUser {
+                user: user_name.to_owned(),
+            })?;
+        if let Some(keys) = self
+            .localized
+            .get(&(user_name.to_owned(), engine_id.to_vec()))
+        {
+            return Ok(keys.clone());
+        }
+        let auth = user
+            .auth
+            .as_ref()
+            .map(|(protocol, ku)| {
+                localize_key(*protocol, ku, engine_id).map(|key| (*protocol, key))
+            })
+            .transpose()?;
+        let privacy = match (&user.privacy, &user.auth) {
+            (Some((protocol, ku)), Some((auth_protocol, _))) => {
+                Some((*protocol, localize_key(*auth_protocol, ku, engine_id)?))
+            }
+            _ => None,
+        };
+        Ok(LocalizedKeys { auth, privacy })
+    }
+
+    /// Remembers the keys of a user localized to an engine, once a message was authenticated
+    /// with them.
+    pub(super) fn remember(&mut self, user_name: &str, engine_id: &[u8], keys: &LocalizedKeys) {
+        if keys.auth.is_some() {
+            self.localized
+                .entry((user_name.to_owned(), engine_id.to_vec()))
+                .or_insert_with(|| keys.clone());
+        }
+    }
+}
+
+/// Derives a key from a password, by hashing a megabyte of the repeated password.
+fn password_to_key(protocol: AuthProtocol, password: &str) -> Result<Vec<u8>, UsmError> {
+    const EXPANDED_LENGTH: usize = 1_048_576;
+
+    let mut hasher = Hasher::new(protocol.digest()).context(CryptoSnafu)?;
+    let password = password.as_bytes();
+    let mut chunk = [0u8; 64];
+    let mut index = 0;
+    for _ in 0..EXPANDED_LENGTH / chunk.len() {
+        for byte in chunk.iter_mut() {
+            *byte = password[index % password.len()];
+            index += 1;
+        }
+        hasher.update(&chunk).context(CryptoSnafu)?;
+    }
+    Ok(hasher.finish().context(CryptoSnafu)?.to_vec())
+}
+
+fn localize_key(protocol: AuthProtocol, ku: &[u8], engine_id: &[u8]) -> Result<Vec<u8>, UsmError> {
+    let mut hasher = Hasher::new(protocol.digest()).context(CryptoSnafu)?;
+    hasher.update(ku).context(CryptoSnafu)?;
+    hasher.update(engine_id).context(CryptoSnafu)?;
+    hasher.update(ku).context(CryptoSnafu)?;
+    Ok(hasher.finish().context(CryptoSnafu)?.to_vec())
+}
+
+/// Computes the authentication parameters of a message whose own authentication parameters
+/// are zeroed.
+pub(super) fn authenticate(
+    protocol: AuthProtocol,
+    key: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>, UsmError> {
+    let key = PKey::hmac(key).context(CryptoSnafu)?;
+    let mut signer = Signer::new(protocol.digest(), &key).context(CryptoSnafu)?;
+    signer.update(message).context(CryptoSnafu)?;
+    let mut digest = signer.sign_to_vec().context(CryptoSnafu)?;
+    digest.truncate(protocol.parameters_length());
+    Ok(digest)
+}
+
+fn cipher(
+    protocol: PrivProtocol,
+    key: &[u8],
+    boots: u32,
+    time: u32,
+    salt: &[u8],
+) -> Result<(Cipher, Vec<u8>, Vec<u8>), UsmError> {
+    if salt.len() != 8 {
+        return Err(UsmError::InvalidPrivacyParameters);
+    }
+    match protocol {
+        PrivProtocol::Des => {
+            if key.len() < 16 {
+                return Err(UsmError::InvalidPrivacyParameters);
+            }
+            let iv = key[8..16]
+                .iter()
+                .zip(salt)
+                .map(|(pre_iv, salt)| pre_iv ^ salt)
+                .collect();
+            Ok((Cipher::des_cbc(), key[..8].to_vec(), iv))
+        }
+        PrivProtocol::Aes => {
+            if key.len() < 16 {
+                return Err(UsmError::InvalidPrivacyParameters);
+            }
+            let mut iv = Vec::with_capacity(16);
+            iv.extend_from_slice(&boots.to_be_bytes());
+            iv.extend_from_slice(&time.to_be_bytes());
+            iv.extend_from_slice(salt);
+            Ok((Cipher::aes_128_cfb128(), key[..16].to_vec(), iv))
+        }
+    }
+}
+
+fn crypt(
+    mode: Mode,
+    (cipher, key, iv): (Cipher, Vec<u8>, Vec<u8>),
+    data: &[u8],
+) -> Result<Vec<u8>, UsmError> {
+    let mut crypter = Crypter::new(cipher, mode, &key, Some(&iv)).context(CryptoSnafu)?;
+    crypter.pad(false);
+    let mut output = vec![0; data.len() + cipher.block_size()];
+    let mut length = crypter.update(data, &mut output).context(CryptoSnafu)?;
+    length += crypter
+        .finalize(&mut output[length..])
+        .context(CryptoSnafu)?;
+    output.truncate(length);
+    Ok(output)
+}
+
+pub(super) fn decrypt(
+    protocol: PrivProtocol,
+    key: &[u8],
+    boots: u32,
+    time: u32,
+    salt: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, UsmError> {
+    if protocol == PrivProtocol::Des && data.len() % 8 != 0 {
+        return Err(UsmError::InvalidPrivacyParameters);
+    }
+    crypt(
+        Mode::Decrypt,
+        cipher(protocol, key, boots, time, salt)?,
+        data,
+    )
+}
+
+pub(super) fn encrypt(
+    protocol: PrivProtocol,
+    key: &[u8],
+    boots: u32,
+    time: u32,
+    salt: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, UsmError> {
+    let mut data = data.to_vec();
+    if protocol == PrivProtocol::Des {
+        // The scoped PDU is decoded as a single TLV, so the padding after it is ignored.
+        data.resize((data.len() + 7) / 8 * 8, 0);
+    }
+    crypt(
+        Mode::Encrypt,
+        cipher(protocol, key, boots, time, salt)?,
+        &data,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGINE_ID: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+
+    // The test vectors of RFC 3414 appendix A.3.
+    #[test]
+    fn localizes_md5_keys() {
+        let ku = password_to_key(AuthProtocol::Md5, "maplesyrup").unwrap();
+        assert_eq!(hex::encode(&ku), "9faf3283884e92834ebc9847d8edd963");
+        let kul = localize_key(AuthProtocol::Md5, &ku, &ENGINE_ID).unwrap();
+        assert_eq!(hex::encode(&kul), "526f5eed9fcce26f8964c2930787d82b");
+    }
+
+    #[test]
+    fn localizes_sha_keys() {
+        let ku = password_to_key(AuthProtocol::Sha, "maplesyrup").unwrap();
+        assert_eq!(hex::encode(&ku), "9fb5cc0381497b3793528939ff788d5d79145211");
+        let kul = localize_key(AuthProtocol::Sha, &ku, &ENGINE_ID).unwrap();
+        assert_eq!(
+            hex::encode(&kul),
+            "6695febc9288e36282235fc7151f128497b38f3f"
+        );
+    }
+
+    #[test]
+    fn encryption_round_trips() {
+        let key = localize_key(
+            AuthProtocol::Sha,
+            &password_to_key(AuthProtocol::Sha, "maplesyrup").unwrap(),
+            &ENGINE_ID,
+        )
+        .unwrap();
+        let salt = [1, 2, 3, 4, 5, 6, 7, 8];
+        let data = b"a scoped PDU, which isn't a multiple of 8 bytes";
+        for protocol in [PrivProtocol::Des, PrivProtocol::Aes] {
+            let encrypted = encrypt(protocol, &key, 1, 42, &salt, data).unwrap();
+            assert_ne!(&encrypted[..data.len()], &data[..]);
+            let decrypted = decrypt(protocol, &key, 1, 42, &salt, &encrypted).unwrap();
+            assert_eq!(&decrypted[..data.len()], &data[..]);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_users() {
+        let user = |auth_password: Option<&str>, priv_protocol| UserConfig {
+            name: "admin".to_owned(),
+            auth_protocol: auth_password.map(|_| AuthProtocol::Sha),
+            auth_password: auth_password.map(ToOwned::to_owned),
+            priv_protocol,
+            priv_password: priv_protocol.map(|_| "privpassword".to_owned()),
+        };
+        assert!(Usm::new(&[user(Some("authpassword"), Some(PrivProtocol::Aes))]).is_ok());
+        assert!(Usm::new(&[user(Some("short"), None)]).is_err());
+        assert!(Usm::new(&[user(None, Some(PrivProtocol::Des))]).is_err());
+    }
+}
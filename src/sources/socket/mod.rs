@@ -117,19 +117,14 @@ impl SourceConfig for SocketConfig {
                     .host_key()
                     .clone()
                     .unwrap_or_else(|| log_schema().host_key().to_string());
+                crate::udp::validate_listener(
+                    config.multicast().as_ref(),
+                    config.reuse_port_sockets(),
+                )?;
                 let decoder =
                     DecodingConfig::new(config.framing().clone(), config.decoding().clone())
                         .build();
-                Ok(udp::udp(
-                    config.address(),
-                    config.max_length(),
-                    host_key,
-                    config.port_key().clone(),
-                    config.receive_buffer_bytes(),
-                    decoder,
-                    cx.shutdown,
-                    cx.out,
-                ))
+                Ok(udp::udp(&config, host_key, decoder, cx.shutdown, cx.out))
             }
             #[cfg(unix)]
             Mode::UnixDatagram(config) => {
@@ -253,7 +248,7 @@ mod test {
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
         test_util::{
-            collect_n, collect_n_limited,
+            collect_n, collect_n_limited, collect_ready,
             components::{self, SOURCE_TESTS, TCP_SOURCE_TAGS},
             next_addr, random_string, send_lines, send_lines_tls, wait_for_tcp,
         },
@@ -713,6 +708,55 @@ mod test {
         );
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn udp_reuse_port_sockets_receive_datagrams_once() {
+        let (tx, rx) = SourceSender::new_test();
+        let address = next_addr();
+        let config: SocketConfig = toml::from_str(&format!(
+            r#"
+            mode = "udp"
+            address = "{}"
+            reuse_port_sockets = 4"#,
+            address
+        ))
+        .unwrap();
+        let server = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        send_lines_udp(address, vec!["test".to_string()]);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let events = collect_ready(rx).await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "test".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn udp_rejects_multicast_with_reuse_port_sockets() {
+        let (tx, _rx) = SourceSender::new_test();
+        let config: SocketConfig = toml::from_str(&format!(
+            r#"
+            mode = "udp"
+            address = "{}"
+            multicast.groups = ["239.0.0.1"]
+            reuse_port_sockets = 4"#,
+            next_addr()
+        ))
+        .unwrap();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn udp_it_includes_host() {
         let (tx, rx) = SourceSender::new_test();
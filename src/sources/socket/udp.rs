@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, num::NonZeroUsize};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -6,10 +6,10 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use futures::StreamExt;
+use futures::future;
 use serde::{Deserialize, Serialize};
 use tokio::net::UdpSocket;
-use tokio_util::codec::FramedRead;
+use tokio_util::codec::Decoder as _;
 use vector_core::ByteSizeOf;
 
 use crate::{
@@ -22,7 +22,8 @@ use crate::{
     serde::{default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     sources::Source,
-    udp, SourceSender,
+    udp::{self, MulticastConfig},
+    SourceSender,
 };
 
 /// UDP processes messages per packet, where messages are separated by newline.
@@ -35,6 +36,8 @@ pub struct UdpConfig {
     host_key: Option<String>,
     port_key: Option<String>,
     receive_buffer_bytes: Option<usize>,
+    multicast: Option<MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
     #[serde(default = "default_framing_message_based")]
     framing: FramingConfig,
     #[serde(default = "default_decoding")]
@@ -70,6 +73,14 @@ impl UdpConfig {
         self.receive_buffer_bytes
     }
 
+    pub const fn multicast(&self) -> &Option<MulticastConfig> {
+        &self.multicast
+    }
+
+    pub const fn reuse_port_sockets(&self) -> Option<NonZeroUsize> {
+        self.reuse_port_sockets
+    }
+
     pub fn from_address(address: SocketAddr) -> Self {
         Self {
             address,
@@ -77,6 +88,8 @@ impl UdpConfig {
             host_key: None,
             port_key: Some(String::from("port")),
             receive_buffer_bytes: None,
+            multicast: None,
+            reuse_port_sockets: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
         }
@@ -84,99 +97,147 @@ impl UdpConfig {
 }
 
 pub fn udp(
-    address: SocketAddr,
-    max_length: usize,
+    config: &UdpConfig,
     host_key: String,
-    port_key: Option<String>,
-    receive_buffer_bytes: Option<usize>,
     decoder: Decoder,
-    mut shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
 ) -> Source {
-    Box::pin(async move {
-        let socket = UdpSocket::bind(&address)
-            .await
-            .expect("Failed to bind to udp listener socket");
+    let address = config.address();
+    let receive_buffer_bytes = config.receive_buffer_bytes();
+    let multicast = config.multicast().clone();
+    let reuse_port_sockets = config.reuse_port_sockets();
+    let port_key = config.port_key().clone();
+    // Only `chunked_gelf` reassembles the messages split into several datagrams,
+    // the other framers decode each datagram on its own.
+    let keep_decoder = matches!(config.framing(), FramingConfig::ChunkedGelf { .. });
+
+    let max_length = if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+        std::cmp::min(config.max_length(), receive_buffer_bytes)
+    } else {
+        config.max_length()
+    };
 
-        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+    Box::pin(async move {
+        let sockets = udp::bind_listeners(
+            address,
+            receive_buffer_bytes,
+            multicast.as_ref(),
+            reuse_port_sockets,
+        )
+        .expect("Failed to bind to udp listener socket");
+
+        info!(message = "Listening.", address = %address, sockets = sockets.len());
+
+        // Each socket is received from in its own task, so they are processed in parallel.
+        let tasks = sockets.into_iter().map(|socket| {
+            tokio::spawn(receive(
+                socket,
+                max_length,
+                host_key.clone(),
+                port_key.clone(),
+                decoder.clone(),
+                keep_decoder,
+                shutdown.clone(),
+                out.clone(),
+            ))
+        });
+        for result in future::join_all(tasks).await {
+            if !matches!(result, Ok(Ok(()))) {
+                return Err(());
             }
         }
+        Ok(())
+    })
+}
 
-        let max_length = if let Some(receive_buffer_bytes) = receive_buffer_bytes {
-            std::cmp::min(max_length, receive_buffer_bytes)
-        } else {
-            max_length
-        };
-
-        info!(message = "Listening.", address = %address);
-
-        let mut buf = BytesMut::with_capacity(max_length);
-        loop {
-            buf.resize(max_length, 0);
-            tokio::select! {
-                recv = socket.recv_from(&mut buf) => {
-                    let (byte_size, address) = recv.map_err(|error| {
-                        let error = codecs::decoding::Error::FramingError(error.into());
-                        emit!(SocketReceiveError {
-                            mode: SocketMode::Udp,
-                            error: &error
-                        })
-                    })?;
-
-                    emit!(BytesReceived { byte_size, protocol: "udp" });
-
-                    let payload = buf.split_to(byte_size);
-
-                    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
-
-                    while let Some(result) = stream.next().await {
-                        match result {
-                            Ok((mut events, _byte_size)) => {
-                                let count = events.len();
-                                emit!(SocketEventsReceived {
-                                    mode: SocketMode::Udp,
-                                    byte_size: events.size_of(),
-                                    count,
-                                });
-
-                                let now = Utc::now();
-
-                                for event in &mut events {
-                                    if let Event::Log(ref mut log) = event {
-                                        log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
-                                        log.try_insert(log_schema().timestamp_key(), now);
-                                        log.try_insert(host_key.as_str(), address.ip().to_string());
-
-                                        if let Some(port_key) = &port_key {
-                                            log.try_insert(port_key.as_str(), address.port());
-                                        }
+async fn receive(
+    socket: UdpSocket,
+    max_length: usize,
+    host_key: String,
+    port_key: Option<String>,
+    mut decoder: Decoder,
+    keep_decoder: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut buf = BytesMut::with_capacity(max_length);
+    loop {
+        buf.resize(max_length, 0);
+        tokio::select! {
+            recv = socket.recv_from(&mut buf) => {
+                let (byte_size, address) = recv.map_err(|error| {
+                    let error = codecs::decoding::Error::FramingError(error.into());
+                    emit!(SocketReceiveError {
+                        mode: SocketMode::Udp,
+                        error: &error
+                    })
+                })?;
+
+                emit!(BytesReceived { byte_size, protocol: "udp" });
+
+                let mut payload = buf.split_to(byte_size);
+
+                // A datagram never leaves a partial frame to the next one, unless the
+                // decoder is kept across the datagrams of the socket.
+                let mut datagram_decoder;
+                let decoder = if keep_decoder {
+                    &mut decoder
+                } else {
+                    datagram_decoder = decoder.clone();
+                    &mut datagram_decoder
+                };
+                loop {
+                    let remaining = payload.len();
+                    match decoder.decode_eof(&mut payload) {
+                        Ok(None) => break,
+                        Ok(Some((mut events, _byte_size))) => {
+                            let count = events.len();
+                            emit!(SocketEventsReceived {
+                                mode: SocketMode::Udp,
+                                byte_size: events.size_of(),
+                                count,
+                            });
+
+                            let now = Utc::now();
+
+                            for event in &mut events {
+                                if let Event::Log(ref mut log) = event {
+                                    log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
+                                    log.try_insert(log_schema().timestamp_key(), now);
+                                    log.try_insert(host_key.as_str(), address.ip().to_string());
+
+                                    if let Some(port_key) = &port_key {
+                                        log.try_insert(port_key.as_str(), address.port());
                                     }
                                 }
+                            }
 
-                                tokio::select!{
-                                    result = out.send_batch(events) => {
-                                        if let Err(error) = result {
-                                            emit!(StreamClosedError { error, count });
-                                            return Ok(())
-                                        }
+                            tokio::select!{
+                                result = out.send_batch(events) => {
+                                    if let Err(error) = result {
+                                        emit!(StreamClosedError { error, count });
+                                        return Ok(())
                                     }
-                                    _ = &mut shutdown => return Ok(()),
                                 }
+                                _ = &mut shutdown => return Ok(()),
                             }
-                            Err(error) => {
-                                // Error is logged by `crate::codecs::Decoder`, no
-                                // further handling is needed here.
-                                if !error.can_continue() {
-                                    break;
-                                }
+                        }
+                        Err(error) => {
+                            // Error is logged by `crate::codecs::Decoder`, no
+                            // further handling is needed here.
+                            if !error.can_continue() {
+                                break;
                             }
                         }
                     }
+                    // The rest of the datagram is dropped once the decoder stops consuming it.
+                    if payload.is_empty() || payload.len() == remaining {
+                        break;
+                    }
                 }
-                _ = &mut shutdown => return Ok(()),
             }
+            _ = &mut shutdown => return Ok(()),
         }
-    })
+    }
 }
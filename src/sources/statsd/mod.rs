@@ -1,7 +1,10 @@
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    num::NonZeroUsize,
+};
 
 use bytes::Bytes;
-use futures::{StreamExt, TryFutureExt};
+use futures::{future, StreamExt};
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
 use tokio::net::UdpSocket;
@@ -13,7 +16,8 @@ use super::util::{SocketListenAddr, TcpNullAcker, TcpSource};
 use crate::{
     codecs::Decoder,
     config::{
-        self, GenerateConfig, Output, Resource, SourceConfig, SourceContext, SourceDescription,
+        self, log_schema, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
+        SourceDescription,
     },
     event::Event,
     internal_events::{
@@ -23,7 +27,8 @@ use crate::{
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsConfig},
-    udp, SourceSender,
+    udp::{self, MulticastConfig},
+    SourceSender,
 };
 use codecs::{
     decoding::{self, Deserializer, Framer},
@@ -34,7 +39,7 @@ pub mod parser;
 #[cfg(unix)]
 mod unix;
 
-use parser::parse;
+use parser::{parse, parse_dogstatsd};
 #[cfg(unix)]
 use unix::{statsd_unix, UnixConfig};
 
@@ -51,6 +56,10 @@ enum StatsdConfig {
 pub struct UdpConfig {
     address: SocketAddr,
     receive_buffer_bytes: Option<usize>,
+    multicast: Option<MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
+    #[serde(default)]
+    dogstatsd: bool,
 }
 
 impl UdpConfig {
@@ -58,6 +67,9 @@ impl UdpConfig {
         Self {
             address,
             receive_buffer_bytes: None,
+            multicast: None,
+            reuse_port_sockets: None,
+            dogstatsd: false,
         }
     }
 }
@@ -72,6 +84,8 @@ struct TcpConfig {
     shutdown_timeout_secs: u64,
     receive_buffer_bytes: Option<usize>,
     connection_limit: Option<u32>,
+    #[serde(default)]
+    dogstatsd: bool,
 }
 
 impl TcpConfig {
@@ -85,6 +99,7 @@ impl TcpConfig {
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
             receive_buffer_bytes: None,
             connection_limit: None,
+            dogstatsd: false,
         }
     }
 }
@@ -93,6 +108,18 @@ const fn default_shutdown_timeout_secs() -> u64 {
     30
 }
 
+impl StatsdConfig {
+    /// Whether the DogStatsD extensions, events and service checks are parsed.
+    const fn dogstatsd(&self) -> bool {
+        match self {
+            Self::Tcp(config) => config.dogstatsd,
+            Self::Udp(config) => config.dogstatsd,
+            #[cfg(unix)]
+            Self::Unix(config) => config.dogstatsd,
+        }
+    }
+}
+
 inventory::submit! {
     SourceDescription::new::<StatsdConfig>("statsd")
 }
@@ -112,11 +139,15 @@ impl SourceConfig for StatsdConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         match self {
             StatsdConfig::Udp(config) => {
+                udp::validate_listener(config.multicast.as_ref(), config.reuse_port_sockets)?;
                 Ok(Box::pin(statsd_udp(config.clone(), cx.shutdown, cx.out)))
             }
             StatsdConfig::Tcp(config) => {
                 let tls = MaybeTlsSettings::from_config(&config.tls, true)?;
-                StatsdTcpSource.run(
+                let source = StatsdTcpSource {
+                    dogstatsd: config.dogstatsd,
+                };
+                source.run(
                     config.address,
                     config.keepalive,
                     config.shutdown_timeout_secs,
@@ -133,7 +164,14 @@ impl SourceConfig for StatsdConfig {
     }
 
     fn outputs(&self) -> Vec<Output> {
-        vec![Output::default(config::DataType::Metric)]
+        if self.dogstatsd() {
+            // The DogStatsD events and service checks are logs.
+            vec![Output::default(
+                config::DataType::Metric | config::DataType::Log,
+            )]
+        } else {
+            vec![Output::default(config::DataType::Metric)]
+        }
     }
 
     fn source_type(&self) -> &'static str {
@@ -155,7 +193,15 @@ impl SourceConfig for StatsdConfig {
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct StatsdDeserializer;
+pub(crate) struct StatsdDeserializer {
+    dogstatsd: bool,
+}
+
+impl StatsdDeserializer {
+    pub(crate) const fn new(dogstatsd: bool) -> Self {
+        Self { dogstatsd }
+    }
+}
 
 impl decoding::format::Deserializer for StatsdDeserializer {
     fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
@@ -165,10 +211,17 @@ impl decoding::format::Deserializer for StatsdDeserializer {
         });
         match std::str::from_utf8(&bytes)
             .map_err(ParseError::InvalidUtf8)
-            .and_then(parse)
-        {
-            Ok(metric) => {
-                let event = Event::Metric(metric);
+            .and_then(|packet| {
+                if self.dogstatsd {
+                    parse_dogstatsd(packet)
+                } else {
+                    parse(packet).map(Event::Metric)
+                }
+            }) {
+            Ok(mut event) => {
+                if let Event::Log(log) = &mut event {
+                    log.insert(log_schema().source_type_key(), Bytes::from("statsd"));
+                }
                 emit!(EventsReceived {
                     count: 1,
                     byte_size: event.size_of(),
@@ -189,27 +242,46 @@ impl decoding::format::Deserializer for StatsdDeserializer {
 async fn statsd_udp(
     config: UdpConfig,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
 ) -> Result<(), ()> {
-    let socket = UdpSocket::bind(&config.address)
-        .map_err(|error| emit!(StatsdSocketError::bind(error)))
-        .await?;
-
-    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
-        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-        }
-    }
+    let sockets = udp::bind_listeners(
+        config.address,
+        config.receive_buffer_bytes,
+        config.multicast.as_ref(),
+        config.reuse_port_sockets,
+    )
+    .map_err(|error| emit!(StatsdSocketError::bind(error)))?;
 
     info!(
         message = "Listening.",
         addr = %config.address,
-        r#type = "udp"
+        r#type = "udp",
+        sockets = sockets.len(),
     );
 
+    // Each socket is received from in its own task, so they are processed in parallel.
+    let tasks = sockets.into_iter().map(|socket| {
+        tokio::spawn(statsd_udp_receive(
+            socket,
+            config.dogstatsd,
+            shutdown.clone(),
+            out.clone(),
+        ))
+    });
+    future::join_all(tasks).await;
+
+    Ok(())
+}
+
+async fn statsd_udp_receive(
+    socket: UdpSocket,
+    dogstatsd: bool,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) {
     let codec = Decoder::new(
         Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
-        Deserializer::Boxed(Box::new(StatsdDeserializer)),
+        Deserializer::Boxed(Box::new(StatsdDeserializer::new(dogstatsd))),
     );
     let mut stream = UdpFramed::new(socket, codec).take_until(shutdown);
     while let Some(frame) = stream.next().await {
@@ -225,12 +297,12 @@ async fn statsd_udp(
             }
         }
     }
-
-    Ok(())
 }
 
 #[derive(Clone)]
-struct StatsdTcpSource;
+struct StatsdTcpSource {
+    dogstatsd: bool,
+}
 
 impl TcpSource for StatsdTcpSource {
     type Error = codecs::decoding::Error;
@@ -241,7 +313,7 @@ impl TcpSource for StatsdTcpSource {
     fn decoder(&self) -> Self::Decoder {
         Decoder::new(
             Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
-            Deserializer::Boxed(Box::new(StatsdDeserializer)),
+            Deserializer::Boxed(Box::new(StatsdDeserializer::new(self.dogstatsd))),
         )
     }
 
@@ -313,6 +385,7 @@ mod test {
         let in_path = tempfile::tempdir().unwrap().into_path().join("unix_test");
         let config = StatsdConfig::Unix(UnixConfig {
             path: in_path.clone(),
+            dogstatsd: false,
         });
         let (sender, mut receiver) = mpsc::channel(200);
         tokio::spawn(async move {
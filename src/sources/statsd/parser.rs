@@ -5,15 +5,38 @@ use std::{
     str::Utf8Error,
 };
 
+use chrono::{DateTime, TimeZone, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Metric, MetricKind, MetricValue, StatisticKind},
+        Event, LogEvent, Value,
+    },
+};
 
 static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
 static NONALPHANUM: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^a-zA-Z_\-0-9\.]").unwrap());
 
 pub fn parse(packet: &str) -> Result<Metric, ParseError> {
+    parse_metric(packet, false)
+}
+
+/// Parses a DogStatsD datagram, which is either a metric supporting the container ID and timestamp
+/// extensions, an event or a service check. The events and service checks are parsed as logs.
+pub fn parse_dogstatsd(packet: &str) -> Result<Event, ParseError> {
+    if let Some(event) = packet.strip_prefix("_e{") {
+        parse_event(event).map(Event::Log)
+    } else if let Some(service_check) = packet.strip_prefix("_sc|") {
+        parse_service_check(service_check).map(Event::Log)
+    } else {
+        parse_metric(packet, true).map(Event::Metric)
+    }
+}
+
+fn parse_metric(packet: &str, dogstatsd: bool) -> Result<Metric, ParseError> {
     // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/#datagram-format
     let key_and_body = packet.splitn(2, ':').collect::<Vec<_>>();
     if key_and_body.len() != 2 {
@@ -33,26 +56,29 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
     let name = sanitize_key(key);
     let metric_type = parts[1];
 
-    // sampling part is optional and comes after metric type part
-    let sampling = parts.get(2).filter(|s| s.starts_with('@'));
-    let sample_rate = if let Some(s) = sampling {
-        1.0 / sanitize_sampling(parse_sampling(s)?)
-    } else {
-        1.0
-    };
-
-    // tags are optional and could be found either after sampling of after metric type part
-    let tags = if sampling.is_none() {
-        parts.get(2)
-    } else {
-        parts.get(3)
-    };
-    let tags = tags.filter(|s| s.starts_with('#'));
-    let tags = if let Some(t) = tags {
-        Some(parse_tags(t)?)
-    } else {
-        None
-    };
+    // sampling and tags parts are optional and come after metric type part, along with the
+    // container ID and timestamp extensions of DogStatsD
+    let mut sample_rate = 1.0;
+    let mut tags = None;
+    let mut container_id = None;
+    let mut timestamp = None;
+    for part in &parts[2..] {
+        if part.starts_with('@') {
+            sample_rate = 1.0 / sanitize_sampling(parse_sampling(part)?);
+        } else if part.starts_with('#') {
+            tags = Some(parse_tags(part)?);
+        } else if !dogstatsd {
+            continue;
+        } else if let Some(id) = part.strip_prefix("c:") {
+            container_id = Some(id);
+        } else if let Some(seconds) = part.strip_prefix('T') {
+            timestamp = Some(parse_timestamp(seconds)?);
+        }
+    }
+    if let Some(id) = container_id {
+        tags.get_or_insert_with(BTreeMap::new)
+            .insert("container_id".to_owned(), id.to_owned());
+    }
 
     let metric = match metric_type {
         "c" => {
@@ -110,7 +136,120 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
         .with_tags(tags),
         other => return Err(ParseError::UnknownMetricType(other.into())),
     };
-    Ok(metric)
+    Ok(metric.with_timestamp(timestamp))
+}
+
+/// Parses the part following `_e{` of an event, formatted as
+/// `_e{<TITLE_LENGTH>,<TEXT_LENGTH>}:<TITLE>|<TEXT>|d:<TIMESTAMP>|h:<HOSTNAME>|p:<PRIORITY>|t:<ALERT_TYPE>|#<TAGS>`.
+fn parse_event(input: &str) -> Result<LogEvent, ParseError> {
+    // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/?tab=events
+    let (lengths, body) = input
+        .split_once("}:")
+        .ok_or(ParseError::Malformed("event should have lengths and body"))?;
+    let (title_length, text_length) = lengths.split_once(',').ok_or(ParseError::Malformed(
+        "event lengths should be comma separated",
+    ))?;
+    let (title_length, text_length): (usize, usize) = (title_length.parse()?, text_length.parse()?);
+
+    let title = body.get(..title_length).ok_or(ParseError::Malformed(
+        "event title is shorter than its length",
+    ))?;
+    let text = body
+        .get(title_length..)
+        .and_then(|rest| rest.strip_prefix('|'))
+        .and_then(|rest| rest.get(..text_length))
+        .ok_or(ParseError::Malformed(
+            "event text is shorter than its length",
+        ))?;
+    let metadata = &body[title_length + 1 + text_length..];
+    if !metadata.is_empty() && !metadata.starts_with('|') {
+        return Err(ParseError::Malformed(
+            "event text is longer than its length",
+        ));
+    }
+
+    let mut log = LogEvent::default();
+    log.insert("kind", "event");
+    log.insert("title", title.replace("\\n", "\n"));
+    log.insert(log_schema().message_key(), text.replace("\\n", "\n"));
+    for part in metadata.split('|').skip(1) {
+        if let Some(field) = part.strip_prefix("k:") {
+            log.insert("aggregation_key", field);
+        } else if let Some(field) = part.strip_prefix("p:") {
+            log.insert("priority", field);
+        } else if let Some(field) = part.strip_prefix("s:") {
+            log.insert("source_type_name", field);
+        } else if let Some(field) = part.strip_prefix("t:") {
+            log.insert("alert_type", field);
+        } else {
+            parse_log_metadata(&mut log, part)?;
+        }
+    }
+    insert_current_timestamp(&mut log);
+    Ok(log)
+}
+
+/// Parses the part following `_sc|` of a service check, formatted as
+/// `_sc|<NAME>|<STATUS>|d:<TIMESTAMP>|h:<HOSTNAME>|#<TAGS>|m:<MESSAGE>`.
+fn parse_service_check(input: &str) -> Result<LogEvent, ParseError> {
+    // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/?tab=servicechecks
+    let mut parts = input.split('|');
+    let name = parts
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or(ParseError::Malformed("service check should have a name"))?;
+    let status: u8 = parts
+        .next()
+        .ok_or(ParseError::Malformed("service check should have a status"))?
+        .parse()?;
+    if status > 3 {
+        return Err(ParseError::Malformed(
+            "service check status should be between 0 and 3",
+        ));
+    }
+
+    let mut log = LogEvent::default();
+    log.insert("kind", "service_check");
+    log.insert("name", name);
+    log.insert("status", i64::from(status));
+    for part in parts {
+        if let Some(message) = part.strip_prefix("m:") {
+            log.insert(log_schema().message_key(), message.replace("\\n", "\n"));
+        } else {
+            parse_log_metadata(&mut log, part)?;
+        }
+    }
+    insert_current_timestamp(&mut log);
+    Ok(log)
+}
+
+/// Parses the timestamp, host name and tags parts shared by events and service checks, ignoring
+/// the unknown ones.
+fn parse_log_metadata(log: &mut LogEvent, part: &str) -> Result<(), ParseError> {
+    if let Some(seconds) = part.strip_prefix("d:") {
+        log.insert(log_schema().timestamp_key(), parse_timestamp(seconds)?);
+    } else if let Some(host) = part.strip_prefix("h:") {
+        log.insert(log_schema().host_key(), host);
+    } else if part.starts_with('#') {
+        let tags = parse_tags(part)?
+            .into_iter()
+            .map(|(key, value)| (key, Value::from(value)))
+            .collect::<BTreeMap<_, _>>();
+        log.insert("tags", tags);
+    }
+    Ok(())
+}
+
+fn insert_current_timestamp(log: &mut LogEvent) {
+    if !log.contains(log_schema().timestamp_key()) {
+        log.insert(log_schema().timestamp_key(), Utc::now());
+    }
+}
+
+fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, ParseError> {
+    Utc.timestamp_opt(input.parse()?, 0)
+        .single()
+        .ok_or(ParseError::Malformed("timestamp is out of range"))
 }
 
 fn parse_sampling(input: &str) -> Result<f64, ParseError> {
@@ -228,8 +367,16 @@ impl From<ParseFloatError> for ParseError {
 mod test {
     use vector_common::assert_event_data_eq;
 
-    use super::{parse, sanitize_key, sanitize_sampling};
-    use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+    use chrono::{TimeZone, Utc};
+
+    use super::{parse, parse_dogstatsd, sanitize_key, sanitize_sampling, ParseError};
+    use crate::{
+        config::log_schema,
+        event::{
+            metric::{Metric, MetricKind, MetricValue, StatisticKind},
+            Value,
+        },
+    };
 
     #[test]
     fn basic_counter() {
@@ -409,4 +556,99 @@ mod test {
         assert_eq!(2.5, sanitize_sampling(2.5));
         assert_eq!(-5.0, sanitize_sampling(-5.0));
     }
+
+    #[test]
+    fn dogstatsd_extensions() {
+        assert_event_data_eq!(
+            parse_dogstatsd("foo:1|c|#tag1|c:83c0a99c0a54c0c187f461c7980e9b57f3f6a8b0c918c8d93df19a9de6f3fe1d|T1656581400")
+                .map(|event| event.into_metric()),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(
+                vec![
+                    ("tag1".to_owned(), "true".to_owned()),
+                    (
+                        "container_id".to_owned(),
+                        "83c0a99c0a54c0c187f461c7980e9b57f3f6a8b0c918c8d93df19a9de6f3fe1d".to_owned()
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_timestamp(Some(Utc.timestamp(1656581400, 0)))),
+        );
+    }
+
+    #[test]
+    fn dogstatsd_extensions_ignored_by_statsd() {
+        assert_event_data_eq!(
+            parse("foo:1|c|c:abc|T1656581400"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )),
+        );
+    }
+
+    #[test]
+    fn dogstatsd_event() {
+        let log = parse_dogstatsd(
+            "_e{5,15}:Title|Text\\nwith line|d:1656581400|h:my-host|p:low|t:warning|#env:prod,web",
+        )
+        .unwrap()
+        .into_log();
+
+        assert_eq!(log["kind"], "event".into());
+        assert_eq!(log["title"], "Title".into());
+        assert_eq!(log[log_schema().message_key()], "Text\nwith line".into());
+        assert_eq!(log[log_schema().host_key()], "my-host".into());
+        assert_eq!(log["priority"], "low".into());
+        assert_eq!(log["alert_type"], "warning".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Value::from(Utc.timestamp(1656581400, 0))
+        );
+        assert_eq!(log["tags.env"], "prod".into());
+        assert_eq!(log["tags.web"], "true".into());
+    }
+
+    #[test]
+    fn dogstatsd_event_with_wrong_lengths() {
+        assert!(matches!(
+            parse_dogstatsd("_e{5,4}:Title|Text is longer"),
+            Err(ParseError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse_dogstatsd("_e{10,4}:Title|Text"),
+            Err(ParseError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn dogstatsd_service_check() {
+        let log =
+            parse_dogstatsd("_sc|redis.can_connect|2|h:my-host|#env:prod|m:Connection timed out")
+                .unwrap()
+                .into_log();
+
+        assert_eq!(log["kind"], "service_check".into());
+        assert_eq!(log["name"], "redis.can_connect".into());
+        assert_eq!(log["status"], Value::from(2_i64));
+        assert_eq!(log[log_schema().host_key()], "my-host".into());
+        assert_eq!(
+            log[log_schema().message_key()],
+            "Connection timed out".into()
+        );
+        assert_eq!(log["tags.env"], "prod".into());
+        assert!(log.contains(log_schema().timestamp_key()));
+
+        assert!(matches!(
+            parse_dogstatsd("_sc|redis.can_connect|4"),
+            Err(ParseError::Malformed(_))
+        ));
+    }
 }
@@ -17,6 +17,8 @@ use crate::{
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct UnixConfig {
     pub path: PathBuf,
+    #[serde(default)]
+    pub dogstatsd: bool,
 }
 
 pub fn statsd_unix(
@@ -26,7 +28,7 @@ pub fn statsd_unix(
 ) -> crate::Result<Source> {
     let decoder = Decoder::new(
         Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
-        Deserializer::Boxed(Box::new(StatsdDeserializer)),
+        Deserializer::Boxed(Box::new(StatsdDeserializer::new(config.dogstatsd))),
     );
 
     build_unix_stream_source(
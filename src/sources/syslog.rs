@@ -1,6 +1,6 @@
-use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
+use std::{net::SocketAddr, num::NonZeroUsize};
 
 use crate::codecs::Decoder;
 use bytes::Bytes;
@@ -9,7 +9,7 @@ use codecs::{
     decoding::{Deserializer, Framer},
     BytesDecoder, OctetCountingDecoder, SyslogDeserializer,
 };
-use futures::StreamExt;
+use futures::{future, StreamExt};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use tokio::net::UdpSocket;
@@ -28,7 +28,8 @@ use crate::{
     sources::util::{SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsConfig},
-    udp, SourceSender,
+    udp::{self, MulticastConfig},
+    SourceSender,
 };
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -56,6 +57,8 @@ pub enum Mode {
     Udp {
         address: SocketAddr,
         receive_buffer_bytes: Option<usize>,
+        multicast: Option<MulticastConfig>,
+        reuse_port_sockets: Option<NonZeroUsize>,
     },
     #[cfg(unix)]
     Unix {
@@ -132,14 +135,21 @@ impl SourceConfig for SyslogConfig {
             Mode::Udp {
                 address,
                 receive_buffer_bytes,
-            } => Ok(udp(
-                address,
-                self.max_length,
-                host_key,
-                receive_buffer_bytes,
-                cx.shutdown,
-                cx.out,
-            )),
+                multicast,
+                reuse_port_sockets,
+            } => {
+                udp::validate_listener(multicast.as_ref(), reuse_port_sockets)?;
+                Ok(udp(
+                    address,
+                    self.max_length,
+                    host_key,
+                    receive_buffer_bytes,
+                    multicast,
+                    reuse_port_sockets,
+                    cx.shutdown,
+                    cx.out,
+                ))
+            }
             #[cfg(unix)]
             Mode::Unix {
                 path,
@@ -219,63 +229,87 @@ pub fn udp(
     _max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
+    multicast: Option<MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
 ) -> super::Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&addr)
-            .await
-            .expect("Failed to bind to UDP listener socket");
-
-        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-            }
-        }
+        let sockets = udp::bind_listeners(
+            addr,
+            receive_buffer_bytes,
+            multicast.as_ref(),
+            reuse_port_sockets,
+        )
+        .expect("Failed to bind to UDP listener socket");
 
         info!(
             message = "Listening.",
             addr = %addr,
-            r#type = "udp"
+            r#type = "udp",
+            sockets = sockets.len(),
         );
 
-        let mut stream = UdpFramed::new(
-            socket,
-            Decoder::new(
-                Framer::Bytes(BytesDecoder::new()),
-                Deserializer::Syslog(SyslogDeserializer),
-            ),
-        )
-        .take_until(shutdown)
-        .filter_map(|frame| {
-            let host_key = host_key.clone();
-            async move {
-                match frame {
-                    Ok(((mut events, _byte_size), received_from)) => {
-                        let received_from = received_from.ip().to_string().into();
-                        handle_events(&mut events, &host_key, Some(received_from));
-                        Some(events.remove(0))
-                    }
-                    Err(error) => {
-                        emit!(SyslogUdpReadError { error });
-                        None
-                    }
-                }
+        // Each socket is received from in its own task, so they are processed in parallel.
+        let tasks = sockets.into_iter().map(|socket| {
+            tokio::spawn(receive(
+                socket,
+                host_key.clone(),
+                shutdown.clone(),
+                out.clone(),
+            ))
+        });
+        for result in future::join_all(tasks).await {
+            if !matches!(result, Ok(Ok(()))) {
+                return Err(());
             }
-        })
-        .boxed();
+        }
+        Ok(())
+    })
+}
 
-        match out.send_event_stream(&mut stream).await {
-            Ok(()) => {
-                info!("Finished sending.");
-                Ok(())
-            }
-            Err(error) => {
-                error!(message = "Error sending line.", %error);
-                Err(())
+async fn receive(
+    socket: UdpSocket,
+    host_key: String,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut stream = UdpFramed::new(
+        socket,
+        Decoder::new(
+            Framer::Bytes(BytesDecoder::new()),
+            Deserializer::Syslog(SyslogDeserializer),
+        ),
+    )
+    .take_until(shutdown)
+    .filter_map(|frame| {
+        let host_key = host_key.clone();
+        async move {
+            match frame {
+                Ok(((mut events, _byte_size), received_from)) => {
+                    let received_from = received_from.ip().to_string().into();
+                    handle_events(&mut events, &host_key, Some(received_from));
+                    Some(events.remove(0))
+                }
+                Err(error) => {
+                    emit!(SyslogUdpReadError { error });
+                    None
+                }
             }
         }
     })
+    .boxed();
+
+    match out.send_event_stream(&mut stream).await {
+        Ok(()) => {
+            info!("Finished sending.");
+            Ok(())
+        }
+        Err(error) => {
+            error!(message = "Error sending line.", %error);
+            Err(())
+        }
+    }
 }
 
 fn handle_events(events: &mut [Event], host_key: &str, default_host: Option<Bytes>) {
@@ -371,6 +405,40 @@ mod test {
         assert_eq!(receive_buffer_bytes, Some(256));
     }
 
+    #[test]
+    fn config_udp_with_multicast_and_reuse_port() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "udp"
+            address = "0.0.0.0:514"
+            multicast.groups = ["239.1.2.3"]
+            multicast.interface = "192.168.1.10"
+            reuse_port_sockets = 4
+          "#,
+        )
+        .unwrap();
+
+        match config.mode {
+            Mode::Udp {
+                multicast,
+                reuse_port_sockets,
+                ..
+            } => {
+                assert_eq!(
+                    multicast,
+                    Some(MulticastConfig {
+                        groups: vec!["239.1.2.3".parse().unwrap()],
+                        interface: Some(udp::MulticastInterface::Address(
+                            "192.168.1.10".parse().unwrap()
+                        )),
+                    })
+                );
+                assert_eq!(reuse_port_sockets, NonZeroUsize::new(4));
+            }
+            _ => panic!("expected Mode::Udp"),
+        }
+    }
+
     #[test]
     fn config_tcp_keepalive_empty() {
         let config: SyslogConfig = toml::from_str(
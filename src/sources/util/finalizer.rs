@@ -15,13 +15,19 @@ use crate::shutdown::ShutdownSignal;
     feature = "sources-file",
     feature = "sources-journald",
     feature = "sources-kafka",
+    feature = "sources-mqtt",
 ))]
 pub(crate) type OrderedFinalizer<T> = FinalizerSet<T, FuturesOrdered<FinalizerFuture<T>>>;
 
 /// The `UnorderedFinalizer` framework marks events from a source as
 /// done in a single background task *in the order the finalization
 /// happens on the event batches*, using `FinalizerSet`.
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-splunk_hec"))]
+#[cfg(any(
+    feature = "sources-amqp",
+    feature = "sources-aws_sqs",
+    feature = "sources-redis",
+    feature = "sources-splunk_hec"
+))]
 pub(crate) type UnorderedFinalizer<T> = FinalizerSet<T, FuturesUnordered<FinalizerFuture<T>>>;
 
 /// The `FinalizerSet` framework here is a mechanism for marking
@@ -38,13 +44,36 @@ where
     T: Send + 'static,
     S: FuturesSet<FinalizerFuture<T>> + Default + Send + Unpin + 'static,
 {
+    /// Creates a finalizer applying `apply_done` to the entries whose events were delivered.
     pub(crate) fn new<F, Fut>(shutdown: Shared<ShutdownSignal>, apply_done: F) -> Self
     where
         F: Fn(T) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self::new_with_status(shutdown, move |status, entry| {
+            let done = (status == BatchStatus::Delivered).then(|| apply_done(entry));
+            async move {
+                if let Some(done) = done {
+                    done.await;
+                }
+            }
+        })
+    }
+
+    /// Creates a finalizer applying `apply_status` to every entry along with the status of its
+    /// events, for the sources that must also report the failures upstream.
+    pub(crate) fn new_with_status<F, Fut>(shutdown: Shared<ShutdownSignal>, apply_status: F) -> Self
+    where
+        F: Fn(BatchStatus, T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
     {
         let (sender, receiver) = mpsc::unbounded_channel();
-        tokio::spawn(run_finalizer(shutdown, receiver, apply_done, S::default()));
+        tokio::spawn(run_finalizer(
+            shutdown,
+            receiver,
+            apply_status,
+            S::default(),
+        ));
         Self {
             sender: Some(sender),
             _phantom: Default::default(),
@@ -63,7 +92,7 @@ where
 async fn run_finalizer<T, F: Future<Output = ()>>(
     shutdown: Shared<ShutdownSignal>,
     mut new_entries: mpsc::UnboundedReceiver<(BatchStatusReceiver, T)>,
-    apply_done: impl Fn(T) -> F,
+    apply_status: impl Fn(BatchStatus, T) -> F,
     mut status_receivers: impl FuturesSet<FinalizerFuture<T>> + Unpin,
 ) {
     loop {
@@ -79,9 +108,7 @@ async fn run_finalizer<T, F: Future<Output = ()>>(
                 None => break,
             },
             finished = status_receivers.next(), if !status_receivers.is_empty() => match finished {
-                Some((status, entry)) => if status == BatchStatus::Delivered {
-                    apply_done(entry).await;
-                }
+                Some((status, entry)) => apply_status(status, entry).await,
                 // The is_empty guard above prevents this from being reachable.
                 None => unreachable!(),
             },
@@ -91,9 +118,7 @@ async fn run_finalizer<T, F: Future<Output = ()>>(
     // closed. Wait for the last statuses to come in before indicating
     // we are done.
     while let Some((status, entry)) = status_receivers.next().await {
-        if status == BatchStatus::Delivered {
-            apply_done(entry).await;
-        }
+        apply_status(status, entry).await;
     }
     drop(shutdown);
 }
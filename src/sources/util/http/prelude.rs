@@ -2,7 +2,7 @@ use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{FutureExt, TryFutureExt};
+use futures::FutureExt;
 use vector_core::{
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
     ByteSizeOf,
@@ -12,9 +12,10 @@ use warp::{
         path::{FullPath, Tail},
         BoxedFilter,
     },
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, Method, StatusCode},
     reject::Rejection,
-    Filter,
+    reply::Response,
+    Filter, Reply,
 };
 
 use super::{
@@ -29,6 +30,17 @@ use crate::{
     SourceSender,
 };
 
+/// A path served by an HTTP source, with its own authentication, accepted methods and output.
+pub struct HttpSourceRoute<S> {
+    pub source: S,
+    pub path: String,
+    pub strict_path: bool,
+    pub methods: Vec<Method>,
+    pub auth: Option<HttpSourceAuthConfig>,
+    /// The named output the events are sent to, the default output if `None`.
+    pub output: Option<String>,
+}
+
 #[async_trait]
 pub trait HttpSource: Clone + Send + Sync + 'static {
     fn build_events(
@@ -48,72 +60,45 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         auth: &Option<HttpSourceAuthConfig>,
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
+    ) -> crate::Result<crate::sources::Source> {
+        let route = HttpSourceRoute {
+            source: self,
+            path: path.to_owned(),
+            strict_path,
+            methods: vec![Method::POST],
+            auth: auth.clone(),
+            output: None,
+        };
+        Self::run_routes(vec![route], address, tls, cx, acknowledgements)
+    }
+
+    /// Serves the routes on a single listener. They are matched in order.
+    fn run_routes(
+        routes: Vec<HttpSourceRoute<Self>>,
+        address: SocketAddr,
+        tls: &Option<TlsConfig>,
+        cx: SourceContext,
+        acknowledgements: AcknowledgementsConfig,
     ) -> crate::Result<crate::sources::Source> {
         let tls = MaybeTlsSettings::from_config(tls, true)?;
         let protocol = tls.http_protocol_name();
-        let auth = HttpSourceAuth::try_from(auth.as_ref())?;
-        let path = path.to_owned();
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let routes = routes
+            .into_iter()
+            .map(|route| {
+                let auth = HttpSourceAuth::try_from(route.auth.as_ref())?;
+                Ok((route, auth))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
         Ok(Box::pin(async move {
             let span = crate::trace::current_span();
-            let mut filter: BoxedFilter<()> = warp::post().boxed();
-            for s in path.split('/').filter(|&x| !x.is_empty()) {
-                filter = filter.and(warp::path(s.to_string())).boxed()
-            }
-            let svc = filter
-                .and(warp::path::tail())
-                .and_then(move |tail: Tail| async move {
-                    if !strict_path || tail.as_str().is_empty() {
-                        Ok(())
-                    } else {
-                        debug!(message = "Path rejected.");
-                        Err(warp::reject::custom(ErrorMessage::new(
-                            StatusCode::NOT_FOUND,
-                            "Not found".to_string(),
-                        )))
-                    }
+            let svc = routes
+                .into_iter()
+                .map(|(route, auth)| {
+                    route_filter(route, auth, protocol, acknowledgements, cx.out.clone())
                 })
-                .untuple_one()
-                .and(warp::path::full())
-                .and(warp::header::optional::<String>("authorization"))
-                .and(warp::header::optional::<String>("content-encoding"))
-                .and(warp::header::headers_cloned())
-                .and(warp::body::bytes())
-                .and(warp::query::<HashMap<String, String>>())
-                .and_then(
-                    move |path: FullPath,
-                          auth_header,
-                          encoding_header,
-                          headers: HeaderMap,
-                          body: Bytes,
-                          query_parameters: HashMap<String, String>| {
-                        debug!(message = "Handling HTTP request.", headers = ?headers);
-                        let http_path = path.as_str();
-                        emit!(HttpBytesReceived {
-                            byte_size: body.len(),
-                            http_path,
-                            protocol,
-                        });
-
-                        let events = auth
-                            .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
-                            .and_then(|body| {
-                                self.build_events(body, headers, query_parameters, path.as_str())
-                            })
-                            .map(|events| {
-                                emit!(HttpEventsReceived {
-                                    count: events.len(),
-                                    byte_size: events.size_of(),
-                                    http_path,
-                                    protocol,
-                                });
-                                events
-                            });
-
-                        handle_request(events, acknowledgements, cx.out.clone())
-                    },
-                )
+                .reduce(|routes, route| routes.or(route).unify().boxed())
+                .expect("at least one route")
                 .with(warp::trace(move |_info| span.clone()));
 
             let ping = warp::get().and(warp::path("ping")).map(|| "pong");
@@ -141,6 +126,100 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     }
 }
 
+fn route_filter<S: HttpSource>(
+    route: HttpSourceRoute<S>,
+    auth: HttpSourceAuth,
+    protocol: &'static str,
+    acknowledgements: bool,
+    out: SourceSender,
+) -> BoxedFilter<(Response,)> {
+    let HttpSourceRoute {
+        source,
+        path,
+        strict_path,
+        methods,
+        output,
+        ..
+    } = route;
+
+    let mut filter: BoxedFilter<()> = warp::any().boxed();
+    for s in path.split('/').filter(|&x| !x.is_empty()) {
+        filter = filter.and(warp::path(s.to_string())).boxed()
+    }
+    filter
+        .and(warp::path::tail())
+        .and_then(move |tail: Tail| async move {
+            if !strict_path || tail.as_str().is_empty() {
+                Ok(())
+            } else {
+                debug!(message = "Path rejected.");
+                Err(warp::reject::custom(ErrorMessage::new(
+                    StatusCode::NOT_FOUND,
+                    "Not found".to_string(),
+                )))
+            }
+        })
+        .untuple_one()
+        .and(warp::method())
+        .and_then(move |method: Method| {
+            let allowed = methods.contains(&method);
+            async move {
+                if allowed {
+                    Ok(())
+                } else {
+                    debug!(message = "Method rejected.", %method);
+                    Err(warp::reject::custom(ErrorMessage::new(
+                        StatusCode::METHOD_NOT_ALLOWED,
+                        "Method not allowed".to_string(),
+                    )))
+                }
+            }
+        })
+        .untuple_one()
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::header::headers_cloned())
+        .and(warp::body::bytes())
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(
+            move |path: FullPath,
+                  auth_header,
+                  encoding_header,
+                  headers: HeaderMap,
+                  body: Bytes,
+                  query_parameters: HashMap<String, String>| {
+                debug!(message = "Handling HTTP request.", headers = ?headers);
+                let http_path = path.as_str();
+                emit!(HttpBytesReceived {
+                    byte_size: body.len(),
+                    http_path,
+                    protocol,
+                });
+
+                let events = auth
+                    .is_valid(&auth_header)
+                    .and_then(|()| decode(&encoding_header, body))
+                    .and_then(|body| {
+                        source.build_events(body, headers, query_parameters, path.as_str())
+                    })
+                    .map(|events| {
+                        emit!(HttpEventsReceived {
+                            count: events.len(),
+                            byte_size: events.size_of(),
+                            http_path,
+                            protocol,
+                        });
+                        events
+                    });
+
+                handle_request(events, acknowledgements, out.clone(), output.clone())
+            },
+        )
+        .map(Reply::into_response)
+        .boxed()
+}
+
 struct RejectShuttingDown;
 
 impl fmt::Debug for RejectShuttingDown {
@@ -155,21 +234,26 @@ async fn handle_request(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
+    output: Option<String>,
 ) -> Result<impl warp::Reply, Rejection> {
     match events {
         Ok(mut events) => {
             let receiver = BatchNotifier::maybe_apply_to_events(acknowledgements, &mut events);
 
-            out.send_batch(events)
-                .map_err(move |error: crate::source_sender::ClosedError| {
+            let sent = match &output {
+                Some(output) => out.send_batch_named(output, events).await,
+                None => out.send_batch(events).await,
+            };
+            match sent {
+                Ok(()) => handle_batch_status(receiver).await,
+                Err(error) => {
                     // can only fail if receiving end disconnected, so we are shutting down,
                     // probably not gracefully.
                     error!(message = "Failed to forward events, downstream is closed.");
                     error!(message = "Tried to send the following event.", %error);
-                    warp::reject::custom(RejectShuttingDown)
-                })
-                .and_then(|_| handle_batch_status(receiver))
-                .await
+                    Err(warp::reject::custom(RejectShuttingDown))
+                }
+            }
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
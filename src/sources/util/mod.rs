@@ -2,15 +2,20 @@
 mod body_decoding;
 mod encoding_config;
 #[cfg(any(
+    feature = "sources-amqp",
     feature = "sources-aws_sqs",
     feature = "sources-file",
     feature = "sources-journald",
     feature = "sources-kafka",
+    feature = "sources-mqtt",
+    feature = "sources-redis",
     feature = "sources-splunk_hec"
 ))]
 pub mod finalizer;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
+#[cfg(any(feature = "sources-opentelemetry", feature = "sources-vector"))]
+mod grpc;
 #[cfg(any(
     feature = "sources-utils-http-auth",
     feature = "sources-utils-http-encoding",
@@ -28,7 +33,11 @@ mod unix;
 mod unix_datagram;
 #[cfg(all(unix, feature = "sources-utils-unix"))]
 mod unix_stream;
-#[cfg(any(feature = "sources-utils-tls", feature = "sources-vector"))]
+#[cfg(any(
+    feature = "sources-opentelemetry",
+    feature = "sources-utils-tls",
+    feature = "sources-vector"
+))]
 mod wrappers;
 
 #[cfg(feature = "sources-file")]
@@ -42,7 +51,11 @@ pub use unix::change_socket_permissions;
 pub use unix_datagram::build_unix_datagram_source;
 #[cfg(all(unix, feature = "sources-utils-unix",))]
 pub use unix_stream::build_unix_stream_source;
-#[cfg(any(feature = "sources-utils-tls", feature = "sources-vector"))]
+#[cfg(any(
+    feature = "sources-opentelemetry",
+    feature = "sources-utils-tls",
+    feature = "sources-vector"
+))]
 pub use wrappers::AfterReadExt;
 
 #[cfg(any(feature = "sources-http"))]
@@ -57,6 +70,6 @@ pub use self::http::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use self::http::HttpSource;
+pub use self::http::{HttpSource, HttpSourceRoute};
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
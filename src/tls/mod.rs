@@ -120,6 +120,9 @@ pub enum TlsError {
     NewCaStack { source: ErrorStack },
     #[snafu(display("Could not push intermediate certificate onto stack"))]
     CaStackPush { source: ErrorStack },
+    #[cfg(feature = "native-tls")]
+    #[snafu(display("Could not build native TLS connector: {}", source))]
+    NativeTls { source: native_tls::Error },
 }
 
 impl MaybeTlsStream<TcpStream> {
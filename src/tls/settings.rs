@@ -15,6 +15,8 @@ use openssl::{
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 
+#[cfg(feature = "native-tls")]
+use super::NativeTlsSnafu;
 use super::{
     AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
     FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu, NewStoreBuilderSnafu,
@@ -188,6 +190,29 @@ impl TlsSettings {
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
     }
+
+    /// Builds a `native-tls` connector from these settings, for the clients that can't be
+    /// given an OpenSSL connector.
+    #[cfg(feature = "native-tls")]
+    pub fn native_tls_connector(&self) -> Result<native_tls::TlsConnector> {
+        let mut builder = native_tls::TlsConnector::builder();
+        builder
+            .danger_accept_invalid_certs(!self.verify_certificate)
+            .danger_accept_invalid_hostnames(!self.verify_hostname);
+        for authority in &self.authorities {
+            let der = authority.to_der().context(DerExportSnafu)?;
+            builder.add_root_certificate(
+                native_tls::Certificate::from_der(&der).context(NativeTlsSnafu)?,
+            );
+        }
+        if let Some(identity) = &self.identity {
+            builder.identity(
+                native_tls::Identity::from_pkcs12(&identity.0, &identity.1)
+                    .context(NativeTlsSnafu)?,
+            );
+        }
+        builder.build().context(NativeTlsSnafu)
+    }
 }
 
 impl TlsOptions {
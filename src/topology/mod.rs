@@ -22,6 +22,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use chrono::{DateTime, Utc};
 use futures::{Future, FutureExt};
 pub(super) use running::RunningTopology;
 use tokio::sync::{mpsc, watch};
@@ -71,6 +72,40 @@ pub struct TapResource {
     pub removals: HashSet<ComponentKey>,
 }
 
+/// How an attempt at reloading the configuration ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadStatus {
+    /// The new configuration is running.
+    Applied,
+    /// The new configuration was refused before any component was changed.
+    Rejected,
+    /// The new configuration couldn't be started, and the previous one was restored.
+    Restored,
+    /// The new configuration was started, but reverted to the previous one as it was unhealthy.
+    RolledBack,
+    /// The previous configuration couldn't be restored.
+    Failed,
+}
+
+/// The outcome of the last attempt at reloading the configuration.
+#[derive(Debug, Clone)]
+pub struct ReloadOutcome {
+    pub status: ReloadStatus,
+    pub timestamp: DateTime<Utc>,
+    /// Why the new configuration isn't running, if it isn't.
+    pub reason: Option<String>,
+}
+
+impl ReloadOutcome {
+    fn new(status: ReloadStatus, reason: impl Into<Option<String>>) -> Self {
+        Self {
+            status,
+            timestamp: Utc::now(),
+            reason: reason.into(),
+        }
+    }
+}
+
 // Watcher types for topology changes.
 type WatchTx = watch::Sender<TapResource>;
 pub type WatchRx = watch::Receiver<TapResource>;
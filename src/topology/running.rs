@@ -9,14 +9,19 @@ use std::{
 use futures::{future, Future, FutureExt};
 use tokio::{
     sync::{mpsc, watch},
+    task::JoinHandle,
     time::{interval, sleep_until, Duration, Instant},
 };
 use tracing::Instrument;
 use vector_buffers::topology::channel::BufferSender;
 
 use crate::{
-    config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource},
-    event::EventArray,
+    config::{
+        ComponentKey, Config, ConfigDiff, DiffSummary, HealthcheckOptions, OutputId, Resource,
+    },
+    event::{metric::MetricValue, EventArray},
+    internal_events::{VectorConfigChanged, VectorReloadRolledBack},
+    metrics::Controller,
     shutdown::SourceShutdownCoordinator,
     spawn_named,
     topology::{
@@ -25,7 +30,7 @@ use crate::{
         fanout::{ControlChannel, ControlMessage},
         handle_errors, retain, take_healthchecks,
         task::TaskOutput,
-        BuiltBuffer, TaskHandle, WatchRx, WatchTx,
+        BuiltBuffer, ReloadOutcome, ReloadStatus, TaskHandle, WatchRx, WatchTx,
     },
     trigger::DisabledTrigger,
 };
@@ -44,6 +49,18 @@ pub struct RunningTopology {
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    last_reload: Option<ReloadOutcome>,
+    pending_rollback: Option<PendingRollback>,
+}
+
+/// A reload whose components are being watched for errors during the grace period, along with
+/// the configuration to bring back should they report too many.
+struct PendingRollback {
+    previous_config: Config,
+    watch: JoinHandle<Result<(), String>>,
+    /// Why the reload is to be rolled back, once the watch found it unhealthy. Kept so that the
+    /// finished watch isn't polled again before the rollback.
+    failure: Option<String>,
 }
 
 impl RunningTopology {
@@ -59,6 +76,8 @@ impl RunningTopology {
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            last_reload: None,
+            pending_rollback: None,
         }
     }
 
@@ -101,6 +120,9 @@ impl RunningTopology {
     pub fn stop(self) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
+        if let Some(pending) = &self.pending_rollback {
+            pending.watch.abort();
+        }
         // Create handy handles collections of all tasks for the subsequent
         // operations.
         let mut wait_handles = Vec::new();
@@ -189,6 +211,11 @@ impl RunningTopology {
         futures::future::join(source_shutdown_complete, shutdown_complete_future).map(|_| ())
     }
 
+    /// Gets the outcome of the last attempt at reloading the configuration, if any.
+    pub const fn last_reload(&self) -> Option<&ReloadOutcome> {
+        self.last_reload.as_ref()
+    }
+
     /// Attempts to load a new configuration and update this running topology.
     ///
     /// If the new configuration was valid, and all changes were able to be made -- removing of
@@ -200,6 +227,10 @@ impl RunningTopology {
     /// topology back to its previous state.  If either of these scenarios occur, then `Ok(false)`
     /// is returned.
     ///
+    /// If `healthchecks.reload_rollback` is set in the new configuration, the healthchecks of new
+    /// and changed sinks must pass, and those components are then watched for errors in the
+    /// background during the grace period. See `watch_reload`.
+    ///
     /// # Errors
     ///
     /// If all changes from the new configuration cannot be made, and the current configuration
@@ -212,15 +243,142 @@ impl RunningTopology {
                 message =
                 "Global options can't be changed while reloading config file; reload aborted. Please restart Vector to reload the configuration file."
             );
+            self.last_reload = Some(ReloadOutcome::new(
+                ReloadStatus::Rejected,
+                "Global options can't be changed while reloading.".to_owned(),
+            ));
             return Ok(false);
         }
 
-        // Calculate the change between the current configuration and the new configuration, and
-        // shutdown any components that are changing so that we can reclaim their buffers before
+        let options = new_config.healthchecks;
+        let diff = ConfigDiff::new(&self.config, &new_config);
+        let watched = diff
+            .sources
+            .changed_and_added()
+            .chain(diff.transforms.changed_and_added())
+            .chain(diff.sinks.changed_and_added())
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        // A new reload supersedes the one being watched, if any.
+        if let Some(pending) = self.pending_rollback.take() {
+            pending.watch.abort();
+        }
+
+        let old_config = match self
+            .respawn(new_config, diff, options.reload_rollback)
+            .await
+        {
+            Ok(Some(old_config)) => old_config,
+            Ok(None) => {
+                self.last_reload = Some(ReloadOutcome::new(
+                    ReloadStatus::Restored,
+                    "The new configuration failed to start.".to_owned(),
+                ));
+                return Ok(false);
+            }
+            Err(()) => {
+                self.last_reload = Some(ReloadOutcome::new(
+                    ReloadStatus::Failed,
+                    "The new configuration failed to start.".to_owned(),
+                ));
+                return Err(());
+            }
+        };
+
+        if options.reload_rollback {
+            self.pending_rollback = Some(PendingRollback {
+                previous_config: old_config,
+                watch: tokio::spawn(watch_errors(watched, options)),
+                failure: None,
+            });
+        }
+
+        self.last_reload = Some(ReloadOutcome::new(ReloadStatus::Applied, None));
+        Ok(true)
+    }
+
+    /// Waits for the grace period of the last reload to end. Never completes if no reload is being
+    /// watched, so that it can be raced against other events.
+    ///
+    /// Returns why the reload should be rolled back if the new and changed components reported
+    /// too many errors, in which case `rollback_reload` must be called next. This is cancel safe,
+    /// as it changes nothing until the watch completes.
+    pub async fn watch_reload(&mut self) -> Option<String> {
+        let pending = match self.pending_rollback.as_mut() {
+            Some(pending) => pending,
+            None => return future::pending().await,
+        };
+        if pending.failure.is_none() {
+            match (&mut pending.watch).await {
+                Ok(Ok(())) => {
+                    self.pending_rollback = None;
+                    return None;
+                }
+                Ok(Err(reason)) => pending.failure = Some(reason),
+                Err(error) => {
+                    error!(message = "Failed to watch reloaded components for errors.", %error);
+                    self.pending_rollback = None;
+                    return None;
+                }
+            }
+        }
+        pending.failure.clone()
+    }
+
+    /// Brings the previous configuration back after `watch_reload` found the last reload to be
+    /// unhealthy for the given reason.
+    ///
+    /// Returns `Ok(true)` if the reloaded configuration is kept running, and `Ok(false)` if it was
+    /// rolled back.
+    ///
+    /// # Errors
+    ///
+    /// If neither the previous configuration nor the reloaded one can be fully restored, then
+    /// `Err(())` is returned.
+    pub async fn rollback_reload(&mut self, reason: String) -> Result<bool, ()> {
+        let old_config = match self.pending_rollback.take() {
+            Some(pending) => pending.previous_config,
+            None => return Ok(true),
+        };
+        emit!(VectorReloadRolledBack { reason: &reason });
+
+        let diff = ConfigDiff::new(&self.config, &old_config);
+        match self.respawn(old_config, diff, false).await {
+            Ok(Some(_)) => {
+                self.last_reload = Some(ReloadOutcome::new(ReloadStatus::RolledBack, reason));
+                Ok(false)
+            }
+            // The new configuration is running again, so the reload did take effect.
+            Ok(None) => {
+                self.last_reload = Some(ReloadOutcome::new(
+                    ReloadStatus::Failed,
+                    format!("{} The previous configuration failed to start.", reason),
+                ));
+                Ok(true)
+            }
+            Err(()) => {
+                self.last_reload = Some(ReloadOutcome::new(ReloadStatus::Failed, reason));
+                Err(())
+            }
+        }
+    }
+
+    /// Applies the given configuration diff, replacing the current configuration with
+    /// `new_config`.
+    ///
+    /// Returns the replaced configuration if all changes were made, or `None` if they couldn't be
+    /// and the current configuration was restored instead.
+    async fn respawn(
+        &mut self,
+        new_config: Config,
+        diff: ConfigDiff,
+        require_healthy: bool,
+    ) -> Result<Option<Config>, ()> {
+        // Shutdown any components that are changing so that we can reclaim their buffers before
         // spawning the new version of the component.
         //
         // We also shutdown any component that is simply being removed entirely.
-        let diff = ConfigDiff::new(&self.config, &new_config);
         let buffers = self.shutdown_diff(&diff, &new_config).await;
 
         // Gives windows some time to make available any port
@@ -239,17 +397,25 @@ impl RunningTopology {
             // If healthchecks are configured for any of the changing/new components, try running
             // them before moving forward with connecting and spawning.  In some cases, healthchecks
             // failing may be configured as a non-blocking issue and so we'll still continue on.
+            let mut healthchecks = new_config.healthchecks;
+            healthchecks.require_healthy |= require_healthy;
             if self
-                .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
+                .run_healthchecks(&diff, &mut new_pieces, healthchecks)
                 .await
             {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
-                self.config = new_config;
+                match DiffSummary::new(&self.config, &new_config) {
+                    Ok(summary) => emit!(VectorConfigChanged { summary: &summary }),
+                    Err(error) => {
+                        warn!(message = "Failed to summarize configuration changes.", %error)
+                    }
+                }
+                let old_config = std::mem::replace(&mut self.config, new_config);
 
                 info!("New configuration loaded successfully.");
 
-                return Ok(true);
+                return Ok(Some(old_config));
             }
         }
 
@@ -269,7 +435,7 @@ impl RunningTopology {
 
                 info!("Old configuration restored successfully.");
 
-                return Ok(false);
+                return Ok(None);
             }
         }
 
@@ -864,3 +1030,55 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Vec<OutputId>) -> Vec<Outp
 
     changed_outputs
 }
+
+/// Watches the errors reported by the given components during the reload grace period, failing
+/// as soon as they exceed the allowed number.
+async fn watch_errors(
+    keys: HashSet<ComponentKey>,
+    options: HealthcheckOptions,
+) -> Result<(), String> {
+    info!(
+        message = "Watching new and changed components for errors.",
+        grace_period_secs = options.reload_grace_period().as_secs(),
+    );
+
+    let baseline = component_errors(&keys);
+    let deadline = Instant::now() + options.reload_grace_period();
+    let mut interval = interval(Duration::from_secs(1));
+    while Instant::now() < deadline {
+        interval.tick().await;
+
+        let errors = component_errors(&keys) - baseline;
+        if errors > options.reload_max_errors() as f64 {
+            return Err(format!(
+                "New and changed components reported {} errors during the grace period.",
+                errors
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Sums the `component_errors_total` counters of the given components.
+fn component_errors(keys: &HashSet<ComponentKey>) -> f64 {
+    let controller = match Controller::get() {
+        Ok(controller) => controller,
+        Err(_) => return 0.0,
+    };
+
+    controller
+        .capture_metrics()
+        .into_iter()
+        .filter(|metric| metric.name() == "component_errors_total")
+        .filter(|metric| {
+            metric
+                .tag_value("component_id")
+                .map_or(false, |id| keys.contains(&ComponentKey::from(id)))
+        })
+        .map(|metric| match metric.value() {
+            MetricValue::Counter { value } => *value,
+            _ => 0.0,
+        })
+        .sum()
+}
@@ -4,18 +4,20 @@ use std::{
 };
 
 use futures::StreamExt;
+use metrics::counter;
 use tokio::time::sleep;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
     buffers::{BufferConfig, WhenFull},
-    config::Config,
+    config::{ComponentKey, Config},
     sinks::{
         console::{ConsoleSinkConfig, Encoding, Target},
         prometheus::exporter::PrometheusExporterConfig,
     },
     sources::{demo_logs::DemoLogsConfig, splunk_hec::SplunkConfig},
     test_util::{next_addr, start_topology, temp_dir, wait_for_tcp},
+    topology::ReloadStatus,
     transforms::log_to_metric::{GaugeConfig, LogToMetricConfig, MetricConfig},
 };
 
@@ -111,6 +113,65 @@ async fn topology_old() {
         .unwrap());
 }
 
+#[tokio::test]
+async fn topology_rolls_back_unhealthy_reload() {
+    crate::test_util::components::init_test();
+    let address = next_addr();
+
+    let mut old_config = Config::builder();
+    old_config.add_source("in1", SplunkConfig::on(address));
+    old_config.add_sink(
+        "out",
+        &[&"in1"],
+        ConsoleSinkConfig {
+            target: Target::Stdout,
+            encoding: Encoding::Text.into(),
+        },
+    );
+
+    let mut new_config = old_config.clone();
+    new_config.add_sink(
+        "errors",
+        &[&"in1"],
+        ConsoleSinkConfig {
+            target: Target::Stdout,
+            encoding: Encoding::Text.into(),
+        },
+    );
+    new_config.healthchecks.reload_rollback = true;
+    new_config.healthchecks.reload_grace_period_secs = Some(5);
+
+    let (mut topology, _crash) = start_topology(old_config.build().unwrap(), false).await;
+    assert!(topology
+        .reload_config_and_respawn(new_config.build().unwrap())
+        .await
+        .unwrap());
+    assert!(topology
+        .config()
+        .sink(&ComponentKey::from("errors"))
+        .is_some());
+
+    // Let the watch take its baseline before the new sink reports errors.
+    sleep(Duration::from_millis(100)).await;
+    counter!("component_errors_total", 1, "component_id" => "errors");
+
+    let reason = tokio::time::timeout(Duration::from_secs(5), topology.watch_reload())
+        .await
+        .expect("reload wasn't found unhealthy")
+        .expect("reload wasn't found unhealthy");
+    assert!(!topology.rollback_reload(reason).await.unwrap());
+
+    assert!(topology
+        .config()
+        .sink(&ComponentKey::from("errors"))
+        .is_none());
+    assert!(topology.config().sink(&ComponentKey::from("out")).is_some());
+    assert_eq!(
+        topology.last_reload().map(|outcome| outcome.status),
+        Some(ReloadStatus::RolledBack)
+    );
+}
+
 #[tokio::test]
 async fn topology_reuse_old_port_sink() {
     let address = next_addr();
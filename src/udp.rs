@@ -1,4 +1,11 @@
-use socket2::SockRef;
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::NonZeroUsize,
+};
+
+use serde::{Deserialize, Serialize};
+use socket2::{Domain, Protocol, SockRef, Socket, Type};
 use tokio::net::UdpSocket;
 
 // This function will be obsolete after tokio/mio internally use `socket2` and expose the methods to
@@ -12,3 +19,160 @@ pub fn set_receive_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Resu
 pub fn set_send_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Result<()> {
     SockRef::from(socket).set_send_buffer_size(size)
 }
+
+/// The multicast groups a UDP listener joins.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MulticastConfig {
+    /// The IPv4 or IPv6 multicast addresses of the groups.
+    pub groups: Vec<IpAddr>,
+    /// The interface the groups are joined on, chosen by the system by default.
+    pub interface: Option<MulticastInterface>,
+}
+
+/// The interface multicast groups are joined on: an IPv4 address of the interface for IPv4 groups,
+/// or the index of the interface for IPv6 groups.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum MulticastInterface {
+    Address(Ipv4Addr),
+    Index(u32),
+}
+
+/// Checks that a listener doesn't both join multicast groups and bind several sockets: the system
+/// hands each multicast datagram to every socket bound to the port, rather than balancing them, so
+/// they would be received as many times as there are sockets.
+pub fn validate_listener(
+    multicast: Option<&MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
+) -> io::Result<()> {
+    match (multicast, reuse_port_sockets) {
+        (Some(_), Some(count)) if count.get() > 1 => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "`multicast` can't be used along with more than one of `reuse_port_sockets`, as every socket would receive each multicast datagram",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Binds the UDP sockets of a listener, joining the multicast groups on each of them.
+///
+/// With `reuse_port_sockets`, that many sockets are bound to the address with `SO_REUSEPORT`, so
+/// the system balances the datagrams between them, and they can be received in parallel.
+pub fn bind_listeners(
+    address: SocketAddr,
+    receive_buffer_bytes: Option<usize>,
+    multicast: Option<&MulticastConfig>,
+    reuse_port_sockets: Option<NonZeroUsize>,
+) -> io::Result<Vec<UdpSocket>> {
+    validate_listener(multicast, reuse_port_sockets)?;
+    let count = reuse_port_sockets.map_or(1, NonZeroUsize::get);
+    (0..count)
+        .map(|_| {
+            let socket = Socket::new(
+                Domain::for_address(address),
+                Type::DGRAM,
+                Some(Protocol::UDP),
+            )?;
+            if multicast.is_some() {
+                // Lets other listeners join the same groups on the same port.
+                socket.set_reuse_address(true)?;
+            }
+            if reuse_port_sockets.is_some() {
+                set_reuse_port(&socket)?;
+            }
+            if let Some(size) = receive_buffer_bytes {
+                if let Err(error) = socket.set_recv_buffer_size(size) {
+                    warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+                }
+            }
+            socket.set_nonblocking(true)?;
+            socket.bind(&address.into())?;
+            if let Some(multicast) = multicast {
+                join_multicast_groups(&socket, multicast)?;
+            }
+            UdpSocket::from_std(socket.into())
+        })
+        .collect()
+}
+
+fn join_multicast_groups(socket: &Socket, multicast: &MulticastConfig) -> io::Result<()> {
+    for group in &multicast.groups {
+        match (group, multicast.interface) {
+            (IpAddr::V4(group), None) => socket.join_multicast_v4(group, &Ipv4Addr::UNSPECIFIED)?,
+            (IpAddr::V4(group), Some(MulticastInterface::Address(interface))) => {
+                socket.join_multicast_v4(group, &interface)?
+            }
+            (IpAddr::V6(group), None) => socket.join_multicast_v6(group, 0)?,
+            (IpAddr::V6(group), Some(MulticastInterface::Index(index))) => {
+                socket.join_multicast_v6(group, index)?
+            }
+            (group, Some(interface)) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "interface {:?} can't be used to join the multicast group {}, IPv4 groups need an interface address and IPv6 groups an interface index",
+                        interface, group
+                    ),
+                ))
+            }
+        }
+        info!(message = "Joined multicast group.", %group);
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_reuse_port(socket: &Socket) -> io::Result<()> {
+    socket.set_reuse_port(true)
+}
+
+#[cfg(not(unix))]
+fn set_reuse_port(_socket: &Socket) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "`reuse_port_sockets` is only supported on Unix",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
+
+    use super::*;
+    use crate::test_util::next_addr;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn binds_reuse_port_sockets() {
+        let address = next_addr();
+        let sockets =
+            bind_listeners(address, None, None, NonZeroUsize::new(4)).expect("sockets bound");
+        assert_eq!(sockets.len(), 4);
+        for socket in sockets {
+            assert_eq!(socket.local_addr().unwrap(), address);
+        }
+    }
+
+    #[test]
+    fn rejects_multicast_with_reuse_port_sockets() {
+        let multicast = MulticastConfig {
+            groups: vec![IpAddr::V4(Ipv4Addr::new(239, 0, 0, 1))],
+            interface: None,
+        };
+        assert!(validate_listener(Some(&multicast), NonZeroUsize::new(1)).is_ok());
+        let error = validate_listener(Some(&multicast), NonZeroUsize::new(4)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn rejects_mismatched_multicast_interface() {
+        let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP)).unwrap();
+        let multicast = MulticastConfig {
+            groups: vec![IpAddr::V6(Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0x1234))],
+            interface: Some(MulticastInterface::Address(Ipv4Addr::LOCALHOST)),
+        };
+        let error = join_multicast_groups(&socket, &multicast).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
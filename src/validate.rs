@@ -4,8 +4,10 @@ use clap::Parser;
 use colored::*;
 use exitcode::ExitCode;
 
+use url::Url;
+
 use crate::{
-    config::{self, Config, ConfigDiff},
+    config::{self, Config, ConfigDiff, DiffSummary},
     topology::{self, builder::Pieces},
 };
 
@@ -69,6 +71,15 @@ pub struct Opts {
         use_value_delimiter(true)
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Compare the validated config against a previous one and list the added, removed
+    /// and changed components.
+    ///
+    /// Either a path to the previous config file or directory, or the URL of the GraphQL API
+    /// of a running Vector instance, e.g. `http://localhost:8686/graphql`. When comparing
+    /// against a running instance only the component types can be compared.
+    #[clap(long, value_name = "PATH_OR_URL")]
+    pub diff_against: Option<String>,
 }
 
 impl Opts {
@@ -95,11 +106,15 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
 
     let mut validated = true;
 
-    let mut config = match validate_config(opts, &mut fmt) {
-        Some(config) => config,
+    let (mut config, secrets) = match load_config(opts, &mut fmt) {
+        Some(loaded) => loaded,
         None => return exitcode::CONFIG,
     };
 
+    if let Some(target) = &opts.diff_against {
+        validated &= validate_diff(target, &config, secrets, &mut fmt).await;
+    }
+
     if !opts.no_environment {
         if let Some(tmp_directory) = create_tmp_directory(&mut config, &mut fmt) {
             validated &= validate_environment(opts, &config, &mut fmt).await;
@@ -118,6 +133,12 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
 }
 
 pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
+    load_config(opts, fmt).map(|(config, _)| config)
+}
+
+/// Loads and builds the config like `validate_config`, also returning the secrets interpolated
+/// into it.
+fn load_config(opts: &Opts, fmt: &mut Formatter) -> Option<(Config, HashMap<String, String>)> {
     // Prepare paths
     let paths = opts.paths_with_formats();
     let paths = if let Some(paths) = config::process_paths(&paths) {
@@ -137,7 +158,7 @@ pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
     config::init_log_schema(&paths, true)
         .map_err(&mut report_error)
         .ok()?;
-    let (builder, load_warnings) = config::load_builder_from_paths(&paths)
+    let (builder, secrets, load_warnings) = config::load_builder_and_secrets_from_paths(&paths)
         .map_err(&mut report_error)
         .ok()?;
 
@@ -164,7 +185,128 @@ pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
         fmt.success(format!("Loaded {:?}", &paths_list));
     }
 
-    Some(config)
+    Some((config, secrets))
+}
+
+/// Lists the changes between the config being validated and the one `target` points to. The
+/// values of changed fields are printed with the secrets of either config redacted.
+async fn validate_diff(
+    target: &str,
+    config: &Config,
+    mut secrets: HashMap<String, String>,
+    fmt: &mut Formatter,
+) -> bool {
+    let summary = match Url::parse(target) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => running_diff(url, config).await,
+        _ => file_diff(target, config, &mut secrets),
+    };
+
+    match summary {
+        Ok(mut summary) => {
+            summary.redact(&secrets);
+            fmt.title(format!("Changes against {:?}", target));
+            fmt.diff(&summary);
+            true
+        }
+        Err(errors) => {
+            fmt.title(format!("Failed to diff against {:?}", target));
+            fmt.sub_error(errors);
+            false
+        }
+    }
+}
+
+/// Loads the config at `target` to diff against, adding the secrets interpolated into it to
+/// `secrets`.
+fn file_diff(
+    target: &str,
+    config: &Config,
+    secrets: &mut HashMap<String, String>,
+) -> Result<DiffSummary, Vec<String>> {
+    let path = PathBuf::from(target);
+    let path = if path.is_dir() {
+        config::ConfigPath::Dir(path)
+    } else {
+        config::ConfigPath::File(path, None)
+    };
+    let (builder, old_secrets, _) = config::load_builder_and_secrets_from_paths(&[path])?;
+    let old = builder.build()?;
+    // Both configs may retrieve the same secret with different values, so they're kept apart.
+    secrets.extend(
+        old_secrets
+            .into_iter()
+            .map(|(key, secret)| (format!("{}:{}", target, key), secret)),
+    );
+
+    DiffSummary::new(&old, config).map_err(|error| vec![error.to_string()])
+}
+
+/// The API only exposes the identity of the running components, so the diff is limited to
+/// their types.
+#[cfg(feature = "api-client")]
+async fn running_diff(url: Url, config: &Config) -> Result<DiffSummary, Vec<String>> {
+    use serde_json::json;
+    use vector_api_client::{gql::ComponentsQueryExt, Client};
+
+    use crate::config::{ComponentChanges, ComponentKey};
+
+    let components = Client::new(url)
+        .components_query(i16::max_value() as i64)
+        .await
+        .map_err(|error| vec![error.to_string()])?
+        .data
+        .ok_or_else(|| vec!["The API returned no components.".to_owned()])?
+        .components
+        .edges
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|edge| edge.node)
+        .collect::<Vec<_>>();
+
+    let running = |kind: &str| {
+        components
+            .iter()
+            .filter(|node| node.on.to_string() == kind)
+            .map(|node| {
+                (
+                    ComponentKey::from(node.component_id.as_str()),
+                    json!({ "type": node.component_type }),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let typed =
+        |key: &ComponentKey, component_type: &str| (key.clone(), json!({ "type": component_type }));
+
+    Ok(DiffSummary {
+        sources: ComponentChanges::from_values(
+            running("source"),
+            config
+                .sources()
+                .map(|(key, source)| typed(key, source.inner.source_type())),
+        ),
+        transforms: ComponentChanges::from_values(
+            running("transform"),
+            config
+                .transforms()
+                .map(|(key, transform)| typed(key, transform.inner.transform_type())),
+        ),
+        sinks: ComponentChanges::from_values(
+            running("sink"),
+            config
+                .sinks()
+                .map(|(key, sink)| typed(key, sink.inner.sink_type())),
+        ),
+        enrichment_tables: ComponentChanges::default(),
+    })
+}
+
+#[cfg(not(feature = "api-client"))]
+async fn running_diff(_url: Url, _config: &Config) -> Result<DiffSummary, Vec<String>> {
+    Err(vec![
+        "Diffing against a running instance requires the `api-client` feature.".to_owned(),
+    ])
 }
 
 async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter) -> bool {
@@ -279,6 +421,8 @@ pub struct Formatter {
     error_intro: String,
     warning_intro: String,
     success_intro: String,
+    added_intro: String,
+    removed_intro: String,
 }
 
 impl Formatter {
@@ -301,6 +445,16 @@ impl Formatter {
             } else {
                 "√".to_owned()
             },
+            added_intro: if color {
+                "+".green().to_string()
+            } else {
+                "+".to_owned()
+            },
+            removed_intro: if color {
+                "-".red().to_string()
+            } else {
+                "-".to_owned()
+            },
             color,
         }
     }
@@ -358,6 +512,30 @@ impl Formatter {
         ))
     }
 
+    /// Added, removed and changed components that go with a title. Changed components
+    /// are followed by their changed fields.
+    fn diff(&mut self, summary: &DiffSummary) {
+        if summary.is_empty() {
+            self.print("No changes\n");
+        }
+
+        for (kind, changes) in summary.kinds() {
+            for key in &changes.added {
+                self.print(format!("{} {} {:?}\n", self.added_intro, kind, key.id()));
+            }
+            for key in &changes.removed {
+                self.print(format!("{} {} {:?}\n", self.removed_intro, kind, key.id()));
+            }
+            for (key, fields) in &changes.changed {
+                self.print(format!("{} {} {:?}\n", self.warning_intro, kind, key.id()));
+                for field in fields {
+                    self.print(format!("    {}\n", field));
+                }
+            }
+        }
+        self.space();
+    }
+
     /// A list of warnings that go with a title.
     fn sub_warning<I: IntoIterator>(&mut self, warnings: I)
     where
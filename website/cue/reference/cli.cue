@@ -202,6 +202,68 @@ cli: {
 			}
 		}
 
+		"file-fingerprints": {
+			description: """
+				Show the fingerprints the `file` source identifies the files matching
+				glob patterns by, and the checkpoints they map to, then exit
+				"""
+
+			example: "vector file-fingerprints --include '/var/log/*.csv' --data-dir /var/lib/vector/my_file_source --strategy device_and_inode_checksum"
+
+			flags: _default_flags & {
+				"json": {
+					description: "Output the files as JSON lines"
+				}
+			}
+
+			options: {
+				"include": {
+					description: "Glob patterns of the files to fingerprint (comma-separated), as in the `include` option of the `file` source"
+					type:        "list"
+				}
+				"exclude": {
+					description: "Glob patterns of the files to leave out (comma-separated), as in the `exclude` option of the `file` source"
+					type:        "list"
+					default:     ""
+				}
+				"data-dir": {
+					description: "The directory the `file` source checkpoints the files in, which is its `data_dir`, or the global one, followed by the ID of the source. The checkpoints aren't shown when not set"
+					type:        "string"
+					default:     ""
+				}
+				"strategy": {
+					description: "The strategy the files are fingerprinted with, as in the `fingerprint.strategy` option of the `file` source"
+					type:        "enum"
+					default:     "checksum"
+					enum: {
+						checksum:                  "Checksum the first lines of the files"
+						device_and_inode:          "Use the device and inode of the files"
+						device_and_inode_checksum: "Use the device and inode of the files, with a checksum of their first bytes"
+					}
+				}
+				"bytes": {
+					description: "The number of bytes to checksum with the `device_and_inode_checksum` strategy"
+					type:        "integer"
+					default:     256
+				}
+				"ignored-header-bytes": {
+					description: "The number of bytes to skip before the bytes or lines to checksum"
+					type:        "integer"
+					default:     0
+				}
+				"lines": {
+					description: "The number of lines to checksum with the `checksum` strategy"
+					type:        "integer"
+					default:     1
+				}
+				"max-line-bytes": {
+					description: "The maximum number of bytes of the lines to checksum, as in the `max_line_bytes` option of the `file` source"
+					type:        "integer"
+					default:     102400
+				}
+			}
+		}
+
 		"help": {
 			description: "Prints this message or the help of the given subcommand(s)"
 		}
@@ -409,6 +471,15 @@ cli: {
 						"""
 					type: "string"
 				}
+				"diff-against": {
+					description: """
+						A previous config file or directory, or the GraphQL API URL of a running
+						Vector instance, to compare against. Added, removed, and changed components
+						are listed along with the fields that changed. Only component types can
+						be compared against a running instance.
+						"""
+					type: "string"
+				}
 			}
 
 			args: {
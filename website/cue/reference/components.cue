@@ -264,6 +264,11 @@ components: {
 		relevant_when?: string
 	}
 
+	#FeaturesUdpListeners: {
+		enabled:        bool
+		relevant_when?: string
+	}
+
 	#FeaturesKeepalive: {
 		enabled: bool
 	}
@@ -307,6 +312,8 @@ components: {
 
 		receive_buffer_bytes?: #FeaturesReceiveBufferBytes
 
+		udp_listeners?: #FeaturesUdpListeners
+
 		tls: #FeaturesTLS & {_args: {mode: "accept"}}
 	}
 
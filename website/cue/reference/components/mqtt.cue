@@ -0,0 +1,132 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: {
+		host: {
+			description: "The host name or IP address of the MQTT broker."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1", "broker.example.com"]
+			}
+		}
+		port: {
+			common:      true
+			description: "The port of the MQTT broker, usually `8883` with TLS."
+			required:    false
+			type: uint: {
+				default: 1883
+				unit:    null
+			}
+		}
+		client_id: {
+			common:      false
+			description: "The client identifier, which must be unique among the clients of the broker as it identifies the session."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-edge-1"]
+			}
+		}
+		protocol: {
+			common:      false
+			description: "The version of the MQTT protocol spoken with the broker."
+			required:    false
+			type: string: {
+				default: "3.1.1"
+				enum: {
+					"3.1.1": "MQTT 3.1.1"
+					"5":     "MQTT 5"
+				}
+			}
+		}
+		keep_alive_secs: {
+			common:      false
+			description: "The interval of the keep alive pings sent to the broker, which must be at least 5 seconds."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		user: {
+			common:      false
+			description: "The user name to authenticate with."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector"]
+			}
+		}
+		password: {
+			common:      false
+			description: "The password to authenticate with, along with `user`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${MQTT_PASSWORD}"]
+			}
+		}
+		qos: {
+			common:      false
+			description: "The quality of service level of the messages."
+			required:    false
+			type: uint: {
+				default: 1
+				enum: {
+					"0": "At most once: the messages are sent without being acknowledged."
+					"1": "At least once: the messages are acknowledged, and sent again until they are."
+				}
+				unit: null
+			}
+		}
+	}
+
+	how_it_works: {
+		qos: {
+			title: "Quality of service"
+			body: """
+				Messages are exchanged with QoS 0 (at most once) or QoS 1 (at least once). QoS 2 (exactly
+				once) isn't supported.
+				"""
+		}
+	}
+}
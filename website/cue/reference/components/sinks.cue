@@ -181,6 +181,9 @@ components: sinks: [Name=string]: {
 									let batched = features.send.encoding.codec.batched
 									enum: {
 										for codec in features.send.encoding.codec.enum {
+											if codec == "gelf" {
+												gelf: "[GELF](\(urls.gelf)) 1.1 encoded event, whose fields which aren't part of the specification are sent as additional fields."
+											}
 											if codec == "text" {
 												if batched {
 													text: "Newline delimited list of messages generated from the message key from each event."
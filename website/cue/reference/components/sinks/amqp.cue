@@ -0,0 +1,89 @@
+package metadata
+
+components: sinks: amqp: {
+	title: "AMQP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text", "ndjson"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: components._amqp.features.send.to
+		}
+	}
+
+	support: components._amqp.support
+
+	configuration: components._amqp.configuration & {
+		exchange: {
+			description: "The exchange to publish the messages to, the empty string being the default exchange."
+			required:    true
+			type: string: {
+				examples: ["logs", "{{ application }}"]
+				syntax: "template"
+			}
+		}
+		routing_key: {
+			common:      true
+			description: "The routing key of the messages."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["{{ service }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		publisher_confirms: {
+			title: "Publisher confirms"
+			body: """
+				The channel is put in confirm mode, and the events are acknowledged once the broker
+				confirmed their messages. The messages refused by the broker are reported as errors.
+				"""
+		}
+		reconnections: {
+			title: "Reconnections"
+			body: """
+				The sink connects to the broker once it publishes its first message, and connects
+				again, with an exponential backoff, when the connection or its channel is lost.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:      components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:  components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total: components.sources.internal_metrics.output.metrics.component_sent_events_total
+	}
+}
@@ -297,6 +297,18 @@ components: sinks: elasticsearch: {
 				}
 			}
 		}
+		request_retry_partial: {
+			common: false
+			description: """
+				Whether to retry only the documents of a bulk request that failed with a retriable
+				status (`429` or `5xx`), instead of giving up on them. The documents are re-sent on
+				their own, with the backoff of the `request` options, at most 5 times or
+				`request.retry_attempts` times if lower. This requires keeping a copy of every event in
+				memory until its request completes.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		suppress_type_name: {
 			common: false
 			description: """
@@ -345,6 +357,12 @@ components: sinks: elasticsearch: {
 				due to Elasticsearch index mapping errors, where data keys aren't consistently
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
+
+				Vector inspects the result of every item in the bulk response. Documents that were
+				permanently refused are marked as rejected, each with its own reason, while the rest
+				of the request is acknowledged as delivered. Documents that were refused for a
+				transient reason are re-sent on their own when `request_retry_partial` is enabled,
+				and marked as errored otherwise.
 				"""
 		}
 
@@ -0,0 +1,78 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text", "ndjson"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: components._mqtt.features.send.to
+		}
+	}
+
+	support: components._mqtt.support
+
+	configuration: components._mqtt.configuration & {
+		topic: {
+			description: "The topic to publish the messages to, which can't contain wildcards."
+			required:    true
+			type: string: {
+				examples: ["vector", "sensors/{{ host }}"]
+				syntax: "template"
+			}
+		}
+		retain: {
+			common:      false
+			description: "Whether the broker keeps the last message of the topic, delivering it to the future subscribers."
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: components._mqtt.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				The events are acknowledged once their messages are sent to the broker with QoS 0, or
+				once the broker acknowledged them with QoS 1.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:      components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:  components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total: components.sources.internal_metrics.output.metrics.component_sent_events_total
+	}
+}
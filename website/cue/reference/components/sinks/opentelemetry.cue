@@ -0,0 +1,179 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs, metrics and traces to receivers of the OpenTelemetry protocol (OTLP), such as
+		the OpenTelemetry Collector.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			proxy: enabled:    true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	configuration: {
+		endpoint: {
+			description: """
+				The endpoint of the OTLP receiver. With the `http` protocol, the signals are exported to the
+				`/v1/logs`, `/v1/metrics` and `/v1/traces` paths below it.
+				"""
+			required: true
+			type: string: {
+				examples: ["http://localhost:4317", "https://otlp.example.com:4318"]
+			}
+		}
+		protocol: {
+			description: "The transport used to export the data."
+			common:      true
+			required:    false
+			type: string: {
+				default: "grpc"
+				enum: {
+					grpc: "Export with unary gRPC calls."
+					http: "Export with HTTP POST requests of binary Protobuf payloads."
+				}
+			}
+		}
+		headers: {
+			description: "Headers sent along with each request, as gRPC metadata or HTTP headers."
+			common:      false
+			required:    false
+			type: object: {
+				examples: [{"authorization": "Bearer ${OTLP_TOKEN}"}]
+				options: {
+					"*": {
+						description: "A header."
+						required:    true
+						type: string: {}
+					}
+				}
+			}
+		}
+		resource_tags: {
+			description: """
+				The metric tags describing the resource the metrics originate from, such as `service.name`.
+				They are exported as resource attributes rather than data point attributes.
+				"""
+			common:   false
+			required: false
+			type: array: {
+				default: []
+				items: type: string: examples: ["service.name", "host.name"]
+			}
+		}
+		buckets: {
+			common:      false
+			description: "Buckets to use for aggregating [distribution](\(urls.vector_metric)/#distribution) metrics into histograms."
+			required:    false
+			type: array: {
+				default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+				items: type: float: examples: [0.005, 0.01]
+			}
+		}
+		quantiles: {
+			common:      false
+			description: "Quantiles to use for exporting sketches as summaries."
+			required:    false
+			type: array: {
+				default: [0.5, 0.75, 0.9, 0.95, 0.99]
+				items: type: float: examples: [0.5, 0.75, 0.9, 0.95, 0.99]
+			}
+		}
+	}
+
+	how_it_works: {
+		mapping: {
+			title: "Mapping of events"
+			body: """
+				Events are mapped back to OTLP the same way the `opentelemetry` source maps OTLP to events.
+				For logs, the `message` field becomes the body and fields other than the OTLP ones are
+				exported as attributes. Incremental metrics are exported with the delta temporality and
+				absolute ones with the cumulative temporality, sets as gauges of their size and
+				distributions as histograms.
+				"""
+		}
+		partial_success: {
+			title: "Partial success"
+			body: """
+				Receivers can accept an export request while rejecting some of its items. These
+				rejections are logged and counted as errors, but the request is not retried, as
+				recommended by the specification. As the response doesn't identify the rejected items,
+				all the events of the request are marked as rejected for end-to-end acknowledgements.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
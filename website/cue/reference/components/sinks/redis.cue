@@ -73,13 +73,14 @@ components: sinks: redis: {
 		}
 		data_type: {
 			common:      false
-			description: "The Redis data type (`list` or `channel`) to use."
+			description: "The Redis data type (`list`, `channel` or `stream`) to use."
 			required:    false
 			type: string: {
 				default: "list"
 				enum: {
 					list:    "Use the Redis `list` data type."
 					channel: "Use the Redis `channel` data type."
+					stream:  "Use the Redis `stream` data type, adding the messages with `XADD`."
 				}
 			}
 		}
@@ -105,6 +106,34 @@ components: sinks: redis: {
 				}
 			}
 		}
+		stream: {
+			common:      false
+			description: "Options for the Redis `stream` data type."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					field: {
+						common:      false
+						description: "The field of the stream entries holding the encoded event."
+						required:    false
+						type: string: {
+							default: "message"
+						}
+					}
+					maxlen: {
+						common:      false
+						description: "When set, the stream is trimmed to approximately this many entries as the messages are added."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [100000]
+							unit: "events"
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
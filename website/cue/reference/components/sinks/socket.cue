@@ -21,7 +21,7 @@ components: sinks: socket: {
 				enabled: true
 				codec: {
 					enabled: true
-					enum: ["json", "text"]
+					enum: ["gelf", "json", "text"]
 				}
 			}
 			send_buffer_bytes: {
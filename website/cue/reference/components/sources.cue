@@ -86,6 +86,7 @@ components: sources: [Name=string]: {
 								enum: {
 									bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (e.g. split between messages or stream segments)."
 									character_delimited: "Byte frames which are delimited by a chosen character."
+									chunked_gelf:        "Byte frames which are [GELF](\(urls.gelf)) messages received over UDP, reassembled from their chunks and decompressed with gzip or zlib when needed."
 									length_delimited:    "Byte frames whose length is encoded in a header."
 									newline_delimited:   "Byte frames which are delimited by a newline character."
 									octet_counting:      "Byte frames according to the [octet counting](\(urls.rfc_6587_3_4_1)) format."
@@ -116,6 +117,33 @@ components: sources: [Name=string]: {
 								}
 							}
 						}
+						chunked_gelf: {
+							description:   "Options for `chunked_gelf` framing."
+							required:      false
+							common:        false
+							relevant_when: "method = `chunked_gelf`"
+							type: object: options: {
+								timeout_secs: {
+									description: "The number of seconds the chunks of a message are kept while waiting for its missing chunks. The incomplete messages are discarded afterwards. At most 1000 incomplete messages are kept at once, the chunks of any further messages are discarded."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 5
+										unit:    "seconds"
+									}
+								}
+								max_length: {
+									description: "The maximum length of a message, once reassembled and decompressed. Any messages longer than `max_length` bytes will be discarded entirely."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 8388608
+										examples: [65535, 102400]
+										unit: "bytes"
+									}
+								}
+							}
+						}
 						newline_delimited: {
 							description:   "Options for `newline_delimited` framing."
 							required:      false
@@ -167,6 +195,7 @@ components: sources: [Name=string]: {
 								default: "bytes"
 								enum: {
 									bytes:  "Events containing the byte frame as-is."
+									gelf:   "Events being parsed from a [GELF](\(urls.gelf)) 1.1 message."
 									json:   "Events being parsed from a JSON string."
 									syslog: "Events being parsed from a Syslog message."
 								}
@@ -233,6 +262,51 @@ components: sources: [Name=string]: {
 				}
 			}
 
+			if features.receive.udp_listeners != _|_ {
+				multicast: {
+					common:      false
+					description: "The multicast groups to join on the UDP socket."
+					required:    false
+					type: object: {
+						examples: []
+						options: {
+							groups: {
+								description: "The IPv4 or IPv6 addresses of the multicast groups."
+								required:    true
+								type: array: items: type: string: {
+									examples: ["239.1.2.3", "ff02::1234"]
+								}
+							}
+							interface: {
+								common:      false
+								description: "The interface to join the groups on: an IPv4 address of the interface for IPv4 groups, or the integer index of the interface for IPv6 groups. The system chooses the interface by default."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["192.168.1.10"]
+								}
+							}
+						}
+					}
+					if features.receive.udp_listeners.relevant_when != _|_ {
+						relevant_when: features.receive.udp_listeners.relevant_when
+					}
+				}
+				reuse_port_sockets: {
+					common:      false
+					description: "Opens this many UDP sockets bound to the same address with the `SO_REUSEPORT` option, each received from in its own task, for the system to balance the datagrams between them and process them on several cores. Only supported on Unix, and can't be more than one along with `multicast`, as every socket would receive each multicast datagram."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [4]
+						unit: null
+					}
+					if features.receive.udp_listeners.relevant_when != _|_ {
+						relevant_when: features.receive.udp_listeners.relevant_when
+					}
+				}
+			}
+
 			if features.receive.keepalive != _|_ {
 				keepalive: {
 					common:      false
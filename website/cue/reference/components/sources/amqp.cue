@@ -0,0 +1,165 @@
+package metadata
+
+components: sources: amqp: {
+	title: "AMQP"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			from: components._amqp.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._amqp.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: components._amqp.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		queue: {
+			description: "The queue to consume the messages from."
+			required:    true
+			type: string: {
+				examples: ["vector"]
+			}
+		}
+		consumer: {
+			common:      false
+			description: "The consumer tag, identifying the consumer on the channel."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["vector-edge-1"]
+			}
+		}
+		prefetch_count: {
+			common:      false
+			description: "The maximum number of messages delivered by the broker before they are acknowledged. Unlimited by default."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [100]
+				unit: "events"
+			}
+		}
+		requeue_errored: {
+			common:      false
+			description: "Whether the messages whose events failed to be delivered by the sinks are requeued, to be delivered again by the broker, rather than rejected."
+			required:    false
+			type: bool: default: false
+		}
+		exchange_key: {
+			common:      false
+			description: "The log field name to use for the exchange the message was published to."
+			required:    false
+			type: string: {
+				default: "exchange"
+			}
+		}
+		routing_key_field: {
+			common:      false
+			description: "The log field name to use for the routing key of the message."
+			required:    false
+			type: string: {
+				default: "routing"
+			}
+		}
+		offset_key: {
+			common:      false
+			description: "The log field name to use for the delivery tag of the message."
+			required:    false
+			type: string: {
+				default: "offset"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual AMQP message"
+		fields: {
+			message: {
+				description: "The payload of the AMQP message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			exchange: {
+				description: "The exchange the message was published to, empty for the default exchange."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+				}
+			}
+			routing: {
+				description: "The routing key of the message."
+				required:    true
+				type: string: {
+					examples: ["nginx"]
+				}
+			}
+			offset: {
+				description: "The delivery tag of the message on the channel."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["amqp"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The `timestamp` property of the message, or the time it was received."
+			}
+		}
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				With acknowledgements enabled, a message is acknowledged once its events are delivered
+				by the sinks. It's rejected when a sink fails to deliver or refuses them, letting the
+				broker dead-letter it if the queue is configured to, unless `requeue_errored` is set,
+				requeueing the messages that failed to be delivered. Otherwise, the messages are
+				acknowledged by the broker as soon as they are delivered.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
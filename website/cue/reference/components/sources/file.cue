@@ -28,6 +28,10 @@ components: sources: file: {
 		}
 		multiline: enabled: true
 		encoding: enabled:  true
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 	}
 
 	support: {
@@ -82,15 +86,29 @@ components: sources: file: {
 					type: string: {
 						default: "checksum"
 						enum: {
-							checksum:         "Read first N lines of the file, skipping the first `ignored_header_bytes` bytes, to uniquely identify files via a checksum."
-							device_and_inode: "Uses the [device and inode](\(urls.inode)) to unique identify files."
+							checksum:                  "Read first N lines of the file, skipping the first `ignored_header_bytes` bytes, to uniquely identify files via a checksum."
+							device_and_inode:          "Uses the [device and inode](\(urls.inode)) to unique identify files."
+							device_and_inode_checksum: "Uses the [device and inode](\(urls.inode)) along with a checksum of the first `bytes` bytes, skipping the first `ignored_header_bytes` bytes, to uniquely identify files. The files are read again from the beginning when truncated."
 						}
 					}
 				}
+				bytes: {
+					common: false
+					description: """
+						The number of bytes to read when generating a unique fingerprint.
+						If the file has less than this amount of bytes then it won't be read at all.
+						"""
+					relevant_when: "strategy = \"device_and_inode_checksum\""
+					required:      false
+					type: uint: {
+						default: 256
+						unit:    "bytes"
+					}
+				}
 				ignored_header_bytes: {
 					common:        false
 					description:   "The number of bytes to skip ahead (or ignore) when generating a unique fingerprint. This is helpful if all files share a common header."
-					relevant_when: "strategy = \"checksum\""
+					relevant_when: "strategy = \"checksum\" or strategy = \"device_and_inode_checksum\""
 					required:      false
 					type: uint: {
 						default: 0
@@ -172,7 +190,7 @@ components: sources: file: {
 		}
 		max_line_bytes: {
 			common:      false
-			description: "The maximum number of a bytes a line can contain before being discarded. This protects against malformed lines or tailing incorrect files."
+			description: "The maximum number of a bytes a line, or a frame when the `framing` option is set, can contain before being discarded. This protects against malformed lines or tailing incorrect files."
 			required:    false
 			type: uint: {
 				default: 102_400
@@ -287,10 +305,12 @@ components: sources: file: {
 			title: "Compressed Files"
 			body: """
 				Vector will transparently detect files which have been compressed
-				using Gzip and decompress them for reading. This detection process
-				looks for the unique sequence of bytes in the Gzip header and does
-				not rely on the compressed files adhering to any kind of naming
-				convention.
+				using Gzip, Zstandard, Bzip2 or XZ and decompress them for reading.
+				This detection process looks for the unique sequence of bytes in
+				the header of each format and does not rely on the compressed files
+				adhering to any kind of naming convention, such as the `.gz`,
+				`.zst`, `.bz2` or `.xz` extensions `logrotate` gives the rotated
+				files.
 
 				One caveat with reading compressed files is that Vector is not able
 				to efficiently seek into them. Rather than implement a
@@ -300,6 +320,11 @@ components: sources: file: {
 				this reason, users should take care to allow Vector to fully
 				process any compressed files before shutting the process down or moving the
 				files to another location on disk.
+
+				Compressed files modified before the `ignore_older_secs` threshold
+				are not read at all, and neither are compressed files found while
+				`read_from` is `end`, since their end can't be found without
+				decompressing them.
 				"""
 		}
 
@@ -388,6 +413,19 @@ components: sources: file: {
 				This strategy avoids the common pitfalls associated with using device and inode
 				names since inode names can be reused across files. This enables Vector to properly
 				tail files across various rotation strategies.
+
+				Files sharing their first lines, like CSV exports with the same header, can't be
+				told apart by their checksum, while files rotated by being copied then truncated
+				keep their device and inode. The `device_and_inode_checksum` strategy combines the
+				device and inode of the files with a checksum of their first bytes, and reads a file
+				again from the beginning once its size shrinks below the position read up to. When
+				the first bytes written after the truncation differ, like another header, the file
+				keeps being read under its new fingerprint, so that its lines aren't read twice.
+				The truncation is only detected while the file is smaller than the position read
+				up to, so the lines written faster than the file is read may be skipped.
+
+				The `vector file-fingerprints` command shows the fingerprint of each file matching
+				glob patterns, and the checkpoint it maps to, to debug how files are identified.
 				"""
 		}
 
@@ -409,6 +447,28 @@ components: sources: file: {
 				"""
 		}
 
+		framing: {
+			title: "Framing"
+			body: """
+				When the `framing` option is set, the files are split into the frames
+				of its method, like `character_delimited` or `length_delimited` for
+				binary files, instead of lines ending with the `line_delimiter`. The
+				frames are then decoded into events with the `decoding` option, so
+				that files of JSON lines can be read directly with the `json` codec.
+
+				The checkpoint of a file is only advanced past whole frames, so that
+				the frame being written when Vector restarts is read from its start.
+				As binary files may not contain any line to be fingerprinted with
+				the `checksum` strategy, the `device_and_inode` strategy should be
+				used for them.
+
+				The frames longer than `max_line_bytes` are discarded, unless the
+				framing sets a `max_length` of its own. The bytes of these frames,
+				and those which can't be framed, are skipped up to the next
+				`line_delimiter`, where the frames are resumed from.
+				"""
+		}
+
 		multiline_messages: {
 			title: "Multiline Messages"
 			body: """
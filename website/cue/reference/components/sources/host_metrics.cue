@@ -68,7 +68,7 @@ components: sources: host_metrics: {
 
 	configuration: {
 		collectors: {
-			description: "The list of host metric collector services to use. Defaults to all collectors."
+			description: "The list of host metric collector services to use. Defaults to all collectors except `process`."
 			common:      true
 			required:    false
 			type: array: {
@@ -83,6 +83,7 @@ components: sources: host_metrics: {
 						host:       "Metrics related to host"
 						memory:     "Metrics related to memory utilization."
 						network:    "Metrics related to network utilization."
+						process:    "Metrics related to each process (Linux only)."
 					}
 				}
 			}
@@ -401,6 +402,100 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		process: {
+			common: false
+			description: #"""
+				Options for the "process" metrics collector.
+
+				Note: this collector is only available on Linux systems, and has to be listed in `collectors` to be used.
+				Vector needs the privileges to read the open file descriptors and I/O statistics of the processes of other users.
+				"""#
+			required: false
+			type: object: options: {
+				names: {
+					common:      false
+					required:    false
+					description: "Lists of process name patterns to include or exclude."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["vector", "postgres*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["kworker/*"]
+								}
+							}
+						}
+					}
+				}
+				cmdlines: {
+					common:      false
+					required:    false
+					description: "Lists of process command line patterns to include or exclude. The command line is the arguments of the process joined with spaces."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process command line patterns for which to gather metrics.
+
+								Defaults to including all processes. Kernel threads have no command line, so they are never included once this is set.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["java *-jar /opt/app/*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process command line patterns for which to gather metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["*--debug*"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: metrics: {
@@ -494,6 +589,14 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_cpu_seconds_total:      _host & _process_counter & {description: "The number of CPU seconds used by this process in user or system mode."}
+		process_io_read_bytes_total:    _host & _process_counter & {description: "The number of bytes this process read from storage."}
+		process_io_written_bytes_total: _host & _process_counter & {description: "The number of bytes this process wrote to storage."}
+		process_memory_rss_bytes:       _host & _process_gauge & {description:   "The number of bytes of main memory this process holds, its resident set size."}
+		process_open_fds:               _host & _process_gauge & {description:   "The number of file descriptors this process has open."}
+		process_threads:                _host & _process_gauge & {description:   "The number of threads of this process."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -583,6 +686,34 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_counter: {
+			type: "counter"
+			tags: _process_tags
+			relevant_when: "OS is Linux"
+		}
+		_process_gauge: {
+			type: "gauge"
+			tags: _process_tags
+			relevant_when: "OS is Linux"
+		}
+		_process_tags: _host_metrics_tags & {
+			collector: examples: ["process"]
+			pid: {
+				description: "The ID of the process."
+				required:    true
+				examples: ["1", "4242"]
+			}
+			name: {
+				description: "The name of the process."
+				required:    true
+				examples: ["vector", "systemd"]
+			}
+			mode: {
+				description: "Which mode the process was running in during the given time, only for `process_cpu_seconds_total`."
+				required:    false
+				examples: ["user", "system"]
+			}
+		}
 	}
 
 	telemetry: metrics: {
@@ -119,6 +119,68 @@ components: sources: http: {
 			required: false
 			type: bool: default: true
 		}
+		routes: {
+			common:      false
+			description: """
+				Serves several paths on the same address, each with its own decoding, authentication, accepted methods
+				and output. The routes are matched in order. When set, `path` and `strict_path` are ignored, and
+				`framing`, `decoding` and `auth` are the defaults of the routes that don't set their own.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: [{path: "/github", decoding: {codec: "json"}, output: "github"}]
+					options: {
+						path: {
+							description: "The URL path of the route."
+							required:    true
+							type: string: examples: ["/github", "/pagerduty"]
+						}
+						strict_path: {
+							description: "If set to `false`, requests sent to a URL path that starts with `path` are accepted too."
+							common:      false
+							required:    false
+							type: bool: default: true
+						}
+						methods: {
+							description: "The HTTP methods accepted by the route. Other methods are answered with a 405 status."
+							common:      false
+							required:    false
+							type: array: {
+								default: ["POST"]
+								items: type: string: examples: ["POST", "PUT"]
+							}
+						}
+						framing: {
+							description: "The framing of the requests of the route. See the source-wide `framing` option."
+							common:      false
+							required:    false
+							type: object: options: {}
+						}
+						decoding: {
+							description: "The decoding of the requests of the route. See the source-wide `decoding` option."
+							common:      false
+							required:    false
+							type: object: options: {}
+						}
+						auth: configuration._http_basic_auth
+						output: {
+							description: """
+								The name of the output the events of the route are sent to, which other components
+								consume as `<source_id>.<output>`. The events go to the default output if unset.
+								"""
+							common:   true
+							required: false
+							type: string: {
+								default: null
+								examples: ["github"]
+							}
+						}
+					}
+				}
+			}
+		}
 		path_key: {
 			common:      false
 			description: "The event key in which the requested URL path used to send the request will be stored."
@@ -0,0 +1,197 @@
+package metadata
+
+components: sources: http_client: {
+	title: "HTTP Client"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar", "aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.http
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The HTTP endpoint to request."
+			required:    true
+			type: string: {
+				examples: ["http://localhost:8080/api/logs"]
+			}
+		}
+		scrape_interval_secs: {
+			description: "The interval between scrapes."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		query: {
+			common: false
+			description: """
+				Custom parameters for the request query string.
+				One or more values for the same parameter key can be provided.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						"limit": ["100"]
+						"level": ["warn", "error"]
+					},
+				]
+				options: {}
+			}
+		}
+		headers: {
+			common:      false
+			description: "Headers added to the requests."
+			required:    false
+			type: object: {
+				examples: [{"Accept": "application/json"}]
+				options: {}
+			}
+		}
+		auth: configuration._http_auth & {_args: {
+			password_example: "${HTTP_PASSWORD}"
+			username_example: "${HTTP_USERNAME}"
+		}}
+		pagination: {
+			common: false
+			description: """
+				Follows the pages of paginated APIs. The cursor of the next page is persisted in the data
+				directory, so that a restarted Vector resumes from the page it stopped at.
+				"""
+			required: false
+			type: object: options: {
+				type: {
+					description: "Where the next page is described."
+					required:    true
+					type: string: enum: {
+						link_header: "The next page is the `rel=\"next\"` link of the [`Link` header](\(urls.rfc_8288))."
+						field:       "The next page is described by a field of the JSON response."
+					}
+				}
+				field: {
+					description:   "The dotted path of the field holding the next page. Array items are addressed by their index."
+					relevant_when: "type = \"field\""
+					required:      true
+					type: string: {
+						examples: ["next", "meta.next_cursor"]
+					}
+				}
+				query_parameter: {
+					common:        true
+					description:   "The query parameter the token of the next page is sent as. If unset, the field holds the URL of the next page."
+					relevant_when: "type = \"field\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["cursor", "page_token"]
+					}
+				}
+				max_pages: {
+					common:      false
+					description: "The maximum number of pages requested during a single scrape. The following pages are requested during the next scrape."
+					required:    false
+					type: uint: {
+						default: 100
+						unit:    null
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An event decoded from a response."
+		fields: {
+			message: {
+				description: "The raw response body, when decoded with the default `bytes` codec."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["{\"status\": \"ok\"}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["http_client"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		pagination: {
+			title: "Pagination"
+			body: """
+				Every `scrape_interval_secs`, the source requests the page of the persisted cursor, or the
+				`endpoint` when there is none. While the responses describe a next page and contain events, the
+				following pages are requested right away, up to `max_pages` per scrape. The cursor is persisted
+				after the events of a page are sent.
+
+				Once a response describes no next page, the cursor of that last page is kept along with its number
+				of events, and the next scrape requests it again to pick up new entries. Only the events past those
+				already received are emitted, which assumes that new entries are appended to the last page. The
+				cursor is only cleared, and the scrapes started again from the `endpoint`, if it can't be turned
+				into a URL.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		http_error_response_total:            components.sources.internal_metrics.output.metrics.http_error_response_total
+		http_request_errors_total:            components.sources.internal_metrics.output.metrics.http_request_errors_total
+	}
+}
@@ -0,0 +1,263 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the events of a Kubernetes cluster, which report what happens to
+		its resources, like pods being scheduled or containers being killed, via the
+		Kubernetes API.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.kubernetes
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "required"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: {
+		namespaces: {
+			common:      true
+			description: "The namespaces to collect the events of. The events of all the namespaces are collected when empty."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["default", "kube-system"]
+				}
+			}
+		}
+		field_selector: {
+			common:      true
+			description: "The [field selector](\(urls.kubernetes)/docs/concepts/overview/working-with-objects/field-selectors/) filtering the events to collect."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["type=Warning", "regarding.kind=Pod"]
+			}
+		}
+		label_selector: {
+			common:      false
+			description: "The [label selector](\(urls.kubernetes)/docs/concepts/overview/working-with-objects/labels/#label-selectors) filtering the events to collect."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["app=checkout"]
+			}
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the resource versions the events are watched from. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "file_system_path"
+			}
+		}
+		kube_config_file: {
+			common:      false
+			description: "Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
+			required:    false
+			type: string: default: null
+		}
+	}
+
+	output: logs: event: {
+		description: "A Kubernetes event."
+		fields: {
+			message: {
+				description: "The human readable description of the event."
+				required:    true
+				type: string: {
+					examples: ["Successfully assigned default/checkout-5d8f7c9b6-x2x7q to node-1"]
+				}
+			}
+			name: {
+				description: "The name of the event."
+				required:    true
+				type: string: {
+					examples: ["checkout-5d8f7c9b6-x2x7q.16f1b7a4c2d3e5f0"]
+				}
+			}
+			namespace: {
+				description: "The namespace of the event."
+				required:    true
+				type: string: {
+					examples: ["default"]
+				}
+			}
+			uid: {
+				description: "The UID of the event."
+				required:    true
+				type: string: {
+					examples: ["ba5d1b0a-5f3c-4b0e-9f3d-5c1e0a7a8b3c"]
+				}
+			}
+			resource_version: {
+				description: "The resource version of the update of the event."
+				required:    true
+				type: string: {
+					examples: ["123456"]
+				}
+			}
+			reason: {
+				description: "Why the action was taken, in a machine readable form."
+				required:    false
+				type: string: {
+					examples: ["Scheduled", "BackOff", "Unhealthy"]
+				}
+			}
+			type: {
+				description: "The type of the event."
+				required:    false
+				type: string: {
+					examples: ["Normal", "Warning"]
+				}
+			}
+			action: {
+				description: "The action taken, or failed to be taken, on the regarding object."
+				required:    false
+				type: string: {
+					examples: ["Binding", "Killing"]
+				}
+			}
+			count: {
+				description: "How many times the event occurred."
+				required:    false
+				type: uint: {
+					examples: [3]
+					unit: null
+				}
+			}
+			first_timestamp: {
+				description: "When the event first occurred."
+				required:    false
+				type: timestamp: {}
+			}
+			reporting_controller: {
+				description: "The controller which reported the event."
+				required:    false
+				type: string: {
+					examples: ["kubelet", "default-scheduler"]
+				}
+			}
+			reporting_instance: {
+				description: "The instance of the controller which reported the event."
+				required:    false
+				type: string: {
+					examples: ["kubelet-node-1"]
+				}
+			}
+			regarding: {
+				description: "The object the event is about, with its `api_version`, `kind`, `name`, `namespace`, `uid`, `resource_version` and `field_path`."
+				required:    false
+				type: object: {
+					examples: [{kind: "Pod", name: "checkout-5d8f7c9b6-x2x7q", namespace: "default"}]
+					options: {}
+				}
+			}
+			related: {
+				description: "A secondary object the event is about, with the same fields as `regarding`."
+				required:    false
+				type: object: {
+					examples: [{kind: "Node", name: "node-1"}]
+					options: {}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: {
+				description: "When the event last occurred."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		api_version: {
+			title: "Kubernetes API version"
+			body: """
+				The `events.k8s.io/v1` API was introduced in Kubernetes `1.19`, the oldest version
+				Vector supports and runs its end-to-end tests against. Vector is built against the
+				Kubernetes `1.19` API for this reason, which the `kubernetes_logs` source, only
+				reading pods, namespaces and nodes, is also compatible with.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The events are listed once, then [watched](\(urls.kubernetes_watch_api)) from the
+				resource version of the last change seen. This resource version is persisted under
+				the `data_dir`, one per namespace, along with the last update collected of each
+				event, so that the events aren't collected again when Vector restarts. When it's
+				too old for the API server to resume the watch from, the events are listed again,
+				and only the updates which weren't collected yet are.
+				"""
+		}
+		deduplication: {
+			title: "Deduplication"
+			body: """
+				The updates of an event, like its count being incremented, are collected as new
+				events, which share the `uid` of the event. The same update, identified by its
+				`uid` and `resource_version`, is only collected once when the events are listed
+				again.
+				"""
+		}
+		permissions: {
+			title: "Permissions"
+			body: """
+				The events are read from the `events.k8s.io/v1` API, so the service account of
+				Vector must be allowed to `get`, `list` and `watch` the `events` of the
+				`events.k8s.io` API group, in the namespaces collected, with
+				[RBAC](\(urls.kubernetes_rbac)).
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -146,6 +146,22 @@ components: sources: kubernetes_logs: {
 							default: "kubernetes.pod_owner"
 						}
 					}
+					workload_kind: {
+						common:      false
+						description: "Event field for the kind of the Pod workload."
+						required:    false
+						type: string: {
+							default: "kubernetes.workload_kind"
+						}
+					}
+					workload_name: {
+						common:      false
+						description: "Event field for the name of the Pod workload."
+						required:    false
+						type: string: {
+							default: "kubernetes.workload_name"
+						}
+					}
 				}
 			}
 		}
@@ -167,6 +183,45 @@ components: sources: kubernetes_logs: {
 				}
 			}
 		}
+		annotate_node_metadata: {
+			common:      false
+			description: "Annotate the events with the metadata of the Node Vector runs at. This requires permissions to `list` and `watch` the `nodes`."
+			required:    false
+			type: bool: default: false
+		}
+		node_annotation_fields: {
+			common:      false
+			description: "Configuration for how the events are annotated with Node metadata."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					node_labels: {
+						common:      false
+						description: "Event field for Node labels. The labels aren't annotated when it's empty."
+						required:    false
+						type: string: {
+							default: "kubernetes.node_labels"
+						}
+					}
+					node_annotations: {
+						common:      false
+						description: "Event field for Node annotations. The annotations aren't annotated when it's empty, which is the default."
+						required:    false
+						type: string: {
+							default: ""
+							examples: ["kubernetes.node_annotations"]
+						}
+					}
+				}
+			}
+		}
+		resolve_workload_owners: {
+			common:      false
+			description: "Resolve the workload of the Pods owned by a ReplicaSet or a Job to the Deployment or CronJob owning it. This requires permissions to `list` and `watch` the `replicasets` of the `apps` API group and the `jobs` of the `batch` API group."
+			required:    false
+			type: bool: default: false
+		}
 		auto_partial_merge: {
 			common:      false
 			description: "Automatically merge partial messages into a single event. Partial here is in respect to messages that were split by the Kubernetes Container Runtime log driver."
@@ -400,6 +455,42 @@ components: sources: kubernetes_logs: {
 					examples: ["ba46d8c9-9541-4f6b-bbf9-d23b36f2f136"]
 				}
 			}
+			"kubernetes.node_labels": {
+				description: "Set of labels attached to the Node, when the `annotate_node_metadata` option is enabled."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"topology.kubernetes.io/zone": "us-east-1a", "node.kubernetes.io/instance-type": "m5.large"}]
+					options: {}
+				}
+			}
+			"kubernetes.node_annotations": {
+				description: "Set of annotations attached to the Node, when the `node_annotation_fields.node_annotations` option is set to this field."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"node.alpha.kubernetes.io/ttl": "0"}]
+					options: {}
+				}
+			}
+			"kubernetes.workload_kind": {
+				description: "The kind of the workload of the Pod, which is the controller owning it, or the Deployment or CronJob owning this one when `resolve_workload_owners` is enabled."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Deployment", "StatefulSet", "DaemonSet", "CronJob"]
+				}
+			}
+			"kubernetes.workload_name": {
+				description: "The name of the workload of the Pod."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["coredns"]
+				}
+			}
 			message: {
 				description: "The raw line from the Pod log file."
 				required:    true
@@ -477,6 +568,19 @@ components: sources: kubernetes_logs: {
 					"""
 		}
 
+		workload_resolution: {
+			title: "Workload resolution"
+			body:  """
+				The `kubernetes.workload_kind` and `kubernetes.workload_name` fields hold the
+				controller owning the `Pod`, like a `StatefulSet` or a `DaemonSet`. The `Pod`s of
+				`Deployment`s and `CronJob`s are owned through `ReplicaSet`s and `Job`s, which
+				are resolved to their owners when the `resolve_workload_owners` option is enabled.
+				Vector then watches the `ReplicaSet`s and `Job`s of the cluster, so it must be
+				allowed to `list` and `watch` them, in addition to the `Namespace`s, `Node`s and
+				`Pod`s it always watches.
+				"""
+		}
+
 		filtering: {
 			title: "Filtering"
 			body: """
@@ -645,6 +749,21 @@ components: sources: kubernetes_logs: {
 				`ClusterRoleBinding` and a `ServiceAccount` in our `kubectl` YAML
 				config, and the `rbac` configuration at the Helm chart.
 
+				The `resolve_workload_owners` option requires more permissions, which
+				this configuration doesn't grant: Vector needs to `list` and `watch`
+				the `replicasets` of the `apps` API group and the `jobs` of the `batch`
+				API group, which can be granted by adding these rules to the
+				`ClusterRole`:
+
+				```yaml
+				- apiGroups: ["apps"]
+				  resources: ["replicasets"]
+				  verbs: ["list", "watch"]
+				- apiGroups: ["batch"]
+				  resources: ["jobs"]
+				  verbs: ["list", "watch"]
+				```
+
 				If your cluster doesn't use any access control scheme	and doesn't
 				restrict access to the Kubernetes API, you don't need to do any extra
 				configuration - Vector will just work.
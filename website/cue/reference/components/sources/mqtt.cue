@@ -0,0 +1,117 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			from: components._mqtt.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._mqtt.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: components._mqtt.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		session_expiry_secs: {
+			common:      false
+			description: "How long MQTT 5 brokers keep the session of the source once it is disconnected. MQTT 3.1.1 brokers keep sessions according to their own configuration."
+			required:    false
+			type: uint: {
+				default: 86400
+				unit:    "seconds"
+			}
+		}
+		topics: {
+			description: "The topics to subscribe to, which may contain the `+` (single level) and `#` (multiple levels) wildcards."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["vector/#", "sensors/+/temperature"]
+			}
+		}
+		topic_key: {
+			common:      false
+			description: "The log field name to use for the topic the message was published to."
+			required:    false
+			type: string: {
+				default: "topic"
+				examples: ["topic"]
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual MQTT message"
+		fields: {
+			message: {
+				description: "The payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["mqtt"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			topic: {
+				description: "The topic the message was published to."
+				required:    true
+				type: string: {
+					examples: ["sensors/kitchen/temperature"]
+				}
+			}
+		}
+	}
+
+	how_it_works: components._mqtt.how_it_works & {
+		sessions: {
+			title: "Sessions"
+			body: """
+				The source keeps a persistent session on the broker, identified by the `client_id`
+				(which defaults to `vector-<component id>`). The messages published while Vector is
+				disconnected are delivered on reconnection, as are the QoS 1 messages that weren't
+				acknowledged. With acknowledgements enabled, a QoS 1 message is only acknowledged once
+				its events are delivered by the sinks.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,168 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+				interface: socket: {
+					api: {
+						title: "IPFIX"
+						url:   urls.ipfix
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			udp_listeners: enabled:        true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for NetFlow and IPFIX datagrams on. It _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "[::]:4739"]
+			}
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the templates received from the exporters. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "file_system_path"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "A flow record, or an options record, with a field per information element of its template."
+		fields: {
+			version: {
+				description: "The version of the protocol: `5` and `9` for NetFlow, `10` for IPFIX."
+				required:    true
+				type: uint: {
+					examples: [5, 9, 10]
+					unit: null
+				}
+			}
+			exporter: {
+				description: "The IP address of the exporter the record was received from."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			observation_domain_id: {
+				description: "The observation domain ID of an IPFIX message, or the source ID of a NetFlow v9 packet."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [1]
+					unit: null
+				}
+			}
+			template_id: {
+				description: "The ID of the template of a NetFlow v9 or IPFIX record."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [256]
+					unit: null
+				}
+			}
+			record_type: {
+				description: "Whether a NetFlow v9 or IPFIX record is a flow record, `data`, or an options record, `options`."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					enum: {
+						data:    "A flow record."
+						options: "An options record, describing the exporter or its processes."
+					}
+				}
+			}
+			"*": {
+				description: "The information elements of the record, named after the [IANA information elements](\(urls.ipfix_information_elements)) in snake case. The elements the source doesn't know are named `field_<id>`, or `enterprise_<number>_<id>` for enterprise-specific ones, and output as hexadecimal strings."
+				required:    true
+				type: "*": {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["netflow"]
+				}
+			}
+			timestamp: {
+				description: "The export time of the packet."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		templates: {
+			title: "Templates"
+			body: """
+				The records of [NetFlow v9](\(urls.netflow_v9)) and IPFIX are described by templates
+				sent periodically by the exporters, and the records can only be decoded once their
+				template is received; until then they are dropped. The templates are kept per
+				exporter address and observation domain, and persisted in the data directory every
+				10 seconds and on shutdown, so that the records received after a restart of Vector are
+				decoded right away.
+				"""
+		}
+		versions: {
+			title: "Versions"
+			body: """
+				NetFlow v5, NetFlow v9 and IPFIX are decoded from the same socket. The fixed records
+				of NetFlow v5 are output with the names of the equivalent IPFIX information elements.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,228 @@
+package metadata
+
+components: sources: opentelemetry: {
+	_grpc_port: 4317
+	_http_port: 4318
+
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs, metrics and traces over the OpenTelemetry protocol (OTLP), from
+		OpenTelemetry SDKs and collectors.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _grpc_port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			// TLS is configured per listener, see the `grpc` and `http` options.
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		grpc: {
+			common:      true
+			description: "Configures the OTLP/gRPC listener. At least one of `grpc` or `http` must be configured."
+			required:    false
+			type: object: options: {
+				address: {
+					description: "The address to listen for OTLP/gRPC requests on. It _must_ include a port."
+					required:    true
+					type: string: examples: ["0.0.0.0:\(_grpc_port)"]
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+		http: {
+			common:      true
+			description: "Configures the OTLP/HTTP listener. At least one of `grpc` or `http` must be configured."
+			required:    false
+			type: object: options: {
+				address: {
+					description: "The address to listen for OTLP/HTTP requests on. It _must_ include a port."
+					required:    true
+					type: string: examples: ["0.0.0.0:\(_http_port)"]
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+		exponential_histograms: {
+			common:      false
+			description: "How exponential histograms are converted, as Vector has no equivalent metric type."
+			required:    false
+			type: string: {
+				default: "aggregated_histogram"
+				enum: {
+					aggregated_histogram: "Convert each exponential bucket to a bucket of an aggregated histogram."
+					sketch:               "Interpolate the exponential buckets into a sketch."
+				}
+			}
+		}
+	}
+
+	outputs: [
+		{
+			name: "logs"
+			description: """
+				Received log records. Use `<component_id>.logs` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "metrics"
+			description: """
+				Received metric data points. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "traces"
+			description: """
+				Received spans. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
+	]
+
+	output: {
+		logs: record: {
+			description: "An OTLP log record."
+			fields: {
+				message: {
+					description: "The body of the log record."
+					required:    false
+					type: "*": {}
+				}
+				timestamp: {
+					description: "The time of the log record, or the time it was observed when unset."
+					required:    false
+					type: timestamp: {}
+				}
+				observed_timestamp: {
+					description: "The time the log record was observed by the collection system."
+					required:    false
+					type: timestamp: {}
+				}
+				severity_text: {
+					description: "The severity of the log record, as known by its source."
+					required:    false
+					type: string: examples: ["INFO"]
+				}
+				severity_number: {
+					description: "The normalized severity of the log record."
+					required:    false
+					type: uint: {
+						examples: [9]
+						unit: null
+					}
+				}
+				trace_id: {
+					description: "The hex encoded ID of the trace the log record is part of."
+					required:    false
+					type: string: examples: ["5b8efff798038103d269b633813fc60c"]
+				}
+				span_id: {
+					description: "The hex encoded ID of the span the log record is part of."
+					required:    false
+					type: string: examples: ["eee19b7ec3c1b174"]
+				}
+				attributes: {
+					description: "The attributes of the log record."
+					required:    false
+					type: object: options: {}
+				}
+				resources: {
+					description: "The attributes of the resource that produced the log record."
+					required:    false
+					type: object: options: {}
+				}
+				scope: {
+					description: "The instrumentation scope that produced the log record, with its `name`, `version` and `attributes`."
+					required:    false
+					type: object: options: {}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: examples: ["opentelemetry"]
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			summary:      output._passthrough_summary
+		}
+		traces: {
+			description: "An OTLP span, with its `trace_id`, `span_id`, `parent_span_id`, `name`, `kind`, `start_timestamp`, `end_timestamp`, `attributes`, `events`, `links`, `status`, `resources` and `scope`."
+			fields: {}
+		}
+	}
+
+	how_it_works: {
+		metric_conversion: {
+			title: "Metric conversion"
+			body: """
+				Gauges become gauges. Monotonic sums become counters, and other sums become gauges.
+				Histograms and summaries become aggregated histograms and summaries. Exponential
+				histograms become aggregated histograms or sketches, depending on the
+				`exponential_histograms` option.
+
+				Metrics with delta temporality are incremental, the others are absolute. Resource
+				and data point attributes become tags, with data point attributes taking precedence.
+				Data points flagged as having no recorded value are dropped.
+				"""
+		}
+		http: {
+			title: "OTLP/HTTP"
+			body: """
+				The HTTP listener accepts binary protobuf requests on the `/v1/logs`, `/v1/metrics`
+				and `/v1/traces` paths, as specified by the [protocol](\(urls.opentelemetry_protocol)).
+				JSON encoded requests are not supported yet.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
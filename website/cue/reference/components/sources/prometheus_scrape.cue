@@ -53,15 +53,187 @@ components: sources: prometheus_scrape: {
 
 	configuration: {
 		endpoints: {
-			description: "Endpoints to scrape metrics from."
-			required:    true
+			description: "Endpoints to scrape metrics from. Required unless targets are discovered with `discovery`."
+			common:      true
+			required:    false
 			warnings: ["You must explicitly add the path to your endpoints. Vector will _not_ automatically add `/metrics`."]
 			type: array: {
+				default: []
 				items: type: string: {
 					examples: ["http://localhost:9090/metrics"]
 				}
 			}
 		}
+		discovery: {
+			common:      false
+			description: """
+				Discovers the targets to scrape, in addition to the `endpoints`. See
+				[service discovery](#service-discovery) for the labels of the discovered targets.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: [{type: "file", paths: ["/etc/vector/targets/*.json"]}]
+					options: {
+						type: {
+							description: "The discovery mechanism."
+							required:    true
+							type: string: enum: {
+								file:       "Reads the targets from files using the Prometheus `file_sd` format, in JSON if the file has a `.json` extension and YAML otherwise."
+								dns:        "Resolves the targets from DNS records."
+								kubernetes: "Watches the pods or services annotated with `prometheus.io/scrape: \"true\"`."
+							}
+						}
+						paths: {
+							description:   "The files to read the targets from. Glob patterns are supported."
+							relevant_when: "type = \"file\""
+							required:      true
+							type: array: items: type: string: examples: ["/etc/vector/targets/*.json"]
+						}
+						names: {
+							description:   "The DNS names to resolve."
+							relevant_when: "type = \"dns\""
+							required:      true
+							type: array: items: type: string: examples: ["_metrics._tcp.example.com"]
+						}
+						record_type: {
+							description:   "The type of the DNS records to resolve."
+							relevant_when: "type = \"dns\""
+							common:        false
+							required:      false
+							type: string: {
+								default: "SRV"
+								enum: {
+									SRV:  "Resolves the hosts and ports of the targets."
+									A:    "Resolves the IPv4 addresses of the targets, scraped on `port`."
+									AAAA: "Resolves the IPv6 addresses of the targets, scraped on `port`."
+								}
+							}
+						}
+						port: {
+							description:   "The port of the targets, required for `A` and `AAAA` records."
+							relevant_when: "type = \"dns\""
+							common:        false
+							required:      false
+							type: uint: {
+								default: null
+								examples: [9100]
+							}
+						}
+						refresh_interval_secs: {
+							description:   "The interval between refreshes of the targets."
+							relevant_when: "type = \"file\" or type = \"dns\""
+							common:        false
+							required:      false
+							type: uint: {
+								default: 30
+								unit:    "seconds"
+							}
+						}
+						role: {
+							description:   "The kind of Kubernetes resources to discover."
+							relevant_when: "type = \"kubernetes\""
+							common:        false
+							required:      false
+							type: string: {
+								default: "pod"
+								enum: {
+									pod:     "Scrapes the IP address of the annotated pods."
+									service: "Scrapes the cluster DNS name of the annotated services."
+								}
+							}
+						}
+						namespaces: {
+							description:   "The namespaces to discover targets in, all of them if empty."
+							relevant_when: "type = \"kubernetes\""
+							common:        false
+							required:      false
+							type: array: {
+								default: []
+								items: type: string: examples: ["default"]
+							}
+						}
+						kube_config_file: {
+							description:   "Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
+							relevant_when: "type = \"kubernetes\""
+							common:        false
+							required:      false
+							type: string: {
+								default: null
+								examples: ["/path/to/.kube/config"]
+							}
+						}
+					}
+				}
+			}
+		}
+		relabel: {
+			common:      false
+			description: """
+				Rules rewriting the labels of the discovered targets before they are scraped, applied in order. They follow
+				Prometheus's [`relabel_config`](\(urls.prometheus_relabel_config)). The regular expressions are anchored on
+				both ends.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: [{source_labels: ["__meta_kubernetes_pod_label_app"], target_label: "app"}]
+					options: {
+						action: {
+							description: "The action to perform."
+							common:      false
+							required:    false
+							type: string: {
+								default: "replace"
+								enum: {
+									replace:   "Sets `target_label` to `replacement` if the concatenated `source_labels` match `regex`."
+									keep:      "Drops the targets whose concatenated `source_labels` don't match `regex`."
+									drop:      "Drops the targets whose concatenated `source_labels` match `regex`."
+									labelmap:  "Copies the labels whose name matches `regex` to the label named after `replacement`."
+									labeldrop: "Removes the labels whose name matches `regex`."
+									labelkeep: "Removes the labels whose name doesn't match `regex`."
+								}
+							}
+						}
+						source_labels: {
+							description: "The labels whose values are concatenated with `separator` and matched against `regex`."
+							common:      true
+							required:    false
+							type: array: {
+								default: []
+								items: type: string: examples: ["__meta_kubernetes_namespace"]
+							}
+						}
+						separator: {
+							description: "The separator of the concatenated `source_labels`."
+							common:      false
+							required:    false
+							type: string: default: ";"
+						}
+						regex: {
+							description: "The regular expression matched against the concatenated `source_labels`, or against the label names."
+							common:      true
+							required:    false
+							type: string: default: "(.*)"
+						}
+						target_label: {
+							description:   "The label set by the `replace` action."
+							relevant_when: "action = \"replace\""
+							required:      true
+							type: string: examples: ["namespace"]
+						}
+						replacement: {
+							description: "The value of the label, in which the capture groups of `regex` are expanded with `$1`, `$2`..."
+							common:      false
+							required:    false
+							type: string: default: "$1"
+						}
+					}
+				}
+			}
+		}
 		scrape_interval_secs: {
 			common:      true
 			description: "The interval between scrapes, in seconds."
@@ -149,6 +321,33 @@ components: sources: prometheus_scrape: {
 		}}
 	}
 
+	how_it_works: {
+		service_discovery: {
+			title: "Service discovery"
+			body: """
+				Like in Prometheus, a discovered target is described by labels. The `__address__` label holds the
+				host and port to scrape, and the optional `__scheme__`, `__metrics_path__` and `__param_<name>` labels
+				set the scheme, the path (`/metrics` by default) and the query parameters. Labels starting with
+				`__meta_` describe the target and are only available to the `relabel` rules. After relabeling, the
+				labels that don't start with `__` are added as tags to the scraped metrics, following `honor_labels`
+				on conflicts.
+
+				* `file` targets are labeled with the labels of their group, and `__meta_filepath`.
+				* `dns` targets are labeled with `__meta_dns_name`, plus `__meta_dns_srv_record_target` and
+				  `__meta_dns_srv_record_port` for SRV records.
+				* `kubernetes` targets are labeled with `namespace` and `pod` or `service`, and the
+				  `__meta_kubernetes_namespace`, `__meta_kubernetes_<role>_name`,
+				  `__meta_kubernetes_<role>_label_<name>` and `__meta_kubernetes_<role>_annotation_<name>` meta
+				  labels. The `prometheus.io/port`, `prometheus.io/path` and `prometheus.io/scheme` annotations
+				  set the port, path and scheme to scrape. Without a port annotation, each container or service
+				  port is scraped.
+
+				Targets whose discovery fails are kept until they are discovered again. The endpoints configured with
+				`endpoints` aren't relabeled.
+				"""
+		}
+	}
+
 	output: metrics: {
 		_extra_tags: {
 			"instance": {
@@ -4,7 +4,7 @@ components: sources: redis: {
 	title: "Redis"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -58,6 +58,7 @@ components: sources: redis: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._source_acknowledgements
 		url: {
 			description: "The Redis URL to connect to. The url _must_ take the form of `protocol://server:port/db` where the protocol can either be `redis` or `rediss` for connections secured via TLS."
 			groups: ["tcp"]
@@ -79,13 +80,14 @@ components: sources: redis: {
 		}
 		data_type: {
 			common:      false
-			description: "The Redis data type (`list` or `channel`) to use."
+			description: "The Redis data type (`list`, `channel` or `stream`) to use."
 			required:    false
 			type: string: {
 				default: "list"
 				enum: {
 					list:    "Use the Redis `list` data type."
 					channel: "Use the Redis `channel` data type."
+					stream:  "Use the Redis `stream` data type, reading the entries as a member of a consumer group."
 				}
 				syntax: "literal"
 			}
@@ -114,6 +116,54 @@ components: sources: redis: {
 				}
 			}
 		}
+		stream: {
+			common:      false
+			description: "Options for the Redis `stream` data type."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					group: {
+						description: "The consumer group to read the entries as, created at the end of the stream if it doesn't exist."
+						required:    true
+						type: string: {
+							examples: ["vector"]
+							syntax: "literal"
+						}
+					}
+					consumer: {
+						common:      false
+						description: "The name of the consumer within the group. Defaults to the host name."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["vector-0"]
+							syntax: "literal"
+						}
+					}
+					field: {
+						common:      false
+						description: "The field of the entries holding the message."
+						required:    false
+						type: string: {
+							default: "message"
+							syntax:  "literal"
+						}
+					}
+					claim_min_idle_secs: {
+						common:      false
+						description: "When set, the entries of the group delivered to other consumers and left unacknowledged for at least this long are claimed on startup."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [300]
+							unit: "seconds"
+						}
+					}
+				}
+			}
+		}
 		redis_key: {
 			common:      false
 			description: "The log field name to use for the redis key. If set to an empty string or null, the key is not added to the log event."
@@ -146,6 +196,15 @@ components: sources: redis: {
 	}
 
 	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				With the `stream` data type, the entries are acknowledged with `XACK` once their events are
+				delivered by the sinks, and stay pending in the consumer group otherwise. On startup, the
+				entries pending for the consumer are read again before the new ones. The `list` and `channel`
+				data types remove the messages as they are read, so they can't be acknowledged.
+				"""
+		}
 		redis_rs: {
 			title: "redis-rs"
 			body:  """
@@ -0,0 +1,325 @@
+package metadata
+
+components: sources: sflow: {
+	_port: 6343
+
+	title: "sFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.sflow
+				interface: socket: {
+					api: {
+						title: "sFlow v5"
+						url:   urls.sflow_v5
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			udp_listeners: enabled:        true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for sFlow datagrams on. It _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+	}
+
+	output: logs: flow_sample: {
+		description: "A flow sample, with the decoded headers of the sampled packet."
+		fields: {
+			agentAddress: {
+				description: "The IP address of the agent which sent the sample."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			subAgentId: {
+				description: "The ID of the sub-agent which sent the sample."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			datagramSequenceNumber: {
+				description: "The sequence number of the datagram the sample was sent in."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			uptime: {
+				description: "The uptime of the agent."
+				required:    true
+				type: uint: {
+					examples: [60000]
+					unit: "milliseconds"
+				}
+			}
+			sampleType: {
+				description: "The type of the sample."
+				required:    true
+				type: string: {
+					examples: ["flow"]
+				}
+			}
+			sequenceNumber: {
+				description: "The sequence number of the sample."
+				required:    true
+				type: uint: {
+					examples: [7]
+					unit: null
+				}
+			}
+			sourceIdType: {
+				description: "The type of the data source of the sample: `0` for an interface, `1` for a VLAN and `2` for a physical entity."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			sourceIdIndex: {
+				description: "The index of the data source of the sample, such as the `ifIndex` of the interface."
+				required:    true
+				type: uint: {
+					examples: [3]
+					unit: null
+				}
+			}
+			samplingRate: {
+				description: "The number of packets the sampled packet stands for."
+				required:    true
+				type: uint: {
+					examples: [1024]
+					unit: null
+				}
+			}
+			samplePool: {
+				description: "The total number of packets that could have been sampled."
+				required:    true
+				type: uint: {
+					examples: [8192]
+					unit: null
+				}
+			}
+			drops: {
+				description: "The number of samples dropped by the agent for lack of resources."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			inputInterface: {
+				description: "The `ifIndex` of the interface the packet was received on, when known."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [3]
+					unit: null
+				}
+			}
+			outputInterface: {
+				description: "The `ifIndex` of the interface the packet was sent on, when it was sent on a single one."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [4]
+					unit: null
+				}
+			}
+			sampledHeader: {
+				description: "The header of the sampled packet, as decoded as far as its Ethernet, IP, TCP and UDP headers."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [
+						{
+							"headerProtocol": 1
+							"frameLength":    1518
+							"stripped":       4
+							"headerLength":   128
+							"ethernet": {
+								"sourceMac":      "66:77:88:99:aa:bb"
+								"destinationMac": "00:11:22:33:44:55"
+								"etherType":      2048
+								"vlanId":         10
+							}
+							"ip": {
+								"version":            4
+								"sourceAddress":      "10.0.0.1"
+								"destinationAddress": "10.0.0.2"
+								"protocol":           6
+								"tos":                16
+								"ttl":                64
+								"totalLength":        1500
+							}
+							"transport": {
+								"sourcePort":      50000
+								"destinationPort": 443
+								"tcpFlags":        18
+							}
+						},
+					]
+					options: {}
+				}
+			}
+			error: {
+				description: "The error that occurred while parsing the sample, if any."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["Truncated sFlow data"]
+				}
+			}
+			rawData: {
+				description: "The base64 encoded sample, when it couldn't be parsed."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["AAAABwAAAAM="]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["sflow"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	output: metrics: {
+		_sflow_tags: {
+			agent_address: {
+				description: "The IP address of the agent which sent the counters."
+				required:    true
+				examples: ["192.0.2.1"]
+			}
+			sub_agent_id: {
+				description: "The ID of the sub-agent which sent the counters."
+				required:    true
+				examples: ["0"]
+			}
+			source_id_type: {
+				description: "The type of the data source of the counters: `0` for an interface, `1` for a VLAN and `2` for a physical entity."
+				required:    true
+				examples: ["0"]
+			}
+			source_id_index: {
+				description: "The index of the data source of the counters, such as the `ifIndex` of the interface."
+				required:    true
+				examples: ["3"]
+			}
+		}
+		_sflow_counter: {
+			type:              "counter"
+			default_namespace: "sflow"
+			tags:              _sflow_tags
+		}
+		_sflow_gauge: {
+			type:              "gauge"
+			default_namespace: "sflow"
+			tags:              _sflow_tags
+		}
+
+		interface_speed_bits_per_second:             _sflow_gauge & {description: "The speed of the interface."}
+		interface_admin_up:                          _sflow_gauge & {description: "Whether the interface is administratively up."}
+		interface_oper_up:                           _sflow_gauge & {description: "Whether the interface is operationally up."}
+		interface_promiscuous_mode:                  _sflow_gauge & {description: "Whether the interface is in promiscuous mode."}
+		interface_in_octets_total:                   _sflow_counter & {description: "The number of octets received on the interface."}
+		interface_in_unicast_packets_total:          _sflow_counter & {description: "The number of unicast packets received on the interface."}
+		interface_in_multicast_packets_total:        _sflow_counter & {description: "The number of multicast packets received on the interface."}
+		interface_in_broadcast_packets_total:        _sflow_counter & {description: "The number of broadcast packets received on the interface."}
+		interface_in_discards_total:                 _sflow_counter & {description: "The number of received packets discarded by the interface."}
+		interface_in_errors_total:                   _sflow_counter & {description: "The number of received packets with errors."}
+		interface_in_unknown_protocols_total:        _sflow_counter & {description: "The number of received packets of unknown protocols."}
+		interface_out_octets_total:                  _sflow_counter & {description: "The number of octets sent on the interface."}
+		interface_out_unicast_packets_total:         _sflow_counter & {description: "The number of unicast packets sent on the interface."}
+		interface_out_multicast_packets_total:       _sflow_counter & {description: "The number of multicast packets sent on the interface."}
+		interface_out_broadcast_packets_total:       _sflow_counter & {description: "The number of broadcast packets sent on the interface."}
+		interface_out_discards_total:                _sflow_counter & {description: "The number of packets to send discarded by the interface."}
+		interface_out_errors_total:                  _sflow_counter & {description: "The number of packets that couldn't be sent because of errors."}
+		ethernet_alignment_errors_total:             _sflow_counter & {description: "The number of frames received with alignment errors."}
+		ethernet_fcs_errors_total:                   _sflow_counter & {description: "The number of frames received with frame check sequence errors."}
+		ethernet_single_collision_frames_total:      _sflow_counter & {description: "The number of frames sent after a single collision."}
+		ethernet_multiple_collision_frames_total:    _sflow_counter & {description: "The number of frames sent after multiple collisions."}
+		ethernet_sqe_test_errors_total:              _sflow_counter & {description: "The number of SQE test errors."}
+		ethernet_deferred_transmissions_total:       _sflow_counter & {description: "The number of frames whose transmission was deferred."}
+		ethernet_late_collisions_total:              _sflow_counter & {description: "The number of late collisions."}
+		ethernet_excessive_collisions_total:         _sflow_counter & {description: "The number of frames not sent because of excessive collisions."}
+		ethernet_internal_mac_transmit_errors_total: _sflow_counter & {description: "The number of frames not sent because of internal MAC errors."}
+		ethernet_carrier_sense_errors_total:         _sflow_counter & {description: "The number of carrier sense errors."}
+		ethernet_frame_too_longs_total:              _sflow_counter & {description: "The number of frames received exceeding the maximum size."}
+		ethernet_internal_mac_receive_errors_total:  _sflow_counter & {description: "The number of frames not received because of internal MAC errors."}
+		ethernet_symbol_errors_total:                _sflow_counter & {description: "The number of symbol errors."}
+		processor_cpu_5s_percent:                    _sflow_gauge & {description: "The CPU load of the device over the last 5 seconds."}
+		processor_cpu_1m_percent:                    _sflow_gauge & {description: "The CPU load of the device over the last minute."}
+		processor_cpu_5m_percent:                    _sflow_gauge & {description: "The CPU load of the device over the last 5 minutes."}
+		processor_memory_total_bytes:                _sflow_gauge & {description: "The total memory of the device."}
+		processor_memory_free_bytes:                 _sflow_gauge & {description: "The free memory of the device."}
+	}
+
+	how_it_works: {
+		samples: {
+			title: "Samples"
+			body: """
+				The flow samples of the [sFlow v5](\(urls.sflow_v5)) datagrams are output as logs,
+				one per sample, along with their sampled packet header decoded as far as its
+				Ethernet, IPv4 or IPv6, and TCP or UDP headers. The generic interface, Ethernet
+				interface and processor counter samples are output as metrics, the other counter
+				records and the enterprise-specific samples are skipped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,362 @@
+package metadata
+
+components: sources: snmp_trap: {
+	_port: 162
+
+	title: "SNMP Trap"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			udp_listeners: enabled:        true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for SNMP traps and informs on. It _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		communities: {
+			common:      true
+			description: "The communities accepted in the SNMPv1 and SNMPv2c messages. The messages of all the communities are accepted when empty."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["public"]
+				}
+			}
+		}
+		users: {
+			common:      true
+			description: "The users the SNMPv3 messages are accepted from. The messages of the other users are discarded."
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: [{name: "admin", auth_protocol: "sha", auth_password: "${SNMP_AUTH_PASSWORD}", priv_protocol: "aes", priv_password: "${SNMP_PRIV_PASSWORD}"}]
+					options: {
+						name: {
+							description: "The name of the user."
+							required:    true
+							type: string: examples: ["admin"]
+						}
+						auth_protocol: {
+							description: "The protocol authenticating the messages of the user. The unauthenticated messages are accepted when unset."
+							common:      true
+							required:    false
+							type: string: {
+								default: null
+								enum: {
+									md5:    "HMAC-MD5-96."
+									sha:    "HMAC-SHA-96."
+									sha224: "HMAC-SHA-224, truncated to 128 bits."
+									sha256: "HMAC-SHA-256, truncated to 192 bits."
+									sha384: "HMAC-SHA-384, truncated to 256 bits."
+									sha512: "HMAC-SHA-512, truncated to 384 bits."
+								}
+								syntax: "literal"
+							}
+						}
+						auth_password: {
+							description: "The password the authentication key of the user is derived from. It must have at least 8 characters."
+							common:      true
+							required:    false
+							type: string: {
+								default: null
+								examples: ["${SNMP_AUTH_PASSWORD}"]
+							}
+						}
+						priv_protocol: {
+							description: "The protocol encrypting the messages of the user, which requires `auth_protocol`. The plaintext messages are accepted when unset."
+							common:      true
+							required:    false
+							type: string: {
+								default: null
+								enum: {
+									des: "DES in CBC mode."
+									aes: "AES-128 in CFB mode."
+								}
+								syntax: "literal"
+							}
+						}
+						priv_password: {
+							description: "The password the privacy key of the user is derived from. It must have at least 8 characters."
+							common:      true
+							required:    false
+							type: string: {
+								default: null
+								examples: ["${SNMP_PRIV_PASSWORD}"]
+							}
+						}
+					}
+				}
+			}
+		}
+		engine_id: {
+			common:      false
+			description: "The SNMP engine ID of the source, as a hexadecimal string of 5 to 32 bytes. The SNMPv3 informs are sent to this engine. By default, it is made of the hostname."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["800000000401020304"]
+			}
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the number of times the SNMP engine of the source booted. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "file_system_path"
+			}
+		}
+		mib_paths: {
+			common:      true
+			description: "The MIB files, or directories of MIB files, used to translate the OIDs to names. The OIDs are output as numbers when empty."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/usr/share/snmp/mibs"]
+					syntax: "file_system_path"
+				}
+			}
+		}
+		host_key: {
+			category:    "Context"
+			common:      false
+			description: """
+				The key name added to each event representing the address of the sender. This can also be globally set via the
+				[global `host_key` option](\(urls.vector_configuration)/global-options#log_schema.host_key).
+				"""
+			required:    false
+			type: string: {
+				default: "host"
+			}
+		}
+	}
+
+	output: logs: notification: {
+		description: "A trap or an inform."
+		fields: {
+			version: {
+				description: "The version of SNMP of the message."
+				required:    true
+				type: string: {
+					enum: {
+						"1":  "SNMPv1."
+						"2c": "SNMPv2c."
+						"3":  "SNMPv3."
+					}
+				}
+			}
+			pdu_type: {
+				description: "Whether the message is a trap, or an inform acknowledged by the source."
+				required:    true
+				type: string: {
+					enum: {
+						trap:   "A trap."
+						inform: "An inform."
+					}
+				}
+			}
+			community: {
+				description: "The community of an SNMPv1 or SNMPv2c message."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["public"]
+				}
+			}
+			user: {
+				description: "The user of an SNMPv3 message."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["admin"]
+				}
+			}
+			security_level: {
+				description: "The security level of an SNMPv3 message."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					enum: {
+						noAuthNoPriv: "Neither authenticated nor encrypted."
+						authNoPriv:   "Authenticated but not encrypted."
+						authPriv:     "Authenticated and encrypted."
+					}
+				}
+			}
+			context_engine_id: {
+				description: "The hexadecimal context engine ID of an SNMPv3 message."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["800000000401020304"]
+				}
+			}
+			context_name: {
+				description: "The context name of an SNMPv3 message."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: [""]
+				}
+			}
+			enterprise: {
+				description: "The OID of the device type of an SNMPv1 trap."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["1.3.6.1.4.1.8072.3.2.10"]
+				}
+			}
+			agent_address: {
+				description: "The IP address of the agent of an SNMPv1 trap."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["192.0.2.1"]
+				}
+			}
+			generic_trap: {
+				description: "The generic trap number of an SNMPv1 trap, `6` for an enterprise-specific trap."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [2, 6]
+					unit: null
+				}
+			}
+			specific_trap: {
+				description: "The enterprise-specific trap number of an SNMPv1 trap."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [17]
+					unit: null
+				}
+			}
+			uptime: {
+				description: "The uptime of the agent, in hundredths of a second."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [4200]
+					unit: null
+				}
+			}
+			trap_oid: {
+				description: "The OID of the notification. For the SNMPv1 traps, it is the OID of the equivalent SNMPv2 notification, as described in [RFC 3584](\(urls.rfc_3584))."
+				required:    true
+				type: string: {
+					examples: ["1.3.6.1.6.3.1.1.5.3", "linkDown"]
+				}
+			}
+			varbinds: {
+				description: "The variable bindings of the notification, keyed by OID. The OIDs are translated to names when `mib_paths` is set. The octet strings which aren't printable text, and the opaque values, are output as hexadecimal strings."
+				required:    true
+				type: object: {
+					examples: [{"1.3.6.1.2.1.2.2.1.1.3": 3, "ifDescr.3": "eth0"}]
+					options: {}
+				}
+			}
+			host: {
+				description: "The IP address of the sender of the message."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["snmp_trap"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		informs: {
+			title: "Informs"
+			body: """
+				The informs are acknowledged with a response as soon as they are received. The SNMPv3
+				informs are sent to the engine of the source, whose ID the senders discover with
+				a first message the source answers with a report. The keys of the SNMPv3 users are
+				localized to this engine ID for the informs, and to the engine ID of the sender for
+				the traps, as described in [RFC 3414](\(urls.rfc_3414)). The authenticated informs
+				must carry the boots and time of the engine, within 150 seconds, or they're answered
+				with a report the senders synchronize with. The boots of the engine are counted in
+				the data directory, so that the informs sent before a restart can't be replayed.
+				"""
+		}
+		mibs: {
+			title: "MIBs"
+			body: """
+				Only the OID assignments of the MIB modules are read, and their names are resolved
+				across all the modules loaded, so the modules they import don't need to be loaded
+				as long as they're only imported for their names. The names of `SNMPv2-SMI`, and
+				those of the generic notifications of `SNMPv2-MIB`, are always known.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -35,6 +35,10 @@ components: sources: socket: {
 				enabled:       true
 				relevant_when: "mode = `tcp` or mode = `udp`"
 			}
+			udp_listeners: {
+				enabled:       true
+				relevant_when: "mode = `udp`"
+			}
 			keepalive: enabled: true
 			tls: {
 				enabled:                true
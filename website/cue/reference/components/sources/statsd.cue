@@ -35,6 +35,10 @@ components: sources: statsd: {
 				enabled:       true
 				relevant_when: "mode = `tcp` or mode = `udp`"
 			}
+			udp_listeners: {
+				enabled:       true
+				relevant_when: "mode = `udp`"
+			}
 			keepalive: enabled: true
 			tls: {
 				enabled:                true
@@ -102,7 +106,12 @@ components: sources: statsd: {
 				unit:    "concurrency"
 			}
 		}
-
+		dogstatsd: {
+			common:      false
+			description: "Whether to parse the DogStatsD extensions of the protocol: the container ID and timestamp of the metrics, along with the events and service checks, which are output as logs."
+			required:    false
+			type: bool: default: false
+		}
 	}
 
 	output: metrics: {
@@ -112,6 +121,145 @@ components: sources: statsd: {
 		set:          output._passthrough_set
 	}
 
+	output: logs: {
+		event: {
+			description: "A DogStatsD event, when `dogstatsd` is enabled."
+			fields: {
+				kind: {
+					description: "The kind of the DogStatsD datagram."
+					required:    true
+					type: string: {
+						examples: ["event"]
+					}
+				}
+				title: {
+					description: "The title of the event."
+					required:    true
+					type: string: {
+						examples: ["Deployment finished"]
+					}
+				}
+				message: {
+					description: "The text of the event."
+					required:    true
+					type: string: {
+						examples: ["Version 1.2.3 is live."]
+					}
+				}
+				host: {
+					description: "The host name of the event, from the `h:` field."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["my-host"]
+					}
+				}
+				aggregation_key: {
+					description: "The aggregation key of the event, from the `k:` field."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["deployments"]
+					}
+				}
+				priority: {
+					description: "The priority of the event, from the `p:` field."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["normal", "low"]
+					}
+				}
+				source_type_name: {
+					description: "The source type name of the event, from the `s:` field."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["jenkins"]
+					}
+				}
+				alert_type: {
+					description: "The alert type of the event, from the `t:` field."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["info", "warning", "error", "success"]
+					}
+				}
+				tags: {
+					description: "The tags of the event."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"env": "prod"}]
+						options: {}
+					}
+				}
+				timestamp: fields._current_timestamp
+			}
+		}
+		service_check: {
+			description: "A DogStatsD service check, when `dogstatsd` is enabled."
+			fields: {
+				kind: {
+					description: "The kind of the DogStatsD datagram."
+					required:    true
+					type: string: {
+						examples: ["service_check"]
+					}
+				}
+				name: {
+					description: "The name of the service check."
+					required:    true
+					type: string: {
+						examples: ["redis.can_connect"]
+					}
+				}
+				status: {
+					description: "The status of the service check: `0` for OK, `1` for warning, `2` for critical and `3` for unknown."
+					required:    true
+					type: uint: {
+						examples: [0, 2]
+						unit: null
+					}
+				}
+				message: {
+					description: "The message of the service check, from the `m:` field."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["Connection timed out"]
+					}
+				}
+				host: {
+					description: "The host name of the service check, from the `h:` field."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["my-host"]
+					}
+				}
+				tags: {
+					description: "The tags of the service check."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"env": "prod"}]
+						options: {}
+					}
+				}
+				timestamp: fields._current_timestamp
+			}
+		}
+	}
+
 	how_it_works: {
 		timestamps: {
 			title: "Timestamps"
@@ -122,6 +270,18 @@ components: sources: statsd: {
 				`null` timestamps are substituted with the current time by downstream sinks or
 				third-party services during sending/ingestion. See the
 				[metric data model](\(urls.vector_metric)) page for more info.
+
+				With `dogstatsd` enabled, the metrics carrying a `|T<UNIX_TIMESTAMP>` extension are
+				assigned that timestamp instead.
+				"""
+		}
+		dogstatsd: {
+			title: "DogStatsD"
+			body: """
+				With `dogstatsd` enabled, the source accepts the DogStatsD datagrams sent to the Datadog
+				Agent. The container ID of a metric, given with the `|c:<CONTAINER_ID>` extension, is
+				added as its `container_id` tag. Events (`_e{...}`) and service checks (`_sc|...`) are
+				output as logs, whose `kind` field tells them apart.
 				"""
 		}
 	}
@@ -31,6 +31,10 @@ components: sources: syslog: {
 				enabled:       true
 				relevant_when: "mode = `tcp` or mode = `udp`"
 			}
+			udp_listeners: {
+				enabled:       true
+				relevant_when: "mode = `udp`"
+			}
 			keepalive: enabled: true
 			tls: sources.socket.features.receive.tls
 		}
@@ -245,6 +245,42 @@ configuration: {
 							default: false
 						}
 					}
+
+					reload_rollback: {
+						common: false
+						description: """
+							When reloading, wait for the health checks of new and changed sinks,
+							then watch new and changed components for errors during
+							`reload_grace_period_secs`. If a health check fails or more than
+							`reload_max_errors` errors are reported, the previous configuration is
+							restored. The outcome of the last reload is logged and exposed by the
+							API as `lastReload`. Signals are handled once the grace period ends.
+							"""
+						required: false
+						type: bool: {
+							default: false
+						}
+					}
+
+					reload_grace_period_secs: {
+						common:      false
+						description: "How long new and changed components are watched for errors after a reload, when `reload_rollback` is enabled."
+						required:    false
+						type: uint: {
+							default: 30
+							unit:    "seconds"
+						}
+					}
+
+					reload_max_errors: {
+						common:      false
+						description: "The number of errors new and changed components may report during the grace period before the reload is rolled back."
+						required:    false
+						type: uint: {
+							default: 0
+							unit:    null
+						}
+					}
 				}
 			}
 		}
@@ -369,6 +405,61 @@ configuration: {
 				},
 			]
 		}
+		secrets: {
+			title: "Secrets"
+			body: """
+				Vector can retrieve secrets from secret backends, so that credentials don't need to be set in
+				the environment or written in plain text. Backends are declared in the `secret` section, and
+				secrets are referenced with the `SECRET[<backend>.<key>]` syntax:
+
+				```toml title="vector.toml"
+				[secret.local]
+				  type = "exec"
+				  command = ["/usr/local/bin/fetch-secrets"]
+
+				[secret.k8s]
+				  type = "directory"
+				  path = "/var/run/secrets/vector"
+				  remove_trailing_whitespace = true
+
+				[sinks.es]
+				  type = "elasticsearch"
+				  auth.password = "SECRET[local.es_password]"
+				```
+
+				Secrets are retrieved when the configuration is loaded, and again on every reload. They are never
+				included in the output of `vector config`.
+				"""
+
+			sub_sections: [
+				{
+					title: "Exec backend"
+					body: """
+						The `exec` backend runs `command` (with a `timeout_secs` defaulting to 5 seconds) and
+						writes the requested keys to its standard input as `{"version": "1.0", "secrets": ["key"]}`.
+						The command must print `{"key": {"value": "<secret>", "error": null}}` on its standard
+						output, which is the protocol of the Datadog Agent's secrets backend.
+						"""
+				},
+				{
+					title: "File and directory backends"
+					body: """
+						The `file` backend reads a JSON object of keys to values from `path`. The `directory`
+						backend reads each secret from the file named after its key in `path`, which is how
+						Kubernetes mounts secrets.
+						"""
+				},
+				{
+					title: "AWS Secrets Manager backend"
+					body: """
+						The `aws_secrets_manager` backend, available when Vector is built with the
+						`secrets-aws-secrets-manager` feature, reads the secret `secret_id`, whose value must be a
+						JSON object of keys to values. It supports the same `region`, `endpoint`, `auth` and `tls`
+						options as the other AWS components.
+						"""
+				},
+			]
+		}
 		formats: {
 			title: "Formats"
 			body:  """
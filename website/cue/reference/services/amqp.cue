@@ -0,0 +1,10 @@
+package metadata
+
+services: amqp: {
+	name:     "AMQP"
+	thing:    "an \(name) broker"
+	url:      urls.amqp
+	versions: "0.9.1"
+
+	description: "[AMQP](\(urls.amqp)) is an open messaging protocol, in its 0.9.1 version implemented by brokers such as RabbitMQ."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow"
+	thing:    "a \(name) or IPFIX exporter"
+	url:      urls.netflow
+	versions: null
+
+	description: "[NetFlow](\(urls.netflow)) is a protocol exporting the IP flows seen by network devices, such as routers and switches, and standardized as [IPFIX](\(urls.ipfix))."
+}
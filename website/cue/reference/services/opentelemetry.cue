@@ -0,0 +1,10 @@
+package metadata
+
+services: opentelemetry: {
+	name:     "OpenTelemetry"
+	thing:    "an \(name) SDK or collector"
+	url:      urls.opentelemetry
+	versions: null
+
+	description: "[OpenTelemetry](\(urls.opentelemetry)) is a collection of APIs, SDKs and tools to instrument, generate, collect and export telemetry data. Its applications exchange logs, metrics and traces over the OpenTelemetry protocol (OTLP)."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: sflow: {
+	name:     "sFlow"
+	thing:    "an \(name) agent"
+	url:      urls.sflow
+	versions: null
+
+	description: "[sFlow](\(urls.sflow)) is a sampling technology exporting packet headers and interface counters from switches and routers."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP"
+	thing:    "an \(name) agent"
+	url:      urls.snmp
+	versions: null
+
+	description: "[SNMP](\(urls.snmp)) is the protocol used to monitor network devices, which send traps and informs to notify events like link or hardware failures."
+}
@@ -5,6 +5,8 @@ urls: {
 	affine_type_system:                                       "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:                        "/blog/adaptive-request-concurrency/"
 	amazon_linux:                                             "https://aws.amazon.com/amazon-linux-ami/"
+	amqp:                                                     "https://www.amqp.org/"
+	amqp_uri:                                                 "https://www.rabbitmq.com/uri-spec.html"
 	ansi_escape_codes:                                        "\(wikipedia)/wiki/ANSI_escape_code"
 	apache:                                                   "https://httpd.apache.org"
 	apache_common:                                            "\(apache)/docs/current/logs.html#common"
@@ -209,6 +211,7 @@ urls: {
 	gcs_predefined_acl:                                       "\(gcp)/storage/docs/access-control/lists#predefined-acl"
 	gcs_storage_classes:                                      "\(gcp)/storage/docs/storage-classes"
 	gcs_custom_metadata:                                      "\(gcp)/storage/docs/metadata#custom-metadata"
+	gelf:                                                     "https://go2docs.graylog.org/current/getting_in_log_data/gelf.html"
 	git:                                                      "https://git-scm.com/"
 	github:                                                   "https://github.com"
 	github_protected_branches:                                "https://help.github.com/en/github/administering-a-repository/about-protected-branches"
@@ -255,6 +258,8 @@ urls: {
 	ip_ntoa:                                                  "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                                  "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                                  "https://linux.die.net/man/3/inet_pton"
+	ipfix:                                                    "https://www.rfc-editor.org/rfc/rfc7011"
+	ipfix_information_elements:                               "https://www.iana.org/assignments/ipfix/ipfix.xhtml"
 	iso_8601:                                                 "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                                "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                               "\(vector_repo)/issues/1694"
@@ -334,9 +339,12 @@ urls: {
 	mongodb:                                                  "https://www.mongodb.com"
 	mongodb_command_server_status:                            "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:                     "https://docs.mongodb.com/manual/reference/connection-string/"
+	mqtt:                                                     "https://mqtt.org/"
 	musl_builder_docker_image:                                "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	nats:                                                     "https://nats.io/"
 	nats_rs:                                                  "\(github)/nats-io/nats.rs"
+	netflow:                                                  "\(wikipedia)/wiki/NetFlow"
+	netflow_v9:                                               "https://www.rfc-editor.org/rfc/rfc3954"
 	new_bug_report:                                           "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                                      "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                                "https://newrelic.com/"
@@ -355,6 +363,8 @@ urls: {
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
 	openssl:                                                  "https://www.openssl.org/"
+	opentelemetry:                                            "https://opentelemetry.io/"
+	opentelemetry_protocol:                                   "https://opentelemetry.io/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
 	papertrail_syslog:                                        "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
@@ -384,6 +394,7 @@ urls: {
 	prometheus_summary:                                       "https://prometheus.io/docs/concepts/metric_types/#summary"
 	prometheus_text_based_exposition_format:                  "\(github)/prometheus/docs/blob/master/content/docs/instrumenting/exposition_formats.md#text-based-format"
 	prometheus_metric_naming:                                 "https://prometheus.io/docs/practices/naming/#metric-names"
+	prometheus_relabel_config:                                "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#relabel_config"
 	prometheus_remote_integrations:                           "https://prometheus.io/docs/operating/integrations/#remote-endpoints-and-storage"
 	prometheus_remote_write:                                  "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:                         "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
@@ -405,9 +416,12 @@ urls: {
 	rfc_2460:                                                 "https://tools.ietf.org/html/rfc2460"
 	rfc_2822:                                                 "https://tools.ietf.org/html/rfc2822#section-3.3"
 	rfc_3339:                                                 "https://tools.ietf.org/html/rfc3339"
+	rfc_3414:                                                 "https://tools.ietf.org/html/rfc3414"
+	rfc_3584:                                                 "https://tools.ietf.org/html/rfc3584"
 	rfc_4180:                                                 "https://tools.ietf.org/html/rfc4180"
 	rfc_6587_3_4_1:                                           "https://tools.ietf.org/html/rfc6587#section-3.4.1"
 	rfc_6891:                                                 "https://tools.ietf.org/html/rfc6891"
+	rfc_8288:                                                 "https://tools.ietf.org/html/rfc8288"
 	rhel:                                                     "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rpm:                                                      "https://rpm.org/"
 	rust:                                                     "https://www.rust-lang.org/"
@@ -431,12 +445,15 @@ urls: {
 	sematext_monitoring:                                      "https://sematext.com/docs/monitoring/"
 	sematext_registration:                                    "https://apps.sematext.com/ui/registration"
 	semver:                                                   "https://semver.org/"
+	sflow:                                                    "https://sflow.org/"
+	sflow_v5:                                                 "https://sflow.org/sflow_version_5.txt"
 	sha1:                                                     "\(wikipedia)/wiki/SHA-1"
 	sha2:                                                     "\(wikipedia)/wiki/SHA-2"
 	sha3:                                                     "\(wikipedia)/wiki/SHA-3"
 	signal:                                                   "\(wikipedia)/wiki/Signal_(IPC)"
 	snake_case:                                               "\(wikipedia)/wiki/Snake_case"
 	snappy:                                                   "https://google.github.io/snappy/"
+	snmp:                                                     "\(wikipedia)/wiki/Simple_Network_Management_Protocol"
 	socket:                                                   "\(wikipedia)/wiki/Network_socket"
 	splunk:                                                   "https://www.splunk.com"
 	splunk_hec:                                               "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"